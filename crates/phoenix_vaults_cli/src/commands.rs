@@ -0,0 +1,663 @@
+use anchor_spl::associated_token::get_associated_token_address;
+use phoenix::state::{OrderPacket, Side as PhoenixSide};
+use phoenix_vaults_client::instructions as ix;
+use phoenix_vaults_client::phoenix_vaults::accounts;
+use phoenix_vaults_client::phoenix_vaults::instructions::{
+    CancelMultipleOrdersParams, CancelOrderParams, PlaceOrderParams, Side, UpdateVaultParams,
+    VaultParams,
+};
+use phoenix_vaults_client::phoenix_vaults::math::shares_to_amount;
+use phoenix_vaults_client::phoenix_vaults::state::{
+    MarketTransferParams, Vault, WithdrawRequest, WithdrawUnit,
+};
+use phoenix_vaults_client::{
+    decode_name, encode_name, equity_remaining_accounts, fetch_investor, fetch_market_registry,
+    fetch_phoenix_market_accounts, fetch_vault, fetch_vault_valuation, get_investor_address,
+    get_market_registry_address, get_nav_history_address, get_vault_address,
+    get_vault_token_address, market_remaining_accounts, PhoenixMarketAccounts,
+};
+use serde_json::{json, Value};
+use solana_sdk::commitment_config::CommitmentConfig;
+use solana_sdk::instruction::{AccountMeta, Instruction};
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signer::Signer;
+
+use crate::config::Config;
+use crate::{
+    CancelOrdersArgs, DepositArgs, InitVaultArgs, MarketArgs, MarketTransferArgs, OrderSide,
+    PlaceOrderArgs, RequestWithdrawArgs, ShowInvestorArgs, UpdateVaultArgs, VaultArgs,
+    WithdrawUnitArg,
+};
+
+/// Parses `<bid|ask>:<price_in_ticks>:<order_sequence_number>`.
+pub fn parse_cancel_order(s: &str) -> Result<CancelOrderParams, String> {
+    let parts: Vec<&str> = s.split(':').collect();
+    let [side, price_in_ticks, order_sequence_number] = parts.as_slice() else {
+        return Err(format!(
+            "expected <bid|ask>:<price_in_ticks>:<order_sequence_number>, got {}",
+            s
+        ));
+    };
+    let side = match side.to_lowercase().as_str() {
+        "bid" => Side::Bid,
+        "ask" => Side::Ask,
+        other => return Err(format!("invalid side: {}", other)),
+    };
+    Ok(CancelOrderParams {
+        side,
+        price_in_ticks: price_in_ticks
+            .parse()
+            .map_err(|e| format!("invalid price_in_ticks: {}", e))?,
+        order_sequence_number: order_sequence_number
+            .parse()
+            .map_err(|e| format!("invalid order_sequence_number: {}", e))?,
+    })
+}
+
+impl From<WithdrawUnitArg> for WithdrawUnit {
+    fn from(unit: WithdrawUnitArg) -> Self {
+        match unit {
+            WithdrawUnitArg::Shares => WithdrawUnit::Shares,
+            WithdrawUnitArg::Token => WithdrawUnit::Token,
+            WithdrawUnitArg::SharesPercent => WithdrawUnit::SharesPercent,
+        }
+    }
+}
+
+async fn send(config: &Config, ixs: &[Instruction]) -> anyhow::Result<Value> {
+    let signature = config.send(ixs).await?;
+    Ok(json!({ "signature": signature.to_string() }))
+}
+
+async fn send_with(
+    config: &Config,
+    ixs: &[Instruction],
+    mut output: Value,
+) -> anyhow::Result<Value> {
+    let signature = config.send(ixs).await?;
+    output["signature"] = json!(signature.to_string());
+    Ok(output)
+}
+
+/// The nav history account is optional, so it's only passed once initialized.
+async fn nav_history(config: &Config, vault: &Pubkey) -> anyhow::Result<Option<Pubkey>> {
+    let key = get_nav_history_address(vault);
+    let account = config
+        .client
+        .get_account_with_commitment(&key, CommitmentConfig::processed())
+        .await
+        .map_err(|e| anyhow::anyhow!("{:?}", e))?
+        .value;
+    Ok(account.map(|_| key))
+}
+
+/// Vault, market registry and the remaining accounts the program values the vault with.
+async fn load_vault(
+    config: &Config,
+    vault_key: &Pubkey,
+) -> anyhow::Result<(Vault, Vec<AccountMeta>)> {
+    let vault = fetch_vault(&config.client, vault_key).await?;
+    let registry = fetch_market_registry(&config.client, &get_market_registry_address()).await?;
+    let remaining_accounts = equity_remaining_accounts(&vault, &registry);
+    Ok((vault, remaining_accounts))
+}
+
+/// Vault, the Phoenix accounts of `market`, and the remaining accounts of an instruction trading it.
+async fn load_vault_market(
+    config: &Config,
+    vault_key: &Pubkey,
+    market: &Pubkey,
+) -> anyhow::Result<(Vault, PhoenixMarketAccounts, Vec<AccountMeta>)> {
+    let vault = fetch_vault(&config.client, vault_key).await?;
+    let registry = fetch_market_registry(&config.client, &get_market_registry_address()).await?;
+    let market_accounts = fetch_phoenix_market_accounts(&config.client, vault_key, market).await?;
+    let remaining_accounts = market_remaining_accounts(&vault, &registry, *market);
+    Ok((vault, market_accounts, remaining_accounts))
+}
+
+pub async fn init_vault(config: &Config, args: InitVaultArgs) -> anyhow::Result<Value> {
+    let name = encode_name(&args.name)?;
+    let vault = get_vault_address(&name);
+    let signer = config.signer.pubkey();
+    let instruction = ix::initialize_vault(
+        accounts::InitializeVault {
+            vault,
+            manager: signer,
+            usdc_token_account: get_vault_token_address(&vault, &args.usdc_mint),
+            usdc_mint: args.usdc_mint,
+            sol_token_account: get_vault_token_address(&vault, &args.sol_mint),
+            sol_mint: args.sol_mint,
+            payer: signer,
+            rent: solana_sdk::sysvar::rent::ID,
+            system_program: solana_sdk::system_program::ID,
+            token_program: anchor_spl::token::ID,
+            associated_token_program: anchor_spl::associated_token::ID,
+        },
+        VaultParams {
+            name,
+            redeem_period: args.redeem_period,
+            max_tokens: args.max_tokens,
+            management_fee: args.management_fee,
+            min_deposit_amount: args.min_deposit_amount,
+            profit_share: args.profit_share,
+            hurdle_rate: args.hurdle_rate,
+            permissioned: args.permissioned,
+            protocol: args.protocol,
+            protocol_fee: args.protocol_fee,
+            protocol_profit_share: args.protocol_profit_share,
+            epoch_duration: args.epoch_duration,
+            entry_fee_bps: args.entry_fee_bps,
+            exit_fee_bps: args.exit_fee_bps,
+            max_drawdown: args.max_drawdown,
+            manager_min_stake: args.manager_min_stake,
+            liquidity_buffer: args.liquidity_buffer,
+            lockup_duration: args.lockup_duration,
+            lockup_penalty_bps: args.lockup_penalty_bps,
+            liquidation_window: args.liquidation_window,
+            fundraising_soft_cap: args.fundraising_soft_cap,
+            fundraising_deadline: args.fundraising_deadline,
+        },
+        vec![],
+    );
+    send_with(
+        config,
+        &[instruction],
+        json!({ "vault": vault.to_string() }),
+    )
+    .await
+}
+
+pub async fn update_vault(config: &Config, args: UpdateVaultArgs) -> anyhow::Result<Value> {
+    let instruction = ix::update_vault(
+        accounts::UpdateVault {
+            vault: args.vault,
+            manager: config.signer.pubkey(),
+        },
+        UpdateVaultParams {
+            redeem_period: args.redeem_period,
+            max_tokens: args.max_tokens,
+            management_fee: args.management_fee,
+            min_deposit_amount: args.min_deposit_amount,
+            profit_share: args.profit_share,
+            hurdle_rate: args.hurdle_rate,
+            permissioned: args.permissioned,
+            delegate: args.delegate,
+            entry_fee_bps: args.entry_fee_bps,
+            exit_fee_bps: args.exit_fee_bps,
+            max_drawdown: args.max_drawdown,
+            manager_min_stake: args.manager_min_stake,
+            liquidity_buffer: args.liquidity_buffer,
+            lockup_duration: args.lockup_duration,
+            liquidation_window: args.liquidation_window,
+        },
+        vec![],
+    );
+    send(config, &[instruction]).await
+}
+
+pub async fn claim_seat(config: &Config, args: MarketArgs) -> anyhow::Result<Value> {
+    let market = fetch_phoenix_market_accounts(&config.client, &args.vault, &args.market).await?;
+    let signer = config.signer.pubkey();
+    let instruction = ix::claim_seat(
+        accounts::ClaimSeat {
+            vault: args.vault,
+            delegate: signer,
+            phoenix: phoenix::ID,
+            log_authority: market.log_authority,
+            market: market.market,
+            seat_manager: market.seat_manager,
+            seat_deposit_collector: market.seat_deposit_collector,
+            payer: signer,
+            seat: market.seat,
+            system_program: solana_sdk::system_program::ID,
+            phoenix_seat_manager: phoenix_seat_manager::ID,
+        },
+        vec![],
+    );
+    send_with(
+        config,
+        &[instruction],
+        json!({ "seat": market.seat.to_string() }),
+    )
+    .await
+}
+
+pub async fn market_deposit(config: &Config, args: MarketTransferArgs) -> anyhow::Result<Value> {
+    let (_, market, remaining_accounts) =
+        load_vault_market(config, &args.vault, &args.market).await?;
+    let instruction = ix::market_deposit(
+        accounts::MarketDeposit {
+            vault: args.vault,
+            delegate: config.signer.pubkey(),
+            market_registry: get_market_registry_address(),
+            phoenix: phoenix::ID,
+            log_authority: market.log_authority,
+            market: market.market,
+            seat: market.seat,
+            base_mint: market.base_mint,
+            quote_mint: market.quote_mint,
+            vault_base_token_account: market.vault_base_token_account,
+            vault_quote_token_account: market.vault_quote_token_account,
+            market_base_token_account: market.market_base_token_account,
+            market_quote_token_account: market.market_quote_token_account,
+            token_program: anchor_spl::token::ID,
+        },
+        MarketTransferParams {
+            quote_lots: args.quote_lots,
+            base_lots: args.base_lots,
+        },
+        remaining_accounts,
+    );
+    send(config, &[instruction]).await
+}
+
+pub async fn market_withdraw(config: &Config, args: MarketTransferArgs) -> anyhow::Result<Value> {
+    let (_, market, remaining_accounts) =
+        load_vault_market(config, &args.vault, &args.market).await?;
+    let instruction = ix::market_withdraw(
+        accounts::MarketWithdraw {
+            vault: args.vault,
+            delegate: config.signer.pubkey(),
+            phoenix: phoenix::ID,
+            log_authority: market.log_authority,
+            market: market.market,
+            base_mint: market.base_mint,
+            quote_mint: market.quote_mint,
+            vault_base_token_account: market.vault_base_token_account,
+            vault_quote_token_account: market.vault_quote_token_account,
+            market_base_token_account: market.market_base_token_account,
+            market_quote_token_account: market.market_quote_token_account,
+            token_program: anchor_spl::token::ID,
+        },
+        MarketTransferParams {
+            quote_lots: args.quote_lots,
+            base_lots: args.base_lots,
+        },
+        remaining_accounts,
+    );
+    send(config, &[instruction]).await
+}
+
+pub async fn place_order(config: &Config, args: PlaceOrderArgs) -> anyhow::Result<Value> {
+    let (_, market, remaining_accounts) =
+        load_vault_market(config, &args.vault, &args.market).await?;
+    let side = match args.side {
+        OrderSide::Bid => PhoenixSide::Bid,
+        OrderSide::Ask => PhoenixSide::Ask,
+    };
+    let order_packet = if args.post_only {
+        OrderPacket::new_post_only_default(side, args.price_in_ticks, args.base_lots)
+    } else {
+        OrderPacket::new_limit_order_default(side, args.price_in_ticks, args.base_lots)
+    };
+    // the program takes the Phoenix instruction data, tag included
+    let order =
+        phoenix::program::instruction_builders::create_new_order_with_free_funds_instruction(
+            &market.market,
+            &args.vault,
+            &order_packet,
+        )
+        .data;
+    let instruction = ix::place_limit_order(
+        accounts::PlaceLimitOrder {
+            vault: args.vault,
+            delegate: config.signer.pubkey(),
+            phoenix: phoenix::ID,
+            log_authority: market.log_authority,
+            market: market.market,
+            seat: market.seat,
+            base_mint: market.base_mint,
+            quote_mint: market.quote_mint,
+            vault_base_token_account: market.vault_base_token_account,
+            vault_quote_token_account: market.vault_quote_token_account,
+            market_base_token_account: market.market_base_token_account,
+            market_quote_token_account: market.market_quote_token_account,
+            token_program: anchor_spl::token::ID,
+        },
+        PlaceOrderParams { order },
+        remaining_accounts,
+    );
+    send(config, &[instruction]).await
+}
+
+pub async fn cancel_all_orders(config: &Config, args: MarketArgs) -> anyhow::Result<Value> {
+    let (_, market, remaining_accounts) =
+        load_vault_market(config, &args.vault, &args.market).await?;
+    let instruction = ix::cancel_all_orders(
+        accounts::CancelAllOrders {
+            vault: args.vault,
+            delegate: config.signer.pubkey(),
+            phoenix: phoenix::ID,
+            log_authority: market.log_authority,
+            market: market.market,
+        },
+        remaining_accounts,
+    );
+    send(config, &[instruction]).await
+}
+
+pub async fn cancel_orders(config: &Config, args: CancelOrdersArgs) -> anyhow::Result<Value> {
+    let (_, market, remaining_accounts) =
+        load_vault_market(config, &args.vault, &args.market).await?;
+    let instruction = ix::cancel_multiple_orders_by_id(
+        accounts::CancelMultipleOrdersById {
+            vault: args.vault,
+            delegate: config.signer.pubkey(),
+            phoenix: phoenix::ID,
+            log_authority: market.log_authority,
+            market: market.market,
+        },
+        CancelMultipleOrdersParams {
+            orders: args.orders,
+        },
+        remaining_accounts,
+    );
+    send(config, &[instruction]).await
+}
+
+pub async fn manager_deposit(config: &Config, args: DepositArgs) -> anyhow::Result<Value> {
+    let (vault, remaining_accounts) = load_vault(config, &args.vault).await?;
+    let manager = config.signer.pubkey();
+    let instruction = ix::manager_deposit(
+        accounts::ManagerDeposit {
+            vault: args.vault,
+            manager,
+            market_registry: get_market_registry_address(),
+            nav_history: nav_history(config, &args.vault).await?,
+            manager_quote_token_account: get_associated_token_address(&manager, &vault.usdc_mint),
+            vault_quote_token_account: vault.usdc_token_account,
+            token_program: anchor_spl::token::ID,
+        },
+        args.amount,
+        remaining_accounts,
+    );
+    send(config, &[instruction]).await
+}
+
+pub async fn manager_request_withdraw(
+    config: &Config,
+    args: RequestWithdrawArgs,
+) -> anyhow::Result<Value> {
+    let (vault, remaining_accounts) = load_vault(config, &args.vault).await?;
+    let instruction = ix::manager_request_withdraw(
+        accounts::ManagerRequestWithdraw {
+            vault: args.vault,
+            manager: config.signer.pubkey(),
+            market_registry: get_market_registry_address(),
+            vault_usdc_token_account: vault.usdc_token_account,
+        },
+        args.amount,
+        args.unit.into(),
+        remaining_accounts,
+    );
+    send(config, &[instruction]).await
+}
+
+pub async fn manager_cancel_withdraw_request(
+    config: &Config,
+    args: VaultArgs,
+) -> anyhow::Result<Value> {
+    let (vault, remaining_accounts) = load_vault(config, &args.vault).await?;
+    let instruction = ix::manager_cancel_withdraw_request(
+        accounts::ManagerCancelWithdrawRequest {
+            vault: args.vault,
+            manager: config.signer.pubkey(),
+            market_registry: get_market_registry_address(),
+            vault_usdc_token_account: vault.usdc_token_account,
+        },
+        remaining_accounts,
+    );
+    send(config, &[instruction]).await
+}
+
+pub async fn manager_withdraw(config: &Config, args: VaultArgs) -> anyhow::Result<Value> {
+    let (vault, remaining_accounts) = load_vault(config, &args.vault).await?;
+    let registry = fetch_market_registry(&config.client, &get_market_registry_address()).await?;
+    // withdrawals settle through the SOL/USDC market
+    let market =
+        fetch_phoenix_market_accounts(&config.client, &args.vault, &registry.sol_usdc_market)
+            .await?;
+    let manager = config.signer.pubkey();
+    let instruction = ix::manager_withdraw(
+        accounts::ManagerWithdraw {
+            vault: args.vault,
+            manager,
+            market_registry: get_market_registry_address(),
+            nav_history: nav_history(config, &args.vault).await?,
+            manager_quote_token_account: get_associated_token_address(&manager, &vault.usdc_mint),
+            phoenix: phoenix::ID,
+            log_authority: market.log_authority,
+            market: market.market,
+            seat: market.seat,
+            base_mint: market.base_mint,
+            quote_mint: market.quote_mint,
+            vault_base_token_account: vault.sol_token_account,
+            vault_quote_token_account: vault.usdc_token_account,
+            market_base_token_account: market.market_base_token_account,
+            market_quote_token_account: market.market_quote_token_account,
+            token_program: anchor_spl::token::ID,
+        },
+        remaining_accounts,
+    );
+    send(config, &[instruction]).await
+}
+
+pub async fn protocol_request_withdraw(
+    config: &Config,
+    args: RequestWithdrawArgs,
+) -> anyhow::Result<Value> {
+    let (vault, remaining_accounts) = load_vault(config, &args.vault).await?;
+    let instruction = ix::protocol_request_withdraw(
+        accounts::ProtocolRequestWithdraw {
+            vault: args.vault,
+            protocol: config.signer.pubkey(),
+            market_registry: get_market_registry_address(),
+            vault_usdc_token_account: vault.usdc_token_account,
+        },
+        args.amount,
+        args.unit.into(),
+        remaining_accounts,
+    );
+    send(config, &[instruction]).await
+}
+
+pub async fn protocol_cancel_withdraw_request(
+    config: &Config,
+    args: VaultArgs,
+) -> anyhow::Result<Value> {
+    let (vault, remaining_accounts) = load_vault(config, &args.vault).await?;
+    let instruction = ix::protocol_cancel_withdraw_request(
+        accounts::ProtocolCancelWithdrawRequest {
+            vault: args.vault,
+            protocol: config.signer.pubkey(),
+            market_registry: get_market_registry_address(),
+            vault_usdc_token_account: vault.usdc_token_account,
+        },
+        remaining_accounts,
+    );
+    send(config, &[instruction]).await
+}
+
+pub async fn protocol_withdraw(config: &Config, args: VaultArgs) -> anyhow::Result<Value> {
+    let (vault, remaining_accounts) = load_vault(config, &args.vault).await?;
+    let registry = fetch_market_registry(&config.client, &get_market_registry_address()).await?;
+    // withdrawals settle through the SOL/USDC market
+    let market =
+        fetch_phoenix_market_accounts(&config.client, &args.vault, &registry.sol_usdc_market)
+            .await?;
+    let protocol = config.signer.pubkey();
+    let instruction = ix::protocol_withdraw(
+        accounts::ProtocolWithdraw {
+            vault: args.vault,
+            protocol,
+            market_registry: get_market_registry_address(),
+            nav_history: nav_history(config, &args.vault).await?,
+            protocol_quote_token_account: get_associated_token_address(&protocol, &vault.usdc_mint),
+            phoenix: phoenix::ID,
+            log_authority: market.log_authority,
+            market: market.market,
+            seat: market.seat,
+            base_mint: market.base_mint,
+            quote_mint: market.quote_mint,
+            vault_base_token_account: vault.sol_token_account,
+            vault_quote_token_account: vault.usdc_token_account,
+            market_base_token_account: market.market_base_token_account,
+            market_quote_token_account: market.market_quote_token_account,
+            token_program: anchor_spl::token::ID,
+        },
+        remaining_accounts,
+    );
+    send(config, &[instruction]).await
+}
+
+fn withdraw_request_json(request: &WithdrawRequest) -> Value {
+    json!({
+        "shares": request.shares.to_string(),
+        "value": request.value,
+        "ts": request.ts,
+    })
+}
+
+pub async fn show_vault(config: &Config, args: VaultArgs) -> anyhow::Result<Value> {
+    let vault = fetch_vault(&config.client, &args.vault).await?;
+    let now = config
+        .client
+        .get_block_time(config.client.get_slot().await?)
+        .await?;
+    let positions: Vec<Value> = vault
+        .positions
+        .iter()
+        .filter(|position| !position.is_available())
+        .map(|position| {
+            json!({
+                "market": position.market.to_string(),
+                "quote_lots_free": position.quote_lots_free,
+                "quote_lots_locked": position.quote_lots_locked,
+                "base_lots_free": position.base_lots_free,
+                "base_lots_locked": position.base_lots_locked,
+            })
+        })
+        .collect();
+    // u128 share counts are strings since JSON numbers lose precision past 2^53
+    Ok(json!({
+        "vault": args.vault.to_string(),
+        "name": decode_name(&vault.name),
+        "state": format!("{:?}", vault.state(now)),
+        "manager": vault.manager.to_string(),
+        "delegate": vault.delegate.to_string(),
+        "protocol": vault.protocol.to_string(),
+        "liquidator": vault.liquidator.to_string(),
+        "usdc_token_account": vault.usdc_token_account.to_string(),
+        "sol_token_account": vault.sol_token_account.to_string(),
+        "total_shares": vault.total_shares.to_string(),
+        "investor_shares": vault.investor_shares.to_string(),
+        "protocol_shares": vault.protocol_profit_and_fee_shares.to_string(),
+        "total_deposits": vault.total_deposits,
+        "total_withdraws": vault.total_withdraws,
+        "total_withdraw_requested": vault.total_withdraw_requested,
+        "redeem_period": vault.redeem_period,
+        "max_tokens": vault.max_tokens,
+        "min_deposit_amount": vault.min_deposit_amount,
+        "management_fee": vault.management_fee,
+        "profit_share": vault.profit_share,
+        "hurdle_rate": vault.hurdle_rate,
+        "protocol_fee": vault.protocol_fee,
+        "protocol_profit_share": vault.protocol_profit_share,
+        "permissioned": vault.permissioned,
+        "trading_halted": vault.trading_halted,
+        "last_manager_withdraw_request": withdraw_request_json(&vault.last_manager_withdraw_request),
+        "last_protocol_withdraw_request": withdraw_request_json(&vault.last_protocol_withdraw_request),
+        "positions": positions,
+    }))
+}
+
+pub async fn show_investor(config: &Config, args: ShowInvestorArgs) -> anyhow::Result<Value> {
+    let authority = args.authority.unwrap_or(config.signer.pubkey());
+    let investor_key = get_investor_address(&args.vault, &authority);
+    let investor = fetch_investor(&config.client, &investor_key).await?;
+    let vault = fetch_vault(&config.client, &args.vault).await?;
+    let valuation = fetch_vault_valuation(&config.client, &args.vault).await?;
+    let shares = investor
+        .checked_vault_shares(&vault)
+        .map_err(|e| anyhow::anyhow!("{:?}", e))?;
+    // before any fees the program would crystallize on the next investor action
+    let equity = shares_to_amount(shares, vault.total_shares, valuation.equity)
+        .map_err(|e| anyhow::anyhow!("{:?}", e))?;
+    Ok(json!({
+        "investor": investor_key.to_string(),
+        "vault": args.vault.to_string(),
+        "authority": authority.to_string(),
+        "shares": shares.to_string(),
+        "equity": equity,
+        "net_deposits": investor.net_deposits,
+        "total_deposits": investor.total_deposits,
+        "total_withdraws": investor.total_withdraws,
+        "profit_share_fee_paid": investor.profit_share_fee_paid,
+        "last_withdraw_request": withdraw_request_json(&investor.last_withdraw_request),
+    }))
+}
+
+pub async fn show_equity(config: &Config, args: VaultArgs) -> anyhow::Result<Value> {
+    let valuation = fetch_vault_valuation(&config.client, &args.vault).await?;
+    Ok(json!({
+        "vault": args.vault.to_string(),
+        "equity": valuation.equity,
+        "share_price": valuation.share_price,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_cancel_order() {
+        for (name, s, expected) in [
+            (
+                "bid",
+                "bid:15000:42",
+                Ok(CancelOrderParams {
+                    side: Side::Bid,
+                    price_in_ticks: 15_000,
+                    order_sequence_number: 42,
+                }),
+            ),
+            (
+                "side is case insensitive",
+                "ASK:15100:7",
+                Ok(CancelOrderParams {
+                    side: Side::Ask,
+                    price_in_ticks: 15_100,
+                    order_sequence_number: 7,
+                }),
+            ),
+            (
+                "missing field",
+                "bid:15000",
+                Err("expected <bid|ask>:<price_in_ticks>:<order_sequence_number>, got bid:15000"),
+            ),
+            ("invalid side", "buy:15000:42", Err("invalid side: buy")),
+        ] {
+            assert_eq!(
+                parse_cancel_order(s),
+                expected.map_err(String::from),
+                "{}",
+                name
+            );
+        }
+        for (name, s, prefix) in [
+            ("invalid price", "bid:abc:42", "invalid price_in_ticks"),
+            (
+                "invalid sequence number",
+                "bid:15000:-1",
+                "invalid order_sequence_number",
+            ),
+        ] {
+            assert!(
+                parse_cancel_order(s).unwrap_err().starts_with(prefix),
+                "{}",
+                name
+            );
+        }
+    }
+}
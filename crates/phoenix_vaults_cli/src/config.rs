@@ -0,0 +1,107 @@
+use serde_json::Value;
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_sdk::commitment_config::CommitmentConfig;
+use solana_sdk::instruction::Instruction;
+use solana_sdk::signature::{read_keypair_file, Keypair, Signature};
+use solana_sdk::signer::Signer;
+use solana_sdk::transaction::Transaction;
+
+pub struct Config {
+    pub client: RpcClient,
+    pub signer: Keypair,
+    pub json: bool,
+}
+
+impl Config {
+    pub fn new(url: &str, keypair: Option<&str>, json: bool) -> anyhow::Result<Self> {
+        let keypair_path = match keypair {
+            Some(path) => path.to_string(),
+            None => {
+                let home = std::env::var("HOME")
+                    .map_err(|_| anyhow::anyhow!("HOME not set, pass --keypair"))?;
+                format!("{}/.config/solana/id.json", home)
+            }
+        };
+        let signer = read_keypair_file(&keypair_path)
+            .map_err(|e| anyhow::anyhow!("Failed to read keypair {}: {:?}", keypair_path, e))?;
+        Ok(Self {
+            client: RpcClient::new_with_commitment(url.to_string(), CommitmentConfig::confirmed()),
+            signer,
+            json,
+        })
+    }
+
+    /// Signs with the configured keypair, which also pays the fee, and waits for confirmation.
+    pub async fn send(&self, ixs: &[Instruction]) -> anyhow::Result<Signature> {
+        let blockhash = self
+            .client
+            .get_latest_blockhash()
+            .await
+            .map_err(|e| anyhow::anyhow!("{:?}", e))?;
+        let tx = Transaction::new_signed_with_payer(
+            ixs,
+            Some(&self.signer.pubkey()),
+            &[&self.signer],
+            blockhash,
+        );
+        self.client
+            .send_and_confirm_transaction(&tx)
+            .await
+            .map_err(|e| anyhow::anyhow!("{:?}", e))
+    }
+
+    /// Prints a command result as JSON, or as `key: value` lines.
+    pub fn print(&self, output: &Value) {
+        println!("{}", format_output(output, self.json));
+    }
+}
+
+/// A command result as JSON, or as `key: value` lines.
+pub fn format_output(output: &Value, json: bool) -> String {
+    if json {
+        return output.to_string();
+    }
+    match output {
+        Value::Object(map) => map
+            .iter()
+            .map(|(key, value)| match value {
+                Value::String(s) => format!("{}: {}", key, s),
+                _ => format!("{}: {}", key, value),
+            })
+            .collect::<Vec<_>>()
+            .join("\n"),
+        _ => output.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_format_output() {
+        let output = json!({
+            "shares": 1_000,
+            "signature": "5sig",
+            "withdraw_request": { "value": 10 },
+        });
+        for (name, output, json, expected) in [
+            (
+                "json",
+                output.clone(),
+                true,
+                r#"{"shares":1000,"signature":"5sig","withdraw_request":{"value":10}}"#,
+            ),
+            (
+                "plain strings are unquoted",
+                output,
+                false,
+                "shares: 1000\nsignature: 5sig\nwithdraw_request: {\"value\":10}",
+            ),
+            ("plain non-object", json!([1, 2]), false, "[1,2]"),
+        ] {
+            assert_eq!(format_output(&output, json), expected, "{}", name);
+        }
+    }
+}
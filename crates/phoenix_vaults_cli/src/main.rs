@@ -0,0 +1,308 @@
+//! `phoenix-vaults` command line interface for vault managers, delegates and the protocol.
+//!
+//! Every command that sends a transaction signs with `--keypair` as both the authority
+//! (manager, delegate or protocol) and the fee payer.
+
+mod commands;
+mod config;
+
+use clap::{Args, Parser, Subcommand, ValueEnum};
+use solana_sdk::pubkey::Pubkey;
+
+use crate::config::Config;
+
+#[derive(Parser)]
+#[command(name = "phoenix-vaults", version, about)]
+struct Cli {
+    /// RPC URL, defaults to a local test validator
+    #[arg(
+        long,
+        short,
+        global = true,
+        env = "RPC_URL",
+        default_value = "http://127.0.0.1:8899"
+    )]
+    url: String,
+    /// Signer keypair file, defaults to the Solana CLI keypair
+    #[arg(long, short, global = true, env = "KEYPAIR")]
+    keypair: Option<String>,
+    /// Print results as JSON
+    #[arg(long, global = true)]
+    json: bool,
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Create a vault managed by the signer
+    InitVault(InitVaultArgs),
+    /// Update the vault parameters the manager controls
+    UpdateVault(UpdateVaultArgs),
+    /// Claim a seat for the vault on a Phoenix market, signed by the delegate
+    ClaimSeat(MarketArgs),
+    /// Move vault tokens into a Phoenix market
+    MarketDeposit(MarketTransferArgs),
+    /// Move vault tokens out of a Phoenix market
+    MarketWithdraw(MarketTransferArgs),
+    /// Place a limit order with the vault's free funds on a market
+    PlaceOrder(PlaceOrderArgs),
+    /// Cancel all of the vault's orders on a market
+    CancelAllOrders(MarketArgs),
+    /// Cancel orders by id, each given as `<bid|ask>:<price_in_ticks>:<order_sequence_number>`
+    CancelOrders(CancelOrdersArgs),
+    ManagerDeposit(DepositArgs),
+    ManagerRequestWithdraw(RequestWithdrawArgs),
+    ManagerCancelWithdrawRequest(VaultArgs),
+    ManagerWithdraw(VaultArgs),
+    ProtocolRequestWithdraw(RequestWithdrawArgs),
+    ProtocolCancelWithdrawRequest(VaultArgs),
+    ProtocolWithdraw(VaultArgs),
+    /// Read vault state
+    #[command(subcommand)]
+    Show(ShowCommand),
+}
+
+#[derive(Subcommand)]
+enum ShowCommand {
+    Vault(VaultArgs),
+    /// An investor of the vault, by default the signer
+    Investor(ShowInvestorArgs),
+    /// Vault equity and share price, valued as the program values them
+    Equity(VaultArgs),
+}
+
+#[derive(Args)]
+struct VaultArgs {
+    /// Vault address
+    #[arg(long)]
+    vault: Pubkey,
+}
+
+#[derive(Args)]
+struct MarketArgs {
+    #[arg(long)]
+    vault: Pubkey,
+    /// Phoenix market address
+    #[arg(long)]
+    market: Pubkey,
+}
+
+#[derive(Args)]
+struct MarketTransferArgs {
+    #[arg(long)]
+    vault: Pubkey,
+    #[arg(long)]
+    market: Pubkey,
+    #[arg(long, default_value_t = 0)]
+    quote_lots: u64,
+    #[arg(long, default_value_t = 0)]
+    base_lots: u64,
+}
+
+#[derive(Args)]
+struct PlaceOrderArgs {
+    #[arg(long)]
+    vault: Pubkey,
+    #[arg(long)]
+    market: Pubkey,
+    #[arg(long, value_enum)]
+    side: OrderSide,
+    #[arg(long)]
+    price_in_ticks: u64,
+    #[arg(long)]
+    base_lots: u64,
+    /// Reject the order instead of crossing the spread
+    #[arg(long)]
+    post_only: bool,
+}
+
+#[derive(Args)]
+struct CancelOrdersArgs {
+    #[arg(long)]
+    vault: Pubkey,
+    #[arg(long)]
+    market: Pubkey,
+    #[arg(long = "order", required = true, value_parser = commands::parse_cancel_order)]
+    orders: Vec<phoenix_vaults_client::phoenix_vaults::instructions::CancelOrderParams>,
+}
+
+#[derive(Args)]
+struct DepositArgs {
+    #[arg(long)]
+    vault: Pubkey,
+    /// USDC amount, precision: QUOTE_PRECISION
+    #[arg(long)]
+    amount: u64,
+}
+
+#[derive(Args)]
+struct RequestWithdrawArgs {
+    #[arg(long)]
+    vault: Pubkey,
+    #[arg(long)]
+    amount: u64,
+    #[arg(long, value_enum)]
+    unit: WithdrawUnitArg,
+}
+
+#[derive(Args)]
+struct ShowInvestorArgs {
+    #[arg(long)]
+    vault: Pubkey,
+    /// Investor authority, defaults to the signer
+    #[arg(long)]
+    authority: Option<Pubkey>,
+}
+
+#[derive(Args)]
+struct InitVaultArgs {
+    /// Up to 32 characters, the vault address is derived from it
+    #[arg(long)]
+    name: String,
+    #[arg(long)]
+    usdc_mint: Pubkey,
+    #[arg(long)]
+    sol_mint: Pubkey,
+    #[arg(long)]
+    protocol: Pubkey,
+    #[arg(long, default_value_t = 0)]
+    redeem_period: i64,
+    #[arg(long, default_value_t = 0)]
+    max_tokens: u64,
+    #[arg(long, default_value_t = 0)]
+    management_fee: i64,
+    #[arg(long, default_value_t = 0)]
+    min_deposit_amount: u64,
+    #[arg(long, default_value_t = 0)]
+    profit_share: u32,
+    #[arg(long, default_value_t = 0)]
+    hurdle_rate: u32,
+    #[arg(long)]
+    permissioned: bool,
+    #[arg(long, default_value_t = 0)]
+    protocol_fee: u64,
+    #[arg(long, default_value_t = 0)]
+    protocol_profit_share: u32,
+    #[arg(long, default_value_t = 0)]
+    epoch_duration: i64,
+    #[arg(long, default_value_t = 0)]
+    entry_fee_bps: u16,
+    #[arg(long, default_value_t = 0)]
+    exit_fee_bps: u16,
+    #[arg(long, default_value_t = 0)]
+    max_drawdown: u32,
+    #[arg(long, default_value_t = 0)]
+    manager_min_stake: u32,
+    #[arg(long, default_value_t = 0)]
+    liquidity_buffer: u32,
+    #[arg(long, default_value_t = 0)]
+    lockup_duration: i64,
+    #[arg(long, default_value_t = 0)]
+    lockup_penalty_bps: u16,
+    #[arg(long, default_value_t = 0)]
+    liquidation_window: i64,
+    #[arg(long, default_value_t = 0)]
+    fundraising_soft_cap: u64,
+    #[arg(long, default_value_t = 0)]
+    fundraising_deadline: i64,
+}
+
+#[derive(Args)]
+struct UpdateVaultArgs {
+    #[arg(long)]
+    vault: Pubkey,
+    #[arg(long)]
+    redeem_period: Option<i64>,
+    #[arg(long)]
+    max_tokens: Option<u64>,
+    #[arg(long)]
+    management_fee: Option<i64>,
+    #[arg(long)]
+    min_deposit_amount: Option<u64>,
+    #[arg(long)]
+    profit_share: Option<u32>,
+    #[arg(long)]
+    hurdle_rate: Option<u32>,
+    #[arg(long)]
+    permissioned: Option<bool>,
+    #[arg(long)]
+    delegate: Option<Pubkey>,
+    #[arg(long)]
+    entry_fee_bps: Option<u16>,
+    #[arg(long)]
+    exit_fee_bps: Option<u16>,
+    #[arg(long)]
+    max_drawdown: Option<u32>,
+    #[arg(long)]
+    manager_min_stake: Option<u32>,
+    #[arg(long)]
+    liquidity_buffer: Option<u32>,
+    #[arg(long)]
+    lockup_duration: Option<i64>,
+    #[arg(long)]
+    liquidation_window: Option<i64>,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum OrderSide {
+    Bid,
+    Ask,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum WithdrawUnitArg {
+    Shares,
+    Token,
+    SharesPercent,
+}
+
+#[tokio::main]
+async fn main() {
+    let cli = Cli::parse();
+    let config = match Config::new(&cli.url, cli.keypair.as_deref(), cli.json) {
+        Ok(config) => config,
+        Err(e) => exit_with_error(cli.json, e),
+    };
+    let result = match cli.command {
+        Command::InitVault(args) => commands::init_vault(&config, args).await,
+        Command::UpdateVault(args) => commands::update_vault(&config, args).await,
+        Command::ClaimSeat(args) => commands::claim_seat(&config, args).await,
+        Command::MarketDeposit(args) => commands::market_deposit(&config, args).await,
+        Command::MarketWithdraw(args) => commands::market_withdraw(&config, args).await,
+        Command::PlaceOrder(args) => commands::place_order(&config, args).await,
+        Command::CancelAllOrders(args) => commands::cancel_all_orders(&config, args).await,
+        Command::CancelOrders(args) => commands::cancel_orders(&config, args).await,
+        Command::ManagerDeposit(args) => commands::manager_deposit(&config, args).await,
+        Command::ManagerRequestWithdraw(args) => {
+            commands::manager_request_withdraw(&config, args).await
+        }
+        Command::ManagerCancelWithdrawRequest(args) => {
+            commands::manager_cancel_withdraw_request(&config, args).await
+        }
+        Command::ManagerWithdraw(args) => commands::manager_withdraw(&config, args).await,
+        Command::ProtocolRequestWithdraw(args) => {
+            commands::protocol_request_withdraw(&config, args).await
+        }
+        Command::ProtocolCancelWithdrawRequest(args) => {
+            commands::protocol_cancel_withdraw_request(&config, args).await
+        }
+        Command::ProtocolWithdraw(args) => commands::protocol_withdraw(&config, args).await,
+        Command::Show(ShowCommand::Vault(args)) => commands::show_vault(&config, args).await,
+        Command::Show(ShowCommand::Investor(args)) => commands::show_investor(&config, args).await,
+        Command::Show(ShowCommand::Equity(args)) => commands::show_equity(&config, args).await,
+    };
+    match result {
+        Ok(output) => config.print(&output),
+        Err(e) => exit_with_error(cli.json, e),
+    }
+}
+
+fn exit_with_error(json: bool, e: anyhow::Error) -> ! {
+    if json {
+        println!("{}", serde_json::json!({ "error": format!("{:#}", e) }));
+    } else {
+        eprintln!("Error: {:#}", e);
+    }
+    std::process::exit(1);
+}
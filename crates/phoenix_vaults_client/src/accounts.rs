@@ -0,0 +1,58 @@
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::{Discriminator, ZeroCopy};
+use phoenix_vaults::state::{Investor, MarketRegistry, Vault};
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_sdk::commitment_config::CommitmentConfig;
+
+/// Deserializes a zero-copy program account from its raw data, discriminator included.
+pub fn deserialize_account<T: ZeroCopy + Discriminator>(data: &[u8]) -> anyhow::Result<T> {
+    let size = std::mem::size_of::<T>();
+    if data.len() < 8 + size {
+        return Err(anyhow::anyhow!(
+            "Account data is {} bytes, expected at least {}",
+            data.len(),
+            8 + size
+        ));
+    }
+    let (discriminator, bytes) = data.split_at(8);
+    if discriminator != T::DISCRIMINATOR {
+        return Err(anyhow::anyhow!("Account discriminator mismatch"));
+    }
+    Ok(bytemuck::try_pod_read_unaligned::<T>(&bytes[..size])
+        .map_err(|e| anyhow::anyhow!("Failed to read account: {:?}", e))?)
+}
+
+pub async fn fetch_account<T: ZeroCopy + Discriminator>(
+    client: &RpcClient,
+    key: &Pubkey,
+) -> anyhow::Result<T> {
+    let account = client
+        .get_account_with_commitment(key, CommitmentConfig::processed())
+        .await
+        .map_err(|e| anyhow::anyhow!("{:?}", e))?
+        .value
+        .ok_or(anyhow::anyhow!("Account not found: {:?}", key))?;
+    if account.owner != phoenix_vaults::ID {
+        return Err(anyhow::anyhow!(
+            "Account {:?} is owned by {:?}, not phoenix-vaults",
+            key,
+            account.owner
+        ));
+    }
+    deserialize_account(&account.data)
+}
+
+pub async fn fetch_vault(client: &RpcClient, vault: &Pubkey) -> anyhow::Result<Vault> {
+    fetch_account(client, vault).await
+}
+
+pub async fn fetch_investor(client: &RpcClient, investor: &Pubkey) -> anyhow::Result<Investor> {
+    fetch_account(client, investor).await
+}
+
+pub async fn fetch_market_registry(
+    client: &RpcClient,
+    market_registry: &Pubkey,
+) -> anyhow::Result<MarketRegistry> {
+    fetch_account(client, market_registry).await
+}
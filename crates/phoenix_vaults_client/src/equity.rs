@@ -0,0 +1,102 @@
+use std::collections::HashMap;
+
+use anchor_lang::prelude::Pubkey;
+use anchor_spl::token::spl_token::solana_program::program_pack::Pack;
+use anchor_spl::token::spl_token::state::Account as TokenAccount;
+use phoenix_vaults::state::{calculate_equity, MarketRegistry, Vault};
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_sdk::commitment_config::CommitmentConfig;
+
+use crate::{
+    equity_remaining_accounts, fetch_market_registry, fetch_vault, get_market_registry_address,
+};
+
+/// Equity and share price of a vault, computed exactly as the program computes them.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct VaultValuation {
+    /// USDC owned by current shareholders, precision: QUOTE_PRECISION
+    pub equity: u64,
+    /// USDC per share, precision: PRICE_PRECISION. `None` if the vault has no shares
+    pub share_price: Option<u64>,
+}
+
+/// Vault equity from fetched market account data, in any order.
+/// The markets are ordered the way the program reads its remaining accounts before valuing the vault.
+pub fn vault_equity(
+    vault: &Vault,
+    vault_usdc_amount: u64,
+    registry: &MarketRegistry,
+    market_accounts: &HashMap<Pubkey, Vec<u8>>,
+) -> anyhow::Result<u64> {
+    let markets = equity_remaining_accounts(vault, registry)
+        .iter()
+        .map(|meta| {
+            market_accounts
+                .get(&meta.pubkey)
+                .map(|data| (meta.pubkey, data.as_slice()))
+                .ok_or(anyhow::anyhow!("Market account missing: {:?}", meta.pubkey))
+        })
+        .collect::<anyhow::Result<Vec<(Pubkey, &[u8])>>>()?;
+    calculate_equity(vault, vault_usdc_amount, registry, &markets)
+        .map_err(|e| anyhow::anyhow!("{:?}", e))
+}
+
+pub fn vault_valuation(
+    vault: &Vault,
+    vault_usdc_amount: u64,
+    registry: &MarketRegistry,
+    market_accounts: &HashMap<Pubkey, Vec<u8>>,
+) -> anyhow::Result<VaultValuation> {
+    let equity = vault_equity(vault, vault_usdc_amount, registry, market_accounts)?;
+    let share_price = vault
+        .share_price(equity)
+        .map_err(|e| anyhow::anyhow!("{:?}", e))?;
+    Ok(VaultValuation {
+        equity,
+        share_price,
+    })
+}
+
+/// Fetches the vault, its USDC token account and every market it is valued over, then values it.
+pub async fn fetch_vault_valuation(
+    client: &RpcClient,
+    vault_key: &Pubkey,
+) -> anyhow::Result<VaultValuation> {
+    let vault = fetch_vault(client, vault_key).await?;
+    let registry = fetch_market_registry(client, &get_market_registry_address()).await?;
+
+    let vault_usdc = vault.usdc_token_account;
+    let market_keys: Vec<Pubkey> = equity_remaining_accounts(&vault, &registry)
+        .iter()
+        .map(|meta| meta.pubkey)
+        .collect();
+    let keys: Vec<Pubkey> = std::iter::once(vault_usdc)
+        .chain(market_keys.iter().copied())
+        .collect();
+
+    // one request so the token balance and markets are read at the same slot
+    let accounts = client
+        .get_multiple_accounts_with_commitment(&keys, CommitmentConfig::processed())
+        .await
+        .map_err(|e| anyhow::anyhow!("{:?}", e))?
+        .value;
+    let mut accounts = keys.iter().zip(accounts).map(|(key, account)| {
+        account
+            .map(|account| account.data)
+            .ok_or(anyhow::anyhow!("Account not found: {:?}", key))
+    });
+
+    let vault_usdc_data = accounts
+        .next()
+        .ok_or(anyhow::anyhow!("Account not found: {:?}", vault_usdc))??;
+    let vault_usdc_amount = TokenAccount::unpack(&vault_usdc_data)
+        .map_err(|e| anyhow::anyhow!("Failed to unpack token account: {:?}", e))?
+        .amount;
+    let market_accounts = market_keys
+        .into_iter()
+        .zip(accounts)
+        .map(|(key, data)| data.map(|data| (key, data)))
+        .collect::<anyhow::Result<HashMap<Pubkey, Vec<u8>>>>()?;
+
+    vault_valuation(&vault, vault_usdc_amount, &registry, &market_accounts)
+}
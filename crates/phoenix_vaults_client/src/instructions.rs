@@ -0,0 +1,205 @@
+use anchor_lang::prelude::AccountMeta;
+use anchor_lang::solana_program::instruction::Instruction;
+use anchor_lang::{InstructionData, ToAccountMetas};
+use phoenix_vaults::instructions::{
+    CancelMultipleOrdersParams, MarketLookupTableParams, PlaceOrderParams, UpdateVaultParams,
+    VaultParams,
+};
+use phoenix_vaults::state::{MarketTransferParams, WithdrawUnit};
+
+/// Appends `remaining_accounts` to the Anchor accounts of a phoenix-vaults instruction.
+pub fn build_instruction(
+    accounts: impl ToAccountMetas,
+    data: impl InstructionData,
+    remaining_accounts: Vec<AccountMeta>,
+) -> Instruction {
+    let mut metas = accounts.to_account_metas(None);
+    metas.extend(remaining_accounts);
+    Instruction {
+        program_id: phoenix_vaults::ID,
+        accounts: metas,
+        data: data.data(),
+    }
+}
+
+/// Declares a builder named after the `lib.rs` entry point that takes its Anchor accounts,
+/// its arguments, and the remaining accounts (see [`crate::remaining_accounts`]).
+macro_rules! instruction_builder {
+    ($name:ident, $accounts:ident, $data:ident $(, $arg:ident: $ty:ty)*) => {
+        pub fn $name(
+            accounts: phoenix_vaults::accounts::$accounts,
+            $($arg: $ty,)*
+            remaining_accounts: Vec<AccountMeta>,
+        ) -> Instruction {
+            build_instruction(
+                accounts,
+                phoenix_vaults::instruction::$data { $($arg),* },
+                remaining_accounts,
+            )
+        }
+    };
+}
+
+instruction_builder!(initialize_vault, InitializeVault, InitializeVault, params: VaultParams);
+instruction_builder!(initialize_investor, InitializeInvestor, InitializeInvestor);
+instruction_builder!(
+    initialize_market_registry,
+    InitializeMarketRegistry,
+    InitializeMarketRegistry,
+    params: MarketLookupTableParams
+);
+instruction_builder!(
+    investor_deposit,
+    InvestorDeposit,
+    InvestorDeposit,
+    amount: u64,
+    min_shares_out: Option<u128>,
+    deadline: Option<i64>
+);
+instruction_builder!(
+    investor_withdraw,
+    InvestorWithdraw,
+    InvestorWithdraw,
+    min_amount_out: Option<u64>,
+    deadline: Option<i64>
+);
+instruction_builder!(
+    investor_withdraw_in_kind,
+    InvestorWithdrawInKind,
+    InvestorWithdrawInKind
+);
+instruction_builder!(claim_seat, ClaimSeat, ClaimSeat);
+instruction_builder!(place_limit_order, PlaceLimitOrder, PlaceLimitOrder, params: PlaceOrderParams);
+instruction_builder!(
+    investor_request_withdraw,
+    InvestorRequestWithdraw,
+    InvestorRequestWithdraw,
+    withdraw_amount: u64,
+    withdraw_unit: WithdrawUnit
+);
+instruction_builder!(
+    cancel_withdraw_request,
+    InvestorCancelWithdrawRequest,
+    CancelWithdrawRequest
+);
+instruction_builder!(market_deposit, MarketDeposit, MarketDeposit, params: MarketTransferParams);
+instruction_builder!(market_withdraw, MarketWithdraw, MarketWithdraw, params: MarketTransferParams);
+instruction_builder!(
+    appoint_investor_liquidator,
+    AppointInvestorLiquidator,
+    AppointInvestorLiquidator
+);
+instruction_builder!(
+    appoint_manager_liquidator,
+    AppointManagerLiquidator,
+    AppointManagerLiquidator
+);
+instruction_builder!(
+    appoint_protocol_liquidator,
+    AppointProtocolLiquidator,
+    AppointProtocolLiquidator
+);
+instruction_builder!(
+    investor_liquidate_usdc_market,
+    InvestorLiquidateUsdcMarket,
+    InvestorLiquidateUsdcMarket
+);
+instruction_builder!(
+    investor_liquidate_sol_market,
+    InvestorLiquidateSolMarket,
+    InvestorLiquidateSolMarket
+);
+instruction_builder!(
+    manager_liquidate_usdc_market,
+    ManagerLiquidateUsdcMarket,
+    ManagerLiquidateUsdcMarket
+);
+instruction_builder!(
+    manager_liquidate_sol_market,
+    ManagerLiquidateSolMarket,
+    ManagerLiquidateSolMarket
+);
+instruction_builder!(
+    protocol_liquidate_sol_market,
+    ProtocolLiquidateSolMarket,
+    ProtocolLiquidateSolMarket
+);
+instruction_builder!(
+    protocol_liquidate_usdc_market,
+    ProtocolLiquidateUsdcMarket,
+    ProtocolLiquidateUsdcMarket
+);
+instruction_builder!(update_vault, UpdateVault, UpdateVault, params: UpdateVaultParams);
+instruction_builder!(migrate_vault, MigrateVault, MigrateVault);
+instruction_builder!(manager_withdraw, ManagerWithdraw, ManagerWithdraw);
+instruction_builder!(manager_deposit, ManagerDeposit, ManagerDeposit, amount: u64);
+instruction_builder!(
+    manager_request_withdraw,
+    ManagerRequestWithdraw,
+    ManagerRequestWithdraw,
+    withdraw_amount: u64,
+    withdraw_unit: WithdrawUnit
+);
+instruction_builder!(
+    manager_cancel_withdraw_request,
+    ManagerCancelWithdrawRequest,
+    ManagerCancelWithdrawRequest
+);
+instruction_builder!(protocol_withdraw, ProtocolWithdraw, ProtocolWithdraw);
+instruction_builder!(
+    protocol_request_withdraw,
+    ProtocolRequestWithdraw,
+    ProtocolRequestWithdraw,
+    withdraw_amount: u64,
+    withdraw_unit: WithdrawUnit
+);
+instruction_builder!(
+    protocol_cancel_withdraw_request,
+    ProtocolCancelWithdrawRequest,
+    ProtocolCancelWithdrawRequest
+);
+instruction_builder!(settle_epoch, SettleEpoch, SettleEpoch);
+instruction_builder!(crystallize_fees, CrystallizeFees, CrystallizeFees);
+instruction_builder!(
+    initialize_nav_history,
+    InitializeNavHistory,
+    InitializeNavHistory
+);
+instruction_builder!(update_nav_history, UpdateNavHistory, UpdateNavHistory);
+instruction_builder!(
+    initialize_share_price,
+    InitializeSharePrice,
+    InitializeSharePrice,
+    max_staleness_slots: u64
+);
+instruction_builder!(update_share_price, UpdateSharePrice, UpdateSharePrice);
+instruction_builder!(resume_trading, ResumeTrading, ResumeTrading);
+instruction_builder!(wind_down, WindDown, WindDown);
+instruction_builder!(
+    wind_down_liquidate_market,
+    WindDownLiquidateMarket,
+    WindDownLiquidateMarket
+);
+instruction_builder!(finalize_wind_down, FinalizeWindDown, FinalizeWindDown);
+instruction_builder!(
+    investor_claim_wind_down,
+    InvestorClaimWindDown,
+    InvestorClaimWindDown
+);
+instruction_builder!(view_vault_equity, ViewVaultEquity, ViewVaultEquity);
+instruction_builder!(view_investor_equity, ViewInvestorEquity, ViewInvestorEquity);
+instruction_builder!(preview_deposit, PreviewDeposit, PreviewDeposit, amount: u64);
+instruction_builder!(
+    preview_withdraw,
+    PreviewWithdraw,
+    PreviewWithdraw,
+    withdraw_amount: u64,
+    withdraw_unit: WithdrawUnit
+);
+instruction_builder!(cancel_all_orders, CancelAllOrders, CancelAllOrders);
+instruction_builder!(
+    cancel_multiple_orders_by_id,
+    CancelMultipleOrdersById,
+    CancelMultipleOrdersById,
+    params: CancelMultipleOrdersParams
+);
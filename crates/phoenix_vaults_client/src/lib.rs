@@ -0,0 +1,17 @@
+//! Rust client for the phoenix-vaults program: PDA derivations, instruction builders
+//! for every program entry point, account fetching, the Phoenix accounts a vault trades through,
+//! the remaining accounts the program needs to value a vault, and the same valuation off-chain.
+
+pub mod accounts;
+pub mod equity;
+pub mod instructions;
+pub mod market;
+pub mod pda;
+pub mod remaining_accounts;
+
+pub use accounts::*;
+pub use equity::*;
+pub use market::*;
+pub use pda::*;
+pub use phoenix_vaults;
+pub use remaining_accounts::*;
@@ -0,0 +1,89 @@
+use anchor_lang::prelude::Pubkey;
+use phoenix::program::MarketHeader;
+use phoenix_seat_manager::{get_seat_deposit_collector_address, get_seat_manager_address};
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_sdk::commitment_config::CommitmentConfig;
+
+use crate::get_vault_token_address;
+
+/// Phoenix accounts a vault instruction passes through to a market CPI.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PhoenixMarketAccounts {
+    pub market: Pubkey,
+    pub log_authority: Pubkey,
+    pub base_mint: Pubkey,
+    pub quote_mint: Pubkey,
+    /// Market token account for the base mint, seeds are `[b"vault", market, base_mint]` under Phoenix
+    pub market_base_token_account: Pubkey,
+    /// Market token account for the quote mint, seeds are `[b"vault", market, quote_mint]` under Phoenix
+    pub market_quote_token_account: Pubkey,
+    /// The vault's seat on the market
+    pub seat: Pubkey,
+    pub seat_manager: Pubkey,
+    pub seat_deposit_collector: Pubkey,
+    pub vault_base_token_account: Pubkey,
+    pub vault_quote_token_account: Pubkey,
+}
+
+impl PhoenixMarketAccounts {
+    pub fn new(vault: &Pubkey, market: &Pubkey, header: &MarketHeader) -> Self {
+        let base_mint = header.base_params.mint_key;
+        let quote_mint = header.quote_params.mint_key;
+        Self {
+            market: *market,
+            log_authority: phoenix::phoenix_log_authority::id(),
+            base_mint,
+            quote_mint,
+            market_base_token_account: header.base_params.vault_key,
+            market_quote_token_account: header.quote_params.vault_key,
+            seat: phoenix::program::get_seat_address(market, vault).0,
+            seat_manager: get_seat_manager_address(market).0,
+            seat_deposit_collector: get_seat_deposit_collector_address(market).0,
+            vault_base_token_account: get_vault_token_address(vault, &base_mint),
+            vault_quote_token_account: get_vault_token_address(vault, &quote_mint),
+        }
+    }
+}
+
+/// Reads the `MarketHeader` that prefixes Phoenix market account data.
+pub fn deserialize_market_header(data: &[u8]) -> anyhow::Result<MarketHeader> {
+    let size = std::mem::size_of::<MarketHeader>();
+    if data.len() < size {
+        return Err(anyhow::anyhow!(
+            "Market data is {} bytes, expected at least {}",
+            data.len(),
+            size
+        ));
+    }
+    bytemuck::try_pod_read_unaligned::<MarketHeader>(&data[..size])
+        .map_err(|e| anyhow::anyhow!("Failed to read market header: {:?}", e))
+}
+
+pub async fn fetch_market_header(
+    client: &RpcClient,
+    market: &Pubkey,
+) -> anyhow::Result<MarketHeader> {
+    let account = client
+        .get_account_with_commitment(market, CommitmentConfig::processed())
+        .await
+        .map_err(|e| anyhow::anyhow!("{:?}", e))?
+        .value
+        .ok_or(anyhow::anyhow!("Market not found: {:?}", market))?;
+    if account.owner != phoenix::ID {
+        return Err(anyhow::anyhow!(
+            "Market {:?} is owned by {:?}, not Phoenix",
+            market,
+            account.owner
+        ));
+    }
+    deserialize_market_header(&account.data)
+}
+
+pub async fn fetch_phoenix_market_accounts(
+    client: &RpcClient,
+    vault: &Pubkey,
+    market: &Pubkey,
+) -> anyhow::Result<PhoenixMarketAccounts> {
+    let header = fetch_market_header(client, market).await?;
+    Ok(PhoenixMarketAccounts::new(vault, market, &header))
+}
@@ -0,0 +1,51 @@
+use anchor_lang::prelude::Pubkey;
+use anchor_spl::associated_token::get_associated_token_address;
+
+pub const MAX_NAME_LENGTH: usize = 32;
+
+/// Pads `name` with spaces to the 32 bytes the vault PDA is seeded with.
+pub fn encode_name(name: &str) -> anyhow::Result<[u8; MAX_NAME_LENGTH]> {
+    if name.len() > MAX_NAME_LENGTH {
+        return Err(anyhow::anyhow!(
+            "Name ({}) longer than {} characters",
+            name,
+            MAX_NAME_LENGTH
+        ));
+    }
+    let mut encoded = [b' '; MAX_NAME_LENGTH];
+    encoded[..name.len()].copy_from_slice(name.as_bytes());
+    Ok(encoded)
+}
+
+pub fn decode_name(name: &[u8; MAX_NAME_LENGTH]) -> String {
+    String::from_utf8_lossy(name).trim().to_string()
+}
+
+pub fn get_vault_address(name: &[u8; MAX_NAME_LENGTH]) -> Pubkey {
+    Pubkey::find_program_address(&[b"vault", name.as_ref()], &phoenix_vaults::ID).0
+}
+
+pub fn get_investor_address(vault: &Pubkey, authority: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &[b"investor", vault.as_ref(), authority.as_ref()],
+        &phoenix_vaults::ID,
+    )
+    .0
+}
+
+pub fn get_market_registry_address() -> Pubkey {
+    Pubkey::find_program_address(&[b"market_registry"], &phoenix_vaults::ID).0
+}
+
+pub fn get_nav_history_address(vault: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[b"nav_history", vault.as_ref()], &phoenix_vaults::ID).0
+}
+
+pub fn get_share_price_address(vault: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[b"share_price", vault.as_ref()], &phoenix_vaults::ID).0
+}
+
+/// Vault token accounts are associated token accounts owned by the vault PDA.
+pub fn get_vault_token_address(vault: &Pubkey, mint: &Pubkey) -> Pubkey {
+    get_associated_token_address(vault, mint)
+}
@@ -0,0 +1,82 @@
+use anchor_lang::prelude::{AccountMeta, Pubkey};
+use phoenix_vaults::state::{MarketRegistry, Vault};
+
+/// Markets the program needs in the remaining accounts to value the vault.
+///
+/// `MarketMapProvider::equity` walks the vault positions with a single iterator,
+/// so the position markets are kept in the order the vault stores them.
+/// The SOL/USDC market is always needed to price SOL denominated markets
+/// and is searched for separately, so it is appended if the vault has no position in it.
+pub fn equity_remaining_accounts(vault: &Vault, registry: &MarketRegistry) -> Vec<AccountMeta> {
+    let mut markets: Vec<AccountMeta> = vault
+        .positions
+        .iter()
+        .filter(|position| !position.is_available())
+        .map(|position| AccountMeta::new_readonly(position.market, false))
+        .collect();
+    push_market(&mut markets, registry.sol_usdc_market);
+    markets
+}
+
+/// Instructions that trade or liquidate a market also read its trader state from the remaining accounts.
+pub fn market_remaining_accounts(
+    vault: &Vault,
+    registry: &MarketRegistry,
+    market: Pubkey,
+) -> Vec<AccountMeta> {
+    let mut markets = equity_remaining_accounts(vault, registry);
+    push_market(&mut markets, market);
+    markets
+}
+
+fn push_market(markets: &mut Vec<AccountMeta>, market: Pubkey) {
+    if !markets.iter().any(|meta| meta.pubkey == market) {
+        markets.push(AccountMeta::new_readonly(market, false));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use phoenix_vaults::state::MarketPosition;
+
+    #[test]
+    fn test_equity_remaining_accounts_order() {
+        let sol_usdc_market = Pubkey::new_unique();
+        let registry = MarketRegistry {
+            sol_usdc_market,
+            ..MarketRegistry::default()
+        };
+        let jup_sol_market = Pubkey::new_unique();
+        let jup_usdc_market = Pubkey::new_unique();
+
+        let mut vault = Vault::default();
+        vault.positions[0] = MarketPosition {
+            market: jup_usdc_market,
+            base_lots_free: 1,
+            ..MarketPosition::default()
+        };
+        // an emptied position keeps its market but isn't valued
+        vault.positions[1] = MarketPosition {
+            market: Pubkey::new_unique(),
+            ..MarketPosition::default()
+        };
+        vault.positions[2] = MarketPosition {
+            market: jup_sol_market,
+            quote_lots_locked: 1,
+            ..MarketPosition::default()
+        };
+
+        let keys: Vec<Pubkey> = equity_remaining_accounts(&vault, &registry)
+            .iter()
+            .map(|meta| meta.pubkey)
+            .collect();
+        assert_eq!(keys, vec![jup_usdc_market, jup_sol_market, sol_usdc_market]);
+
+        let keys: Vec<Pubkey> = market_remaining_accounts(&vault, &registry, jup_sol_market)
+            .iter()
+            .map(|meta| meta.pubkey)
+            .collect();
+        assert_eq!(keys, vec![jup_usdc_market, jup_sol_market, sol_usdc_market]);
+    }
+}
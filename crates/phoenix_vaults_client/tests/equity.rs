@@ -0,0 +1,147 @@
+use std::collections::HashMap;
+
+use anchor_lang::prelude::Pubkey;
+use phoenix_vaults::state::{equity_from_account_infos, MarketPosition, MarketRegistry, Vault};
+use phoenix_vaults::test_utils::{
+    create_account_info, TestMarket, TestMarketParams, TestTraderLots,
+};
+use phoenix_vaults_client::{equity_remaining_accounts, vault_valuation, VaultValuation};
+
+/// Values the vault as the program's `Context::equity` does, over account infos ordered
+/// by the client's remaining accounts, so a client ordering bug also shows up here.
+fn program_equity(
+    vault: &Vault,
+    vault_usdc_amount: u64,
+    registry: &MarketRegistry,
+    markets: &[TestMarket],
+) -> u64 {
+    let metas = equity_remaining_accounts(vault, registry);
+    let owner = Pubkey::default();
+    let mut lamports: Vec<u64> = vec![0; metas.len()];
+    let mut data: Vec<Vec<u8>> = metas
+        .iter()
+        .map(|meta| {
+            markets
+                .iter()
+                .find(|market| market.key == meta.pubkey)
+                .unwrap()
+                .data
+                .clone()
+        })
+        .collect();
+    let account_infos: Vec<_> = metas
+        .iter()
+        .zip(lamports.iter_mut())
+        .zip(data.iter_mut())
+        .map(|((meta, lamports), data)| create_account_info(&meta.pubkey, lamports, data, &owner))
+        .collect();
+    equity_from_account_infos(vault, vault_usdc_amount, registry, &account_infos).unwrap()
+}
+
+fn client_valuation(
+    vault: &Vault,
+    vault_usdc_amount: u64,
+    registry: &MarketRegistry,
+    markets: &[TestMarket],
+) -> VaultValuation {
+    let market_accounts: HashMap<Pubkey, Vec<u8>> = markets
+        .iter()
+        .map(|market| (market.key, market.data.clone()))
+        .collect();
+    vault_valuation(vault, vault_usdc_amount, registry, &market_accounts).unwrap()
+}
+
+#[test]
+fn test_client_equity_matches_program() {
+    let registry = MarketRegistry {
+        usdc_mint: Pubkey::new_unique(),
+        sol_mint: Pubkey::new_unique(),
+        ..MarketRegistry::default()
+    };
+    let jup_mint = Pubkey::new_unique();
+    let maker = Pubkey::new_unique();
+    let mut vault = Vault {
+        pubkey: Pubkey::new_unique(),
+        usdc_mint: registry.usdc_mint,
+        sol_mint: registry.sol_mint,
+        total_shares: 1_000_000_000,
+        ..Vault::default()
+    };
+
+    // 150 USDC/SOL, 0.01 USDC ticks
+    let sol_usdc = TestMarket::new(TestMarketParams {
+        base_mint: registry.sol_mint,
+        quote_mint: registry.usdc_mint,
+        ..TestMarketParams::default()
+    })
+    .with_bid(&maker, 15_000, 1_000)
+    .with_trader(
+        &vault.pubkey,
+        TestTraderLots {
+            base_lots_free: 1_000,
+            base_lots_locked: 1_000,
+            quote_lots_free: 100_000,
+            ..TestTraderLots::default()
+        },
+    );
+    // 0.05 SOL/JUP, 0.01 SOL ticks
+    let jup_sol = TestMarket::new(TestMarketParams {
+        base_mint: jup_mint,
+        quote_mint: registry.sol_mint,
+        quote_decimals: 9,
+        ..TestMarketParams::default()
+    })
+    .with_bid(&maker, 5, 1_000)
+    .with_trader(
+        &vault.pubkey,
+        TestTraderLots {
+            base_lots_free: 10_000,
+            ..TestTraderLots::default()
+        },
+    );
+    let registry = MarketRegistry {
+        sol_usdc_market: sol_usdc.key,
+        ..registry
+    };
+
+    // the vault has no position in SOL/USDC, it is only needed to price JUP/SOL
+    vault.positions[0] = MarketPosition {
+        market: jup_sol.key,
+        base_lots_free: 10_000,
+        ..MarketPosition::default()
+    };
+    let markets = [sol_usdc, jup_sol];
+    let vault_usdc_amount = 1_000_000_000;
+
+    // 1000 USDC + 10 JUP at 7.5 USDC
+    let expected = 1_075_000_000;
+    assert_eq!(
+        program_equity(&vault, vault_usdc_amount, &registry, &markets),
+        expected
+    );
+    assert_eq!(
+        client_valuation(&vault, vault_usdc_amount, &registry, &markets),
+        VaultValuation {
+            equity: expected,
+            share_price: vault.share_price(expected).unwrap(),
+        }
+    );
+
+    // trading SOL/USDC adds 2 SOL at 150 USDC and 1 USDC to the vault, ahead of JUP/SOL in position order
+    vault.positions.swap(0, 1);
+    vault.positions[0] = MarketPosition {
+        market: registry.sol_usdc_market,
+        base_lots_free: 2_000,
+        quote_lots_free: 100_000,
+        ..MarketPosition::default()
+    };
+    // USDC queued for epoch settlement isn't vault equity
+    vault.epoch_pending_deposits = 100_000_000;
+
+    let expected = 1_276_000_000;
+    let program = program_equity(&vault, vault_usdc_amount, &registry, &markets);
+    let client = client_valuation(&vault, vault_usdc_amount, &registry, &markets);
+    assert_eq!(program, expected);
+    assert_eq!(client.equity, program);
+    assert_eq!(client.share_price, Some(1_276_000));
+}
@@ -0,0 +1,510 @@
+use std::collections::HashMap;
+
+use anchor_spl::associated_token::get_associated_token_address;
+use anchor_spl::associated_token::spl_associated_token_account::instruction::create_associated_token_account_idempotent;
+use anchor_spl::token::spl_token::solana_program::program_pack::Pack;
+use anchor_spl::token::spl_token::state::Account as TokenAccount;
+use phoenix::program::MarketHeader;
+use phoenix_vaults_client::instructions as ix;
+use phoenix_vaults_client::phoenix_vaults::accounts;
+use phoenix_vaults_client::phoenix_vaults::state::{Investor, MarketRegistry, Vault};
+use phoenix_vaults_client::{
+    deserialize_market_header, equity_remaining_accounts, fetch_investor, fetch_market_registry,
+    fetch_vault, get_market_registry_address, get_nav_history_address, market_remaining_accounts,
+    vault_equity, PhoenixMarketAccounts,
+};
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_sdk::account::from_account;
+use solana_sdk::clock::Clock;
+use solana_sdk::commitment_config::CommitmentConfig;
+use solana_sdk::instruction::Instruction;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::{Keypair, Signature};
+use solana_sdk::signer::Signer;
+use solana_sdk::sysvar;
+use solana_sdk::transaction::Transaction;
+
+use crate::scan::{fetch_investors_by_authority, withdraw_status, WithdrawStatus};
+use crate::selection::{liquidation_candidates, select_market, Denomination};
+
+pub struct KeeperConfig {
+    /// Seconds before the liquidation window closes after which no new liquidation is sent,
+    /// so a transaction landing late can't fail on an expired window.
+    pub window_margin: i64,
+    /// Transactions sent for one investor per pass, bounding the liquidate and re-check loop.
+    pub max_steps: usize,
+}
+
+/// Everything the program reads to decide whether an investor can liquidate, fetched at one slot.
+struct Snapshot {
+    now: i64,
+    vault: Vault,
+    investor: Investor,
+    registry: MarketRegistry,
+    vault_usdc_amount: u64,
+    /// Account data of the markets the vault is valued over, by key
+    markets: HashMap<Pubkey, Vec<u8>>,
+}
+
+impl Snapshot {
+    fn market_header(&self, market: &Pubkey) -> anyhow::Result<MarketHeader> {
+        let data = self
+            .markets
+            .get(market)
+            .ok_or(anyhow::anyhow!("Market account missing: {:?}", market))?;
+        deserialize_market_header(data)
+    }
+
+    fn phoenix_accounts(&self, market: &Pubkey) -> anyhow::Result<PhoenixMarketAccounts> {
+        Ok(PhoenixMarketAccounts::new(
+            &self.vault.pubkey,
+            market,
+            &self.market_header(market)?,
+        ))
+    }
+}
+
+/// What one step of the liquidation sequence did, so the caller knows whether to re-check.
+enum Step {
+    Sent(Signature),
+    Done,
+}
+
+pub struct Keeper {
+    pub client: RpcClient,
+    pub authorities: Vec<Keypair>,
+    pub config: KeeperConfig,
+}
+
+impl Keeper {
+    /// Scans the investors of every authority and drives each unfulfillable withdraw request
+    /// through `appoint_investor_liquidator` → `claim_seat` → `investor_liquidate_*` → `investor_withdraw`.
+    ///
+    /// Each step re-reads the chain before acting, so a pass interrupted at any point,
+    /// or racing another liquidator, resumes from whatever state it finds on the next pass.
+    pub async fn run_once(&self) -> anyhow::Result<()> {
+        for authority in &self.authorities {
+            let investors = fetch_investors_by_authority(&self.client, &authority.pubkey()).await?;
+            for (investor_key, investor) in investors {
+                if !investor.last_withdraw_request.pending() {
+                    continue;
+                }
+                if let Err(e) = self.process_investor(authority, &investor_key).await {
+                    log::error!("Investor {}: {:#}", investor_key, e);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    async fn process_investor(
+        &self,
+        authority: &Keypair,
+        investor_key: &Pubkey,
+    ) -> anyhow::Result<()> {
+        for _ in 0..self.config.max_steps {
+            let snapshot = self.snapshot(investor_key).await?;
+            match self.step(authority, investor_key, &snapshot).await? {
+                Step::Sent(signature) => {
+                    log::info!("Investor {}: sent {}", investor_key, signature)
+                }
+                Step::Done => return Ok(()),
+            }
+        }
+        log::warn!(
+            "Investor {}: request still open after {} transactions, resuming next pass",
+            investor_key,
+            self.config.max_steps
+        );
+        Ok(())
+    }
+
+    async fn step(
+        &self,
+        authority: &Keypair,
+        investor_key: &Pubkey,
+        snapshot: &Snapshot,
+    ) -> anyhow::Result<Step> {
+        let vault = &snapshot.vault;
+        let now = snapshot.now;
+        let signer = authority.pubkey();
+        let sol_usdc_header = snapshot.market_header(&snapshot.registry.sol_usdc_market)?;
+        let status = withdraw_status(
+            vault,
+            &snapshot.investor,
+            snapshot.vault_usdc_amount,
+            &sol_usdc_header,
+            now,
+        );
+        match status {
+            WithdrawStatus::NoRequest | WithdrawStatus::Blocked => return Ok(Step::Done),
+            WithdrawStatus::Maturing { ready_ts } => {
+                log::debug!("Investor {}: request matures at {}", investor_key, ready_ts);
+                return Ok(Step::Done);
+            }
+            WithdrawStatus::Withdrawable => {
+                let signature = self.withdraw(authority, investor_key, snapshot).await?;
+                return Ok(Step::Sent(signature));
+            }
+            WithdrawStatus::Unfulfillable => {}
+        }
+
+        let window_end = vault
+            .liquidation_start_ts
+            .saturating_add(vault.get_liquidation_window());
+        if vault.liquidator != signer {
+            if vault.in_liquidation() && !vault.liquidation_expired(now) {
+                log::info!(
+                    "Vault {}: liquidated by {} until {}, retrying after",
+                    vault.pubkey,
+                    vault.liquidator,
+                    window_end
+                );
+                return Ok(Step::Done);
+            }
+            let signature = self
+                .appoint_liquidator(authority, investor_key, snapshot)
+                .await?;
+            return Ok(Step::Sent(signature));
+        }
+        if vault.liquidation_expired(now) {
+            // the program only lets another signer take over an expired liquidation
+            log::warn!(
+                "Vault {}: liquidation window expired at {}, waiting for another liquidator or the manager to reset it",
+                vault.pubkey,
+                window_end
+            );
+            return Ok(Step::Done);
+        }
+        if window_end.saturating_sub(now) <= self.config.window_margin {
+            log::info!(
+                "Vault {}: liquidation window closes at {}, not sending another liquidation",
+                vault.pubkey,
+                window_end
+            );
+            return Ok(Step::Done);
+        }
+
+        let market_data: Vec<(Pubkey, &[u8])> = snapshot
+            .markets
+            .iter()
+            .map(|(key, data)| (*key, data.as_slice()))
+            .collect();
+        let equity = vault_equity(
+            vault,
+            snapshot.vault_usdc_amount,
+            &snapshot.registry,
+            &snapshot.markets,
+        )?;
+        let withdraw_value = snapshot
+            .investor
+            .last_withdraw_request
+            .withdraw_value(vault, equity)
+            .map_err(|e| anyhow::anyhow!("{:?}", e))?;
+        let shortfall =
+            vault.unfulfilled_withdraw_value(withdraw_value, snapshot.vault_usdc_amount);
+        let candidates = liquidation_candidates(vault, &snapshot.registry, &market_data)?;
+        let Some(candidate) = select_market(&candidates, shortfall) else {
+            log::warn!(
+                "Vault {}: no market has free lots with bids to cover {} USDC atoms",
+                vault.pubkey,
+                shortfall
+            );
+            return Ok(Step::Done);
+        };
+        log::info!(
+            "Vault {}: liquidating {:?} market {} for ~{} of {} USDC atoms",
+            vault.pubkey,
+            candidate.denomination,
+            candidate.market,
+            candidate.liquidatable_usdc,
+            shortfall
+        );
+        let signature = self
+            .liquidate(
+                authority,
+                investor_key,
+                snapshot,
+                &candidate.market,
+                candidate.denomination,
+            )
+            .await?;
+        Ok(Step::Sent(signature))
+    }
+
+    async fn snapshot(&self, investor_key: &Pubkey) -> anyhow::Result<Snapshot> {
+        let investor = fetch_investor(&self.client, investor_key).await?;
+        let vault = fetch_vault(&self.client, &investor.vault).await?;
+        let registry = fetch_market_registry(&self.client, &get_market_registry_address()).await?;
+        let market_keys: Vec<Pubkey> = equity_remaining_accounts(&vault, &registry)
+            .iter()
+            .map(|meta| meta.pubkey)
+            .collect();
+        let keys: Vec<Pubkey> = [sysvar::clock::ID, vault.usdc_token_account]
+            .into_iter()
+            .chain(market_keys.iter().copied())
+            .collect();
+        // one request so the clock, token balance and markets are read at the same slot
+        let response = self
+            .client
+            .get_multiple_accounts_with_commitment(&keys, CommitmentConfig::confirmed())
+            .await
+            .map_err(|e| anyhow::anyhow!("{:?}", e))?
+            .value;
+        let mut accounts = keys
+            .iter()
+            .zip(response)
+            .map(|(key, account)| account.ok_or(anyhow::anyhow!("Account not found: {:?}", key)));
+        let clock: Clock = accounts
+            .next()
+            .ok_or(anyhow::anyhow!("Clock sysvar not found"))?
+            .map(|account| from_account(&account))?
+            .ok_or(anyhow::anyhow!("Failed to deserialize clock sysvar"))?;
+        let vault_usdc_data = accounts
+            .next()
+            .ok_or(anyhow::anyhow!(
+                "Account not found: {:?}",
+                vault.usdc_token_account
+            ))??
+            .data;
+        let vault_usdc_amount = TokenAccount::unpack(&vault_usdc_data)
+            .map_err(|e| anyhow::anyhow!("Failed to unpack token account: {:?}", e))?
+            .amount;
+        let markets = market_keys
+            .into_iter()
+            .zip(accounts)
+            .map(|(key, account)| account.map(|account| (key, account.data)))
+            .collect::<anyhow::Result<HashMap<Pubkey, Vec<u8>>>>()?;
+        Ok(Snapshot {
+            now: clock.unix_timestamp,
+            vault,
+            investor,
+            registry,
+            vault_usdc_amount,
+            markets,
+        })
+    }
+
+    async fn send(&self, authority: &Keypair, ixs: &[Instruction]) -> anyhow::Result<Signature> {
+        let blockhash = self
+            .client
+            .get_latest_blockhash()
+            .await
+            .map_err(|e| anyhow::anyhow!("{:?}", e))?;
+        let tx = Transaction::new_signed_with_payer(
+            ixs,
+            Some(&authority.pubkey()),
+            &[authority],
+            blockhash,
+        );
+        self.client
+            .send_and_confirm_transaction(&tx)
+            .await
+            .map_err(|e| anyhow::anyhow!("{:?}", e))
+    }
+
+    async fn account_exists(&self, key: &Pubkey) -> anyhow::Result<bool> {
+        Ok(self
+            .client
+            .get_account_with_commitment(key, CommitmentConfig::confirmed())
+            .await
+            .map_err(|e| anyhow::anyhow!("{:?}", e))?
+            .value
+            .is_some())
+    }
+
+    async fn appoint_liquidator(
+        &self,
+        authority: &Keypair,
+        investor_key: &Pubkey,
+        snapshot: &Snapshot,
+    ) -> anyhow::Result<Signature> {
+        let instruction = ix::appoint_investor_liquidator(
+            accounts::AppointInvestorLiquidator {
+                vault: snapshot.vault.pubkey,
+                investor: *investor_key,
+                authority: authority.pubkey(),
+                market_registry: get_market_registry_address(),
+                vault_quote_token_account: snapshot.vault.usdc_token_account,
+            },
+            equity_remaining_accounts(&snapshot.vault, &snapshot.registry),
+        );
+        self.send(authority, &[instruction]).await
+    }
+
+    /// Claims the vault's seat on `market` if it has none, which the appointed liquidator is allowed to do.
+    async fn claim_seat_instruction(
+        &self,
+        authority: &Keypair,
+        vault: &Pubkey,
+        market: &PhoenixMarketAccounts,
+    ) -> anyhow::Result<Option<Instruction>> {
+        if self.account_exists(&market.seat).await? {
+            return Ok(None);
+        }
+        Ok(Some(ix::claim_seat(
+            accounts::ClaimSeat {
+                vault: *vault,
+                delegate: authority.pubkey(),
+                phoenix: phoenix::ID,
+                log_authority: market.log_authority,
+                market: market.market,
+                seat_manager: market.seat_manager,
+                seat_deposit_collector: market.seat_deposit_collector,
+                payer: authority.pubkey(),
+                seat: market.seat,
+                system_program: solana_sdk::system_program::ID,
+                phoenix_seat_manager: phoenix_seat_manager::ID,
+            },
+            vec![],
+        )))
+    }
+
+    async fn liquidate(
+        &self,
+        authority: &Keypair,
+        investor_key: &Pubkey,
+        snapshot: &Snapshot,
+        market: &Pubkey,
+        denomination: Denomination,
+    ) -> anyhow::Result<Signature> {
+        let vault = &snapshot.vault;
+        let signer = authority.pubkey();
+        let investor_usdc_token_account = get_associated_token_address(&signer, &vault.usdc_mint);
+        let market_accounts = snapshot.phoenix_accounts(market)?;
+        let remaining_accounts = market_remaining_accounts(vault, &snapshot.registry, *market);
+
+        let mut ixs = vec![
+            create_associated_token_account_idempotent(
+                &signer,
+                &signer,
+                &vault.usdc_mint,
+                &anchor_spl::token::ID,
+            ),
+            // the program swaps through the vault's base token account
+            create_associated_token_account_idempotent(
+                &signer,
+                &vault.pubkey,
+                &market_accounts.base_mint,
+                &anchor_spl::token::ID,
+            ),
+        ];
+        ixs.extend(
+            self.claim_seat_instruction(authority, &vault.pubkey, &market_accounts)
+                .await?,
+        );
+
+        let instruction = match denomination {
+            Denomination::Usdc => ix::investor_liquidate_usdc_market(
+                accounts::InvestorLiquidateUsdcMarket {
+                    vault: vault.pubkey,
+                    investor: *investor_key,
+                    authority: signer,
+                    market_registry: get_market_registry_address(),
+                    investor_usdc_token_account,
+                    phoenix: phoenix::ID,
+                    log_authority: market_accounts.log_authority,
+                    market: market_accounts.market,
+                    seat: market_accounts.seat,
+                    base_mint: market_accounts.base_mint,
+                    usdc_mint: vault.usdc_mint,
+                    vault_base_token_account: market_accounts.vault_base_token_account,
+                    vault_usdc_token_account: vault.usdc_token_account,
+                    market_base_token_account: market_accounts.market_base_token_account,
+                    market_usdc_token_account: market_accounts.market_quote_token_account,
+                    token_program: anchor_spl::token::ID,
+                },
+                remaining_accounts,
+            ),
+            Denomination::Sol => {
+                let sol_usdc = snapshot.phoenix_accounts(&snapshot.registry.sol_usdc_market)?;
+                ixs.extend(
+                    self.claim_seat_instruction(authority, &vault.pubkey, &sol_usdc)
+                        .await?,
+                );
+                ix::investor_liquidate_sol_market(
+                    accounts::InvestorLiquidateSolMarket {
+                        vault: vault.pubkey,
+                        investor: *investor_key,
+                        authority: signer,
+                        market_registry: get_market_registry_address(),
+                        investor_usdc_token_account,
+                        phoenix: phoenix::ID,
+                        log_authority: market_accounts.log_authority,
+                        market: market_accounts.market,
+                        seat: market_accounts.seat,
+                        base_mint: market_accounts.base_mint,
+                        sol_mint: vault.sol_mint,
+                        usdc_mint: vault.usdc_mint,
+                        vault_base_token_account: market_accounts.vault_base_token_account,
+                        vault_sol_token_account: vault.sol_token_account,
+                        vault_usdc_token_account: vault.usdc_token_account,
+                        market_base_token_account: market_accounts.market_base_token_account,
+                        market_sol_token_account: market_accounts.market_quote_token_account,
+                        sol_usdc_market: sol_usdc.market,
+                        sol_usdc_market_seat: sol_usdc.seat,
+                        sol_usdc_market_sol_token_account: sol_usdc.market_base_token_account,
+                        sol_usdc_market_usdc_token_account: sol_usdc.market_quote_token_account,
+                        token_program: anchor_spl::token::ID,
+                    },
+                    remaining_accounts,
+                )
+            }
+        };
+        ixs.push(instruction);
+        self.send(authority, &ixs).await
+    }
+
+    async fn withdraw(
+        &self,
+        authority: &Keypair,
+        investor_key: &Pubkey,
+        snapshot: &Snapshot,
+    ) -> anyhow::Result<Signature> {
+        let vault = &snapshot.vault;
+        let signer = authority.pubkey();
+        // withdrawals settle through the SOL/USDC market
+        let sol_usdc = snapshot.phoenix_accounts(&snapshot.registry.sol_usdc_market)?;
+        let nav_history = get_nav_history_address(&vault.pubkey);
+        let nav_history = self
+            .account_exists(&nav_history)
+            .await?
+            .then_some(nav_history);
+        let ixs = [
+            create_associated_token_account_idempotent(
+                &signer,
+                &signer,
+                &vault.usdc_mint,
+                &anchor_spl::token::ID,
+            ),
+            ix::investor_withdraw(
+                accounts::InvestorWithdraw {
+                    vault: vault.pubkey,
+                    investor: *investor_key,
+                    authority: signer,
+                    market_registry: get_market_registry_address(),
+                    nav_history,
+                    investor_quote_token_account: get_associated_token_address(
+                        &signer,
+                        &vault.usdc_mint,
+                    ),
+                    phoenix: phoenix::ID,
+                    log_authority: sol_usdc.log_authority,
+                    market: sol_usdc.market,
+                    seat: sol_usdc.seat,
+                    base_mint: sol_usdc.base_mint,
+                    quote_mint: sol_usdc.quote_mint,
+                    vault_base_token_account: vault.sol_token_account,
+                    vault_quote_token_account: vault.usdc_token_account,
+                    market_base_token_account: sol_usdc.market_base_token_account,
+                    market_quote_token_account: sol_usdc.market_quote_token_account,
+                    token_program: anchor_spl::token::ID,
+                },
+                None,
+                None,
+                equity_remaining_accounts(vault, &snapshot.registry),
+            ),
+        ];
+        self.send(authority, &ixs).await
+    }
+}
@@ -0,0 +1,75 @@
+//! Keeper that settles matured withdraw requests the vault USDC token account can't pay.
+//!
+//! Liquidations are signed by the investor, so the keeper only acts for the investors
+//! whose authority keypairs it is given. For each of their requests it appoints the investor
+//! liquidator, claims any missing seat, liquidates the market that best covers the shortfall,
+//! and withdraws once the vault holds enough USDC.
+
+mod keeper;
+mod scan;
+mod selection;
+
+use std::time::Duration;
+
+use clap::Parser;
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_sdk::commitment_config::CommitmentConfig;
+use solana_sdk::signature::read_keypair_file;
+
+use crate::keeper::{Keeper, KeeperConfig};
+
+#[derive(Parser)]
+#[command(name = "phoenix-vaults-keeper", version, about)]
+struct Args {
+    /// RPC URL, defaults to a local test validator
+    #[arg(long, short, env = "RPC_URL", default_value = "http://127.0.0.1:8899")]
+    url: String,
+    /// Investor authority keypair file, repeat for several investors
+    #[arg(long = "keypair", short, required = true)]
+    keypairs: Vec<String>,
+    /// Seconds between scans
+    #[arg(long, default_value_t = 30)]
+    interval: u64,
+    /// Seconds before a liquidation window closes after which no new liquidation is sent
+    #[arg(long, default_value_t = 60)]
+    window_margin: i64,
+    /// Transactions sent for one investor per scan
+    #[arg(long, default_value_t = 10)]
+    max_steps: usize,
+    /// Scan once and exit
+    #[arg(long)]
+    once: bool,
+}
+
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
+    env_logger::Builder::from_env(env_logger::Env::default().default_filter_or("info")).init();
+    let args = Args::parse();
+
+    let authorities = args
+        .keypairs
+        .iter()
+        .map(|path| {
+            read_keypair_file(path)
+                .map_err(|e| anyhow::anyhow!("Failed to read keypair {}: {:?}", path, e))
+        })
+        .collect::<anyhow::Result<Vec<_>>>()?;
+    let keeper = Keeper {
+        client: RpcClient::new_with_commitment(args.url, CommitmentConfig::confirmed()),
+        authorities,
+        config: KeeperConfig {
+            window_margin: args.window_margin,
+            max_steps: args.max_steps,
+        },
+    };
+
+    loop {
+        if let Err(e) = keeper.run_once().await {
+            log::error!("Scan failed: {:#}", e);
+        }
+        if args.once {
+            return Ok(());
+        }
+        tokio::time::sleep(Duration::from_secs(args.interval)).await;
+    }
+}
@@ -0,0 +1,146 @@
+use anchor_lang::Discriminator;
+use phoenix::program::MarketHeader;
+use phoenix_vaults_client::deserialize_account;
+use phoenix_vaults_client::phoenix_vaults::math::market::quote_atoms_to_quote_lots_rounded_down;
+use phoenix_vaults_client::phoenix_vaults::state::{Investor, Vault, VaultState};
+use solana_account_decoder::UiAccountEncoding;
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_client::rpc_config::{RpcAccountInfoConfig, RpcProgramAccountsConfig};
+use solana_client::rpc_filter::{Memcmp, RpcFilterType};
+use solana_sdk::commitment_config::CommitmentConfig;
+use solana_sdk::pubkey::Pubkey;
+
+/// Byte offset of `Investor::authority` in the account data: discriminator, vault, pubkey.
+const INVESTOR_AUTHORITY_OFFSET: usize = 8 + 32 + 32;
+
+/// Where an investor's last withdraw request stands, as the program would judge it at `now`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum WithdrawStatus {
+    NoRequest,
+    /// The redeem period ends at `ready_ts`
+    Maturing {
+        ready_ts: i64,
+    },
+    /// The vault USDC token account can pay the request, so `investor_withdraw` needs no liquidation
+    Withdrawable,
+    /// Matured but unpayable from the vault USDC token account, so the investor can appoint itself liquidator
+    Unfulfillable,
+    /// The vault doesn't allow liquidations in its current state
+    Blocked,
+}
+
+/// Mirrors the checks of `appoint_investor_liquidator`: the redeem period has passed
+/// and the vault USDC token account can't pay the request, compared in SOL/USDC quote lots.
+pub fn withdraw_status(
+    vault: &Vault,
+    investor: &Investor,
+    vault_usdc_amount: u64,
+    sol_usdc_header: &MarketHeader,
+    now: i64,
+) -> WithdrawStatus {
+    let request = &investor.last_withdraw_request;
+    if !request.pending() {
+        return WithdrawStatus::NoRequest;
+    }
+    let state = vault.state(now);
+    if !matches!(state, VaultState::Active | VaultState::WindDown) {
+        return WithdrawStatus::Blocked;
+    }
+    // a vault winding down has no redeem period
+    let ready_ts = request.ts.saturating_add(vault.redeem_period);
+    if state == VaultState::Active && now < ready_ts {
+        return WithdrawStatus::Maturing { ready_ts };
+    }
+    let quote_lots_available =
+        quote_atoms_to_quote_lots_rounded_down(sol_usdc_header, vault_usdc_amount);
+    let quote_lots_requested =
+        quote_atoms_to_quote_lots_rounded_down(sol_usdc_header, request.value);
+    if quote_lots_available < quote_lots_requested {
+        WithdrawStatus::Unfulfillable
+    } else {
+        WithdrawStatus::Withdrawable
+    }
+}
+
+/// Every `Investor` account whose authority is `authority`, across all vaults.
+pub async fn fetch_investors_by_authority(
+    client: &RpcClient,
+    authority: &Pubkey,
+) -> anyhow::Result<Vec<(Pubkey, Investor)>> {
+    let config = RpcProgramAccountsConfig {
+        filters: Some(vec![
+            RpcFilterType::DataSize((8 + std::mem::size_of::<Investor>()) as u64),
+            RpcFilterType::Memcmp(Memcmp::new_raw_bytes(0, Investor::DISCRIMINATOR.to_vec())),
+            RpcFilterType::Memcmp(Memcmp::new_raw_bytes(
+                INVESTOR_AUTHORITY_OFFSET,
+                authority.to_bytes().to_vec(),
+            )),
+        ]),
+        account_config: RpcAccountInfoConfig {
+            encoding: Some(UiAccountEncoding::Base64),
+            commitment: Some(CommitmentConfig::confirmed()),
+            ..RpcAccountInfoConfig::default()
+        },
+        ..RpcProgramAccountsConfig::default()
+    };
+    let accounts = client
+        .get_program_accounts_with_config(&phoenix_vaults_client::phoenix_vaults::ID, config)
+        .await
+        .map_err(|e| anyhow::anyhow!("{:?}", e))?;
+    accounts
+        .into_iter()
+        .map(|(key, account)| Ok((key, deserialize_account::<Investor>(&account.data)?)))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use phoenix_vaults::state::withdraw_request::WithdrawRequest;
+    use phoenix_vaults::test_utils::{TestMarket, TestMarketParams};
+
+    #[test]
+    fn test_withdraw_status() {
+        let sol_usdc = TestMarket::new(TestMarketParams::default());
+        let header = sol_usdc.header();
+        let now = 1_000_000;
+
+        let vault = Vault {
+            redeem_period: 3_600,
+            ..Vault::default()
+        };
+        let mut investor = Investor::default();
+        assert_eq!(
+            withdraw_status(&vault, &investor, 0, header, now),
+            WithdrawStatus::NoRequest
+        );
+
+        investor.last_withdraw_request = WithdrawRequest {
+            shares: 100_000_000,
+            value: 100_000_000,
+            ts: now - 60,
+        };
+        assert_eq!(
+            withdraw_status(&vault, &investor, 0, header, now),
+            WithdrawStatus::Maturing {
+                ready_ts: now - 60 + 3_600
+            }
+        );
+
+        let now = now + 3_600;
+        assert_eq!(
+            withdraw_status(&vault, &investor, 99_999_999, header, now),
+            WithdrawStatus::Unfulfillable
+        );
+        assert_eq!(
+            withdraw_status(&vault, &investor, 100_000_000, header, now),
+            WithdrawStatus::Withdrawable
+        );
+        // amounts are compared in whole quote lots (10 atoms), as on chain
+        investor.last_withdraw_request.value = 100_000_009;
+        assert_eq!(
+            withdraw_status(&vault, &investor, 100_000_000, header, now),
+            WithdrawStatus::Withdrawable
+        );
+    }
+}
@@ -0,0 +1,243 @@
+use anchor_lang::prelude::Pubkey;
+use phoenix::program::{load_with_dispatch, MarketHeader};
+use phoenix::quantities::WrapperU64;
+use phoenix_vaults_client::deserialize_market_header;
+use phoenix_vaults_client::phoenix_vaults::constants::BPS_PRECISION_U64;
+use phoenix_vaults_client::phoenix_vaults::math::market::{
+    base_atoms_to_base_lots_rounded_down, base_lots_and_price_to_quote_atoms,
+    quote_lots_to_quote_atoms,
+};
+use phoenix_vaults_client::phoenix_vaults::state::{MarketRegistry, Vault};
+
+/// Which liquidation instruction a market needs.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Denomination {
+    /// `investor_liquidate_usdc_market`
+    Usdc,
+    /// `investor_liquidate_sol_market`, which also swaps the SOL on the SOL/USDC market
+    Sol,
+}
+
+/// A vault market position an investor liquidator can sell into USDC.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct LiquidationCandidate {
+    pub market: Pubkey,
+    pub denomination: Denomination,
+    /// USDC atoms that selling the vault's free lots into the bids would raise, net of taker fees
+    pub liquidatable_usdc: u64,
+}
+
+/// Quote atoms raised by selling `base_lots` into the bids of a market, net of the taker fee.
+/// Liquidations sell with an IOC order, so this walks the book rather than valuing at the top bid.
+fn sell_base_lots(header: &MarketHeader, bytes: &[u8], base_lots: u64) -> anyhow::Result<u64> {
+    let market = load_with_dispatch(&header.market_size_params, bytes)
+        .map_err(|e| anyhow::anyhow!("Failed to load market: {:?}", e))?
+        .inner;
+    let mut remaining = base_lots;
+    let mut quote_atoms = 0;
+    for level in market.get_ladder(u64::MAX).bids {
+        if remaining == 0 {
+            break;
+        }
+        let filled = remaining.min(level.size_in_base_lots);
+        quote_atoms += base_lots_and_price_to_quote_atoms(header, filled, level.price_in_ticks);
+        remaining -= filled;
+    }
+    let fee = quote_atoms * market.get_taker_fee_bps() / BPS_PRECISION_U64;
+    Ok(quote_atoms - fee)
+}
+
+/// Quote atoms the vault's free lots on a market are worth to a liquidation.
+fn liquidatable_quote_atoms(
+    vault: &Pubkey,
+    header: &MarketHeader,
+    bytes: &[u8],
+) -> anyhow::Result<u64> {
+    let market = load_with_dispatch(&header.market_size_params, bytes)
+        .map_err(|e| anyhow::anyhow!("Failed to load market: {:?}", e))?
+        .inner;
+    let Some(trader_state) = market.get_trader_state(vault) else {
+        return Ok(0);
+    };
+    let quote_lots_free = trader_state.quote_lots_free.as_u64();
+    let base_lots_free = trader_state.base_lots_free.as_u64();
+    Ok(quote_lots_to_quote_atoms(header, quote_lots_free)
+        + sell_base_lots(header, bytes, base_lots_free)?)
+}
+
+/// Values every vault market position the way an investor liquidation would sell it.
+///
+/// Only free lots are counted since liquidations don't cancel resting orders.
+/// Markets in `markets` can be in any order, and must include the SOL/USDC market
+/// to price SOL denominated markets.
+pub fn liquidation_candidates(
+    vault: &Vault,
+    registry: &MarketRegistry,
+    markets: &[(Pubkey, &[u8])],
+) -> anyhow::Result<Vec<LiquidationCandidate>> {
+    let find = |key: &Pubkey| {
+        markets
+            .iter()
+            .find(|(market, _)| market == key)
+            .map(|(_, data)| *data)
+            .ok_or(anyhow::anyhow!("Market account missing: {:?}", key))
+    };
+    let header_size = std::mem::size_of::<MarketHeader>();
+    let sol_usdc_data = find(&registry.sol_usdc_market)?;
+    let sol_usdc_header = deserialize_market_header(sol_usdc_data)?;
+
+    let mut candidates = vec![];
+    for position in vault.positions.iter().filter(|p| !p.is_available()) {
+        let data = find(&position.market)?;
+        let header = deserialize_market_header(data)?;
+        let quote_atoms = liquidatable_quote_atoms(&vault.pubkey, &header, &data[header_size..])?;
+        let quote_mint = header.quote_params.mint_key;
+        let candidate = if quote_mint == registry.usdc_mint {
+            LiquidationCandidate {
+                market: position.market,
+                denomination: Denomination::Usdc,
+                liquidatable_usdc: quote_atoms,
+            }
+        } else if quote_mint == registry.sol_mint {
+            // the SOL raised is sold again on the SOL/USDC market
+            let sol_lots = base_atoms_to_base_lots_rounded_down(&sol_usdc_header, quote_atoms);
+            LiquidationCandidate {
+                market: position.market,
+                denomination: Denomination::Sol,
+                liquidatable_usdc: sell_base_lots(
+                    &sol_usdc_header,
+                    &sol_usdc_data[header_size..],
+                    sol_lots,
+                )?,
+            }
+        } else {
+            log::warn!(
+                "Skipping market {} with unrecognized quote mint {}",
+                position.market,
+                quote_mint
+            );
+            continue;
+        };
+        candidates.push(candidate);
+    }
+    Ok(candidates)
+}
+
+/// Picks the market to liquidate for a `shortfall` of USDC atoms.
+///
+/// Markets that cover the shortfall in one liquidation come first, USDC denominated ones
+/// before SOL denominated ones since they pay one taker fee instead of two.
+/// Otherwise the market raising the most USDC is liquidated and the rest is left for the next pass.
+pub fn select_market(
+    candidates: &[LiquidationCandidate],
+    shortfall: u64,
+) -> Option<LiquidationCandidate> {
+    candidates
+        .iter()
+        .filter(|c| c.liquidatable_usdc > 0)
+        .max_by_key(|c| {
+            let covers = c.liquidatable_usdc >= shortfall;
+            let usdc = covers && c.denomination == Denomination::Usdc;
+            (covers, usdc, c.liquidatable_usdc)
+        })
+        .copied()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use phoenix_vaults::state::MarketPosition;
+    use phoenix_vaults::test_utils::{TestMarket, TestMarketParams, TestTraderLots};
+
+    #[test]
+    fn test_select_market() {
+        let usdc_mint = Pubkey::new_unique();
+        let sol_mint = Pubkey::new_unique();
+        let jup_mint = Pubkey::new_unique();
+        let vault_key = Pubkey::new_unique();
+        let maker = Pubkey::new_unique();
+
+        // SOL at $100, with 1 SOL of bids at $100 and the rest at $90
+        let sol_usdc = TestMarket::new(TestMarketParams {
+            base_mint: sol_mint,
+            quote_mint: usdc_mint,
+            ..TestMarketParams::default()
+        })
+        .with_bid(&maker, 10_000, 1_000)
+        .with_bid(&maker, 9_000, 100_000)
+        .with_trader(
+            &vault_key,
+            TestTraderLots {
+                base_lots_free: 2_000,
+                ..TestTraderLots::default()
+            },
+        );
+        // JUP at 0.01 SOL, the vault has 50 JUP free and 50 JUP locked in asks
+        let jup_sol = TestMarket::new(TestMarketParams {
+            base_mint: jup_mint,
+            quote_mint: sol_mint,
+            quote_decimals: 9,
+            tick_size_in_quote_lots_per_base_unit: 1,
+            num_quote_lots_per_quote_unit: 1_000_000,
+            ..TestMarketParams::default()
+        })
+        .with_bid(&maker, 10_000, 1_000_000)
+        .with_trader(
+            &vault_key,
+            TestTraderLots {
+                base_lots_free: 50_000,
+                base_lots_locked: 50_000,
+                ..TestTraderLots::default()
+            },
+        );
+
+        let registry = MarketRegistry {
+            sol_usdc_market: sol_usdc.key,
+            usdc_mint,
+            sol_mint,
+            ..MarketRegistry::default()
+        };
+        let mut vault = Vault {
+            pubkey: vault_key,
+            ..Vault::default()
+        };
+        vault.positions[0] = MarketPosition {
+            market: jup_sol.key,
+            base_lots_free: 50_000,
+            base_lots_locked: 50_000,
+            ..MarketPosition::default()
+        };
+        vault.positions[1] = MarketPosition {
+            market: sol_usdc.key,
+            base_lots_free: 2_000,
+            ..MarketPosition::default()
+        };
+
+        let markets = [
+            (sol_usdc.key, sol_usdc.data.as_slice()),
+            (jup_sol.key, jup_sol.data.as_slice()),
+        ];
+        let candidates = liquidation_candidates(&vault, &registry, &markets).unwrap();
+        assert_eq!(candidates.len(), 2);
+
+        // 50 JUP sells for 0.49995 SOL after the taker fee,
+        // of which the 0.499 SOL in whole lots sells for $49.90, less another taker fee
+        let jup = candidates[0];
+        assert_eq!(jup.denomination, Denomination::Sol);
+        assert_eq!(jup.liquidatable_usdc, 49_895_010);
+
+        // 2 SOL sells for $100 + $90 through two bid levels, less a 1 bps taker fee
+        let sol = candidates[1];
+        assert_eq!(sol.denomination, Denomination::Usdc);
+        assert_eq!(sol.liquidatable_usdc, 189_981_000);
+
+        // both cover $40, but the USDC market pays one fee instead of two
+        assert_eq!(select_market(&candidates, 40_000_000), Some(sol));
+        // only the SOL/USDC position covers $100
+        assert_eq!(select_market(&candidates, 100_000_000), Some(sol));
+        // nothing covers $500, so the largest is liquidated first
+        assert_eq!(select_market(&candidates, 500_000_000), Some(sol));
+        assert_eq!(select_market(&[jup], 40_000_000), Some(jup));
+        assert_eq!(select_market(&[], 40_000_000), None);
+    }
+}
@@ -28,8 +28,18 @@ pub const PERCENTAGE_PRECISION_I128: i128 = PERCENTAGE_PRECISION as i128;
 pub const PERCENTAGE_PRECISION_U64: u64 = PERCENTAGE_PRECISION as u64;
 pub const PERCENTAGE_PRECISION_I64: i64 = PERCENTAGE_PRECISION as i64;
 
+pub const BPS_PRECISION: u128 = 10_000; // expo -4 (represents 100%)
+pub const BPS_PRECISION_U64: u64 = BPS_PRECISION as u64;
+
 // TIME
 pub const ONE_HOUR: i64 = 60 * 60;
-pub const TIME_FOR_LIQUIDATION: i64 = ONE_HOUR;
 pub const ONE_DAY: i64 = ONE_HOUR * 24;
 pub const ONE_YEAR: u128 = 31536000;
+pub const MIN_NAV_SAMPLE_INTERVAL: i64 = ONE_HOUR;
+pub const DEFAULT_LIQUIDATION_WINDOW: i64 = ONE_HOUR;
+pub const MIN_LIQUIDATION_WINDOW: i64 = 60 * 10;
+pub const MAX_LIQUIDATION_WINDOW: i64 = ONE_DAY;
+
+// WIND DOWN
+pub const WIND_DOWN_MAX_SLIPPAGE: u64 = PERCENTAGE_PRECISION_U64 / 100; // 1% below the top bid
+pub const WIND_DOWN_MAX_LADDER_LEVELS: u64 = 32;
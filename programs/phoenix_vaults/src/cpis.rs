@@ -1,3 +1,4 @@
+use crate::instructions::InKindMarket;
 use crate::state::MarketTransferParams;
 use anchor_lang::prelude::*;
 use phoenix::program::CancelMultipleOrdersByIdParams;
@@ -41,3 +42,20 @@ pub trait PhoenixCancelMultipleOrdersById {
         params: CancelMultipleOrdersByIdParams,
     ) -> Result<()>;
 }
+
+pub trait PhoenixWithdrawInKind<'info> {
+    fn phoenix_withdraw_in_kind(
+        &self,
+        market: &InKindMarket<'_, 'info>,
+        params: MarketTransferParams,
+    ) -> Result<()>;
+}
+
+pub trait TokenTransferInKind<'info> {
+    fn token_transfer_in_kind(
+        &self,
+        from: &AccountInfo<'info>,
+        to: &AccountInfo<'info>,
+        amount: u64,
+    ) -> Result<()>;
+}
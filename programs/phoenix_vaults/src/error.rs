@@ -87,6 +87,54 @@ pub enum ErrorCode {
     TraderStateNotFound,
     #[msg("MarketPositionNotFound")]
     MarketPositionNotFound,
+    #[msg("InKindMarketAccountsMissing")]
+    InKindMarketAccountsMissing,
+    #[msg("InKindTokenAccountMismatch")]
+    InKindTokenAccountMismatch,
+    #[msg("InKindLotsLocked")]
+    InKindLotsLocked,
+    #[msg("EpochModeDisabled")]
+    EpochModeDisabled,
+    #[msg("EpochNotEnded")]
+    EpochNotEnded,
+    #[msg("EpochUnclaimed")]
+    EpochUnclaimed,
+    #[msg("EpochNotSettled")]
+    EpochNotSettled,
+    #[msg("InvalidInvestorAccount")]
+    InvalidInvestorAccount,
+    #[msg("EpochReservedUsdc")]
+    EpochReservedUsdc,
+    #[msg("NavSampleTooSoon")]
+    NavSampleTooSoon,
+    #[msg("InvalidNavHistory")]
+    InvalidNavHistory,
+    #[msg("InvalidSharePrice")]
+    InvalidSharePrice,
+    #[msg("StaleSharePrice")]
+    StaleSharePrice,
+    #[msg("SlippageExceeded")]
+    SlippageExceeded,
+    #[msg("DeadlineExceeded")]
+    DeadlineExceeded,
+    #[msg("TradingHalted")]
+    TradingHalted,
+    #[msg("TradingNotHalted")]
+    TradingNotHalted,
+    #[msg("InvalidVaultState")]
+    InvalidVaultState,
+    #[msg("WindDownPositionsOpen")]
+    WindDownPositionsOpen,
+    #[msg("WindDownNotFinalized")]
+    WindDownNotFinalized,
+    #[msg("ManagerStakeTooLow")]
+    ManagerStakeTooLow,
+    #[msg("LiquidityBufferBreached")]
+    LiquidityBufferBreached,
+    #[msg("InvestorLockedUp")]
+    InvestorLockedUp,
+    #[msg("InKindEpochMode")]
+    InKindEpochMode,
 }
 
 #[macro_export]
@@ -29,6 +29,14 @@ pub fn is_protocol_for_vault(vault: &AccountLoader<Vault>, protocol: &Signer) ->
     Ok(vault.load()?.protocol.eq(protocol.key))
 }
 
+pub fn is_manager_or_protocol_for_vault(
+    vault: &AccountLoader<Vault>,
+    signer: &Signer,
+) -> Result<bool> {
+    let vault = vault.load()?;
+    Ok(vault.manager.eq(signer.key) || vault.protocol.eq(signer.key))
+}
+
 pub fn is_sol_usdc_market(
     market: &UncheckedAccount,
     registry: &AccountLoader<MarketRegistry>,
@@ -4,7 +4,7 @@ use anchor_spl::token::TokenAccount;
 use crate::constraints::{
     is_authority_for_investor, is_usdc_token_for_vault, is_vault_for_investor,
 };
-use crate::state::{Investor, MarketMapProvider, MarketRegistry, Vault};
+use crate::state::{Investor, MarketMapProvider, MarketRegistry, Vault, VaultState};
 
 /// If the investor can't withdraw their equity from the vault's USDC token account,
 /// then the investor is granted authority to sign for liquidation of the vault position on Phoenix markets.
@@ -16,6 +16,7 @@ pub fn appoint_investor_liquidator<'c: 'info, 'info>(
     let now = Clock::get()?.unix_timestamp;
 
     let mut vault = ctx.accounts.vault.load_mut()?;
+    vault.check_state(now, &[VaultState::Active, VaultState::WindDown])?;
     let investor = ctx.accounts.investor.load()?;
     let registry = ctx.accounts.market_registry.load()?;
     let vault_usdc = &ctx.accounts.vault_quote_token_account;
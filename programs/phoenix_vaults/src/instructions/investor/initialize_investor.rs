@@ -1,7 +1,7 @@
 use anchor_lang::prelude::*;
 
 use crate::error::ErrorCode;
-use crate::state::Vault;
+use crate::state::{Vault, VaultState};
 use crate::{validate, Investor, Size};
 
 pub fn initialize_investor(ctx: Context<InitializeInvestor>) -> Result<()> {
@@ -11,6 +11,8 @@ pub fn initialize_investor(ctx: Context<InitializeInvestor>) -> Result<()> {
     investor.authority = *ctx.accounts.authority.key;
 
     let vault = ctx.accounts.vault.load()?;
+    let now = Clock::get()?.unix_timestamp;
+    vault.check_state(now, &[VaultState::Fundraising, VaultState::Active])?;
     if vault.permissioned {
         validate!(
             vault.manager == *ctx.accounts.payer.key,
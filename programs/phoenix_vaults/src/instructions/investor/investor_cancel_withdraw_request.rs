@@ -4,13 +4,17 @@ use anchor_spl::token::TokenAccount;
 use crate::constraints::{
     is_authority_for_investor, is_usdc_token_for_vault, is_vault_for_investor,
 };
-use crate::state::{Investor, MarketMapProvider, MarketRegistry, Vault};
+use crate::state::{Investor, MarketMapProvider, MarketRegistry, Vault, VaultState};
 
 pub fn investor_cancel_withdraw_request<'c: 'info, 'info>(
     ctx: Context<'_, '_, 'c, 'info, InvestorCancelWithdrawRequest<'info>>,
 ) -> Result<()> {
     let clock = &Clock::get()?;
     let mut vault = ctx.accounts.vault.load_mut()?;
+    vault.check_state(
+        clock.unix_timestamp,
+        &[VaultState::Active, VaultState::WindDown],
+    )?;
     let mut investor = ctx.accounts.investor.load_mut()?;
 
     let registry = ctx.accounts.market_registry.load()?;
@@ -0,0 +1,80 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+
+use crate::constraints::*;
+use crate::cpis::TokenTransfer;
+use crate::declare_vault_seeds;
+use crate::state::{Investor, Vault, VaultState};
+
+/// Once `finalize_wind_down` has closed the vault, the investor claims their pro-rata USDC
+/// of the final equity without a withdraw request or redeem period.
+pub fn investor_claim_wind_down<'c: 'info, 'info>(
+    ctx: Context<'_, '_, 'c, 'info, InvestorClaimWindDown<'info>>,
+) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    let mut vault = ctx.accounts.vault.load_mut()?;
+    vault.check_state(now, &[VaultState::WindDown, VaultState::Closed])?;
+    let mut investor = ctx.accounts.investor.load_mut()?;
+
+    let amount = investor.claim_wind_down(&mut vault, now)?;
+    drop(vault);
+
+    ctx.token_transfer(amount)?;
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct InvestorClaimWindDown<'info> {
+    #[account(mut)]
+    pub vault: AccountLoader<'info, Vault>,
+
+    #[account(
+        mut,
+        seeds = [b"investor", vault.key().as_ref(), authority.key().as_ref()],
+        bump,
+        constraint = is_authority_for_investor(&investor, &authority)?
+    )]
+    pub investor: AccountLoader<'info, Investor>,
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        constraint = is_usdc_mint(&vault, &investor_quote_token_account.mint)?,
+        token::authority = authority,
+    )]
+    pub investor_quote_token_account: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        constraint = is_usdc_token_for_vault(&vault, &vault_quote_token_account)?
+    )]
+    pub vault_quote_token_account: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+impl<'info> TokenTransfer for Context<'_, '_, '_, 'info, InvestorClaimWindDown<'info>> {
+    fn token_transfer(&self, amount: u64) -> Result<()> {
+        declare_vault_seeds!(self.accounts.vault, seeds);
+
+        let cpi_accounts = Transfer {
+            from: self
+                .accounts
+                .vault_quote_token_account
+                .to_account_info()
+                .clone(),
+            to: self
+                .accounts
+                .investor_quote_token_account
+                .to_account_info()
+                .clone(),
+            authority: self.accounts.vault.to_account_info().clone(),
+        };
+        let token_program = self.accounts.token_program.to_account_info().clone();
+        let cpi_context = CpiContext::new_with_signer(token_program, cpi_accounts, seeds);
+        token::transfer(cpi_context, amount)?;
+
+        Ok(())
+    }
+}
@@ -4,15 +4,22 @@ use anchor_spl::token::{self, Token, TokenAccount, Transfer};
 use crate::constraints::*;
 use crate::cpis::TokenTransfer;
 
-use crate::state::{Investor, MarketMapProvider, MarketRegistry, Vault};
+use crate::state::{Investor, MarketMapProvider, MarketRegistry, NavHistory, Vault, VaultState};
 
 pub fn investor_deposit<'c: 'info, 'info>(
     ctx: Context<'_, '_, 'c, 'info, InvestorDeposit<'info>>,
     amount: u64,
+    min_shares_out: Option<u128>,
+    deadline: Option<i64>,
 ) -> Result<()> {
     let clock = &Clock::get()?;
+    Investor::check_deadline(deadline, clock.unix_timestamp)?;
 
     let mut vault = ctx.accounts.vault.load_mut()?;
+    vault.check_state(
+        clock.unix_timestamp,
+        &[VaultState::Fundraising, VaultState::Active],
+    )?;
     let mut investor = ctx.accounts.investor.load_mut()?;
 
     let registry = ctx.accounts.market_registry.load()?;
@@ -20,7 +27,14 @@ pub fn investor_deposit<'c: 'info, 'info>(
     let vault_usdc = &ctx.accounts.vault_quote_token_account;
     let vault_equity = ctx.equity(&vault, vault_usdc, &registry)?;
 
-    investor.deposit(amount, vault_equity, &mut vault, clock.unix_timestamp)?;
+    if let Some(nav_history) = &ctx.accounts.nav_history {
+        nav_history
+            .load_mut()?
+            .record(&vault, vault_equity, clock.unix_timestamp)?;
+    }
+
+    let n_shares = investor.deposit(amount, vault_equity, &mut vault, clock.unix_timestamp)?;
+    Investor::check_min_shares_out(n_shares, min_shares_out, &vault)?;
 
     drop(vault);
 
@@ -56,6 +70,13 @@ pub struct InvestorDeposit<'info> {
     )]
     pub market_registry: AccountLoader<'info, MarketRegistry>,
 
+    #[account(
+        mut,
+        seeds = [b"nav_history", vault.key().as_ref()],
+        bump
+    )]
+    pub nav_history: Option<AccountLoader<'info, NavHistory>>,
+
     #[account(
         mut,
         constraint = is_usdc_mint(&vault, &investor_quote_token_account.mint)?,
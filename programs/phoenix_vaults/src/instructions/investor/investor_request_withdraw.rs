@@ -3,7 +3,7 @@ use anchor_spl::token::TokenAccount;
 
 use crate::constraints::*;
 use crate::math::Cast;
-use crate::state::{Investor, MarketMapProvider, MarketRegistry, Vault, WithdrawUnit};
+use crate::state::{Investor, MarketMapProvider, MarketRegistry, Vault, VaultState, WithdrawUnit};
 
 /// The investor deposits funds to the vault token accounts.
 /// The vault then deposits those funds to various Phoenix markets.
@@ -19,6 +19,10 @@ pub fn investor_request_withdraw<'c: 'info, 'info>(
 ) -> Result<()> {
     let clock = &Clock::get()?;
     let vault = &mut ctx.accounts.vault.load_mut()?;
+    vault.check_state(
+        clock.unix_timestamp,
+        &[VaultState::Active, VaultState::WindDown, VaultState::Closed],
+    )?;
     let mut investor = ctx.accounts.investor.load_mut()?;
 
     let registry = ctx.accounts.market_registry.load()?;
@@ -7,14 +7,22 @@ use crate::constraints::*;
 use crate::cpis::{PhoenixWithdraw, TokenTransfer};
 use crate::declare_vault_seeds;
 use crate::state::{
-    Investor, MarketMapProvider, MarketRegistry, MarketTransferParams, PhoenixProgram, Vault,
+    Investor, MarketMapProvider, MarketRegistry, MarketTransferParams, NavHistory, PhoenixProgram,
+    Vault, VaultState,
 };
 
 pub fn investor_withdraw<'c: 'info, 'info>(
     ctx: Context<'_, '_, 'c, 'info, InvestorWithdraw<'info>>,
+    min_amount_out: Option<u64>,
+    deadline: Option<i64>,
 ) -> Result<()> {
     let clock = &Clock::get()?;
+    Investor::check_deadline(deadline, clock.unix_timestamp)?;
     let mut vault = ctx.accounts.vault.load_mut()?;
+    vault.check_state(
+        clock.unix_timestamp,
+        &[VaultState::Active, VaultState::WindDown, VaultState::Closed],
+    )?;
     let mut investor = ctx.accounts.investor.load_mut()?;
 
     let registry = ctx.accounts.market_registry.load()?;
@@ -22,8 +30,15 @@ pub fn investor_withdraw<'c: 'info, 'info>(
     let vault_usdc = &ctx.accounts.vault_quote_token_account;
     let vault_equity = ctx.equity(&vault, vault_usdc, &registry)?;
 
+    if let Some(nav_history) = &ctx.accounts.nav_history {
+        nav_history
+            .load_mut()?
+            .record(&vault, vault_equity, clock.unix_timestamp)?;
+    }
+
     let (investor_withdraw_amount, finishing_liquidation) =
         investor.withdraw(vault_equity, &mut vault, clock.unix_timestamp)?;
+    Investor::check_min_amount_out(investor_withdraw_amount, min_amount_out)?;
 
     if finishing_liquidation {
         vault.reset_liquidation_delegate();
@@ -65,6 +80,13 @@ pub struct InvestorWithdraw<'info> {
     )]
     pub market_registry: AccountLoader<'info, MarketRegistry>,
 
+    #[account(
+        mut,
+        seeds = [b"nav_history", vault.key().as_ref()],
+        bump
+    )]
+    pub nav_history: Option<AccountLoader<'info, NavHistory>>,
+
     #[account(
         mut,
         constraint = is_usdc_mint(&vault, &investor_quote_token_account.mint)?,
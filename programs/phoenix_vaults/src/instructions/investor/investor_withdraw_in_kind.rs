@@ -0,0 +1,680 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+use phoenix::program::{load_with_dispatch, MarketHeader};
+use phoenix::quantities::WrapperU64;
+use sokoban::ZeroCopy;
+use solana_program::program::invoke_signed;
+
+use crate::constraints::*;
+use crate::cpis::{PhoenixWithdrawInKind, TokenTransferInKind};
+use crate::error::ErrorCode;
+use crate::math::*;
+use crate::state::{
+    Investor, MarketMapProvider, MarketRegistry, MarketTransferParams, NavHistory, PhoenixProgram,
+    Vault, VaultState,
+};
+use crate::{declare_vault_seeds, validate};
+
+/// Number of remaining accounts per vault market position:
+/// market, vault base token account, investor base token account, market base vault, market quote vault.
+const IN_KIND_ACCOUNTS_PER_MARKET: usize = 5;
+
+/// Investor redeems their matured withdraw request in the underlying assets rather than USDC.
+/// The investor receives the same fraction (withdraw value / vault equity) of:
+///     * the vault USDC and SOL token account balances
+///     * the base and quote lots of every vault market position
+///
+/// Base lots are withdrawn from each market to the vault and forwarded to the investor's base token account.
+/// Quote lots are withdrawn to the vault USDC or SOL token account and paid out with the token balances.
+pub fn investor_withdraw_in_kind<'c: 'info, 'info>(
+    ctx: Context<'_, '_, 'c, 'info, InvestorWithdrawInKind<'info>>,
+) -> Result<()> {
+    let clock = &Clock::get()?;
+    let mut vault = ctx.accounts.vault.load_mut()?;
+    vault.check_state(
+        clock.unix_timestamp,
+        &[VaultState::Active, VaultState::WindDown, VaultState::Closed],
+    )?;
+    let mut investor = ctx.accounts.investor.load_mut()?;
+
+    let registry = ctx.accounts.market_registry.load()?;
+
+    let vault_usdc = &ctx.accounts.vault_usdc_token_account;
+    let vault_equity = ctx.equity(&vault, vault_usdc, &registry)?;
+
+    if let Some(nav_history) = &ctx.accounts.nav_history {
+        nav_history
+            .load_mut()?
+            .record(&vault, vault_equity, clock.unix_timestamp)?;
+    }
+    drop(registry);
+
+    // snapshot before market withdrawals land in the vault token accounts
+    let balances = InKindBalances::new(
+        &vault,
+        vault_usdc.amount,
+        ctx.accounts.vault_sol_token_account.amount,
+    );
+
+    let (withdraw_value, finishing_liquidation) =
+        investor.withdraw_in_kind(vault_equity, &mut vault, clock.unix_timestamp)?;
+
+    if finishing_liquidation {
+        vault.reset_liquidation_delegate();
+    }
+
+    let positions = vault.positions;
+    let usdc_mint = vault.usdc_mint;
+    let sol_mint = vault.sol_mint;
+    drop(investor);
+    drop(vault);
+
+    let (mut usdc_out, mut sol_out) = balances.pro_rata(withdraw_value, vault_equity)?;
+
+    let vault_key = ctx.accounts.vault.key();
+    let authority = ctx.accounts.authority.key();
+    let mut cursor = 0;
+    for position in positions.iter() {
+        if position.is_available() {
+            continue;
+        }
+        let market = InKindMarket::next(ctx.remaining_accounts, &mut cursor, &position.market)?;
+        market.validate(&vault_key, &authority)?;
+
+        let (params, quote_atoms) =
+            market.withdraw_params(&vault_key, withdraw_value, vault_equity)?;
+        if market.quote_mint == usdc_mint {
+            usdc_out = usdc_out.safe_add(quote_atoms)?;
+        } else if market.quote_mint == sol_mint {
+            sol_out = sol_out.safe_add(quote_atoms)?;
+        } else {
+            return Err(ErrorCode::UnrecognizedQuoteMint.into());
+        }
+
+        if params.base_lots == 0 && params.quote_lots == 0 {
+            continue;
+        }
+        msg!(
+            "withdrawing in kind {} base lots and {} quote lots from market {:?}",
+            params.base_lots,
+            params.quote_lots,
+            position.market
+        );
+        ctx.phoenix_withdraw_in_kind(&market, params)?;
+
+        let base_atoms = market.base_atoms(params.base_lots)?;
+        if base_atoms > 0 {
+            ctx.token_transfer_in_kind(
+                market.vault_base_token_account,
+                market.investor_base_token_account,
+                base_atoms,
+            )?;
+        }
+    }
+
+    if usdc_out > 0 {
+        ctx.token_transfer_in_kind(
+            &ctx.accounts.vault_usdc_token_account.to_account_info(),
+            &ctx.accounts.investor_usdc_token_account.to_account_info(),
+            usdc_out,
+        )?;
+    }
+    if sol_out > 0 {
+        ctx.token_transfer_in_kind(
+            &ctx.accounts.vault_sol_token_account.to_account_info(),
+            &ctx.accounts.investor_sol_token_account.to_account_info(),
+            sol_out,
+        )?;
+    }
+
+    let mut vault = ctx.accounts.vault.load_mut()?;
+    for position in positions.iter() {
+        if position.is_available() {
+            continue;
+        }
+        let pos = ctx.market_position(&vault, position.market)?;
+        let index = vault.get_market_position_index(&position.market)?;
+        vault.update_market_position(index, pos)?;
+    }
+    drop(vault);
+
+    Ok(())
+}
+
+/// The vault token balances an in-kind withdrawal takes its slice of, snapshotted with the vault equity.
+struct InKindBalances {
+    usdc: u64,
+    sol: u64,
+}
+
+impl InKindBalances {
+    /// USDC reserved for epoch settlement is left out, since the vault equity leaves it out too.
+    fn new(vault: &Vault, vault_usdc_amount: u64, vault_sol_amount: u64) -> Self {
+        Self {
+            usdc: vault_usdc_amount.saturating_sub(vault.epoch_reserved_usdc()),
+            sol: vault_sol_amount,
+        }
+    }
+
+    /// USDC and SOL paid out for `withdraw_value` of `vault_equity`
+    fn pro_rata(&self, withdraw_value: u64, vault_equity: u64) -> Result<(u64, u64)> {
+        Ok((
+            pro_rata(self.usdc, withdraw_value, vault_equity)?,
+            pro_rata(self.sol, withdraw_value, vault_equity)?,
+        ))
+    }
+}
+
+fn pro_rata(amount: u64, withdraw_value: u64, vault_equity: u64) -> Result<u64> {
+    if vault_equity == 0 {
+        return Ok(0);
+    }
+    Ok(
+        get_proportion_u128(amount.cast()?, withdraw_value.cast()?, vault_equity.cast()?)?
+            .cast()?,
+    )
+}
+
+/// The accounts needed to withdraw a slice of a vault market position to the investor.
+pub struct InKindMarket<'a, 'info> {
+    pub market: &'a AccountInfo<'info>,
+    pub vault_base_token_account: &'a AccountInfo<'info>,
+    pub investor_base_token_account: &'a AccountInfo<'info>,
+    pub market_base_token_account: &'a AccountInfo<'info>,
+    pub market_quote_token_account: &'a AccountInfo<'info>,
+    pub base_mint: Pubkey,
+    pub quote_mint: Pubkey,
+}
+
+impl<'a, 'info> InKindMarket<'a, 'info> {
+    /// Find the next account group in the remaining accounts that starts with `market`.
+    pub fn next(
+        remaining_accounts: &'a [AccountInfo<'info>],
+        cursor: &mut usize,
+        market: &Pubkey,
+    ) -> Result<Self> {
+        let start = remaining_accounts
+            .iter()
+            .skip(*cursor)
+            .position(|account| account.key == market)
+            .map(|offset| *cursor + offset)
+            .ok_or(ErrorCode::MarketMissingInRemainingAccounts)?;
+        validate!(
+            start + IN_KIND_ACCOUNTS_PER_MARKET <= remaining_accounts.len(),
+            ErrorCode::InKindMarketAccountsMissing,
+            "missing in-kind token accounts for market {:?}",
+            market
+        )?;
+        *cursor = start + IN_KIND_ACCOUNTS_PER_MARKET;
+
+        let market = &remaining_accounts[start];
+        let account_data = market.try_borrow_data()?;
+        let (header_bytes, _) = account_data.split_at(std::mem::size_of::<MarketHeader>());
+        let header = MarketHeader::load_bytes(header_bytes).ok_or(
+            anchor_lang::error::Error::from(ErrorCode::MarketDeserializationError),
+        )?;
+
+        let in_kind_market = Self {
+            market,
+            vault_base_token_account: &remaining_accounts[start + 1],
+            investor_base_token_account: &remaining_accounts[start + 2],
+            market_base_token_account: &remaining_accounts[start + 3],
+            market_quote_token_account: &remaining_accounts[start + 4],
+            base_mint: header.base_params.mint_key,
+            quote_mint: header.quote_params.mint_key,
+        };
+        validate!(
+            in_kind_market.market_base_token_account.key == &header.base_params.vault_key
+                && in_kind_market.market_quote_token_account.key == &header.quote_params.vault_key,
+            ErrorCode::InKindTokenAccountMismatch,
+            "market vaults do not match market header"
+        )?;
+
+        Ok(in_kind_market)
+    }
+
+    /// The vault and investor base token accounts must be owned by the vault and the investor,
+    /// and hold the market base mint.
+    pub fn validate(&self, vault: &Pubkey, authority: &Pubkey) -> Result<()> {
+        let vault_base = Account::<TokenAccount>::try_from(self.vault_base_token_account)?;
+        validate!(
+            &vault_base.owner == vault && vault_base.mint == self.base_mint,
+            ErrorCode::InKindTokenAccountMismatch,
+            "vault base token account must be owned by the vault with mint {:?}",
+            self.base_mint
+        )?;
+
+        let investor_base = Account::<TokenAccount>::try_from(self.investor_base_token_account)?;
+        validate!(
+            &investor_base.owner == authority && investor_base.mint == self.base_mint,
+            ErrorCode::InKindTokenAccountMismatch,
+            "investor base token account must be owned by the investor with mint {:?}",
+            self.base_mint
+        )?;
+        Ok(())
+    }
+
+    /// The base and quote lots to withdraw from the market, and the quote atoms they are worth.
+    pub fn withdraw_params(
+        &self,
+        vault: &Pubkey,
+        withdraw_value: u64,
+        vault_equity: u64,
+    ) -> Result<(MarketTransferParams, u64)> {
+        let account_data = self.market.try_borrow_data()?;
+        let (header_bytes, bytes) = account_data.split_at(std::mem::size_of::<MarketHeader>());
+        let header = Box::new(MarketHeader::load_bytes(header_bytes).ok_or(
+            anchor_lang::error::Error::from(ErrorCode::MarketDeserializationError),
+        )?);
+        let market_wrapper = load_with_dispatch(&header.market_size_params, bytes)?;
+        let trader_state =
+            market_wrapper
+                .inner
+                .get_trader_state(vault)
+                .ok_or(anchor_lang::error::Error::from(
+                    ErrorCode::TraderStateNotFound,
+                ))?;
+
+        let base_lots_free = trader_state.base_lots_free.as_u64();
+        let quote_lots_free = trader_state.quote_lots_free.as_u64();
+        let base_lots = base_lots_free + trader_state.base_lots_locked.as_u64();
+        let quote_lots = quote_lots_free + trader_state.quote_lots_locked.as_u64();
+
+        let params = MarketTransferParams {
+            base_lots: pro_rata(base_lots, withdraw_value, vault_equity)?,
+            quote_lots: pro_rata(quote_lots, withdraw_value, vault_equity)?,
+        };
+        validate!(
+            params.base_lots <= base_lots_free && params.quote_lots <= quote_lots_free,
+            ErrorCode::InKindLotsLocked,
+            "in-kind slice exceeds free lots, cancel orders first: base {}/{} quote {}/{}",
+            params.base_lots,
+            base_lots_free,
+            params.quote_lots,
+            quote_lots_free
+        )?;
+
+        let quote_atoms = quote_lots_to_quote_atoms(&header, params.quote_lots);
+        Ok((params, quote_atoms))
+    }
+
+    pub fn base_atoms(&self, base_lots: u64) -> Result<u64> {
+        let account_data = self.market.try_borrow_data()?;
+        let (header_bytes, _) = account_data.split_at(std::mem::size_of::<MarketHeader>());
+        let header = MarketHeader::load_bytes(header_bytes).ok_or(
+            anchor_lang::error::Error::from(ErrorCode::MarketDeserializationError),
+        )?;
+        Ok(base_lots_to_base_atoms(header, base_lots))
+    }
+}
+
+#[derive(Accounts)]
+pub struct InvestorWithdrawInKind<'info> {
+    #[account(mut)]
+    pub vault: AccountLoader<'info, Vault>,
+
+    #[account(
+        mut,
+        seeds = [b"investor", vault.key().as_ref(), authority.key().as_ref()],
+        bump,
+        constraint = is_authority_for_investor(&investor, &authority)?
+    )]
+    pub investor: AccountLoader<'info, Investor>,
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [b"market_registry"],
+        bump
+    )]
+    pub market_registry: AccountLoader<'info, MarketRegistry>,
+
+    #[account(
+        mut,
+        seeds = [b"nav_history", vault.key().as_ref()],
+        bump
+    )]
+    pub nav_history: Option<AccountLoader<'info, NavHistory>>,
+
+    #[account(
+        mut,
+        constraint = is_usdc_mint(&vault, &investor_usdc_token_account.mint)?,
+        token::authority = authority,
+    )]
+    pub investor_usdc_token_account: Box<Account<'info, TokenAccount>>,
+    #[account(
+        mut,
+        constraint = is_sol_mint(&vault, &investor_sol_token_account.mint)?,
+        token::authority = authority,
+    )]
+    pub investor_sol_token_account: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        constraint = is_usdc_token_for_vault(&vault, &vault_usdc_token_account)?,
+    )]
+    pub vault_usdc_token_account: Box<Account<'info, TokenAccount>>,
+    #[account(
+        mut,
+        constraint = is_sol_token_for_vault(&vault, &vault_sol_token_account)?,
+    )]
+    pub vault_sol_token_account: Box<Account<'info, TokenAccount>>,
+
+    //
+    // Phoenix CPI accounts
+    //
+    pub phoenix: Program<'info, PhoenixProgram>,
+    /// CHECK: validated in Phoenix CPI
+    pub log_authority: UncheckedAccount<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+impl<'info> PhoenixWithdrawInKind<'info>
+    for Context<'_, '_, '_, 'info, InvestorWithdrawInKind<'info>>
+{
+    fn phoenix_withdraw_in_kind(
+        &self,
+        market: &InKindMarket<'_, 'info>,
+        params: MarketTransferParams,
+    ) -> Result<()> {
+        let vault_quote_token_account =
+            if market.quote_mint == self.accounts.vault_usdc_token_account.mint {
+                self.accounts.vault_usdc_token_account.to_account_info()
+            } else {
+                self.accounts.vault_sol_token_account.to_account_info()
+            };
+
+        let trader_index = 3;
+        let mut ix = phoenix::program::instruction_builders::create_withdraw_funds_with_custom_amounts_instruction(
+            market.market.key,
+            &self.accounts.vault.key(),
+            &market.base_mint,
+            &market.quote_mint,
+            params.base_lots,
+            params.quote_lots
+        );
+        ix.accounts[trader_index].is_signer = true;
+
+        // #[account(0, name = "phoenix_program", desc = "Phoenix program")]
+        // #[account(1, name = "log_authority", desc = "Phoenix log authority")]
+        // #[account(2, writable, name = "market", desc = "This account holds the market state")]
+        // #[account(3, signer, name = "trader")]
+        // #[account(4, writable, name = "base_account", desc = "Trader base token account")]
+        // #[account(5, writable, name = "quote_account", desc = "Trader quote token account")]
+        // #[account(6, writable, name = "base_vault", desc = "Base vault PDA, seeds are [b'vault', market_address, base_mint_address]")]
+        // #[account(7, writable, name = "quote_vault", desc = "Quote vault PDA, seeds are [b'vault', market_address, quote_mint_address]")]
+        // #[account(8, name = "token_program", desc = "Token program")]
+        let accounts = [
+            self.accounts.phoenix.to_account_info(),
+            self.accounts.log_authority.to_account_info(),
+            market.market.clone(),
+            self.accounts.vault.to_account_info(),
+            market.vault_base_token_account.clone(),
+            vault_quote_token_account,
+            market.market_base_token_account.clone(),
+            market.market_quote_token_account.clone(),
+            self.accounts.token_program.to_account_info(),
+        ];
+        declare_vault_seeds!(self.accounts.vault, seeds);
+        invoke_signed(&ix, &accounts, seeds)?;
+
+        Ok(())
+    }
+}
+
+impl<'info> TokenTransferInKind<'info>
+    for Context<'_, '_, '_, 'info, InvestorWithdrawInKind<'info>>
+{
+    fn token_transfer_in_kind(
+        &self,
+        from: &AccountInfo<'info>,
+        to: &AccountInfo<'info>,
+        amount: u64,
+    ) -> Result<()> {
+        declare_vault_seeds!(self.accounts.vault, seeds);
+
+        let cpi_accounts = Transfer {
+            from: from.clone(),
+            to: to.clone(),
+            authority: self.accounts.vault.to_account_info().clone(),
+        };
+        let token_program = self.accounts.token_program.to_account_info().clone();
+        let cpi_context = CpiContext::new_with_signer(token_program, cpi_accounts, seeds);
+        token::transfer(cpi_context, amount)?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{create_account_info, TestMarket, TestMarketParams, TestTraderLots};
+    use anchor_spl::token::spl_token;
+    use solana_program::program_pack::Pack;
+
+    fn token_account_data(mint: Pubkey, owner: Pubkey) -> Vec<u8> {
+        let mut data = vec![0; spl_token::state::Account::LEN];
+        spl_token::state::Account {
+            mint,
+            owner,
+            state: spl_token::state::AccountState::Initialized,
+            ..spl_token::state::Account::default()
+        }
+        .pack_into_slice(&mut data);
+        data
+    }
+
+    fn market_with_vault(vault: &Pubkey, lots: TestTraderLots) -> TestMarket {
+        TestMarket::new(TestMarketParams {
+            base_mint: Pubkey::new_unique(),
+            quote_mint: Pubkey::new_unique(),
+            ..TestMarketParams::default()
+        })
+        .with_trader(vault, lots)
+    }
+
+    fn withdraw_params(
+        market: &TestMarket,
+        vault: &Pubkey,
+        withdraw_value: u64,
+        vault_equity: u64,
+    ) -> Result<(MarketTransferParams, u64)> {
+        let owner = Pubkey::default();
+        let mut lamports = 0;
+        let mut data = market.data.clone();
+        let market_info = create_account_info(&market.key, &mut lamports, &mut data, &owner);
+        let header = market.header();
+        InKindMarket {
+            market: &market_info,
+            vault_base_token_account: &market_info,
+            investor_base_token_account: &market_info,
+            market_base_token_account: &market_info,
+            market_quote_token_account: &market_info,
+            base_mint: header.base_params.mint_key,
+            quote_mint: header.quote_params.mint_key,
+        }
+        .withdraw_params(vault, withdraw_value, vault_equity)
+    }
+
+    #[test]
+    fn test_withdraw_params() {
+        let vault = Pubkey::new_unique();
+        // a quarter of the vault, in SOL/USDC lots of 0.001 SOL and 10 USDC atoms
+        for (name, lots, base_lots, quote_lots, quote_atoms) in [
+            (
+                "free lots",
+                TestTraderLots {
+                    base_lots_free: 1_000,
+                    quote_lots_free: 100_000,
+                    ..TestTraderLots::default()
+                },
+                250,
+                25_000,
+                250_000,
+            ),
+            (
+                "rounded down to whole lots",
+                TestTraderLots {
+                    base_lots_free: 999,
+                    quote_lots_free: 3,
+                    ..TestTraderLots::default()
+                },
+                249,
+                0,
+                0,
+            ),
+            (
+                "locked lots count toward the slice",
+                TestTraderLots {
+                    base_lots_free: 500,
+                    base_lots_locked: 500,
+                    quote_lots_free: 50_000,
+                    quote_lots_locked: 50_000,
+                },
+                250,
+                25_000,
+                250_000,
+            ),
+        ] {
+            let market = market_with_vault(&vault, lots);
+            let (params, atoms) = withdraw_params(&market, &vault, 250, 1_000).unwrap();
+            assert_eq!(params.base_lots, base_lots, "{}", name);
+            assert_eq!(params.quote_lots, quote_lots, "{}", name);
+            assert_eq!(atoms, quote_atoms, "{}", name);
+        }
+
+        let market = market_with_vault(
+            &vault,
+            TestTraderLots {
+                base_lots_free: 1_000,
+                ..TestTraderLots::default()
+            },
+        );
+        let (params, atoms) = withdraw_params(&market, &vault, 0, 0).unwrap();
+        assert_eq!((params.base_lots, params.quote_lots, atoms), (0, 0, 0));
+    }
+
+    #[test]
+    fn test_in_kind_balances() {
+        // a quarter of the vault takes a quarter of the $1,300 vault USDC that isn't reserved, and of the SOL
+        for (name, epoch_pending_deposits, epoch_redemptions_owed, expected) in [
+            ("nothing reserved", 0, 0, (325, 100)),
+            ("pending deposits reserved", 300, 0, (250, 100)),
+            ("redemptions owed reserved", 100, 200, (250, 100)),
+            ("all reserved", 1_300, 0, (0, 100)),
+        ] {
+            let vault = Vault {
+                epoch_pending_deposits,
+                epoch_redemptions_owed,
+                ..Vault::default()
+            };
+            let balances = InKindBalances::new(&vault, 1_300, 400);
+            assert_eq!(balances.pro_rata(500, 2_000).unwrap(), expected, "{}", name);
+        }
+    }
+
+    #[test]
+    fn test_withdraw_params_errors() {
+        let vault = Pubkey::new_unique();
+        for (name, lots) in [
+            (
+                "base lots locked in asks",
+                TestTraderLots {
+                    base_lots_free: 100,
+                    base_lots_locked: 900,
+                    ..TestTraderLots::default()
+                },
+            ),
+            (
+                "quote lots locked in bids",
+                TestTraderLots {
+                    quote_lots_free: 10_000,
+                    quote_lots_locked: 90_000,
+                    ..TestTraderLots::default()
+                },
+            ),
+        ] {
+            let market = market_with_vault(&vault, lots);
+            assert_eq!(
+                withdraw_params(&market, &vault, 250, 1_000).unwrap_err(),
+                anchor_lang::error::Error::from(ErrorCode::InKindLotsLocked),
+                "{}",
+                name
+            );
+        }
+
+        let market = market_with_vault(&Pubkey::new_unique(), TestTraderLots::default());
+        assert_eq!(
+            withdraw_params(&market, &vault, 250, 1_000).unwrap_err(),
+            anchor_lang::error::Error::from(ErrorCode::TraderStateNotFound)
+        );
+    }
+
+    #[test]
+    fn test_validate() {
+        let vault = Pubkey::new_unique();
+        let investor = Pubkey::new_unique();
+        let base_mint = Pubkey::new_unique();
+        let token_program = spl_token::id();
+
+        for (name, vault_base_owner, vault_base_mint, investor_base_owner, valid) in [
+            ("valid", vault, base_mint, investor, true),
+            (
+                "vault base owned by another",
+                investor,
+                base_mint,
+                investor,
+                false,
+            ),
+            (
+                "vault base of another mint",
+                vault,
+                Pubkey::new_unique(),
+                investor,
+                false,
+            ),
+            (
+                "investor base owned by another",
+                vault,
+                base_mint,
+                vault,
+                false,
+            ),
+        ] {
+            let keys = [Pubkey::new_unique(), Pubkey::new_unique()];
+            let mut lamports = [0, 0];
+            let [vault_lamports, investor_lamports] = &mut lamports;
+            let mut vault_data = token_account_data(vault_base_mint, vault_base_owner);
+            let mut investor_data = token_account_data(base_mint, investor_base_owner);
+            let vault_base =
+                create_account_info(&keys[0], vault_lamports, &mut vault_data, &token_program);
+            let investor_base = create_account_info(
+                &keys[1],
+                investor_lamports,
+                &mut investor_data,
+                &token_program,
+            );
+            let market = InKindMarket {
+                market: &vault_base,
+                vault_base_token_account: &vault_base,
+                investor_base_token_account: &investor_base,
+                market_base_token_account: &vault_base,
+                market_quote_token_account: &vault_base,
+                base_mint,
+                quote_mint: Pubkey::new_unique(),
+            };
+            let result = market.validate(&vault, &investor);
+            if valid {
+                assert!(result.is_ok(), "{}", name);
+            } else {
+                assert_eq!(
+                    result.unwrap_err(),
+                    anchor_lang::error::Error::from(ErrorCode::InKindTokenAccountMismatch),
+                    "{}",
+                    name
+                );
+            }
+        }
+    }
+}
@@ -1,17 +1,27 @@
 mod appoint_investor_liquidator;
 mod initialize_investor;
 mod investor_cancel_withdraw_request;
+mod investor_claim_wind_down;
 mod investor_deposit;
 mod investor_liquidate_sol_market;
 mod investor_liquidate_usdc_market;
 mod investor_request_withdraw;
 mod investor_withdraw;
+mod investor_withdraw_in_kind;
+mod preview_deposit;
+mod preview_withdraw;
+mod view_investor_equity;
 
 pub use appoint_investor_liquidator::*;
 pub use initialize_investor::*;
 pub use investor_cancel_withdraw_request::*;
+pub use investor_claim_wind_down::*;
 pub use investor_deposit::*;
 pub use investor_liquidate_sol_market::*;
 pub use investor_liquidate_usdc_market::*;
 pub use investor_request_withdraw::*;
 pub use investor_withdraw::*;
+pub use investor_withdraw_in_kind::*;
+pub use preview_deposit::*;
+pub use preview_withdraw::*;
+pub use view_investor_equity::*;
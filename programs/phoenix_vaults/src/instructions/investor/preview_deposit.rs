@@ -0,0 +1,43 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::TokenAccount;
+
+use crate::constraints::*;
+use crate::state::{DepositPreview, Investor, MarketMapProvider, MarketRegistry, Vault};
+
+/// Read-only instruction that returns the [`DepositPreview`] of an `investor_deposit` of `amount` as return data.
+/// The markets the vault has positions in are passed as remaining accounts.
+pub fn preview_deposit<'c: 'info, 'info>(
+    ctx: Context<'_, '_, 'c, 'info, PreviewDeposit<'info>>,
+    amount: u64,
+) -> Result<DepositPreview> {
+    let now = Clock::get()?.unix_timestamp;
+    let vault = ctx.accounts.vault.load()?;
+    let investor = ctx.accounts.investor.load()?;
+    let registry = ctx.accounts.market_registry.load()?;
+
+    let vault_usdc = &ctx.accounts.vault_usdc_token_account;
+    let vault_equity = ctx.equity(&vault, vault_usdc, &registry)?;
+
+    investor.preview_deposit(amount, vault_equity, &vault, now)
+}
+
+#[derive(Accounts)]
+pub struct PreviewDeposit<'info> {
+    pub vault: AccountLoader<'info, Vault>,
+
+    #[account(
+        constraint = is_vault_for_investor(&investor, &vault)?
+    )]
+    pub investor: AccountLoader<'info, Investor>,
+
+    #[account(
+        seeds = [b"market_registry"],
+        bump
+    )]
+    pub market_registry: AccountLoader<'info, MarketRegistry>,
+
+    #[account(
+        constraint = is_usdc_token_for_vault(&vault, &vault_usdc_token_account)?
+    )]
+    pub vault_usdc_token_account: Account<'info, TokenAccount>,
+}
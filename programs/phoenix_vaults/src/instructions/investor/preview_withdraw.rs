@@ -0,0 +1,47 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::TokenAccount;
+
+use crate::constraints::*;
+use crate::state::{
+    Investor, MarketMapProvider, MarketRegistry, Vault, WithdrawPreview, WithdrawUnit,
+};
+
+/// Read-only instruction that returns the [`WithdrawPreview`] of a withdraw request as return data,
+/// or of the pending withdraw request if the investor has one.
+/// The markets the vault has positions in are passed as remaining accounts.
+pub fn preview_withdraw<'c: 'info, 'info>(
+    ctx: Context<'_, '_, 'c, 'info, PreviewWithdraw<'info>>,
+    withdraw_amount: u64,
+    withdraw_unit: WithdrawUnit,
+) -> Result<WithdrawPreview> {
+    let now = Clock::get()?.unix_timestamp;
+    let vault = ctx.accounts.vault.load()?;
+    let investor = ctx.accounts.investor.load()?;
+    let registry = ctx.accounts.market_registry.load()?;
+
+    let vault_usdc = &ctx.accounts.vault_usdc_token_account;
+    let vault_equity = ctx.equity(&vault, vault_usdc, &registry)?;
+
+    investor.preview_withdraw(withdraw_amount, withdraw_unit, vault_equity, &vault, now)
+}
+
+#[derive(Accounts)]
+pub struct PreviewWithdraw<'info> {
+    pub vault: AccountLoader<'info, Vault>,
+
+    #[account(
+        constraint = is_vault_for_investor(&investor, &vault)?
+    )]
+    pub investor: AccountLoader<'info, Investor>,
+
+    #[account(
+        seeds = [b"market_registry"],
+        bump
+    )]
+    pub market_registry: AccountLoader<'info, MarketRegistry>,
+
+    #[account(
+        constraint = is_usdc_token_for_vault(&vault, &vault_usdc_token_account)?
+    )]
+    pub vault_usdc_token_account: Account<'info, TokenAccount>,
+}
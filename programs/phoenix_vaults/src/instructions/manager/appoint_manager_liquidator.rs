@@ -2,7 +2,7 @@ use anchor_lang::prelude::*;
 use anchor_spl::token::TokenAccount;
 
 use crate::constraints::{is_manager_for_vault, is_usdc_token_for_vault};
-use crate::state::{MarketMapProvider, MarketRegistry, Vault};
+use crate::state::{MarketMapProvider, MarketRegistry, Vault, VaultState};
 
 pub fn appoint_manager_liquidator<'c: 'info, 'info>(
     ctx: Context<'_, '_, 'c, 'info, AppointManagerLiquidator<'info>>,
@@ -10,6 +10,7 @@ pub fn appoint_manager_liquidator<'c: 'info, 'info>(
     let now = Clock::get()?.unix_timestamp;
 
     let mut vault = ctx.accounts.vault.load_mut()?;
+    vault.check_state(now, &[VaultState::Active, VaultState::WindDown])?;
     let registry = ctx.accounts.market_registry.load()?;
     let vault_usdc = &ctx.accounts.vault_quote_token_account;
 
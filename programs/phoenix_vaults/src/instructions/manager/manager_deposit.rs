@@ -4,7 +4,7 @@ use anchor_spl::token::{self, Token, TokenAccount, Transfer};
 use crate::constraints::*;
 use crate::cpis::TokenTransfer;
 
-use crate::state::{MarketMapProvider, MarketRegistry, Vault};
+use crate::state::{MarketMapProvider, MarketRegistry, NavHistory, Vault, VaultState};
 
 pub fn manager_deposit<'c: 'info, 'info>(
     ctx: Context<'_, '_, 'c, 'info, ManagerDeposit<'info>>,
@@ -13,12 +13,22 @@ pub fn manager_deposit<'c: 'info, 'info>(
     let clock = &Clock::get()?;
 
     let mut vault = ctx.accounts.vault.load_mut()?;
+    vault.check_state(
+        clock.unix_timestamp,
+        &[VaultState::Fundraising, VaultState::Active],
+    )?;
 
     let registry = ctx.accounts.market_registry.load()?;
 
     let vault_usdc = &ctx.accounts.vault_quote_token_account;
     let vault_equity = ctx.equity(&vault, vault_usdc, &registry)?;
 
+    if let Some(nav_history) = &ctx.accounts.nav_history {
+        nav_history
+            .load_mut()?
+            .record(&vault, vault_equity, clock.unix_timestamp)?;
+    }
+
     vault.manager_deposit(amount, vault_equity, clock.unix_timestamp)?;
 
     drop(vault);
@@ -44,6 +54,13 @@ pub struct ManagerDeposit<'info> {
     )]
     pub market_registry: AccountLoader<'info, MarketRegistry>,
 
+    #[account(
+        mut,
+        seeds = [b"nav_history", vault.key().as_ref()],
+        bump
+    )]
+    pub nav_history: Option<AccountLoader<'info, NavHistory>>,
+
     #[account(
         mut,
         constraint = is_usdc_mint(&vault, &manager_quote_token_account.mint)?,
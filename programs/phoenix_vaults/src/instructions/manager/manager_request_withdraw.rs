@@ -3,7 +3,7 @@ use anchor_spl::token::TokenAccount;
 
 use crate::constraints::*;
 use crate::math::Cast;
-use crate::state::{MarketMapProvider, MarketRegistry, Vault, WithdrawUnit};
+use crate::state::{MarketMapProvider, MarketRegistry, Vault, VaultState, WithdrawUnit};
 
 pub fn manager_request_withdraw<'c: 'info, 'info>(
     ctx: Context<'_, '_, 'c, 'info, ManagerRequestWithdraw<'info>>,
@@ -12,6 +12,10 @@ pub fn manager_request_withdraw<'c: 'info, 'info>(
 ) -> Result<()> {
     let clock = &Clock::get()?;
     let vault = &mut ctx.accounts.vault.load_mut()?;
+    vault.check_state(
+        clock.unix_timestamp,
+        &[VaultState::Active, VaultState::WindDown, VaultState::Closed],
+    )?;
 
     let registry = ctx.accounts.market_registry.load()?;
 
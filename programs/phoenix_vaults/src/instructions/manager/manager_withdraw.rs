@@ -7,7 +7,8 @@ use crate::constraints::*;
 use crate::cpis::{PhoenixWithdraw, TokenTransfer};
 use crate::declare_vault_seeds;
 use crate::state::{
-    MarketMapProvider, MarketRegistry, MarketTransferParams, PhoenixProgram, Vault,
+    MarketMapProvider, MarketRegistry, MarketTransferParams, NavHistory, PhoenixProgram, Vault,
+    VaultState,
 };
 
 pub fn manager_withdraw<'c: 'info, 'info>(
@@ -15,12 +16,22 @@ pub fn manager_withdraw<'c: 'info, 'info>(
 ) -> Result<()> {
     let clock = &Clock::get()?;
     let mut vault = ctx.accounts.vault.load_mut()?;
+    vault.check_state(
+        clock.unix_timestamp,
+        &[VaultState::Active, VaultState::WindDown, VaultState::Closed],
+    )?;
 
     let registry = ctx.accounts.market_registry.load()?;
 
     let vault_usdc = &ctx.accounts.vault_quote_token_account;
     let vault_equity = ctx.equity(&vault, vault_usdc, &registry)?;
 
+    if let Some(nav_history) = &ctx.accounts.nav_history {
+        nav_history
+            .load_mut()?
+            .record(&vault, vault_equity, clock.unix_timestamp)?;
+    }
+
     let (manager_withdraw_amount, finishing_liquidation) =
         vault.manager_withdraw(vault_equity, clock.unix_timestamp)?;
 
@@ -61,6 +72,13 @@ pub struct ManagerWithdraw<'info> {
     )]
     pub market_registry: AccountLoader<'info, MarketRegistry>,
 
+    #[account(
+        mut,
+        seeds = [b"nav_history", vault.key().as_ref()],
+        bump
+    )]
+    pub nav_history: Option<AccountLoader<'info, NavHistory>>,
+
     #[account(
         mut,
         constraint = is_usdc_mint(&vault, &manager_quote_token_account.mint)?,
@@ -2,13 +2,17 @@ use anchor_lang::prelude::*;
 use anchor_spl::token::TokenAccount;
 
 use crate::constraints::*;
-use crate::state::{MarketMapProvider, MarketRegistry, Vault};
+use crate::state::{MarketMapProvider, MarketRegistry, Vault, VaultState};
 
 pub fn protocol_cancel_withdraw_request<'c: 'info, 'info>(
     ctx: Context<'_, '_, 'c, 'info, ProtocolCancelWithdrawRequest<'info>>,
 ) -> Result<()> {
     let clock = &Clock::get()?;
     let mut vault = ctx.accounts.vault.load_mut()?;
+    vault.check_state(
+        clock.unix_timestamp,
+        &[VaultState::Active, VaultState::WindDown],
+    )?;
 
     let registry = ctx.accounts.market_registry.load()?;
 
@@ -14,7 +14,8 @@ use crate::cpis::*;
 use crate::error::ErrorCode;
 use crate::math::*;
 use crate::state::{
-    MarketMap, MarketMapProvider, MarketRegistry, MarketTransferParams, PhoenixProgram, Vault,
+    LiquidationFill, LiquidationRecord, LiquidatorRole, MarketMap, MarketMapProvider,
+    MarketRegistry, MarketTransferParams, PhoenixProgram, Vault, VaultState,
 };
 use crate::{declare_vault_seeds, validate};
 
@@ -28,11 +29,12 @@ use crate::{declare_vault_seeds, validate};
 ///     * withdraw quote USDC `vault_quote_token_account`
 /// * transfer quote USDC to `investor_quote_token_account`
 pub fn protocol_liquidate_sol_market<'c: 'info, 'info>(
-    ctx: Context<'_, '_, 'c, 'info, ProtocolLiquidateSolMarket<'info>>,
+    mut ctx: Context<'_, '_, 'c, 'info, ProtocolLiquidateSolMarket<'info>>,
 ) -> Result<()> {
     let now = Clock::get()?.unix_timestamp;
 
     let mut vault = ctx.accounts.vault.load_mut()?;
+    vault.check_state(now, &[VaultState::Active, VaultState::WindDown])?;
 
     if let Err(e) = vault.check_liquidator(&ctx.accounts.protocol, now) {
         vault.reset_liquidation_delegate();
@@ -59,6 +61,12 @@ pub fn protocol_liquidate_sol_market<'c: 'info, 'info>(
     let withdraw_request_amount = amount.min(vault.last_protocol_withdraw_request.value);
     msg!("withdraw_request_amount: {}", withdraw_request_amount);
 
+    let withdraw_request_value = withdraw_request_amount;
+    let withdraw_request_value_before =
+        vault.unfulfilled_withdraw_value(withdraw_request_value, vault_usdc.amount);
+    let market_position_before = ctx.market_position(&vault, ctx.accounts.market.key())?;
+    let mut fill = LiquidationFill::default();
+
     drop(vault);
 
     let vault_key = ctx.accounts.vault.key();
@@ -199,6 +207,7 @@ pub fn protocol_liquidate_sol_market<'c: 'info, 'info>(
         drop(header);
         drop(account_data);
         ctx.phoenix_trade(params)?;
+        fill = ctx.liquidation_fill(&vault_key, &market_position_before)?;
 
         // withdraw liquidated base_lots and existing sol_lots to `vault_sol_token_account`
         ctx.phoenix_withdraw(MarketTransferParams {
@@ -263,6 +272,7 @@ pub fn protocol_liquidate_sol_market<'c: 'info, 'info>(
         quote_lots: usdc_lots_on_sol_usdc_market_to_withdraw,
     })?;
 
+    ctx.accounts.vault_usdc_token_account.reload()?;
     let mut vault = ctx.accounts.vault.load_mut()?;
     let pos = ctx.market_position(&vault, ctx.accounts.market.key())?;
     vault.force_update_market_position(pos)?;
@@ -270,6 +280,24 @@ pub fn protocol_liquidate_sol_market<'c: 'info, 'info>(
     let sol_usdc_pos = ctx.market_position(&vault, registry.sol_usdc_market)?;
     vault.force_update_market_position(sol_usdc_pos)?;
 
+    emit!(LiquidationRecord {
+        ts: now,
+        vault: vault_key,
+        liquidator_role: LiquidatorRole::Protocol,
+        liquidator: ctx.accounts.protocol.key(),
+        market: ctx.accounts.market.key(),
+        liquidation_window: vault.get_liquidation_window(),
+        base_lots_sold: fill.base_lots_sold,
+        avg_price: fill.avg_price,
+        quote_received: fill.quote_received,
+        fees: fill.fees,
+        withdraw_request_value_before,
+        withdraw_request_value_after: vault.unfulfilled_withdraw_value(
+            withdraw_request_value,
+            ctx.accounts.vault_usdc_token_account.amount,
+        ),
+    });
+
     drop(vault);
 
     Ok(())
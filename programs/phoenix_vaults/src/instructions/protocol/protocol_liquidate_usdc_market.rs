@@ -13,7 +13,8 @@ use crate::cpis::{PhoenixTrade, PhoenixWithdraw, TokenTransfer};
 use crate::error::ErrorCode;
 use crate::math::*;
 use crate::state::{
-    MarketMap, MarketMapProvider, MarketRegistry, MarketTransferParams, PhoenixProgram, Vault,
+    LiquidationFill, LiquidationRecord, LiquidatorRole, MarketMap, MarketMapProvider,
+    MarketRegistry, MarketTransferParams, PhoenixProgram, Vault, VaultState,
 };
 use crate::{declare_vault_seeds, validate};
 
@@ -24,12 +25,13 @@ use crate::{declare_vault_seeds, validate};
 ///     * withdraw quote USDC to `vault_usdc_token_account`
 ///     * transfer quote USDC to `investor_quote_token_account`
 pub fn protocol_liquidate_usdc_market<'c: 'info, 'info>(
-    ctx: Context<'_, '_, 'c, 'info, ProtocolLiquidateUsdcMarket<'info>>,
+    mut ctx: Context<'_, '_, 'c, 'info, ProtocolLiquidateUsdcMarket<'info>>,
 ) -> Result<()> {
     let now = Clock::get()?.unix_timestamp;
 
     let vault_key = ctx.accounts.vault.key();
     let mut vault = ctx.accounts.vault.load_mut()?;
+    vault.check_state(now, &[VaultState::Active, VaultState::WindDown])?;
 
     if let Err(e) = vault.check_liquidator(&ctx.accounts.protocol, now) {
         vault.reset_liquidation_delegate();
@@ -54,6 +56,12 @@ pub fn protocol_liquidate_usdc_market<'c: 'info, 'info>(
     let withdraw_request_amount = amount.min(vault.last_protocol_withdraw_request.value);
     msg!("withdraw_request_amount: {}", withdraw_request_amount);
 
+    let withdraw_request_value = withdraw_request_amount;
+    let withdraw_request_value_before =
+        vault.unfulfilled_withdraw_value(withdraw_request_value, vault_usdc.amount);
+    let market_position_before = ctx.market_position(&vault, ctx.accounts.market.key())?;
+    let mut fill = LiquidationFill::default();
+
     drop(vault);
 
     let market_key = ctx.accounts.market.key();
@@ -111,6 +119,7 @@ pub fn protocol_liquidate_usdc_market<'c: 'info, 'info>(
         drop(account_data);
         let params = ProtocolLiquidateUsdcMarket::build_swap_params(bl_to_sell)?;
         ctx.phoenix_trade(params)?;
+        fill = ctx.liquidation_fill(&vault_key, &market_position_before)?;
         // withdraw existing quote_lots plus liquidated quote lots from market to vault
         ctx.phoenix_withdraw(MarketTransferParams {
             base_lots: 0,
@@ -130,6 +139,7 @@ pub fn protocol_liquidate_usdc_market<'c: 'info, 'info>(
         })?;
     };
 
+    ctx.accounts.vault_usdc_token_account.reload()?;
     let mut vault = ctx.accounts.vault.load_mut()?;
     let market = ctx.accounts.market.key();
     let pos = ctx.market_position(&vault, market)?;
@@ -139,6 +149,24 @@ pub fn protocol_liquidate_usdc_market<'c: 'info, 'info>(
     let sol_usdc_pos = ctx.market_position(&vault, sol_usdc_market)?;
     vault.force_update_market_position(sol_usdc_pos)?;
 
+    emit!(LiquidationRecord {
+        ts: now,
+        vault: vault_key,
+        liquidator_role: LiquidatorRole::Protocol,
+        liquidator: ctx.accounts.protocol.key(),
+        market: ctx.accounts.market.key(),
+        liquidation_window: vault.get_liquidation_window(),
+        base_lots_sold: fill.base_lots_sold,
+        avg_price: fill.avg_price,
+        quote_received: fill.quote_received,
+        fees: fill.fees,
+        withdraw_request_value_before,
+        withdraw_request_value_after: vault.unfulfilled_withdraw_value(
+            withdraw_request_value,
+            ctx.accounts.vault_usdc_token_account.amount,
+        ),
+    });
+
     drop(vault);
 
     Ok(())
@@ -4,20 +4,38 @@ use solana_program::program::invoke_signed;
 use crate::constraints::is_delegate_for_vault;
 use crate::cpis::PhoenixCancelAllOrders;
 use crate::declare_vault_seeds;
-use crate::state::{MarketMapProvider, PhoenixProgram, Vault};
+use crate::state::{
+    DelegateAction, DelegateActionRecord, MarketMapProvider, PhoenixProgram, Vault, VaultState,
+};
 
 pub fn cancel_all_orders<'c: 'info, 'info>(
     ctx: Context<'_, '_, 'c, 'info, CancelAllOrders<'info>>,
 ) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    let market = ctx.accounts.market.key();
+    let vault = ctx.accounts.vault.load()?;
+    vault.check_state(now, &[VaultState::Active, VaultState::WindDown])?;
+    let position_before = ctx.market_position(&vault, market)?;
+    drop(vault);
+
     ctx.phoenix_cancel_all_orders()?;
 
     let mut vault = ctx.accounts.vault.load_mut()?;
-    let market = ctx.accounts.market.key();
     let pos = ctx.market_position(&vault, market)?;
     let index = vault.force_get_market_position_index(market)?;
     vault.update_market_position(index, pos)?;
     drop(vault);
 
+    emit!(DelegateActionRecord {
+        ts: now,
+        vault: ctx.accounts.vault.key(),
+        delegate: ctx.accounts.delegate.key(),
+        market,
+        action: DelegateAction::CancelAllOrders,
+        position_before,
+        position_after: pos,
+    });
+
     Ok(())
 }
 
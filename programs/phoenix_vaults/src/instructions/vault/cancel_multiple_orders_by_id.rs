@@ -8,21 +8,40 @@ use solana_program::program::invoke_signed;
 use crate::constraints::is_delegate_for_vault;
 use crate::cpis::PhoenixCancelMultipleOrdersById;
 use crate::declare_vault_seeds;
-use crate::state::{MarketMapProvider, PhoenixProgram, Vault};
+use crate::state::{
+    DelegateAction, DelegateActionRecord, MarketMapProvider, PhoenixProgram, Vault, VaultState,
+};
 
 pub fn cancel_multiple_orders_by_id<'c: 'info, 'info>(
     ctx: Context<'_, '_, 'c, 'info, CancelMultipleOrdersById<'info>>,
     params: CancelMultipleOrdersParams,
 ) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    let market = ctx.accounts.market.key();
+    let vault = ctx.accounts.vault.load()?;
+    vault.check_state(now, &[VaultState::Active, VaultState::WindDown])?;
+    let position_before = ctx.market_position(&vault, market)?;
+    drop(vault);
+
+    let orders = params.orders.clone();
     ctx.phoenix_cancel_multiple_orders_by_id(params.into())?;
 
     let mut vault = ctx.accounts.vault.load_mut()?;
-    let market = ctx.accounts.market.key();
     let pos = ctx.market_position(&vault, market)?;
     let index = vault.force_get_market_position_index(market)?;
     vault.update_market_position(index, pos)?;
     drop(vault);
 
+    emit!(DelegateActionRecord {
+        ts: now,
+        vault: ctx.accounts.vault.key(),
+        delegate: ctx.accounts.delegate.key(),
+        market,
+        action: DelegateAction::CancelMultipleOrdersById { orders },
+        position_before,
+        position_after: pos,
+    });
+
     Ok(())
 }
 
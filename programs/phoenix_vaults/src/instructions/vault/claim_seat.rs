@@ -3,11 +3,22 @@ use solana_program::program::invoke_signed;
 
 use crate::constraints::{is_delegate_for_vault, is_liquidator_for_vault};
 use crate::declare_vault_seeds;
-use crate::state::{PhoenixProgram, PhoenixSeatManagerProgram, Vault};
+use crate::state::{PhoenixProgram, PhoenixSeatManagerProgram, Vault, VaultState};
 
 pub fn claim_seat<'c: 'info, 'info>(
     ctx: Context<'_, '_, 'c, 'info, ClaimSeat<'info>>,
 ) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    // liquidators claim seats to unwind positions while the vault winds down
+    ctx.accounts.vault.load()?.check_state(
+        now,
+        &[
+            VaultState::Fundraising,
+            VaultState::Active,
+            VaultState::WindDown,
+        ],
+    )?;
+
     declare_vault_seeds!(ctx.accounts.vault, seeds);
 
     let trader_index = 5;
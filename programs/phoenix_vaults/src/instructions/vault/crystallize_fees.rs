@@ -0,0 +1,63 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::TokenAccount;
+
+use crate::constraints::is_usdc_token_for_vault;
+use crate::state::{Investor, MarketMapProvider, MarketRegistry, Vault, VaultState};
+
+/// Permissionless crank to crystallize fees on an idle vault.
+/// The time-based management and protocol fees are applied to the vault, then profit share is processed
+/// for every [`Investor`] passed in the remaining accounts (after the markets needed for equity).
+/// Investors with a pending withdraw request are skipped since their profit share was taken at request time.
+/// Investors of another vault are rejected.
+pub fn crystallize_fees<'c: 'info, 'info>(
+    ctx: Context<'_, '_, 'c, 'info, CrystallizeFees<'info>>,
+) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    let mut vault = ctx.accounts.vault.load_mut()?;
+    vault.check_state(
+        now,
+        &[VaultState::Active, VaultState::WindDown, VaultState::Closed],
+    )?;
+    let registry = ctx.accounts.market_registry.load()?;
+
+    let vault_usdc = &ctx.accounts.vault_usdc_token_account;
+    let mut vault_equity = ctx.equity(&vault, vault_usdc, &registry)?;
+
+    vault.crystallize_fees(vault_equity, now)?;
+
+    let investors = Investor::load_remaining(ctx.remaining_accounts, &ctx.accounts.vault.key())?;
+    for investor in investors.iter() {
+        let mut investor = investor.load_mut()?;
+        vault_equity = investor.claim_epoch(vault_equity, &mut vault)?;
+        investor.apply_rebase(&mut vault, vault_equity)?;
+        if investor.last_withdraw_request.pending() {
+            msg!(
+                "skipping investor {:?} with pending withdraw request",
+                investor.pubkey
+            );
+            continue;
+        }
+        investor.realize_profits(vault_equity, &mut vault, now)?;
+    }
+
+    drop(vault);
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct CrystallizeFees<'info> {
+    #[account(mut)]
+    pub vault: AccountLoader<'info, Vault>,
+
+    #[account(
+        seeds = [b"market_registry"],
+        bump
+    )]
+    pub market_registry: AccountLoader<'info, MarketRegistry>,
+
+    #[account(
+        constraint = is_usdc_token_for_vault(&vault, &vault_usdc_token_account)?
+    )]
+    pub vault_usdc_token_account: Account<'info, TokenAccount>,
+}
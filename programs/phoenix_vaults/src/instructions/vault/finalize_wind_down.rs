@@ -0,0 +1,62 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::TokenAccount;
+
+use crate::constraints::*;
+use crate::error::ErrorCode;
+use crate::math::base_atoms_to_base_lots_rounded_down;
+use crate::state::{MarketMapProvider, MarketRegistry, Vault};
+use crate::validate;
+
+/// Permissionless crank to close a vault once `wind_down_liquidate_market` has converted every market position to USDC.
+/// The vault equity is then just its USDC, which is snapshotted for investors to claim with `investor_claim_wind_down`.
+/// SOL below a base lot of the SOL/USDC market can't be sold, so it is left out rather than blocking finalization.
+/// Requires the SOL/USDC market in the remaining accounts.
+pub fn finalize_wind_down<'c: 'info, 'info>(
+    ctx: Context<'_, '_, 'c, 'info, FinalizeWindDown<'info>>,
+) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    let mut vault = ctx.accounts.vault.load_mut()?;
+    let registry = ctx.accounts.market_registry.load()?;
+
+    let (_, _, sol_usdc_header) = ctx.load_sol_usdc_market(&registry)?;
+    let sol_lots = base_atoms_to_base_lots_rounded_down(
+        &sol_usdc_header,
+        ctx.accounts.vault_sol_token_account.amount,
+    );
+    validate!(
+        sol_lots == 0,
+        ErrorCode::WindDownPositionsOpen,
+        "vault SOL must be sold on the SOL/USDC market before the wind-down is finalized"
+    )?;
+
+    let vault_equity = ctx
+        .accounts
+        .vault_usdc_token_account
+        .amount
+        .saturating_sub(vault.epoch_reserved_usdc());
+
+    vault.finalize_wind_down(vault_equity, now)?;
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct FinalizeWindDown<'info> {
+    #[account(mut)]
+    pub vault: AccountLoader<'info, Vault>,
+
+    #[account(
+        seeds = [b"market_registry"],
+        bump
+    )]
+    pub market_registry: AccountLoader<'info, MarketRegistry>,
+
+    #[account(
+        constraint = is_usdc_token_for_vault(&vault, &vault_usdc_token_account)?
+    )]
+    pub vault_usdc_token_account: Account<'info, TokenAccount>,
+    #[account(
+        constraint = is_sol_token_for_vault(&vault, &vault_sol_token_account)?
+    )]
+    pub vault_sol_token_account: Account<'info, TokenAccount>,
+}
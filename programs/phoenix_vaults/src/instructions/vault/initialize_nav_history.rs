@@ -0,0 +1,28 @@
+use anchor_lang::prelude::*;
+
+use crate::state::{NavHistory, Vault};
+use crate::Size;
+
+/// Permissionless instruction to create the [`NavHistory`] ring buffer for a vault.
+pub fn initialize_nav_history(ctx: Context<InitializeNavHistory>) -> Result<()> {
+    let mut nav_history = ctx.accounts.nav_history.load_init()?;
+    nav_history.vault = ctx.accounts.vault.key();
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct InitializeNavHistory<'info> {
+    pub vault: AccountLoader<'info, Vault>,
+    #[account(
+        init,
+        seeds = [b"nav_history", vault.key().as_ref()],
+        space = NavHistory::SIZE,
+        bump,
+        payer = payer
+    )]
+    pub nav_history: AccountLoader<'info, NavHistory>,
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub rent: Sysvar<'info, Rent>,
+    pub system_program: Program<'info, System>,
+}
@@ -0,0 +1,44 @@
+use anchor_lang::prelude::*;
+
+use crate::constraints::is_manager_for_vault;
+use crate::state::{SharePrice, Vault};
+use crate::{error::ErrorCode, validate, Size};
+
+/// Manager creates the [`SharePrice`] account for a vault,
+/// and sets how many slots consumers should accept the price for after an update.
+pub fn initialize_share_price(
+    ctx: Context<InitializeSharePrice>,
+    max_staleness_slots: u64,
+) -> Result<()> {
+    validate!(
+        max_staleness_slots > 0,
+        ErrorCode::InvalidSharePrice,
+        "max staleness slots must be > 0"
+    )?;
+
+    let mut share_price = ctx.accounts.share_price.load_init()?;
+    share_price.vault = ctx.accounts.vault.key();
+    share_price.max_staleness_slots = max_staleness_slots;
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct InitializeSharePrice<'info> {
+    #[account(
+        constraint = is_manager_for_vault(&vault, &manager)?,
+    )]
+    pub vault: AccountLoader<'info, Vault>,
+    pub manager: Signer<'info>,
+    #[account(
+        init,
+        seeds = [b"share_price", vault.key().as_ref()],
+        space = SharePrice::SIZE,
+        bump,
+        payer = payer
+    )]
+    pub share_price: AccountLoader<'info, SharePrice>,
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub rent: Sysvar<'info, Rent>,
+    pub system_program: Program<'info, System>,
+}
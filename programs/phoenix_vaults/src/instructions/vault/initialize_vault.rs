@@ -1,11 +1,13 @@
-use crate::constants::PERCENTAGE_PRECISION_U64;
+use crate::constants::{BPS_PRECISION_U64, PERCENTAGE_PRECISION_U64};
 use crate::math::Cast;
 use anchor_lang::prelude::*;
 use anchor_spl::associated_token::AssociatedToken;
 use anchor_spl::token::{Mint, Token, TokenAccount};
 
-use crate::constants::ONE_DAY;
-use crate::state::Vault;
+use crate::constants::{
+    DEFAULT_LIQUIDATION_WINDOW, MAX_LIQUIDATION_WINDOW, MIN_LIQUIDATION_WINDOW, ONE_DAY,
+};
+use crate::state::{Vault, VaultState};
 use crate::{error::ErrorCode, validate, Size};
 
 pub fn initialize_vault<'c: 'info, 'info>(
@@ -67,6 +69,86 @@ pub fn initialize_vault<'c: 'info, 'info>(
     )?;
     vault.hurdle_rate = params.hurdle_rate;
 
+    validate!(
+        params.epoch_duration >= 0 && params.epoch_duration < ONE_DAY * 90,
+        ErrorCode::InvalidVaultInitialization,
+        "epoch duration must be < 90 days"
+    )?;
+    vault.epoch_duration = params.epoch_duration;
+    vault.epoch_start_ts = vault.init_ts;
+
+    validate!(
+        params.entry_fee_bps.cast::<u64>()? < BPS_PRECISION_U64
+            && params.exit_fee_bps.cast::<u64>()? < BPS_PRECISION_U64,
+        ErrorCode::InvalidVaultInitialization,
+        "entry and exit fees must be < 100%"
+    )?;
+    vault.entry_fee_bps = params.entry_fee_bps;
+    vault.exit_fee_bps = params.exit_fee_bps;
+
+    validate!(
+        params.max_drawdown.cast::<u64>()? < PERCENTAGE_PRECISION_U64,
+        ErrorCode::InvalidVaultInitialization,
+        "max drawdown must be < 100%"
+    )?;
+    vault.max_drawdown = params.max_drawdown;
+
+    validate!(
+        params.manager_min_stake.cast::<u64>()? < PERCENTAGE_PRECISION_U64,
+        ErrorCode::InvalidVaultInitialization,
+        "manager minimum stake must be < 100%"
+    )?;
+    vault.manager_min_stake = params.manager_min_stake;
+
+    validate!(
+        params.liquidity_buffer.cast::<u64>()? < PERCENTAGE_PRECISION_U64,
+        ErrorCode::InvalidVaultInitialization,
+        "liquidity buffer must be < 100%"
+    )?;
+    vault.liquidity_buffer = params.liquidity_buffer;
+
+    validate!(
+        params.lockup_duration >= 0 && params.lockup_duration <= ONE_DAY * 365,
+        ErrorCode::InvalidVaultInitialization,
+        "lockup duration must be <= 365 days"
+    )?;
+    validate!(
+        params
+            .exit_fee_bps
+            .saturating_add(params.lockup_penalty_bps)
+            .cast::<u64>()?
+            < BPS_PRECISION_U64,
+        ErrorCode::InvalidVaultInitialization,
+        "exit fee plus lockup penalty must be < 100%"
+    )?;
+    vault.lockup_duration = params.lockup_duration;
+    vault.lockup_penalty_bps = params.lockup_penalty_bps;
+
+    vault.liquidation_window = if params.liquidation_window == 0 {
+        DEFAULT_LIQUIDATION_WINDOW
+    } else {
+        params.liquidation_window
+    };
+    validate!(
+        (MIN_LIQUIDATION_WINDOW..=MAX_LIQUIDATION_WINDOW).contains(&vault.liquidation_window),
+        ErrorCode::InvalidVaultInitialization,
+        "liquidation window must be between {} and {} seconds",
+        MIN_LIQUIDATION_WINDOW,
+        MAX_LIQUIDATION_WINDOW
+    )?;
+
+    if params.fundraising_soft_cap > 0 {
+        validate!(
+            params.fundraising_deadline > vault.init_ts
+                && params.fundraising_deadline < vault.init_ts.saturating_add(ONE_DAY * 90),
+            ErrorCode::InvalidVaultInitialization,
+            "fundraising deadline must be within 90 days"
+        )?;
+        vault.state = VaultState::Fundraising;
+        vault.fundraising_soft_cap = params.fundraising_soft_cap;
+        vault.fundraising_deadline = params.fundraising_deadline;
+    }
+
     drop(vault);
 
     Ok(())
@@ -85,6 +167,31 @@ pub struct VaultParams {
     pub protocol: Pubkey,
     pub protocol_fee: u64,
     pub protocol_profit_share: u32,
+    /// If non-zero, deposits and withdraw requests are queued and priced once per epoch by `settle_epoch`.
+    pub epoch_duration: i64,
+    /// Fee (in basis points) withheld from investor deposits for the remaining shareholders.
+    pub entry_fee_bps: u16,
+    /// Fee (in basis points) withheld from investor withdrawals for the remaining shareholders.
+    pub exit_fee_bps: u16,
+    /// Share price drawdown from its high-water mark (multiplied by PERCENTAGE_PRECISION) that halts delegate trading.
+    /// If zero, trading is never halted.
+    pub max_drawdown: u32,
+    /// The minimum percentage of total shares (multiplied by PERCENTAGE_PRECISION) the manager must hold
+    /// while investors remain in the vault. If zero, the manager can withdraw everything.
+    pub manager_min_stake: u32,
+    /// The percentage of equity (multiplied by PERCENTAGE_PRECISION) reserved in the vault USDC token account,
+    /// on top of outstanding withdraw requests, that the delegate can't deposit to markets.
+    pub liquidity_buffer: u32,
+    /// Seconds from an investor's first deposit during which withdraw requests are penalized,
+    /// or rejected if `lockup_penalty_bps` is zero. If zero, there is no lockup.
+    pub lockup_duration: i64,
+    pub lockup_penalty_bps: u16,
+    /// Seconds an appointed liquidator has to liquidate. If zero, the default of one hour.
+    pub liquidation_window: i64,
+    /// If non-zero, the vault raises funds until `fundraising_deadline` without trading,
+    /// and closes to refund investors if total deposits are below this soft cap.
+    pub fundraising_soft_cap: u64,
+    pub fundraising_deadline: i64,
 }
 
 #[derive(Accounts)]
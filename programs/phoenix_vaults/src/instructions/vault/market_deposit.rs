@@ -6,23 +6,60 @@ use solana_program::program::invoke_signed;
 use crate::constraints::*;
 use crate::cpis::PhoenixDeposit;
 use crate::declare_vault_seeds;
-use crate::state::{MarketMapProvider, MarketTransferParams, PhoenixProgram, Vault};
+use crate::state::{
+    market_position_or_empty, DelegateAction, DelegateActionRecord, MarketMapProvider,
+    MarketPosition, MarketRegistry, MarketTransferParams, PhoenixProgram, Vault, VaultState,
+};
 
 pub fn market_deposit<'c: 'info, 'info>(
-    ctx: Context<'_, '_, 'c, 'info, MarketDeposit<'info>>,
+    mut ctx: Context<'_, '_, 'c, 'info, MarketDeposit<'info>>,
     params: MarketTransferParams,
 ) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    let vault = ctx.accounts.vault.load()?;
+    vault.check_trading_not_halted()?;
+    vault.check_state(now, &[VaultState::Active, VaultState::WindDown])?;
+    let market = ctx.accounts.market.key();
+    let position_before = market_position_or_empty(&vault.pubkey, market, ctx.remaining_accounts);
+    drop(vault);
+
     ctx.phoenix_deposit(params)?;
 
     let mut vault = ctx.accounts.vault.load_mut()?;
-    let market = ctx.accounts.market.key();
 
-    if let Ok(pos) = ctx.market_position(&vault, market) {
-        let index = vault.force_get_market_position_index(market)?;
-        vault.update_market_position(index, pos)?;
+    let position_after = match ctx.market_position(&vault, market) {
+        Ok(pos) => {
+            let index = vault.force_get_market_position_index(market)?;
+            vault.update_market_position(index, pos)?;
+            pos
+        }
+        Err(_) => MarketPosition {
+            market,
+            ..MarketPosition::default()
+        },
+    };
+
+    // orders only use funds already deposited to a market, so this is the only way USDC leaves the vault to trade
+    if ctx.accounts.vault_quote_token_account.mint == vault.usdc_mint {
+        ctx.accounts.vault_quote_token_account.reload()?;
+        let vault_usdc = &ctx.accounts.vault_quote_token_account;
+        vault.check_epoch_reserved_usdc(vault_usdc.amount)?;
+        let registry = ctx.accounts.market_registry.load()?;
+        let vault_equity = ctx.equity(&vault, vault_usdc, &registry)?;
+        vault.check_liquidity_buffer(vault_usdc.amount, vault_equity)?;
     }
     drop(vault);
 
+    emit!(DelegateActionRecord {
+        ts: now,
+        vault: ctx.accounts.vault.key(),
+        delegate: ctx.accounts.delegate.key(),
+        market,
+        action: DelegateAction::MarketDeposit { params },
+        position_before,
+        position_after,
+    });
+
     Ok(())
 }
 
@@ -39,6 +76,12 @@ pub struct MarketDeposit<'info> {
     /// Is manager by default, but can be delegated to another pubkey using `update_delegate`
     pub delegate: Signer<'info>,
 
+    #[account(
+        seeds = [b"market_registry"],
+        bump
+    )]
+    pub market_registry: AccountLoader<'info, MarketRegistry>,
+
     //
     // Phoenix CPI accounts
     //
@@ -5,23 +5,52 @@ use solana_program::program::invoke_signed;
 use crate::constraints::*;
 use crate::cpis::PhoenixWithdraw;
 use crate::declare_vault_seeds;
-use crate::state::{MarketMapProvider, MarketTransferParams, PhoenixProgram, Vault};
+use crate::state::{
+    DelegateAction, DelegateActionRecord, MarketMapProvider, MarketPosition, MarketTransferParams,
+    PhoenixProgram, Vault, VaultState,
+};
 
 pub fn market_withdraw<'c: 'info, 'info>(
     ctx: Context<'_, '_, 'c, 'info, MarketWithdraw<'info>>,
     params: MarketTransferParams,
 ) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    let market = ctx.accounts.market.key();
+    let empty_position = MarketPosition {
+        market,
+        ..MarketPosition::default()
+    };
+    let vault = ctx.accounts.vault.load()?;
+    vault.check_state(now, &[VaultState::Active, VaultState::WindDown])?;
+    let position_before = ctx
+        .market_position(&vault, market)
+        .unwrap_or(empty_position);
+    drop(vault);
+
     ctx.phoenix_withdraw(params)?;
 
     let mut vault = ctx.accounts.vault.load_mut()?;
-    let market = ctx.accounts.market.key();
-    if let Ok(pos) = ctx.market_position(&vault, market) {
-        if let Ok(index) = vault.get_market_position_index(&market) {
-            vault.update_market_position(index, pos)?;
+    let position_after = match ctx.market_position(&vault, market) {
+        Ok(pos) => {
+            if let Ok(index) = vault.get_market_position_index(&market) {
+                vault.update_market_position(index, pos)?;
+            }
+            pos
         }
-    }
+        Err(_) => empty_position,
+    };
     drop(vault);
 
+    emit!(DelegateActionRecord {
+        ts: now,
+        vault: ctx.accounts.vault.key(),
+        delegate: ctx.accounts.delegate.key(),
+        market,
+        action: DelegateAction::MarketWithdraw { params },
+        position_before,
+        position_after,
+    });
+
     Ok(())
 }
 
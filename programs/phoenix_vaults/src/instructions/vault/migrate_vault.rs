@@ -0,0 +1,30 @@
+use anchor_lang::prelude::*;
+
+use crate::state::Vault;
+use crate::Size;
+
+/// Permissionless instruction to grow a vault created before the epoch, fee, drawdown, lifecycle,
+/// wind-down, lockup and liquidation window fields were appended to [`Vault`] up to [`Vault::SIZE`].
+/// Every other instruction fails to load a vault until it is migrated.
+///
+/// The appended fields are zeroed, which leaves the vault `Active` with every new feature off,
+/// so it behaves as it did until the manager configures them. Migrating a migrated vault is a no-op.
+pub fn migrate_vault<'c: 'info, 'info>(
+    _ctx: Context<'_, '_, 'c, 'info, MigrateVault<'info>>,
+) -> Result<()> {
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct MigrateVault<'info> {
+    #[account(
+        mut,
+        realloc = Vault::SIZE,
+        realloc::payer = payer,
+        realloc::zero = true
+    )]
+    pub vault: AccountLoader<'info, Vault>,
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
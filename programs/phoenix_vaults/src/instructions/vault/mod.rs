@@ -1,17 +1,41 @@
 mod cancel_all_orders;
 mod cancel_multiple_orders_by_id;
 mod claim_seat;
+mod crystallize_fees;
+mod finalize_wind_down;
+mod initialize_nav_history;
+mod initialize_share_price;
 mod initialize_vault;
 mod market_deposit;
 mod market_withdraw;
+mod migrate_vault;
 mod place_limit_order;
+mod resume_trading;
+mod settle_epoch;
+mod update_nav_history;
+mod update_share_price;
 mod update_vault;
+mod view_vault_equity;
+mod wind_down;
+mod wind_down_liquidate_market;
 
 pub use cancel_all_orders::*;
 pub use cancel_multiple_orders_by_id::*;
 pub use claim_seat::*;
+pub use crystallize_fees::*;
+pub use finalize_wind_down::*;
+pub use initialize_nav_history::*;
+pub use initialize_share_price::*;
 pub use initialize_vault::*;
 pub use market_deposit::*;
 pub use market_withdraw::*;
+pub use migrate_vault::*;
 pub use place_limit_order::*;
+pub use resume_trading::*;
+pub use settle_epoch::*;
+pub use update_nav_history::*;
+pub use update_share_price::*;
 pub use update_vault::*;
+pub use view_vault_equity::*;
+pub use wind_down::*;
+pub use wind_down_liquidate_market::*;
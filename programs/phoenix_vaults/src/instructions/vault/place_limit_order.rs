@@ -1,7 +1,7 @@
 use anchor_lang::prelude::*;
 use anchor_spl::token::{Mint, Token, TokenAccount};
 use phoenix::program::PhoenixInstruction;
-use phoenix::state::{decode_order_packet, OrderPacket, OrderPacketMetadata};
+use phoenix::state::{decode_order_packet, OrderPacket, OrderPacketMetadata, Side};
 use solana_program::program::invoke_signed;
 
 use crate::constraints::{
@@ -10,7 +10,9 @@ use crate::constraints::{
 };
 use crate::cpis::PhoenixTrade;
 use crate::error::ErrorCode;
-use crate::state::{MarketMapProvider, PhoenixProgram, Vault};
+use crate::state::{
+    DelegateAction, DelegateActionRecord, MarketMapProvider, PhoenixProgram, Vault, VaultState,
+};
 use crate::{declare_vault_seeds, validate};
 
 pub fn place_limit_order<'c: 'info, 'info>(
@@ -33,15 +35,43 @@ pub fn place_limit_order<'c: 'info, 'info>(
     )?;
 
     let order = decode_order_packet(data).ok_or(ErrorCode::OrderPacketDeserialization)?;
+
+    let now = Clock::get()?.unix_timestamp;
+    let vault = ctx.accounts.vault.load()?;
+    vault.check_trading_not_halted()?;
+    vault.check_state(now, &[VaultState::Active, VaultState::WindDown])?;
+    if vault.state(now) == VaultState::WindDown {
+        // vault positions are spot, so only asks reduce risk
+        validate!(
+            order.side() == Side::Ask,
+            ErrorCode::InvalidVaultState,
+            "only asks are allowed while the vault winds down"
+        )?;
+    }
+    let market = ctx.accounts.market.key();
+    let position_before = ctx.market_position(&vault, market)?;
+    drop(vault);
+
     ctx.phoenix_trade(order)?;
 
     let mut vault = ctx.accounts.vault.load_mut()?;
-    let market = ctx.accounts.market.key();
     let pos = ctx.market_position(&vault, market)?;
     let index = vault.force_get_market_position_index(market)?;
     vault.update_market_position(index, pos)?;
     drop(vault);
 
+    emit!(DelegateActionRecord {
+        ts: now,
+        vault: ctx.accounts.vault.key(),
+        delegate: ctx.accounts.delegate.key(),
+        market,
+        action: DelegateAction::PlaceLimitOrder {
+            order: params.order,
+        },
+        position_before,
+        position_after: pos,
+    });
+
     Ok(())
 }
 
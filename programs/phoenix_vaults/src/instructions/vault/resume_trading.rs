@@ -0,0 +1,44 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::TokenAccount;
+
+use crate::constraints::*;
+use crate::state::{MarketMapProvider, MarketRegistry, Vault};
+
+/// The manager and protocol jointly resume delegate trading after the share price drawdown halted it.
+/// The high-water mark resets to the current share price.
+pub fn resume_trading<'c: 'info, 'info>(
+    ctx: Context<'_, '_, 'c, 'info, ResumeTrading<'info>>,
+) -> Result<()> {
+    let mut vault = ctx.accounts.vault.load_mut()?;
+    let registry = ctx.accounts.market_registry.load()?;
+
+    let vault_usdc = &ctx.accounts.vault_usdc_token_account;
+    let vault_equity = ctx.equity(&vault, vault_usdc, &registry)?;
+
+    vault.resume_trading(vault_equity)?;
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct ResumeTrading<'info> {
+    #[account(
+        mut,
+        constraint = is_manager_for_vault(&vault, &manager)?,
+        constraint = is_protocol_for_vault(&vault, &protocol)?
+    )]
+    pub vault: AccountLoader<'info, Vault>,
+    pub manager: Signer<'info>,
+    pub protocol: Signer<'info>,
+
+    #[account(
+        seeds = [b"market_registry"],
+        bump
+    )]
+    pub market_registry: AccountLoader<'info, MarketRegistry>,
+
+    #[account(
+        constraint = is_usdc_token_for_vault(&vault, &vault_usdc_token_account)?
+    )]
+    pub vault_usdc_token_account: Account<'info, TokenAccount>,
+}
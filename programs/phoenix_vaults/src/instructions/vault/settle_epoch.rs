@@ -0,0 +1,68 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::TokenAccount;
+
+use crate::constraints::is_usdc_token_for_vault;
+use crate::error::ErrorCode;
+use crate::state::{Investor, MarketMapProvider, MarketRegistry, Vault, VaultState};
+use crate::validate;
+
+/// Permissionless crank to close an epoch of a vault in epoch mode.
+/// If the epoch has ended, every deposit and withdraw request queued during it is priced at one equity snapshot.
+/// Any [`Investor`] accounts passed in the remaining accounts (after the markets needed for equity)
+/// then claim their share of the settlement, so the crank can be called again with more investors
+/// until every investor of the settled epoch has claimed.
+pub fn settle_epoch<'c: 'info, 'info>(
+    ctx: Context<'_, '_, 'c, 'info, SettleEpoch<'info>>,
+) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    let mut vault = ctx.accounts.vault.load_mut()?;
+    let registry = ctx.accounts.market_registry.load()?;
+
+    validate!(
+        vault.epoch_mode(),
+        ErrorCode::EpochModeDisabled,
+        "vault does not use epochs"
+    )?;
+
+    let vault_usdc = &ctx.accounts.vault_usdc_token_account;
+    let mut vault_equity = ctx.equity(&vault, vault_usdc, &registry)?;
+
+    // a closed vault prices nothing more, investors claim what is left with `investor_claim_wind_down`
+    let settled = vault.epoch_ended(now) && vault.state(now) != VaultState::Closed;
+    if settled {
+        vault.settle_epoch(vault_equity, now)?;
+    }
+
+    let investors = Investor::load_remaining(ctx.remaining_accounts, &vault.pubkey)?;
+    for investor in investors.iter() {
+        vault_equity = investor.load_mut()?.claim_epoch(vault_equity, &mut vault)?;
+    }
+
+    validate!(
+        settled || !investors.is_empty(),
+        ErrorCode::EpochNotEnded,
+        "epoch ends at {}",
+        vault.epoch_start_ts.saturating_add(vault.epoch_duration)
+    )?;
+
+    drop(vault);
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SettleEpoch<'info> {
+    #[account(mut)]
+    pub vault: AccountLoader<'info, Vault>,
+
+    #[account(
+        seeds = [b"market_registry"],
+        bump
+    )]
+    pub market_registry: AccountLoader<'info, MarketRegistry>,
+
+    #[account(
+        constraint = is_usdc_token_for_vault(&vault, &vault_usdc_token_account)?
+    )]
+    pub vault_usdc_token_account: Account<'info, TokenAccount>,
+}
@@ -0,0 +1,50 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::TokenAccount;
+
+use crate::constants::MIN_NAV_SAMPLE_INTERVAL;
+use crate::constraints::is_usdc_token_for_vault;
+use crate::state::{MarketMapProvider, MarketRegistry, NavHistory, Vault};
+
+/// Permissionless crank to append a sample of the vault equity and shares to the [`NavHistory`].
+/// Samples are rate limited to one per [`MIN_NAV_SAMPLE_INTERVAL`], and each one updates the share price drawdown.
+pub fn update_nav_history<'c: 'info, 'info>(
+    ctx: Context<'_, '_, 'c, 'info, UpdateNavHistory<'info>>,
+) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    let mut vault = ctx.accounts.vault.load_mut()?;
+    let registry = ctx.accounts.market_registry.load()?;
+    let mut nav_history = ctx.accounts.nav_history.load_mut()?;
+    nav_history.check_sample_interval(now, MIN_NAV_SAMPLE_INTERVAL)?;
+
+    let vault_usdc = &ctx.accounts.vault_usdc_token_account;
+    let vault_equity = ctx.equity(&vault, vault_usdc, &registry)?;
+    vault.update_drawdown(vault_equity)?;
+
+    nav_history.record(&vault, vault_equity, now)?;
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct UpdateNavHistory<'info> {
+    #[account(mut)]
+    pub vault: AccountLoader<'info, Vault>,
+
+    #[account(
+        mut,
+        seeds = [b"nav_history", vault.key().as_ref()],
+        bump
+    )]
+    pub nav_history: AccountLoader<'info, NavHistory>,
+
+    #[account(
+        seeds = [b"market_registry"],
+        bump
+    )]
+    pub market_registry: AccountLoader<'info, MarketRegistry>,
+
+    #[account(
+        constraint = is_usdc_token_for_vault(&vault, &vault_usdc_token_account)?
+    )]
+    pub vault_usdc_token_account: Account<'info, TokenAccount>,
+}
@@ -0,0 +1,50 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::TokenAccount;
+
+use crate::constraints::is_usdc_token_for_vault;
+use crate::state::{MarketMapProvider, MarketRegistry, SharePrice, Vault};
+
+/// Permissionless crank to refresh the [`SharePrice`] with the full vault equity calculation.
+/// The markets the vault has positions in are passed as remaining accounts.
+pub fn update_share_price<'c: 'info, 'info>(
+    ctx: Context<'_, '_, 'c, 'info, UpdateSharePrice<'info>>,
+) -> Result<()> {
+    let clock = Clock::get()?;
+    let vault = ctx.accounts.vault.load()?;
+    let registry = ctx.accounts.market_registry.load()?;
+
+    let vault_usdc = &ctx.accounts.vault_usdc_token_account;
+    let vault_equity = ctx.equity(&vault, vault_usdc, &registry)?;
+
+    ctx.accounts.share_price.load_mut()?.update(
+        &vault,
+        vault_equity,
+        clock.slot,
+        clock.unix_timestamp,
+    )?;
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct UpdateSharePrice<'info> {
+    pub vault: AccountLoader<'info, Vault>,
+
+    #[account(
+        mut,
+        seeds = [b"share_price", vault.key().as_ref()],
+        bump
+    )]
+    pub share_price: AccountLoader<'info, SharePrice>,
+
+    #[account(
+        seeds = [b"market_registry"],
+        bump
+    )]
+    pub market_registry: AccountLoader<'info, MarketRegistry>,
+
+    #[account(
+        constraint = is_usdc_token_for_vault(&vault, &vault_usdc_token_account)?
+    )]
+    pub vault_usdc_token_account: Account<'info, TokenAccount>,
+}
@@ -1,15 +1,30 @@
+use crate::constants::PERCENTAGE_PRECISION_U64;
 use crate::constraints::is_manager_for_vault;
-use crate::{error::ErrorCode, validate, Vault};
+use crate::math::Cast;
+use crate::{error::ErrorCode, validate, Vault, VaultState};
 use anchor_lang::prelude::*;
 
 pub fn update_vault<'info>(
     ctx: Context<'_, '_, '_, 'info, UpdateVault<'info>>,
     params: UpdateVaultParams,
 ) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
     let mut vault = ctx.accounts.vault.load_mut()?;
+    vault.check_state(
+        now,
+        &[
+            VaultState::Fundraising,
+            VaultState::Active,
+            VaultState::WindDown,
+        ],
+    )?;
+
+    // before an expired liquidation is reset, so a window can't change under an appointed liquidator
+    if let Some(liquidation_window) = params.liquidation_window {
+        vault.update_liquidation_window(liquidation_window)?;
+    }
 
     if vault.in_liquidation() {
-        let now = Clock::get()?.unix_timestamp;
         vault.check_can_exit_liquidation(now)?;
         vault.reset_liquidation_delegate();
     }
@@ -62,10 +77,66 @@ pub fn update_vault<'info>(
         vault.hurdle_rate = hurdle_rate;
     }
 
+    if let Some(entry_fee_bps) = params.entry_fee_bps {
+        validate!(
+            entry_fee_bps < vault.entry_fee_bps,
+            ErrorCode::InvalidVaultUpdate,
+            "new entry fee must be less than existing entry fee"
+        )?;
+        vault.entry_fee_bps = entry_fee_bps;
+    }
+
+    if let Some(exit_fee_bps) = params.exit_fee_bps {
+        validate!(
+            exit_fee_bps < vault.exit_fee_bps,
+            ErrorCode::InvalidVaultUpdate,
+            "new exit fee must be less than existing exit fee"
+        )?;
+        vault.exit_fee_bps = exit_fee_bps;
+    }
+
     if let Some(permissioned) = params.permissioned {
         vault.permissioned = permissioned;
     }
 
+    if let Some(max_drawdown) = params.max_drawdown {
+        validate!(
+            max_drawdown.cast::<u64>()? < PERCENTAGE_PRECISION_U64,
+            ErrorCode::InvalidVaultUpdate,
+            "max drawdown must be < 100%"
+        )?;
+        vault.max_drawdown = max_drawdown;
+    }
+
+    if let Some(manager_min_stake) = params.manager_min_stake {
+        validate!(
+            manager_min_stake > vault.manager_min_stake
+                && manager_min_stake.cast::<u64>()? < PERCENTAGE_PRECISION_U64,
+            ErrorCode::InvalidVaultUpdate,
+            "new manager minimum stake must be greater than existing minimum stake and < 100%"
+        )?;
+        vault.manager_min_stake = manager_min_stake;
+    }
+
+    if let Some(liquidity_buffer) = params.liquidity_buffer {
+        validate!(
+            liquidity_buffer > vault.liquidity_buffer
+                && liquidity_buffer.cast::<u64>()? < PERCENTAGE_PRECISION_U64,
+            ErrorCode::InvalidVaultUpdate,
+            "new liquidity buffer must be greater than existing liquidity buffer and < 100%"
+        )?;
+        vault.liquidity_buffer = liquidity_buffer;
+    }
+
+    if let Some(lockup_duration) = params.lockup_duration {
+        validate!(
+            lockup_duration >= 0 && lockup_duration < vault.lockup_duration,
+            ErrorCode::InvalidVaultUpdate,
+            "new lockup duration must be less than existing lockup duration"
+        )?;
+        vault.lockup_duration = lockup_duration;
+    }
+
     drop(vault);
 
     Ok(())
@@ -81,6 +152,13 @@ pub struct UpdateVaultParams {
     pub hurdle_rate: Option<u32>,
     pub permissioned: Option<bool>,
     pub delegate: Option<Pubkey>,
+    pub entry_fee_bps: Option<u16>,
+    pub exit_fee_bps: Option<u16>,
+    pub max_drawdown: Option<u32>,
+    pub manager_min_stake: Option<u32>,
+    pub liquidity_buffer: Option<u32>,
+    pub lockup_duration: Option<i64>,
+    pub liquidation_window: Option<i64>,
 }
 
 #[derive(Accounts)]
@@ -0,0 +1,35 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::TokenAccount;
+
+use crate::constraints::is_usdc_token_for_vault;
+use crate::state::{MarketMapProvider, MarketRegistry, Vault};
+
+/// Read-only instruction that returns the vault equity in USDC as return data.
+/// The markets the vault has positions in are passed as remaining accounts.
+pub fn view_vault_equity<'c: 'info, 'info>(
+    ctx: Context<'_, '_, 'c, 'info, ViewVaultEquity<'info>>,
+) -> Result<u64> {
+    let vault = ctx.accounts.vault.load()?;
+    let registry = ctx.accounts.market_registry.load()?;
+
+    let vault_usdc = &ctx.accounts.vault_usdc_token_account;
+    let vault_equity = ctx.equity(&vault, vault_usdc, &registry)?;
+
+    Ok(vault_equity)
+}
+
+#[derive(Accounts)]
+pub struct ViewVaultEquity<'info> {
+    pub vault: AccountLoader<'info, Vault>,
+
+    #[account(
+        seeds = [b"market_registry"],
+        bump
+    )]
+    pub market_registry: AccountLoader<'info, MarketRegistry>,
+
+    #[account(
+        constraint = is_usdc_token_for_vault(&vault, &vault_usdc_token_account)?
+    )]
+    pub vault_usdc_token_account: Account<'info, TokenAccount>,
+}
@@ -0,0 +1,24 @@
+use anchor_lang::prelude::*;
+
+use crate::constraints::*;
+use crate::state::Vault;
+
+/// The manager or protocol begins winding down the vault.
+/// Deposits stop, the delegate may only sell, and anyone can crank `wind_down_liquidate_market`
+/// to convert every market position to USDC before `finalize_wind_down` snapshots the final equity.
+pub fn wind_down<'c: 'info, 'info>(ctx: Context<'_, '_, 'c, 'info, WindDown<'info>>) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    let mut vault = ctx.accounts.vault.load_mut()?;
+    vault.wind_down(now)?;
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct WindDown<'info> {
+    #[account(
+        mut,
+        constraint = is_manager_or_protocol_for_vault(&vault, &authority)?
+    )]
+    pub vault: AccountLoader<'info, Vault>,
+    pub authority: Signer<'info>,
+}
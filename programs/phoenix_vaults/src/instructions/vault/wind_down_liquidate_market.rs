@@ -0,0 +1,428 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Mint, Token, TokenAccount};
+use phoenix::program::deposit::DepositParams;
+use phoenix::program::{load_with_dispatch, MarketHeader};
+use phoenix::state::{OrderPacket, SelfTradeBehavior, Side};
+use sokoban::ZeroCopy;
+use solana_program::program::invoke_signed;
+
+use crate::constants::{PERCENTAGE_PRECISION, WIND_DOWN_MAX_LADDER_LEVELS, WIND_DOWN_MAX_SLIPPAGE};
+use crate::constraints::*;
+use crate::cpis::{PhoenixCancelAllOrders, PhoenixDeposit, PhoenixTrade, PhoenixWithdraw};
+use crate::error::ErrorCode;
+use crate::math::*;
+use crate::state::{MarketMapProvider, MarketTransferParams, PhoenixProgram, Vault, VaultState};
+use crate::{declare_vault_seeds, validate};
+
+/// Permissionless crank to convert a market position of a winding down vault to its quote token.
+///     * cancel all orders
+///     * deposit any base tokens held by the vault to the market
+///     * sell free base lots to the bids within `WIND_DOWN_MAX_SLIPPAGE` of the top of book
+///     * withdraw all free quote lots to `vault_quote_token_account`
+/// Crank SOL denominated markets first and the SOL/USDC market last, so the SOL they return is sold to USDC.
+/// If the bids are too thin to fill the whole position, the crank can be called again once the book refills.
+pub fn wind_down_liquidate_market<'c: 'info, 'info>(
+    ctx: Context<'_, '_, 'c, 'info, WindDownLiquidateMarket<'info>>,
+) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    let vault = ctx.accounts.vault.load()?;
+    vault.check_state(now, &[VaultState::WindDown])?;
+    drop(vault);
+
+    ctx.phoenix_cancel_all_orders()?;
+
+    let account_data = ctx.accounts.market.try_borrow_data()?;
+    let (header_bytes, _) = account_data.split_at(std::mem::size_of::<MarketHeader>());
+    let header = Box::new(
+        MarketHeader::load_bytes(header_bytes)
+            .ok_or(anchor_lang::error::Error::from(
+                ErrorCode::MarketDeserializationError,
+            ))?
+            .to_owned(),
+    );
+    drop(account_data);
+
+    let base_lots_to_deposit =
+        base_atoms_to_base_lots_rounded_down(&header, ctx.accounts.vault_base_token_account.amount);
+    if base_lots_to_deposit > 0 {
+        msg!(
+            "depositing {} base lots held by vault",
+            base_lots_to_deposit
+        );
+        ctx.phoenix_deposit(MarketTransferParams {
+            base_lots: base_lots_to_deposit,
+            quote_lots: 0,
+        })?;
+    }
+
+    let market = ctx.accounts.market.key();
+    let vault = ctx.accounts.vault.load()?;
+    let pos = ctx.market_position(&vault, market)?;
+    drop(vault);
+
+    if pos.base_lots_free > 0 {
+        let account_data = ctx.accounts.market.try_borrow_data()?;
+        let params = WindDownLiquidateMarket::build_swap_params(&account_data, pos.base_lots_free)?;
+        drop(account_data);
+        match params {
+            Some(params) => {
+                msg!("selling up to {} base lots", pos.base_lots_free);
+                ctx.phoenix_trade(params)?;
+            }
+            None => msg!(
+                "no bids within slippage to sell {} base lots",
+                pos.base_lots_free
+            ),
+        }
+    }
+
+    let vault = ctx.accounts.vault.load()?;
+    let pos = ctx.market_position(&vault, market)?;
+    drop(vault);
+
+    if pos.quote_lots_free > 0 {
+        msg!(
+            "withdrawing {} quote atoms to vault",
+            quote_lots_to_quote_atoms(&header, pos.quote_lots_free)
+        );
+        ctx.phoenix_withdraw(MarketTransferParams {
+            base_lots: 0,
+            quote_lots: pos.quote_lots_free,
+        })?;
+    }
+
+    // overwrite the position even if it is now empty, since `finalize_wind_down` requires every position be cleared
+    let mut vault = ctx.accounts.vault.load_mut()?;
+    let pos = ctx.market_position(&vault, market)?;
+    let index = vault.force_get_market_position_index(market)?;
+    vault.positions[index] = pos;
+    drop(vault);
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct WindDownLiquidateMarket<'info> {
+    #[account(mut)]
+    pub vault: AccountLoader<'info, Vault>,
+
+    //
+    // Phoenix CPI accounts
+    //
+    pub phoenix: Program<'info, PhoenixProgram>,
+    /// CHECK: validated in Phoenix CPI
+    pub log_authority: UncheckedAccount<'info>,
+    /// CHECK: validated in Phoenix CPI
+    #[account(mut)]
+    pub market: UncheckedAccount<'info>,
+    /// CHECK: validated in Phoenix CPI
+    pub seat: UncheckedAccount<'info>,
+
+    pub base_mint: Account<'info, Mint>,
+    #[account(
+        constraint = is_vault_mint(&vault, &quote_mint.key())?
+    )]
+    pub quote_mint: Account<'info, Mint>,
+
+    #[account(
+        mut,
+        token::mint = base_mint,
+        token::authority = vault
+    )]
+    pub vault_base_token_account: Account<'info, TokenAccount>,
+    #[account(
+        mut,
+        constraint = is_vault_token(&vault, &vault_quote_token_account)?,
+        token::mint = quote_mint
+    )]
+    pub vault_quote_token_account: Account<'info, TokenAccount>,
+    #[account(
+        mut,
+        token::mint = base_mint
+    )]
+    pub market_base_token_account: Account<'info, TokenAccount>,
+    #[account(
+        mut,
+        constraint = is_vault_mint(&vault, &market_quote_token_account.mint)?,
+        token::mint = quote_mint
+    )]
+    pub market_quote_token_account: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+impl<'info> WindDownLiquidateMarket<'info> {
+    /// Sells up to `base_lots_free` into the bids no more than `WIND_DOWN_MAX_SLIPPAGE` below the top of book,
+    /// and requires at least their value at that floor price less the taker fee.
+    /// Anyone can crank this, so a thin or spoofed book leaves the rest for a later crank rather than selling at any price.
+    /// Returns `None` if there are no bids to sell into, given the market's raw account data.
+    pub fn build_swap_params(
+        market_data: &[u8],
+        base_lots_free: u64,
+    ) -> Result<Option<OrderPacket>> {
+        let (header_bytes, bytes) = market_data.split_at(std::mem::size_of::<MarketHeader>());
+        let header = Box::new(MarketHeader::load_bytes(header_bytes).ok_or(
+            anchor_lang::error::Error::from(ErrorCode::MarketDeserializationError),
+        )?);
+        let market_wrapper = load_with_dispatch(&header.market_size_params, bytes)?;
+        let ladder = market_wrapper.inner.get_ladder(WIND_DOWN_MAX_LADDER_LEVELS);
+        let top_price = ladder.bids.first().map_or(0, |bid| bid.price_in_ticks);
+        let min_price = top_price
+            .cast::<u128>()?
+            .safe_mul(PERCENTAGE_PRECISION.safe_sub(WIND_DOWN_MAX_SLIPPAGE.cast()?)?)?
+            .safe_div(PERCENTAGE_PRECISION)?
+            .cast::<u64>()?;
+        if min_price == 0 {
+            return Ok(None);
+        }
+
+        let bid_depth: u64 = ladder
+            .bids
+            .iter()
+            .take_while(|bid| bid.price_in_ticks >= min_price)
+            .map(|bid| bid.size_in_base_lots)
+            .sum();
+        let bl_to_sell = base_lots_free.min(bid_depth);
+        if bl_to_sell == 0 {
+            return Ok(None);
+        }
+
+        let fee_bps = market_wrapper.inner.get_taker_fee_bps().safe_mul(100)?;
+        let min_quote_lots = base_lots_to_quote_lots(&header, bl_to_sell, min_price);
+        let ql_fee = min_quote_lots
+            .cast::<u128>()?
+            .safe_mul(fee_bps.cast()?)?
+            .safe_div_ceil(PERCENTAGE_PRECISION)?
+            .cast::<u64>()?;
+        let min_quote_lots = min_quote_lots.saturating_sub(ql_fee);
+
+        Ok(Some(OrderPacket::new_ioc(
+            Side::Ask,
+            Some(min_price),
+            bl_to_sell,
+            0,
+            0,
+            min_quote_lots,
+            SelfTradeBehavior::CancelProvide,
+            None,
+            0,
+            true,
+            None,
+            None,
+        )))
+    }
+}
+
+impl<'info> PhoenixCancelAllOrders for Context<'_, '_, '_, 'info, WindDownLiquidateMarket<'info>> {
+    fn phoenix_cancel_all_orders(&self) -> Result<()> {
+        let trader_index = 3;
+        let mut ix =
+            phoenix::program::instruction_builders::create_cancel_all_order_with_free_funds_instruction(
+                &self.accounts.market.key(),
+                &self.accounts.vault.key(),
+            );
+        ix.accounts[trader_index].is_signer = true;
+
+        // #[account(0, name = "phoenix_program", desc = "Phoenix program")]
+        // #[account(1, name = "log_authority", desc = "Phoenix log authority")]
+        // #[account(2, writable, name = "market", desc = "This account holds the market state")]
+        // #[account(3, signer, name = "trader")]
+        let accounts = [
+            self.accounts.phoenix.to_account_info(),
+            self.accounts.log_authority.to_account_info(),
+            self.accounts.market.to_account_info(),
+            self.accounts.vault.to_account_info(),
+        ];
+        declare_vault_seeds!(self.accounts.vault, seeds);
+        invoke_signed(&ix, &accounts, seeds)?;
+
+        Ok(())
+    }
+}
+
+impl<'info> PhoenixDeposit for Context<'_, '_, '_, 'info, WindDownLiquidateMarket<'info>> {
+    fn phoenix_deposit(&self, params: MarketTransferParams) -> Result<()> {
+        let trader_index = 3;
+        let mut ix = phoenix::program::instruction_builders::create_deposit_funds_instruction(
+            &self.accounts.market.key(),
+            &self.accounts.vault.key(),
+            &self.accounts.base_mint.key(),
+            &self.accounts.quote_mint.key(),
+            &DepositParams {
+                quote_lots_to_deposit: params.quote_lots,
+                base_lots_to_deposit: params.base_lots,
+            },
+        );
+        ix.accounts[trader_index].is_signer = true;
+
+        // #[account(0, name = "phoenix_program", desc = "Phoenix program")]
+        // #[account(1, name = "log_authority", desc = "Phoenix log authority")]
+        // #[account(2, writable, name = "market", desc = "This account holds the market state")]
+        // #[account(3, signer, name = "trader")]
+        // #[account(4, name = "seat")]
+        // #[account(5, writable, name = "base_account", desc = "Trader base token account")]
+        // #[account(6, writable, name = "quote_account", desc = "Trader quote token account")]
+        // #[account(7, writable, name = "base_vault", desc = "Base vault PDA, seeds are [b'vault', market_address, base_mint_address]")]
+        // #[account(8, writable, name = "quote_vault", desc = "Quote vault PDA, seeds are [b'vault', market_address, quote_mint_address]")]
+        // #[account(9, name = "token_program", desc = "Token program")]
+        let accounts = [
+            self.accounts.phoenix.to_account_info(),
+            self.accounts.log_authority.to_account_info(),
+            self.accounts.market.to_account_info(),
+            self.accounts.vault.to_account_info(),
+            self.accounts.seat.to_account_info(),
+            self.accounts.vault_base_token_account.to_account_info(),
+            self.accounts.vault_quote_token_account.to_account_info(),
+            self.accounts.market_base_token_account.to_account_info(),
+            self.accounts.market_quote_token_account.to_account_info(),
+            self.accounts.token_program.to_account_info(),
+        ];
+        declare_vault_seeds!(self.accounts.vault, seeds);
+        invoke_signed(&ix, &accounts, seeds)?;
+
+        Ok(())
+    }
+}
+
+impl<'info> PhoenixTrade for Context<'_, '_, '_, 'info, WindDownLiquidateMarket<'info>> {
+    fn phoenix_trade(&self, order: OrderPacket) -> Result<()> {
+        validate!(
+            order.is_take_only(),
+            ErrorCode::OrderPacketMustBeTakeOnly,
+            "OrderPacket must be take-only"
+        )?;
+        validate!(
+            order.no_deposit_or_withdrawal(),
+            ErrorCode::OrderPacketMustUseDepositedFunds,
+            "OrderPacket must use deposited funds"
+        )?;
+
+        let trader_index = 3;
+        let mut ix =
+            phoenix::program::instruction_builders::create_new_order_with_free_funds_instruction(
+                &self.accounts.market.key(),
+                &self.accounts.vault.key(),
+                &order,
+            );
+        ix.accounts[trader_index].is_signer = true;
+
+        // #[account(0, name = "phoenix_program", desc = "Phoenix program")]
+        // #[account(1, name = "log_authority", desc = "Phoenix log authority")]
+        // #[account(2, writable, name = "market", desc = "This account holds the market state")]
+        // #[account(3, signer, name = "trader")]
+        // #[account(4, name = "seat")]
+        // #[account(5, writable, name = "base_account", desc = "Trader base token account")]
+        // #[account(6, writable, name = "quote_account", desc = "Trader quote token account")]
+        // #[account(7, writable, name = "base_vault", desc = "Base vault PDA, seeds are [b'vault', market_address, base_mint_address]")]
+        // #[account(8, writable, name = "quote_vault", desc = "Quote vault PDA, seeds are [b'vault', market_address, quote_mint_address]")]
+        // #[account(9, name = "token_program", desc = "Token program")]
+        let accounts = [
+            self.accounts.phoenix.to_account_info(),
+            self.accounts.log_authority.to_account_info(),
+            self.accounts.market.to_account_info(),
+            self.accounts.vault.to_account_info(),
+            self.accounts.seat.to_account_info(),
+            self.accounts.vault_base_token_account.to_account_info(),
+            self.accounts.vault_quote_token_account.to_account_info(),
+            self.accounts.market_base_token_account.to_account_info(),
+            self.accounts.market_quote_token_account.to_account_info(),
+            self.accounts.token_program.to_account_info(),
+        ];
+        declare_vault_seeds!(self.accounts.vault, seeds);
+        invoke_signed(&ix, &accounts, seeds)?;
+        Ok(())
+    }
+}
+
+impl<'info> PhoenixWithdraw for Context<'_, '_, '_, 'info, WindDownLiquidateMarket<'info>> {
+    fn phoenix_withdraw(&self, params: MarketTransferParams) -> Result<()> {
+        let trader_index = 3;
+        let mut ix = phoenix::program::instruction_builders::create_withdraw_funds_with_custom_amounts_instruction(
+            &self.accounts.market.key(),
+            &self.accounts.vault.key(),
+            &self.accounts.base_mint.key(),
+            &self.accounts.quote_mint.key(),
+            params.base_lots,
+            params.quote_lots
+        );
+        ix.accounts[trader_index].is_signer = true;
+
+        // #[account(0, name = "phoenix_program", desc = "Phoenix program")]
+        // #[account(1, name = "log_authority", desc = "Phoenix log authority")]
+        // #[account(2, writable, name = "market", desc = "This account holds the market state")]
+        // #[account(3, signer, name = "trader")]
+        // #[account(4, writable, name = "base_account", desc = "Trader base token account")]
+        // #[account(5, writable, name = "quote_account", desc = "Trader quote token account")]
+        // #[account(6, writable, name = "base_vault", desc = "Base vault PDA, seeds are [b'vault', market_address, base_mint_address]")]
+        // #[account(7, writable, name = "quote_vault", desc = "Quote vault PDA, seeds are [b'vault', market_address, quote_mint_address]")]
+        // #[account(8, name = "token_program", desc = "Token program")]
+        let accounts = [
+            self.accounts.phoenix.to_account_info(),
+            self.accounts.log_authority.to_account_info(),
+            self.accounts.market.to_account_info(),
+            self.accounts.vault.to_account_info(),
+            self.accounts.vault_base_token_account.to_account_info(),
+            self.accounts.vault_quote_token_account.to_account_info(),
+            self.accounts.market_base_token_account.to_account_info(),
+            self.accounts.market_quote_token_account.to_account_info(),
+            self.accounts.token_program.to_account_info(),
+        ];
+        declare_vault_seeds!(self.accounts.vault, seeds);
+        invoke_signed(&ix, &accounts, seeds)?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{TestMarket, TestMarketParams};
+
+    fn ask(price_in_ticks: u64, num_base_lots: u64, min_quote_lots_to_fill: u64) -> OrderPacket {
+        OrderPacket::new_ioc(
+            Side::Ask,
+            Some(price_in_ticks),
+            num_base_lots,
+            0,
+            0,
+            min_quote_lots_to_fill,
+            SelfTradeBehavior::CancelProvide,
+            None,
+            0,
+            true,
+            None,
+            None,
+        )
+    }
+
+    #[test]
+    fn test_build_swap_params() {
+        let maker = Pubkey::new_unique();
+        // $150 SOL/USDC in 0.001 SOL lots and 10 atom quote lots, 1 bps taker fee, floored 1% lower at $148.50
+        for (name, bids, base_lots_free, expected) in [
+            ("empty book", vec![], 500, None),
+            (
+                "deep top of book",
+                vec![(15_000, 1_000)],
+                500,
+                Some(ask(14_850, 500, 7_424_257)),
+            ),
+            (
+                "thin book sells only within slippage",
+                vec![(15_000, 100), (14_900, 100), (14_800, 1_000)],
+                500,
+                Some(ask(14_850, 200, 2_969_703)),
+            ),
+        ] {
+            let market =
+                TestMarket::new(TestMarketParams::default()).with_ladder(&maker, &bids, &[]);
+            assert_eq!(
+                WindDownLiquidateMarket::build_swap_params(&market.data, base_lots_free).unwrap(),
+                expected,
+                "{}",
+                name
+            );
+        }
+    }
+}
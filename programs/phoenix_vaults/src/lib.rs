@@ -1,10 +1,12 @@
-mod constants;
+pub mod constants;
 mod cpis;
-mod error;
-mod instructions;
+pub mod error;
+pub mod instructions;
 pub mod macros;
-mod math;
-mod state;
+pub mod math;
+pub mod state;
+#[cfg(any(test, feature = "test-utils"))]
+pub mod test_utils;
 
 use anchor_lang::prelude::*;
 use instructions::*;
@@ -46,11 +48,16 @@ pub mod phoenix_vaults {
     }
 
     /// Investor deposits funds to the vault USDC token account.
+    ///
+    /// Fails with `SlippageExceeded` if fewer than `min_shares_out` shares are minted,
+    /// or with `DeadlineExceeded` if executed after the `deadline` unix timestamp.
     pub fn investor_deposit<'c: 'info, 'info>(
         ctx: Context<'_, '_, 'c, 'info, InvestorDeposit<'info>>,
         amount: u64,
+        min_shares_out: Option<u128>,
+        deadline: Option<i64>,
     ) -> Result<()> {
-        instructions::investor_deposit(ctx, amount)
+        instructions::investor_deposit(ctx, amount, min_shares_out, deadline)
     }
 
     /// Investor withdraws funds from the vault, assuming funds are in the vault USDC token account.
@@ -60,10 +67,26 @@ pub mod phoenix_vaults {
     ///
     /// Then call `liquidate_usdc_market` or `liquidate_sol_market` to forcefully swap a vault market position back to USDC,
     /// and then withdraw back to the investor.
+    ///
+    /// Fails with `SlippageExceeded` if less than `min_amount_out` USDC is paid out,
+    /// or with `DeadlineExceeded` if executed after the `deadline` unix timestamp.
     pub fn investor_withdraw<'c: 'info, 'info>(
         ctx: Context<'_, '_, 'c, 'info, InvestorWithdraw<'info>>,
+        min_amount_out: Option<u64>,
+        deadline: Option<i64>,
     ) -> Result<()> {
-        instructions::investor_withdraw(ctx)
+        instructions::investor_withdraw(ctx, min_amount_out, deadline)
+    }
+
+    /// Investor withdraws a pro-rata slice of every vault market position and of the vault token balances,
+    /// instead of waiting for the vault to be liquidated to USDC.
+    ///
+    /// The remaining accounts are the SOL/USDC market followed by, for each vault market position in order:
+    /// market, vault base token account, investor base token account, market base vault, market quote vault.
+    pub fn investor_withdraw_in_kind<'c: 'info, 'info>(
+        ctx: Context<'_, '_, 'c, 'info, InvestorWithdrawInKind<'info>>,
+    ) -> Result<()> {
+        instructions::investor_withdraw_in_kind(ctx)
     }
 
     /// Vault delegate claims a seat on a Phoenix market to enable trading.
@@ -98,6 +121,8 @@ pub mod phoenix_vaults {
     }
 
     /// Vault delegate deposits vault assets from the USDC or SOL token account to a Phoenix market.
+    /// USDC can't be deposited below the vault liquidity buffer, so the remaining accounts must include
+    /// the markets needed for equity when depositing from the USDC token account.
     pub fn market_deposit<'c: 'info, 'info>(
         ctx: Context<'_, '_, 'c, 'info, MarketDeposit<'info>>,
         params: MarketTransferParams,
@@ -190,6 +215,14 @@ pub mod phoenix_vaults {
         instructions::update_vault(ctx, params)
     }
 
+    /// Permissionless instruction to grow a vault created by an earlier version of the program to the current [`Vault`] size.
+    /// Other instructions fail on the vault until it is migrated.
+    pub fn migrate_vault<'c: 'info, 'info>(
+        ctx: Context<'_, '_, 'c, 'info, MigrateVault<'info>>,
+    ) -> Result<()> {
+        instructions::migrate_vault(ctx)
+    }
+
     pub fn manager_withdraw<'c: 'info, 'info>(
         ctx: Context<'_, '_, 'c, 'info, ManagerWithdraw<'info>>,
     ) -> Result<()> {
@@ -237,6 +270,119 @@ pub mod phoenix_vaults {
         instructions::protocol_cancel_withdraw_request(ctx)
     }
 
+    /// Permissionless crank that prices the deposits and withdraw requests queued during an epoch
+    /// at one equity snapshot, and lets the [`Investor`] accounts in the remaining accounts claim the result.
+    pub fn settle_epoch<'c: 'info, 'info>(
+        ctx: Context<'_, '_, 'c, 'info, SettleEpoch<'info>>,
+    ) -> Result<()> {
+        instructions::settle_epoch(ctx)
+    }
+
+    /// Permissionless crank that applies the time-based management and protocol fees,
+    /// and processes profit share for the [`Investor`] accounts in the remaining accounts.
+    pub fn crystallize_fees<'c: 'info, 'info>(
+        ctx: Context<'_, '_, 'c, 'info, CrystallizeFees<'info>>,
+    ) -> Result<()> {
+        instructions::crystallize_fees(ctx)
+    }
+
+    /// Create the [`NavHistory`] ring buffer of share price samples for a [`Vault`].
+    pub fn initialize_nav_history<'c: 'info, 'info>(
+        ctx: Context<'_, '_, 'c, 'info, InitializeNavHistory<'info>>,
+    ) -> Result<()> {
+        instructions::initialize_nav_history(ctx)
+    }
+
+    /// Permissionless crank that appends a sample of the vault equity and shares to the [`NavHistory`].
+    pub fn update_nav_history<'c: 'info, 'info>(
+        ctx: Context<'_, '_, 'c, 'info, UpdateNavHistory<'info>>,
+    ) -> Result<()> {
+        instructions::update_nav_history(ctx)
+    }
+
+    /// Manager creates the [`SharePrice`] account other programs read to price vault shares.
+    pub fn initialize_share_price<'c: 'info, 'info>(
+        ctx: Context<'_, '_, 'c, 'info, InitializeSharePrice<'info>>,
+        max_staleness_slots: u64,
+    ) -> Result<()> {
+        instructions::initialize_share_price(ctx, max_staleness_slots)
+    }
+
+    /// Permissionless crank that refreshes the [`SharePrice`] with the current vault equity.
+    pub fn update_share_price<'c: 'info, 'info>(
+        ctx: Context<'_, '_, 'c, 'info, UpdateSharePrice<'info>>,
+    ) -> Result<()> {
+        instructions::update_share_price(ctx)
+    }
+
+    /// Manager and protocol resume delegate trading after the share price drawdown exceeded the vault `max_drawdown`.
+    pub fn resume_trading<'c: 'info, 'info>(
+        ctx: Context<'_, '_, 'c, 'info, ResumeTrading<'info>>,
+    ) -> Result<()> {
+        instructions::resume_trading(ctx)
+    }
+
+    /// Manager or protocol begins winding down the vault. Deposits stop and the delegate may only reduce positions.
+    pub fn wind_down<'c: 'info, 'info>(
+        ctx: Context<'_, '_, 'c, 'info, WindDown<'info>>,
+    ) -> Result<()> {
+        instructions::wind_down(ctx)
+    }
+
+    /// Permissionless crank that cancels all orders and sells a winding down vault's position in a market to its quote token.
+    /// The market must also be passed in the remaining accounts.
+    pub fn wind_down_liquidate_market<'c: 'info, 'info>(
+        ctx: Context<'_, '_, 'c, 'info, WindDownLiquidateMarket<'info>>,
+    ) -> Result<()> {
+        instructions::wind_down_liquidate_market(ctx)
+    }
+
+    /// Permissionless crank that snapshots the final equity and closes the vault once every market position is USDC.
+    pub fn finalize_wind_down<'c: 'info, 'info>(
+        ctx: Context<'_, '_, 'c, 'info, FinalizeWindDown<'info>>,
+    ) -> Result<()> {
+        instructions::finalize_wind_down(ctx)
+    }
+
+    /// Investor claims their pro-rata USDC of the final equity of a wound down vault.
+    pub fn investor_claim_wind_down<'c: 'info, 'info>(
+        ctx: Context<'_, '_, 'c, 'info, InvestorClaimWindDown<'info>>,
+    ) -> Result<()> {
+        instructions::investor_claim_wind_down(ctx)
+    }
+
+    /// Read-only instruction that returns the [`Vault`] equity as return data.
+    pub fn view_vault_equity<'c: 'info, 'info>(
+        ctx: Context<'_, '_, 'c, 'info, ViewVaultEquity<'info>>,
+    ) -> Result<u64> {
+        instructions::view_vault_equity(ctx)
+    }
+
+    /// Read-only instruction that returns the equity of an [`Investor`] as return data.
+    pub fn view_investor_equity<'c: 'info, 'info>(
+        ctx: Context<'_, '_, 'c, 'info, ViewInvestorEquity<'info>>,
+    ) -> Result<u64> {
+        instructions::view_investor_equity(ctx)
+    }
+
+    /// Read-only instruction that returns the shares an [`Investor`] deposit would mint as return data.
+    /// Fails wherever `investor_deposit` would, e.g. if the vault is at capacity or no longer accepts deposits.
+    pub fn preview_deposit<'c: 'info, 'info>(
+        ctx: Context<'_, '_, 'c, 'info, PreviewDeposit<'info>>,
+        amount: u64,
+    ) -> Result<DepositPreview> {
+        instructions::preview_deposit(ctx, amount)
+    }
+
+    /// Read-only instruction that returns the USDC an [`Investor`] withdrawal would pay as return data.
+    pub fn preview_withdraw<'c: 'info, 'info>(
+        ctx: Context<'_, '_, 'c, 'info, PreviewWithdraw<'info>>,
+        withdraw_amount: u64,
+        withdraw_unit: WithdrawUnit,
+    ) -> Result<WithdrawPreview> {
+        instructions::preview_withdraw(ctx, withdraw_amount, withdraw_unit)
+    }
+
     pub fn cancel_all_orders<'c: 'info, 'info>(
         ctx: Context<'_, '_, 'c, 'info, CancelAllOrders<'info>>,
     ) -> Result<()> {
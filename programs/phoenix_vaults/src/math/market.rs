@@ -102,3 +102,247 @@ pub fn base_lots_to_base_atoms(header: &MarketHeader, base_lots: u64) -> u64 {
     let base_atoms_per_base_lot = header.get_base_lot_size().as_u64();
     base_lots * base_atoms_per_base_lot
 }
+
+/// Average price of a fill in quote units per raw base unit. Divide by PRICE_PRECISION to get f64 price
+pub fn fill_price_precision(header: &MarketHeader, base_lots: u64, quote_atoms: u64) -> u64 {
+    if base_lots == 0 {
+        return 0;
+    }
+    let quote_atoms_per_quote_unit = 10u128.pow(header.quote_params.decimals);
+    let raw_base_units_precision = base_lots_to_raw_base_units_precision(header, base_lots) as u128;
+    (quote_atoms as u128 * PRICE_PRECISION * PRICE_PRECISION
+        / quote_atoms_per_quote_unit
+        / raw_base_units_precision.max(1)) as u64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{TestMarket, TestMarketParams};
+
+    /// SOL/USDC: 0.001 SOL base lots, 10 atom quote lots and $0.01 ticks
+    fn sol_usdc() -> MarketHeader {
+        *TestMarket::new(TestMarketParams::default()).header()
+    }
+
+    /// JUP/SOL: 6 decimal JUP in 1000 atom lots, 0.00001 SOL quote lots and 0.01 SOL ticks
+    fn jup_sol() -> MarketHeader {
+        *TestMarket::new(TestMarketParams {
+            base_decimals: 6,
+            quote_decimals: 9,
+            ..TestMarketParams::default()
+        })
+        .header()
+    }
+
+    /// BONK/USDC quoted per million BONK: 1000 BONK lots and $0.01 ticks per million
+    fn bonk_usdc() -> MarketHeader {
+        *TestMarket::new(TestMarketParams {
+            base_decimals: 5,
+            raw_base_units_per_base_unit: 1_000_000,
+            ..TestMarketParams::default()
+        })
+        .header()
+    }
+
+    #[test]
+    fn test_ticks_to_price_precision() {
+        for (name, header, price_in_ticks, expected) in [
+            ("$150 SOL", sol_usdc(), 15_000, 150_000_000),
+            ("empty book", sol_usdc(), 0, 0),
+            ("0.05 SOL JUP", jup_sol(), 5, 50_000),
+            ("$0.00002 BONK", bonk_usdc(), 2_000, 20),
+        ] {
+            assert_eq!(
+                ticks_to_price_precision(&header, price_in_ticks),
+                expected,
+                "{}",
+                name
+            );
+        }
+    }
+
+    #[test]
+    fn test_sol_to_usdc_denom() {
+        for (base_price, sol_price, expected) in [
+            (50_000, 150_000_000, 7_500_000),
+            (1_000_000, 150_000_000, 150_000_000),
+            (0, 150_000_000, 0),
+            (50_000, 0, 0),
+        ] {
+            assert_eq!(sol_to_usdc_denom(base_price, sol_price), expected);
+        }
+    }
+
+    #[test]
+    fn test_lots_to_units_precision() {
+        for (name, header, base_lots, base_units, quote_lots, quote_units) in [
+            ("sol/usdc", sol_usdc(), 2_000, 2_000_000, 100_000, 1_000_000),
+            ("sol/usdc one lot", sol_usdc(), 1, 1_000, 1, 10),
+            ("jup/sol", jup_sol(), 10_000, 10_000_000, 100_000, 1_000_000),
+            ("jup/sol one lot", jup_sol(), 1, 1_000, 1, 10),
+            ("bonk/usdc", bonk_usdc(), 1_000, 1_000_000_000_000, 0, 0),
+        ] {
+            assert_eq!(
+                base_lots_to_raw_base_units_precision(&header, base_lots),
+                base_units,
+                "{}",
+                name
+            );
+            assert_eq!(
+                quote_lots_to_quote_units_precision(&header, quote_lots),
+                quote_units,
+                "{}",
+                name
+            );
+        }
+    }
+
+    #[test]
+    fn test_quote_atoms_to_quote_lots() {
+        for (name, header, quote_atoms, rounded_down, rounded_up) in [
+            (
+                "sol/usdc whole lots",
+                sol_usdc(),
+                1_000_000,
+                100_000,
+                100_000,
+            ),
+            ("sol/usdc part lot", sol_usdc(), 1_234_567, 123_456, 123_457),
+            ("sol/usdc zero", sol_usdc(), 0, 0, 0),
+            ("jup/sol part lot", jup_sol(), 1_234_567, 123, 124),
+            ("jup/sol under a lot", jup_sol(), 9_999, 0, 1),
+        ] {
+            assert_eq!(
+                quote_atoms_to_quote_lots_rounded_down(&header, quote_atoms),
+                rounded_down,
+                "{}",
+                name
+            );
+            assert_eq!(
+                quote_atoms_to_quote_lots_rounded_up(&header, quote_atoms),
+                rounded_up,
+                "{}",
+                name
+            );
+            assert!(quote_lots_to_quote_atoms(&header, rounded_down) <= quote_atoms);
+            assert!(quote_lots_to_quote_atoms(&header, rounded_up) >= quote_atoms);
+        }
+    }
+
+    #[test]
+    fn test_base_and_quote_at_price() {
+        for (name, header, base_lots, price_in_ticks, quote_atoms, quote_lots) in [
+            (
+                "1 SOL at $150",
+                sol_usdc(),
+                1_000,
+                15_000,
+                150_000_000,
+                15_000_000,
+            ),
+            ("a lot at a tick", sol_usdc(), 1, 1, 10, 1),
+            (
+                "10 JUP at 0.05 SOL",
+                jup_sol(),
+                10_000,
+                5,
+                500_000_000,
+                50_000,
+            ),
+            (
+                "1M BONK at $20",
+                bonk_usdc(),
+                1_000,
+                2_000,
+                20_000_000,
+                2_000_000,
+            ),
+        ] {
+            assert_eq!(
+                base_lots_and_price_to_quote_atoms(&header, base_lots, price_in_ticks),
+                quote_atoms,
+                "{}",
+                name
+            );
+            assert_eq!(
+                quote_atoms_and_price_to_base_lots(&header, quote_atoms, price_in_ticks),
+                base_lots,
+                "{}",
+                name
+            );
+            assert_eq!(
+                base_lots_to_quote_lots(&header, base_lots, price_in_ticks),
+                quote_lots,
+                "{}",
+                name
+            );
+            assert_eq!(
+                quote_lots_to_base_lots(&header, quote_lots, price_in_ticks),
+                base_lots,
+                "{}",
+                name
+            );
+        }
+
+        // an atom short of a lot's price buys one lot less
+        let header = sol_usdc();
+        assert_eq!(
+            quote_atoms_and_price_to_base_lots(&header, 149_999_999, 15_000),
+            999
+        );
+    }
+
+    #[test]
+    fn test_base_atoms_to_base_lots() {
+        for (name, header, base_atoms, base_lots, lot_atoms) in [
+            ("sol/usdc", sol_usdc(), 1_500_000, 1, 1_000_000),
+            ("sol/usdc under a lot", sol_usdc(), 999_999, 0, 0),
+            ("jup/sol", jup_sol(), 2_500, 2, 2_000),
+            ("bonk/usdc", bonk_usdc(), 250_000_000, 2, 200_000_000),
+        ] {
+            assert_eq!(
+                base_atoms_to_base_lots_rounded_down(&header, base_atoms),
+                base_lots,
+                "{}",
+                name
+            );
+            assert_eq!(
+                base_lots_to_base_atoms(&header, base_lots),
+                lot_atoms,
+                "{}",
+                name
+            );
+        }
+    }
+
+    #[test]
+    fn test_fill_price_precision() {
+        for (name, header, base_lots, quote_atoms, expected) in [
+            (
+                "1 SOL for $150",
+                sol_usdc(),
+                1_000,
+                150_000_000,
+                150_000_000,
+            ),
+            (
+                "0.5 SOL for $75.01",
+                sol_usdc(),
+                500,
+                75_010_000,
+                150_020_000,
+            ),
+            ("nothing filled", sol_usdc(), 0, 1_000_000, 0),
+            ("10 JUP for 0.5 SOL", jup_sol(), 10_000, 500_000_000, 50_000),
+            ("1M BONK for $20", bonk_usdc(), 1_000, 20_000_000, 20),
+        ] {
+            assert_eq!(
+                fill_price_precision(&header, base_lots, quote_atoms),
+                expected,
+                "{}",
+                name
+            );
+        }
+    }
+}
@@ -119,3 +119,135 @@ fn log10_iter(n: u128) -> u128 {
 
     result
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    /// `floor(value * numerator / denominator)` without overflow
+    fn exact_proportion(value: u128, numerator: u128, denominator: u128) -> U192 {
+        U192::from(value) * U192::from(numerator) / U192::from(denominator)
+    }
+
+    proptest! {
+        // every branch, including the one computed as a ceiling of the complement, rounds down
+        #[test]
+        fn get_proportion_u128_rounds_down(
+            value in any::<u64>().prop_map(u128::from),
+            numerator in any::<u64>().prop_map(u128::from),
+            denominator in 1..=u64::MAX as u128,
+        ) {
+            let expected = exact_proportion(value, numerator, denominator);
+            match get_proportion_u128(value, numerator, denominator) {
+                Ok(proportion) => prop_assert_eq!(U192::from(proportion), expected),
+                Err(_) => prop_assert!(expected > U192::from(u128::MAX)),
+            }
+        }
+
+        #[test]
+        fn get_proportion_u128_wide_values_round_down(
+            value in any::<u128>(),
+            numerator in any::<u128>(),
+            denominator in 1..=u128::MAX,
+        ) {
+            let expected = exact_proportion(value, numerator, denominator);
+            match get_proportion_u128(value, numerator, denominator) {
+                Ok(proportion) => prop_assert_eq!(U192::from(proportion), expected),
+                Err(_) => prop_assert!(expected > U192::from(u128::MAX)),
+            }
+        }
+
+        #[test]
+        fn shares_to_amount_never_exceeds_equity(
+            total_shares in 1..=u64::MAX as u128 * 1_000,
+            total_equity in any::<u64>(),
+            a in any::<u128>(),
+            b in any::<u128>(),
+        ) {
+            let a = a % (total_shares + 1);
+            let b = b % (total_shares - a + 1);
+            let amount_a = shares_to_amount(a, total_shares, total_equity).unwrap();
+            let amount_b = shares_to_amount(b, total_shares, total_equity).unwrap();
+            let amount_ab = shares_to_amount(a + b, total_shares, total_equity).unwrap();
+            // splitting shares never pays more than holding them together
+            prop_assert!(amount_a as u128 + amount_b as u128 <= amount_ab as u128);
+            prop_assert!(amount_ab <= total_equity);
+            prop_assert_eq!(
+                shares_to_amount(total_shares, total_shares, total_equity).unwrap(),
+                total_equity
+            );
+        }
+
+        #[test]
+        fn shares_to_amount_rejects_more_than_total(
+            total_shares in any::<u64>().prop_map(u128::from),
+            excess in 1..=u64::MAX as u128,
+            total_equity in any::<u64>(),
+        ) {
+            prop_assert!(
+                shares_to_amount(total_shares + excess, total_shares, total_equity).is_err()
+            );
+        }
+
+        // a deposit is never worth more than it paid once its shares are minted
+        #[test]
+        fn deposit_creates_no_value(
+            total_shares in 1..=u64::MAX as u128,
+            total_equity in 1..=u64::MAX / 4,
+            amount in 1..=u64::MAX / 4,
+        ) {
+            let shares = amount_to_shares(amount, total_shares, total_equity).unwrap();
+            let value = shares_to_amount(
+                shares,
+                total_shares + shares,
+                total_equity + amount,
+            )
+            .unwrap();
+            prop_assert!(value <= amount);
+
+            // existing holders keep at least what they had
+            let existing =
+                shares_to_amount(total_shares, total_shares + shares, total_equity + amount)
+                    .unwrap();
+            prop_assert!(existing >= total_equity);
+        }
+
+        #[test]
+        fn first_deposit_mints_one_share_per_atom(amount in any::<u64>()) {
+            prop_assert_eq!(amount_to_shares(amount, 0, 0).unwrap(), amount as u128);
+            prop_assert!(amount_to_shares(amount, 1, 0).is_err());
+        }
+
+        #[test]
+        fn rebase_leaves_at_least_ten_shares_per_atom(
+            total_shares in any::<u128>(),
+            equity in 1..=u64::MAX,
+        ) {
+            let (expo, divisor) = calculate_rebase_info(total_shares, equity).unwrap();
+            prop_assert_eq!(divisor, 10_u128.pow(expo));
+            let rebased = total_shares / divisor;
+            if expo > 0 {
+                prop_assert!(rebased >= 10 * equity as u128);
+            }
+            // a second rebase at the same equity is a no-op
+            prop_assert_eq!(calculate_rebase_info(rebased, equity).unwrap(), (0, 1));
+        }
+
+        // rebasing moves a holder's value by less than an atom either way
+        #[test]
+        fn rebase_preserves_share_value(
+            total_shares in 1_000..=u128::MAX / 2,
+            equity in 1..=u64::MAX,
+            shares in any::<u128>(),
+        ) {
+            let shares = shares % (total_shares + 1);
+            let (expo, divisor) = calculate_rebase_info(total_shares, equity).unwrap();
+            prop_assume!(expo > 0);
+
+            let before = shares_to_amount(shares, total_shares, equity).unwrap();
+            let after = shares_to_amount(shares / divisor, total_shares / divisor, equity).unwrap();
+            prop_assert!(before.abs_diff(after) <= 1, "before {} after {}", before, after);
+        }
+    }
+}
@@ -1,12 +1,18 @@
 use anchor_lang::prelude::*;
 use borsh::{BorshDeserialize, BorshSerialize};
 
+use crate::instructions::CancelOrderParams;
+use crate::state::{MarketPosition, MarketTransferParams};
+
 #[event]
 #[derive(Default)]
 pub struct VaultRecord {
     pub ts: i64,
-    pub spot_market_index: u16,
-    pub vault_equity_before: u64,
+    pub vault: Pubkey,
+    pub vault_equity: u64,
+    pub total_shares: u128,
+    pub investor_shares: u128,
+    pub shares_base: u32,
 }
 
 #[event]
@@ -40,6 +46,162 @@ pub struct InvestorRecord {
     pub manager_profit_share: u64,
     pub management_fee: i64,
     pub management_fee_shares: i64,
+
+    pub entry_fee: u64,
+    pub exit_fee: u64,
+    /// Early exit penalty withheld from a withdrawal requested during the investor's lockup
+    pub lockup_penalty: u64,
+}
+
+#[event]
+#[derive(Default)]
+pub struct ManagerRecord {
+    pub ts: i64,
+    pub vault: Pubkey,
+    pub manager: Pubkey,
+    pub action: ManagerAction,
+    /// USDC deposited, requested, withdrawn, or the value of the canceled request
+    pub amount: u64,
+    /// Shares minted, requested, burned, or lost to the vault on cancel
+    pub n_shares: u128,
+
+    pub vault_equity_before: u64,
+
+    pub manager_shares_before: u128,
+    pub total_vault_shares_before: u128,
+    pub user_vault_shares_before: u128,
+    pub protocol_shares_before: u128,
+
+    pub manager_shares_after: u128,
+    pub total_vault_shares_after: u128,
+    pub user_vault_shares_after: u128,
+    pub protocol_shares_after: u128,
+
+    pub protocol_fee: i64,
+    pub protocol_fee_shares: i64,
+    pub management_fee: i64,
+    pub management_fee_shares: i64,
+}
+
+#[event]
+#[derive(Default)]
+pub struct ProtocolRecord {
+    pub ts: i64,
+    pub vault: Pubkey,
+    pub protocol: Pubkey,
+    pub action: ProtocolAction,
+    /// USDC requested, withdrawn, or the value of the canceled request
+    pub amount: u64,
+    /// Shares requested, burned, or lost to the vault on cancel
+    pub n_shares: u128,
+
+    pub vault_equity_before: u64,
+
+    pub protocol_shares_before: u128,
+    pub total_vault_shares_before: u128,
+    pub user_vault_shares_before: u128,
+    pub manager_shares_before: u128,
+
+    pub protocol_shares_after: u128,
+    pub total_vault_shares_after: u128,
+    pub user_vault_shares_after: u128,
+    pub manager_shares_after: u128,
+
+    pub protocol_fee: i64,
+    pub protocol_fee_shares: i64,
+    pub management_fee: i64,
+    pub management_fee_shares: i64,
+}
+
+#[event]
+#[derive(Default)]
+pub struct AppointLiquidatorRecord {
+    pub ts: i64,
+    pub vault: Pubkey,
+    pub liquidator: Pubkey,
+    pub liquidation_window: i64,
+}
+
+#[event]
+#[derive(Default)]
+pub struct LiquidationRecord {
+    pub ts: i64,
+    pub vault: Pubkey,
+    pub liquidator_role: LiquidatorRole,
+    pub liquidator: Pubkey,
+    pub market: Pubkey,
+    pub liquidation_window: i64,
+
+    pub base_lots_sold: u64,
+    /// Quote units per raw base unit (multiplied by PRICE_PRECISION) before fees
+    pub avg_price: u64,
+    /// Quote atoms of `market` received after fees
+    pub quote_received: u64,
+    /// Taker fees in quote atoms of `market`
+    pub fees: u64,
+
+    /// USDC value of the liquidator's withdraw request not covered by the vault USDC token account
+    pub withdraw_request_value_before: u64,
+    pub withdraw_request_value_after: u64,
+}
+
+#[event]
+#[derive(Default)]
+pub struct DelegateActionRecord {
+    pub ts: i64,
+    pub vault: Pubkey,
+    pub delegate: Pubkey,
+    pub market: Pubkey,
+    pub action: DelegateAction,
+    /// The vault trader state on `market` before and after the Phoenix CPI
+    pub position_before: MarketPosition,
+    pub position_after: MarketPosition,
+}
+
+#[event]
+#[derive(Default)]
+pub struct EpochRecord {
+    pub ts: i64,
+    pub vault: Pubkey,
+    pub epoch: u64,
+    pub vault_equity: u64,
+
+    pub deposits: u64,
+    pub deposit_shares: u128,
+    pub withdraw_shares: u128,
+    pub withdraw_value: u64,
+
+    pub total_vault_shares_before: u128,
+    pub total_vault_shares_after: u128,
+
+    pub protocol_fee: i64,
+    pub protocol_fee_shares: i64,
+    pub management_fee: i64,
+    pub management_fee_shares: i64,
+}
+
+/// Management and protocol fees applied by `crystallize_fees` outside of any investor, manager or protocol action
+#[event]
+#[derive(Default)]
+pub struct FeeRecord {
+    pub ts: i64,
+    pub vault: Pubkey,
+    pub vault_equity: u64,
+
+    pub total_vault_shares_before: u128,
+    pub user_vault_shares_before: u128,
+    pub manager_shares_before: u128,
+    pub protocol_shares_before: u128,
+
+    pub total_vault_shares_after: u128,
+    pub user_vault_shares_after: u128,
+    pub manager_shares_after: u128,
+    pub protocol_shares_after: u128,
+
+    pub protocol_fee: i64,
+    pub protocol_fee_shares: i64,
+    pub management_fee: i64,
+    pub management_fee_shares: i64,
 }
 
 #[derive(Clone, Copy, BorshSerialize, BorshDeserialize, PartialEq, Eq, Default)]
@@ -50,4 +212,56 @@ pub enum InvestorAction {
     CancelWithdrawRequest,
     Withdraw,
     FeePayment,
+    WithdrawInKind,
+    WindDownClaim,
+}
+
+#[derive(Clone, Copy, BorshSerialize, BorshDeserialize, PartialEq, Eq, Default)]
+pub enum ManagerAction {
+    #[default]
+    Deposit,
+    WithdrawRequest,
+    CancelWithdrawRequest,
+    Withdraw,
+}
+
+#[derive(Clone, Copy, BorshSerialize, BorshDeserialize, PartialEq, Eq, Default)]
+pub enum ProtocolAction {
+    #[default]
+    WithdrawRequest,
+    CancelWithdrawRequest,
+    Withdraw,
+}
+
+/// The delegate instruction and the order parameters it was called with
+#[derive(Clone, BorshSerialize, BorshDeserialize, PartialEq, Eq)]
+pub enum DelegateAction {
+    /// Raw `PlaceLimitOrderWithFreeFunds` Phoenix instruction data
+    PlaceLimitOrder {
+        order: Vec<u8>,
+    },
+    CancelAllOrders,
+    CancelMultipleOrdersById {
+        orders: Vec<CancelOrderParams>,
+    },
+    MarketDeposit {
+        params: MarketTransferParams,
+    },
+    MarketWithdraw {
+        params: MarketTransferParams,
+    },
+}
+
+impl Default for DelegateAction {
+    fn default() -> Self {
+        DelegateAction::PlaceLimitOrder { order: vec![] }
+    }
+}
+
+#[derive(Clone, Copy, BorshSerialize, BorshDeserialize, PartialEq, Eq, Default)]
+pub enum LiquidatorRole {
+    #[default]
+    Investor,
+    Manager,
+    Protocol,
 }
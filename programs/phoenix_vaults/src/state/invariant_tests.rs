@@ -0,0 +1,362 @@
+//! Randomized sequences of investor actions, fee accruals and equity moves against a [`Vault`],
+//! checking the share accounting after every step.
+
+use anchor_lang::prelude::Pubkey;
+use proptest::prelude::*;
+use proptest::strategy::ValueTree;
+use proptest::test_runner::TestRunner;
+
+use crate::constants::{ONE_YEAR, PERCENTAGE_PRECISION_U64};
+use crate::math::shares_to_amount;
+use crate::state::{Investor, Vault, WithdrawUnit};
+
+const NUM_INVESTORS: usize = 3;
+const START_TS: i64 = 1_700_000_000;
+/// Caps gains so that repeated marks up can't overflow the equity
+const MAX_EQUITY: u64 = 1 << 62;
+
+#[derive(Clone, Copy, Debug)]
+enum Action {
+    Deposit {
+        investor: usize,
+        amount: u64,
+    },
+    RequestWithdraw {
+        investor: usize,
+        percent: u64,
+    },
+    CancelWithdrawRequest {
+        investor: usize,
+    },
+    Withdraw {
+        investor: usize,
+    },
+    /// Crystallizes fees after `seconds`
+    AccrueFees {
+        seconds: i64,
+    },
+    /// Scales the vault equity by `percent` / 100%, then rebases the investors if it fell far enough
+    Mark {
+        percent: u64,
+    },
+}
+
+fn action() -> impl Strategy<Value = Action> {
+    let investor = 0..NUM_INVESTORS;
+    prop_oneof![
+        (investor.clone(), 1..=1_000_000_000_000_u64)
+            .prop_map(|(investor, amount)| Action::Deposit { investor, amount }),
+        (investor.clone(), 1..=PERCENTAGE_PRECISION_U64)
+            .prop_map(|(investor, percent)| Action::RequestWithdraw { investor, percent }),
+        investor
+            .clone()
+            .prop_map(|investor| Action::CancelWithdrawRequest { investor }),
+        investor.prop_map(|investor| Action::Withdraw { investor }),
+        (0..=ONE_YEAR as i64 / 4).prop_map(|seconds| Action::AccrueFees { seconds }),
+        prop_oneof![
+            // losses of 99% or more, enough to rebase shares trading near par
+            1..=PERCENTAGE_PRECISION_U64 / 100,
+            1..=PERCENTAGE_PRECISION_U64,
+            PERCENTAGE_PRECISION_U64..=2 * PERCENTAGE_PRECISION_U64,
+        ]
+        .prop_map(|percent| Action::Mark { percent }),
+    ]
+}
+
+struct Sim {
+    vault: Vault,
+    investors: Vec<Investor>,
+    equity: u64,
+    now: i64,
+}
+
+impl Sim {
+    fn new(management_fee: i64, protocol_fee: u64) -> Self {
+        let vault = Vault {
+            management_fee,
+            protocol_fee,
+            init_ts: START_TS,
+            last_fee_update_ts: START_TS,
+            ..Vault::default()
+        };
+        let investors = (0..NUM_INVESTORS)
+            .map(|_| {
+                Investor::new(
+                    Pubkey::default(),
+                    Pubkey::default(),
+                    Pubkey::default(),
+                    START_TS,
+                )
+            })
+            .collect();
+        Self {
+            vault,
+            investors,
+            equity: 0,
+            now: START_TS,
+        }
+    }
+
+    /// Applies `action`, rolling the vault and investors back if the program would reject it.
+    fn step(&mut self, action: Action) {
+        let vault = self.vault;
+        let investors = self.investors.clone();
+        if self.try_step(action).is_err() {
+            self.vault = vault;
+            self.investors = investors;
+        }
+    }
+
+    fn try_step(&mut self, action: Action) -> anchor_lang::Result<()> {
+        match action {
+            Action::Deposit { investor, amount } => {
+                let investor = &mut self.investors[investor];
+                let shares = investor.deposit(amount, self.equity, &mut self.vault, self.now)?;
+                self.equity += amount;
+                let value = shares_to_amount(shares, self.vault.total_shares, self.equity).unwrap();
+                assert!(value <= amount, "deposit of {} is worth {}", amount, value);
+            }
+            Action::RequestWithdraw { investor, percent } => {
+                self.investors[investor].request_withdraw(
+                    percent,
+                    WithdrawUnit::SharesPercent,
+                    self.equity,
+                    &mut self.vault,
+                    self.now,
+                )?;
+            }
+            Action::CancelWithdrawRequest { investor } => {
+                self.investors[investor].cancel_withdraw_request(
+                    self.equity,
+                    &mut self.vault,
+                    self.now,
+                )?;
+            }
+            Action::Withdraw { investor } => {
+                let investor = &mut self.investors[investor];
+                let request = investor.last_withdraw_request;
+                let (amount, _) = investor.withdraw(self.equity, &mut self.vault, self.now)?;
+                assert!(amount <= request.value);
+                assert!(amount <= self.equity);
+                self.equity -= amount;
+            }
+            Action::AccrueFees { seconds } => {
+                self.now += seconds;
+                let before = self.vault;
+                self.vault.crystallize_fees(self.equity, self.now)?;
+                self.check_fees(&before);
+            }
+            Action::Mark { percent } => {
+                // an empty vault has nothing to mark, and a marked vault keeps some equity
+                if self.equity > 0 {
+                    self.equity = (self.equity as u128 * percent as u128
+                        / PERCENTAGE_PRECISION_U64 as u128)
+                        .clamp(1, MAX_EQUITY as u128) as u64;
+                    self.rebase_and_check();
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn check_fees(&self, before: &Vault) {
+        let vault = &self.vault;
+        assert!(vault.manager_total_fee >= before.manager_total_fee);
+        assert!(vault.protocol_total_fee >= before.protocol_total_fee);
+        // fees are paid in shares, and at most the depositors' equity is taxed
+        let fees = (vault.manager_total_fee - before.manager_total_fee) as u128
+            + (vault.protocol_total_fee - before.protocol_total_fee) as u128;
+        let depositor_equity =
+            shares_to_amount(before.investor_shares, before.total_shares, self.equity).unwrap_or(0)
+                as u128;
+        assert!(
+            fees <= depositor_equity,
+            "fees {} > {}",
+            fees,
+            depositor_equity
+        );
+        if vault.shares_base == before.shares_base {
+            assert_eq!(vault.investor_shares, before.investor_shares);
+            assert!(vault.total_shares >= before.total_shares);
+        }
+    }
+
+    /// Rebases every investor at the current equity, checking that each keeps its value up to an atom.
+    /// Expects the investors to be on the vault's share base, as `sync_and_check` leaves them.
+    fn rebase_and_check(&mut self) {
+        let before = self.vault;
+        let values: Vec<u64> = self
+            .investors
+            .iter()
+            .map(|investor| {
+                shares_to_amount(
+                    investor.unchecked_vault_shares(),
+                    before.total_shares,
+                    self.equity,
+                )
+                .unwrap()
+            })
+            .collect();
+        for (investor, value_before) in self.investors.iter_mut().zip(values) {
+            let divisor = investor.apply_rebase(&mut self.vault, self.equity).unwrap();
+            assert_eq!(
+                divisor.is_some(),
+                self.vault.shares_base != before.shares_base
+            );
+            let value_after = shares_to_amount(
+                investor.unchecked_vault_shares(),
+                self.vault.total_shares,
+                self.equity,
+            )
+            .unwrap();
+            assert!(
+                value_before.abs_diff(value_after) <= 1,
+                "rebase moved {} to {}",
+                value_before,
+                value_after
+            );
+        }
+    }
+
+    /// Brings every investor to the vault's share base, as `crystallize_fees` does, then checks the accounting.
+    fn sync_and_check(&mut self, rebased: &mut bool) {
+        for investor in self.investors.iter_mut() {
+            investor.apply_rebase(&mut self.vault, self.equity).unwrap();
+        }
+        *rebased |= self.vault.shares_base > 0;
+
+        let vault = &self.vault;
+        let investor_shares: u128 = self
+            .investors
+            .iter()
+            .map(|investor| investor.unchecked_vault_shares())
+            .sum();
+        if *rebased {
+            // each investor rounds its shares down on a rebase, leaving dust with the vault
+            assert!(investor_shares <= vault.investor_shares);
+            assert!(vault.investor_shares - investor_shares <= NUM_INVESTORS as u128);
+        } else {
+            assert_eq!(investor_shares, vault.investor_shares);
+        }
+        let manager_shares = vault.get_manager_shares().unwrap();
+        assert_eq!(
+            vault.investor_shares + vault.get_protocol_shares() + manager_shares,
+            vault.total_shares
+        );
+
+        let mut claims: u128 = 0;
+        for investor in self.investors.iter() {
+            let shares = investor.unchecked_vault_shares();
+            assert!(investor.last_withdraw_request.shares <= shares);
+            claims += shares_to_amount(shares, vault.total_shares, self.equity).unwrap() as u128;
+        }
+        claims +=
+            shares_to_amount(manager_shares, vault.total_shares, self.equity).unwrap() as u128;
+        claims += shares_to_amount(vault.get_protocol_shares(), vault.total_shares, self.equity)
+            .unwrap() as u128;
+        assert!(
+            claims <= self.equity as u128,
+            "claims {} > equity {}",
+            claims,
+            self.equity
+        );
+    }
+}
+
+// `share_accounting_holds` only covers the rebase if the generated sequences actually reach one
+#[test]
+fn generated_sequences_rebase() {
+    const SEQUENCES: usize = 256;
+    let mut runner = TestRunner::deterministic();
+    let strategy = prop::collection::vec(action(), 1..64);
+    let mut rebased_sequences = 0;
+    for _ in 0..SEQUENCES {
+        let actions = strategy.new_tree(&mut runner).unwrap().current();
+        let mut sim = Sim::new(0, 0);
+        let mut rebased = false;
+        for action in actions {
+            sim.step(action);
+            sim.sync_and_check(&mut rebased);
+        }
+        if rebased {
+            rebased_sequences += 1;
+        }
+    }
+    assert!(
+        rebased_sequences >= SEQUENCES / 4,
+        "only {} of {} sequences rebased",
+        rebased_sequences,
+        SEQUENCES
+    );
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(512))]
+
+    #[test]
+    fn share_accounting_holds(
+        management_fee in 0..=100_000_i64,
+        protocol_fee in 0..=100_000_u64,
+        actions in prop::collection::vec(action(), 1..64),
+    ) {
+        let mut sim = Sim::new(management_fee, protocol_fee);
+        let mut rebased = false;
+        for action in actions {
+            sim.step(action);
+            sim.sync_and_check(&mut rebased);
+        }
+    }
+
+    // withdrawing everything right after depositing never returns more than was deposited
+    #[test]
+    fn deposit_withdraw_round_trip(
+        existing in 0..=1_000_000_000_000_u64,
+        existing_shares_per_atom in 1..=1_000_u128,
+        amount in 1..=1_000_000_000_000_u64,
+    ) {
+        let mut sim = Sim::new(0, 0);
+        sim.vault.total_shares = existing as u128 * existing_shares_per_atom;
+        sim.equity = existing;
+
+        let investor = &mut sim.investors[0];
+        investor.deposit(amount, sim.equity, &mut sim.vault, sim.now).unwrap();
+        let equity = sim.equity + amount;
+        let investor = &mut sim.investors[0];
+        investor
+            .request_withdraw(
+                PERCENTAGE_PRECISION_U64,
+                WithdrawUnit::SharesPercent,
+                equity,
+                &mut sim.vault,
+                sim.now,
+            )
+            .unwrap();
+        let (withdrawn, _) = investor.withdraw(equity, &mut sim.vault, sim.now).unwrap();
+        prop_assert!(withdrawn <= amount);
+        prop_assert_eq!(investor.unchecked_vault_shares(), 0);
+    }
+
+    // an investor's shares keep their value through a rebase, up to an atom
+    #[test]
+    fn investor_shares_survive_rebase(
+        deposit in 1_000..=1_000_000_000_000_u64,
+        loss in 1_000..=1_000_000_u64,
+    ) {
+        let mut sim = Sim::new(0, 0);
+        let investor = &mut sim.investors[0];
+        investor.deposit(deposit, 0, &mut sim.vault, sim.now).unwrap();
+        let equity = (deposit / loss).max(1);
+        let shares = investor.unchecked_vault_shares();
+        let value_before = shares_to_amount(shares, sim.vault.total_shares, equity).unwrap();
+
+        let divisor = investor.apply_rebase(&mut sim.vault, equity).unwrap();
+        prop_assert_eq!(investor.vault_shares_base, sim.vault.shares_base);
+        prop_assert_eq!(divisor.is_some(), sim.vault.shares_base > 0);
+        let shares = investor.unchecked_vault_shares();
+        let value_after = shares_to_amount(shares, sim.vault.total_shares, equity).unwrap();
+        prop_assert!(value_before.abs_diff(value_after) <= 1);
+
+        // rebasing again at the same equity changes nothing
+        prop_assert_eq!(investor.apply_rebase(&mut sim.vault, equity).unwrap(), None);
+    }
+}
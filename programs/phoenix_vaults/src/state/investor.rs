@@ -2,16 +2,16 @@
 
 use crate::constants::PERCENTAGE_PRECISION;
 use crate::math::casting::Cast;
-use crate::math::{amount_to_shares, shares_to_amount, SafeMath};
+use crate::math::{amount_to_shares, get_proportion_u128, shares_to_amount, SafeMath};
 use anchor_lang::prelude::*;
 use drift_macros::assert_no_slop;
 use static_assertions::const_assert_eq;
 
-use crate::error::ErrorCode;
+use crate::error::{ErrorCode, VaultResult};
 use crate::state::events::{InvestorAction, InvestorRecord};
 use crate::state::withdraw_request::WithdrawRequest;
 use crate::state::withdraw_unit::WithdrawUnit;
-use crate::state::{Vault, VaultFee};
+use crate::state::{DepositPreview, Vault, VaultFee, VaultState, WithdrawPreview};
 use crate::validate;
 use crate::Size;
 
@@ -45,7 +45,15 @@ pub struct Investor {
     /// the exponent for vault_shares decimal places
     pub vault_shares_base: u32,
     pub padding1: u32,
-    pub padding: [u64; 8],
+    /// USDC deposited during an epoch that has not been claimed as shares
+    pub epoch_deposit_amount: u64,
+    /// The epoch `epoch_deposit_amount` was deposited in
+    pub epoch_deposit_epoch: u64,
+    /// The epoch the last withdraw request was queued in
+    pub epoch_withdraw_epoch: u64,
+    /// When the investor first deposited, which starts the vault lockup
+    pub first_deposit_ts: i64,
+    pub padding: [u64; 4],
 }
 
 impl Investor {
@@ -54,6 +62,32 @@ impl Investor {
     }
 }
 
+impl Investor {
+    /// Load every [`Investor`] of `vault` from the remaining accounts.
+    /// Accounts owned by other programs (i.e. Phoenix markets used to compute equity) are skipped.
+    pub fn load_remaining<'info>(
+        remaining_accounts: &'info [AccountInfo<'info>],
+        vault: &Pubkey,
+    ) -> Result<Vec<AccountLoader<'info, Investor>>> {
+        let mut investors = vec![];
+        for account_info in remaining_accounts.iter() {
+            if account_info.owner != &crate::ID {
+                continue;
+            }
+            let investor = AccountLoader::<Investor>::try_from(account_info)?;
+            validate!(
+                &investor.load()?.vault == vault,
+                ErrorCode::InvalidInvestorAccount,
+                "investor {:?} is not for vault {:?}",
+                account_info.key,
+                vault
+            )?;
+            investors.push(investor);
+        }
+        Ok(investors)
+    }
+}
+
 impl Size for Investor {
     const SIZE: usize = 264 + 8;
 }
@@ -75,7 +109,11 @@ impl Investor {
             cumulative_profit_share_amount: 0,
             padding1: 0,
             profit_share_fee_paid: 0,
-            padding: [0u64; 8],
+            epoch_deposit_amount: 0,
+            epoch_deposit_epoch: 0,
+            epoch_withdraw_epoch: 0,
+            first_deposit_ts: 0,
+            padding: [0u64; 4],
         }
     }
 
@@ -195,18 +233,18 @@ impl Investor {
         Ok((0, 0))
     }
 
-    pub fn deposit(
-        &mut self,
-        amount: u64,
-        vault_equity: u64,
-        vault: &mut Vault,
-        now: i64,
-    ) -> Result<()> {
+    /// The checks shared by [`Investor::deposit`] and [`Investor::preview_deposit`], after the epoch is claimed.
+    fn check_deposit(&self, amount: u64, vault_equity: u64, vault: &Vault, now: i64) -> Result<()> {
+        vault.check_state(now, &[VaultState::Fundraising, VaultState::Active])?;
+
+        let vault_equity_after = vault_equity
+            .safe_add(vault.epoch_pending_deposits)?
+            .safe_add(amount)?;
         validate!(
-            vault.max_tokens == 0 || vault.max_tokens > vault_equity.safe_add(amount)?,
+            vault.max_tokens == 0 || vault.max_tokens > vault_equity_after,
             ErrorCode::VaultIsAtCapacity,
             "after deposit vault equity is {} > {}",
-            vault_equity.safe_add(amount)?,
+            vault_equity_after,
             vault.max_tokens
         )?;
 
@@ -230,6 +268,79 @@ impl Investor {
             "withdraw request is in progress"
         )?;
 
+        Ok(())
+    }
+
+    /// Fails with `DeadlineExceeded` if an investor deposit or withdraw executes after its `deadline` unix timestamp.
+    pub fn check_deadline(deadline: Option<i64>, now: i64) -> VaultResult {
+        validate!(
+            deadline.map_or(true, |deadline| now <= deadline),
+            ErrorCode::DeadlineExceeded,
+            "deadline {:?} has passed",
+            deadline
+        )?;
+        Ok(())
+    }
+
+    /// Fails with `SlippageExceeded` if a deposit minted fewer than `min_shares_out` shares.
+    /// Deposits queued for an epoch are priced when it settles, so `min_shares_out` can't be used in epoch mode.
+    pub fn check_min_shares_out(
+        n_shares: u128,
+        min_shares_out: Option<u128>,
+        vault: &Vault,
+    ) -> VaultResult {
+        if let Some(min_shares_out) = min_shares_out {
+            validate!(
+                !vault.epoch_mode(),
+                ErrorCode::InvalidVaultDeposit,
+                "min_shares_out is unsupported in epoch mode, deposits are priced when the epoch settles"
+            )?;
+            validate!(
+                n_shares >= min_shares_out,
+                ErrorCode::SlippageExceeded,
+                "deposit minted {} shares < min_shares_out {}",
+                n_shares,
+                min_shares_out
+            )?;
+        }
+        Ok(())
+    }
+
+    /// Fails with `SlippageExceeded` if a withdrawal paid less than `min_amount_out` USDC.
+    pub fn check_min_amount_out(amount: u64, min_amount_out: Option<u64>) -> VaultResult {
+        if let Some(min_amount_out) = min_amount_out {
+            validate!(
+                amount >= min_amount_out,
+                ErrorCode::SlippageExceeded,
+                "withdraw amount {} < min_amount_out {}",
+                amount,
+                min_amount_out
+            )?;
+        }
+        Ok(())
+    }
+
+    /// Returns the shares minted, which is zero if the deposit is queued for an epoch.
+    pub fn deposit(
+        &mut self,
+        amount: u64,
+        vault_equity: u64,
+        vault: &mut Vault,
+        now: i64,
+    ) -> Result<u128> {
+        let vault_equity = self.claim_epoch(vault_equity, vault)?;
+
+        if self.first_deposit_ts == 0 {
+            self.first_deposit_ts = now;
+        }
+
+        self.check_deposit(amount, vault_equity, vault, now)?;
+
+        if vault.epoch_mode() {
+            self.queue_deposit(amount, vault_equity, vault, now)?;
+            return Ok(0);
+        }
+
         self.apply_rebase(vault, vault_equity)?;
 
         let vault_shares_before = self.checked_vault_shares(vault)?;
@@ -246,7 +357,13 @@ impl Investor {
         let (manager_profit_share, protocol_profit_share) =
             self.apply_profit_share(vault_equity, vault)?;
 
-        let n_shares = amount_to_shares(amount, vault.total_shares, vault_equity)?;
+        // the entry fee is not minted as shares, so it accrues to the existing shareholders
+        let entry_fee = vault.entry_fee(amount)?;
+        let n_shares = amount_to_shares(
+            amount.safe_sub(entry_fee)?,
+            vault.total_shares,
+            vault_equity,
+        )?;
 
         self.total_deposits = self.total_deposits.saturating_add(amount);
         self.net_deposits = self.net_deposits.safe_add(amount.cast()?)?;
@@ -284,10 +401,13 @@ impl Investor {
             management_fee: management_fee_payment,
             management_fee_shares,
             protocol_shares_before,
-            protocol_shares_after
+            protocol_shares_after,
+            entry_fee,
+            exit_fee: 0,
+            lockup_penalty: 0
         });
 
-        Ok(())
+        Ok(n_shares)
     }
 
     pub fn request_withdraw(
@@ -298,15 +418,26 @@ impl Investor {
         vault: &mut Vault,
         now: i64,
     ) -> Result<()> {
+        let vault_equity = self.claim_epoch(vault_equity, vault)?;
+
         let rebase_divisor = self.apply_rebase(vault, vault_equity)?;
+        // in epoch mode fees are applied once by `settle_epoch`
         let VaultFee {
             management_fee_payment,
             management_fee_shares,
             protocol_fee_payment,
             protocol_fee_shares,
-        } = vault.apply_fee(vault_equity, now)?;
-        let (manager_profit_share, protocol_profit_share) =
-            self.apply_profit_share(vault_equity, vault)?;
+        } = if vault.epoch_mode() {
+            VaultFee::default()
+        } else {
+            vault.apply_fee(vault_equity, now)?
+        };
+        // in epoch mode the profit share is taken at the settle price by `claim_epoch`
+        let (manager_profit_share, protocol_profit_share) = if vault.epoch_mode() {
+            (0, 0)
+        } else {
+            self.apply_profit_share(vault_equity, vault)?
+        };
 
         let (withdraw_value, n_shares) = withdraw_unit.get_withdraw_value_and_shares(
             withdraw_amount,
@@ -321,20 +452,36 @@ impl Investor {
             ErrorCode::InvalidVaultWithdrawSize,
             "Requested shares = 0"
         )?;
+        validate!(
+            !vault.in_lockup(self.first_deposit_ts, now) || vault.lockup_penalty_bps > 0,
+            ErrorCode::InvestorLockedUp,
+            "investor is locked up until {}",
+            self.first_deposit_ts.saturating_add(vault.lockup_duration)
+        )?;
 
         let vault_shares_before: u128 = self.checked_vault_shares(vault)?;
         let total_vault_shares_before = vault.total_shares;
         let user_vault_shares_before = vault.investor_shares;
         let protocol_shares_before = vault.get_protocol_shares();
 
-        self.last_withdraw_request.set(
-            vault_shares_before,
-            n_shares,
-            withdraw_value,
-            vault_equity,
-            now,
-        )?;
-        vault.total_withdraw_requested = vault.total_withdraw_requested.safe_add(withdraw_value)?;
+        if vault.epoch_mode() {
+            // the value is priced when the epoch settles
+            self.last_withdraw_request
+                .set(vault_shares_before, n_shares, 0, vault_equity, now)?;
+            self.epoch_withdraw_epoch = vault.current_epoch;
+            vault.epoch_pending_withdraw_shares =
+                vault.epoch_pending_withdraw_shares.safe_add(n_shares)?;
+        } else {
+            self.last_withdraw_request.set(
+                vault_shares_before,
+                n_shares,
+                withdraw_value,
+                vault_equity,
+                now,
+            )?;
+            vault.total_withdraw_requested =
+                vault.total_withdraw_requested.safe_add(withdraw_value)?;
+        }
 
         let vault_shares_after = self.checked_vault_shares(vault)?;
         let protocol_shares_after = vault.get_protocol_shares();
@@ -361,7 +508,10 @@ impl Investor {
             management_fee: management_fee_payment,
             management_fee_shares,
             protocol_shares_before,
-            protocol_shares_after
+            protocol_shares_after,
+            entry_fee: 0,
+            exit_fee: 0,
+            lockup_penalty: 0
         });
 
         Ok(())
@@ -373,6 +523,12 @@ impl Investor {
         vault: &mut Vault,
         now: i64,
     ) -> Result<()> {
+        let vault_equity = self.claim_epoch(vault_equity, vault)?;
+
+        if vault.epoch_mode() {
+            return self.cancel_queued_withdraw_request(vault_equity, vault, now);
+        }
+
         self.apply_rebase(vault, vault_equity)?;
 
         let vault_shares_before: u128 = self.checked_vault_shares(vault)?;
@@ -423,7 +579,10 @@ impl Investor {
             management_fee: management_fee_payment,
             management_fee_shares,
             protocol_shares_before,
-            protocol_shares_after
+            protocol_shares_after,
+            entry_fee: 0,
+            exit_fee: 0,
+            lockup_penalty: 0
         });
 
         vault.total_withdraw_requested = vault
@@ -441,11 +600,47 @@ impl Investor {
         vault: &mut Vault,
         now: i64,
     ) -> Result<(u64, bool)> {
-        self.last_withdraw_request
-            .check_redeem_period_finished(vault, now)?;
+        self.process_withdraw(vault_equity, vault, now, InvestorAction::Withdraw)
+    }
+
+    /// Same accounting as [`Investor::withdraw`], but the caller pays out the returned value
+    /// as a pro-rata slice of the vault's tokens and market positions instead of USDC.
+    /// Not available in epoch mode, where `settle_epoch` already reserves redemptions in USDC.
+    pub fn withdraw_in_kind(
+        &mut self,
+        vault_equity: u64,
+        vault: &mut Vault,
+        now: i64,
+    ) -> Result<(u64, bool)> {
+        validate!(
+            !vault.epoch_mode(),
+            ErrorCode::InKindEpochMode,
+            "epoch redemptions are reserved in USDC and can't be withdrawn in kind"
+        )?;
+        self.process_withdraw(vault_equity, vault, now, InvestorAction::WithdrawInKind)
+    }
+
+    fn process_withdraw(
+        &mut self,
+        vault_equity: u64,
+        vault: &mut Vault,
+        now: i64,
+        action: InvestorAction,
+    ) -> Result<(u64, bool)> {
+        let vault_equity = self.claim_epoch(vault_equity, vault)?;
+
+        if vault.epoch_mode() {
+            return self.process_epoch_withdraw(vault_equity, vault, now, action);
+        }
 
         self.apply_rebase(vault, vault_equity)?;
 
+        // investors can exit immediately while a drawdown has halted trading
+        if !vault.trading_halted {
+            self.last_withdraw_request
+                .check_redeem_period_finished(vault, now)?;
+        }
+
         let vault_shares_before: u128 = self.checked_vault_shares(vault)?;
         let total_vault_shares_before = vault.total_shares;
         let user_vault_shares_before = vault.investor_shares;
@@ -475,7 +670,14 @@ impl Investor {
 
         let amount: u64 = shares_to_amount(n_shares, vault.total_shares, vault_equity)?;
 
-        let withdraw_amount = amount.min(self.last_withdraw_request.value);
+        let withdraw_value = amount.min(self.last_withdraw_request.value);
+        // the exit fee and lockup penalty are withheld from the payout but their shares are burned,
+        // so they accrue to the remaining shareholders
+        let exit_fee = vault.exit_fee(withdraw_value)?;
+        let lockup_penalty = self.lockup_penalty(withdraw_value, vault)?;
+        let withdraw_amount = withdraw_value
+            .safe_sub(exit_fee)?
+            .safe_sub(lockup_penalty)?;
         msg!("vault_equity: {}", vault_equity);
         msg!(
             "amount={}, last_withdraw_request_value={}",
@@ -510,7 +712,7 @@ impl Investor {
             ts: now,
             vault: vault.pubkey,
             depositor_authority: self.authority,
-            action: InvestorAction::Withdraw,
+            action,
             amount: withdraw_amount,
             usdc_mint: vault.usdc_mint,
             sol_mint: vault.sol_mint,
@@ -528,7 +730,10 @@ impl Investor {
             management_fee: management_fee_payment,
             management_fee_shares,
             protocol_shares_before,
-            protocol_shares_after
+            protocol_shares_after,
+            entry_fee: 0,
+            exit_fee,
+            lockup_penalty
         });
 
         let finishing_liquidation = vault.liquidator == self.authority;
@@ -536,122 +741,672 @@ impl Investor {
         Ok((withdraw_amount, finishing_liquidation))
     }
 
-    pub fn apply_profit_share(
+    /// Pay out the investor's pro-rata slice of the equity snapshotted by `finalize_wind_down`, burning all their shares.
+    /// There is no redeem period or exit fee. Deposits still queued for an epoch are refunded,
+    /// and redemptions already priced by `settle_epoch` are paid alongside.
+    pub fn claim_wind_down(&mut self, vault: &mut Vault, now: i64) -> Result<u64> {
+        let vault_equity = vault.wind_down_equity()?;
+
+        let vault_equity = self.claim_epoch(vault_equity, vault)?;
+        self.apply_rebase(vault, vault_equity)?;
+
+        let mut claim_amount: u64 = 0;
+
+        // the epoch this deposit was queued for never settled
+        if self.epoch_deposit_amount > 0 {
+            vault.epoch_pending_deposits = vault
+                .epoch_pending_deposits
+                .safe_sub(self.epoch_deposit_amount)?;
+            claim_amount = claim_amount.safe_add(self.epoch_deposit_amount)?;
+            self.epoch_deposit_amount = 0;
+        }
+
+        if self.last_withdraw_request.pending() {
+            if self.epoch_withdraw_queued() {
+                vault.epoch_pending_withdraw_shares = vault
+                    .epoch_pending_withdraw_shares
+                    .saturating_sub(self.last_withdraw_request.shares);
+            } else {
+                if self.last_withdraw_request.shares == 0 {
+                    // settled by an epoch, the shares are already burned
+                    vault.epoch_redemptions_owed = vault
+                        .epoch_redemptions_owed
+                        .safe_sub(self.last_withdraw_request.value)?;
+                    claim_amount = claim_amount.safe_add(self.last_withdraw_request.value)?;
+                }
+                vault.total_withdraw_requested = vault
+                    .total_withdraw_requested
+                    .safe_sub(self.last_withdraw_request.value)?;
+            }
+            self.last_withdraw_request.reset(now)?;
+        }
+
+        let vault_shares_before: u128 = self.checked_vault_shares(vault)?;
+        let total_vault_shares_before = vault.total_shares;
+        let user_vault_shares_before = vault.investor_shares;
+        let protocol_shares_before = vault.get_protocol_shares();
+
+        let (manager_profit_share, protocol_profit_share) =
+            self.apply_profit_share(vault_equity, vault)?;
+
+        let n_shares = self.checked_vault_shares(vault)?;
+        let amount: u64 = shares_to_amount(n_shares, vault.total_shares, vault_equity)?;
+
+        self.decrease_vault_shares(n_shares, vault)?;
+        vault.total_shares = vault.total_shares.safe_sub(n_shares)?;
+        vault.investor_shares = vault.investor_shares.safe_sub(n_shares)?;
+
+        claim_amount = claim_amount.safe_add(amount)?;
+
+        self.total_withdraws = self.total_withdraws.saturating_add(claim_amount);
+        self.net_deposits = self.net_deposits.safe_sub(claim_amount.cast()?)?;
+
+        vault.total_withdraws = vault.total_withdraws.saturating_add(claim_amount);
+        vault.net_deposits = vault.net_deposits.safe_sub(claim_amount.cast()?)?;
+
+        emit!(InvestorRecord {
+            ts: now,
+            vault: vault.pubkey,
+            depositor_authority: self.authority,
+            action: InvestorAction::WindDownClaim,
+            amount: claim_amount,
+            usdc_mint: vault.usdc_mint,
+            sol_mint: vault.sol_mint,
+            vault_equity_before: vault_equity,
+            vault_shares_before,
+            user_vault_shares_before,
+            total_vault_shares_before,
+            vault_shares_after: self.checked_vault_shares(vault)?,
+            total_vault_shares_after: vault.total_shares,
+            user_vault_shares_after: vault.investor_shares,
+            protocol_profit_share,
+            protocol_fee: 0,
+            protocol_fee_shares: 0,
+            manager_profit_share,
+            management_fee: 0,
+            management_fee_shares: 0,
+            protocol_shares_before,
+            protocol_shares_after: vault.get_protocol_shares(),
+            entry_fee: 0,
+            exit_fee: 0,
+            lockup_penalty: 0
+        });
+
+        Ok(claim_amount)
+    }
+
+    /// The early exit penalty on `withdraw_value` if the pending withdraw request was made during the lockup.
+    fn lockup_penalty(&self, withdraw_value: u64, vault: &Vault) -> VaultResult<u64> {
+        if vault.in_lockup(self.first_deposit_ts, self.last_withdraw_request.ts) {
+            vault.lockup_penalty(withdraw_value)
+        } else {
+            Ok(0)
+        }
+    }
+
+    fn epoch_withdraw_queued(&self) -> bool {
+        self.last_withdraw_request.shares > 0 && self.last_withdraw_request.value == 0
+    }
+
+    /// Credit the investor with their slice of the shares and redemption value priced by the last `settle_epoch`.
+    /// This is a no-op if the vault isn't in epoch mode or the investor has nothing settled to claim.
+    /// Returns the vault equity after any profit share taken from the redemption value is returned to the vault.
+    pub fn claim_epoch(&mut self, mut vault_equity: u64, vault: &mut Vault) -> Result<u64> {
+        if !vault.epoch_mode() {
+            return Ok(vault_equity);
+        }
+
+        self.apply_rebase(vault, vault_equity)?;
+
+        if self.epoch_deposit_amount > 0 && self.epoch_deposit_epoch < vault.current_epoch {
+            let amount = self.epoch_deposit_amount;
+            // the last claimant takes the remainder so no shares are stranded by rounding
+            let n_shares = if amount >= vault.epoch_unclaimed_deposits {
+                vault.epoch_unclaimed_deposit_shares
+            } else {
+                get_proportion_u128(
+                    vault.epoch_unclaimed_deposit_shares,
+                    amount.cast()?,
+                    vault.epoch_unclaimed_deposits.cast()?,
+                )?
+            };
+            vault.epoch_unclaimed_deposits = vault.epoch_unclaimed_deposits.saturating_sub(amount);
+            vault.epoch_unclaimed_deposit_shares =
+                vault.epoch_unclaimed_deposit_shares.safe_sub(n_shares)?;
+
+            self.increase_vault_shares(n_shares, vault)?;
+            self.epoch_deposit_amount = 0;
+            msg!(
+                "claimed {} shares for epoch {}",
+                n_shares,
+                self.epoch_deposit_epoch
+            );
+        }
+
+        if self.epoch_withdraw_queued() && self.epoch_withdraw_epoch < vault.current_epoch {
+            let n_shares = self.last_withdraw_request.shares;
+            let value: u64 = if n_shares >= vault.epoch_unclaimed_withdraw_shares {
+                vault.epoch_unclaimed_withdraw_value
+            } else {
+                get_proportion_u128(
+                    vault.epoch_unclaimed_withdraw_value.cast()?,
+                    n_shares,
+                    vault.epoch_unclaimed_withdraw_shares,
+                )?
+                .cast()?
+            };
+            vault.epoch_unclaimed_withdraw_shares = vault
+                .epoch_unclaimed_withdraw_shares
+                .saturating_sub(n_shares);
+            vault.epoch_unclaimed_withdraw_value =
+                vault.epoch_unclaimed_withdraw_value.safe_sub(value)?;
+
+            let (value, vault_equity_after) =
+                self.apply_epoch_profit_share(n_shares, value, vault_equity, vault)?;
+            vault_equity = vault_equity_after;
+
+            // shares were already burned from the vault totals by `settle_epoch`
+            self.decrease_vault_shares(n_shares, vault)?;
+            self.last_withdraw_request.shares = 0;
+            self.last_withdraw_request.value = value;
+            msg!(
+                "claimed {} withdraw value for epoch {}",
+                value,
+                self.epoch_withdraw_epoch
+            );
+        }
+
+        Ok(vault_equity)
+    }
+
+    /// Take the profit share on the investor's whole position at the price `settle_epoch` redeemed `n_shares` for `value`.
+    /// It is paid with the investor's remaining shares first, as it would have been when the withdraw was requested,
+    /// and the rest is withheld from the redemption value and minted to the manager and protocol at the current price.
+    /// Returns the redemption value after the profit share and the vault equity including the withheld value.
+    fn apply_epoch_profit_share(
         &mut self,
+        n_shares: u128,
+        value: u64,
         vault_equity: u64,
         vault: &mut Vault,
     ) -> Result<(u64, u64)> {
-        validate!(
-            !self.last_withdraw_request.pending(),
-            ErrorCode::InvalidVaultDeposit,
-            "Cannot apply profit share to investor with pending withdraw request"
-        )?;
-
-        let total_amount = shares_to_amount(self.vault_shares, vault.total_shares, vault_equity)?;
+        let total_amount: u64 =
+            get_proportion_u128(value.cast()?, self.vault_shares, n_shares)?.cast()?;
+        if total_amount == 0 {
+            return Ok((value, vault_equity));
+        }
 
         let (manager_profit_share, protocol_profit_share) =
             self.calculate_profit_share_and_update(total_amount, vault)?;
-        let manager_profit_share: u64 = manager_profit_share.cast()?;
-        let protocol_profit_share: u64 = protocol_profit_share.cast()?;
-        let profit_share = manager_profit_share
-            .safe_add(protocol_profit_share)?
-            .cast()?;
+        let profit_share = manager_profit_share.safe_add(protocol_profit_share)?;
+        if profit_share == 0 {
+            return Ok((value, vault_equity));
+        }
 
-        msg!(
-            "investor_shares: {}/{}",
-            self.vault_shares,
-            vault.total_shares
-        );
-        msg!("investor_equity: {}/{}", total_amount, vault_equity);
+        let profit_share_shares =
+            get_proportion_u128(self.vault_shares, profit_share, total_amount.cast()?)?
+                .min(self.vault_shares);
+        let retained_shares = self.vault_shares.safe_sub(n_shares)?;
+        let paid_shares = profit_share_shares.min(retained_shares);
+        let withheld_value: u64 = get_proportion_u128(
+            value.cast()?,
+            profit_share_shares.safe_sub(paid_shares)?,
+            n_shares,
+        )?
+        .cast()?;
 
-        let profit_share_shares: u128 =
-            amount_to_shares(profit_share, vault.total_shares, vault_equity)?;
+        self.decrease_vault_shares(paid_shares, vault)?;
+        vault.investor_shares = vault.investor_shares.safe_sub(paid_shares)?;
 
-        self.decrease_vault_shares(profit_share_shares, vault)?;
+        let minted_shares = amount_to_shares(withheld_value, vault.total_shares, vault_equity)?;
+        vault.total_shares = vault.total_shares.safe_add(minted_shares)?;
+        vault.epoch_redemptions_owed = vault.epoch_redemptions_owed.safe_sub(withheld_value)?;
+        vault.total_withdraw_requested = vault.total_withdraw_requested.safe_sub(withheld_value)?;
 
-        vault.investor_shares = vault.investor_shares.safe_sub(profit_share_shares)?;
+        let protocol_profit_share_shares = get_proportion_u128(
+            paid_shares.safe_add(minted_shares)?,
+            protocol_profit_share,
+            profit_share,
+        )?;
+        vault.protocol_profit_and_fee_shares = vault
+            .protocol_profit_and_fee_shares
+            .saturating_add(protocol_profit_share_shares);
         vault.manager_total_profit_share = vault
             .manager_total_profit_share
-            .saturating_add(manager_profit_share);
-
+            .saturating_add(manager_profit_share.cast()?);
         vault.protocol_total_profit_share = vault
             .protocol_total_profit_share
             .saturating_add(protocol_profit_share.cast()?);
-        let protocol_profit_share_shares: u128 = amount_to_shares(
-            protocol_profit_share.cast()?,
-            vault.total_shares,
-            vault_equity,
-        )?;
-        vault.protocol_profit_and_fee_shares = vault
-            .protocol_profit_and_fee_shares
-            .saturating_add(protocol_profit_share_shares);
 
-        Ok((manager_profit_share, protocol_profit_share))
+        msg!(
+            "epoch profit share: {} ({} shares, {} withheld)",
+            profit_share,
+            paid_shares,
+            withheld_value
+        );
+
+        Ok((
+            value.safe_sub(withheld_value)?,
+            vault_equity.safe_add(withheld_value)?,
+        ))
     }
 
-    pub fn realize_profits(
+    fn queue_deposit(
         &mut self,
+        amount: u64,
         vault_equity: u64,
         vault: &mut Vault,
         now: i64,
-    ) -> Result<u64> {
-        let VaultFee {
-            management_fee_payment,
-            management_fee_shares,
-            protocol_fee_payment,
-            protocol_fee_shares,
-        } = vault.apply_fee(vault_equity, now)?;
+    ) -> Result<()> {
+        validate!(
+            self.epoch_deposit_amount == 0 || self.epoch_deposit_epoch == vault.current_epoch,
+            ErrorCode::EpochUnclaimed,
+            "deposit from epoch {} must be claimed first",
+            self.epoch_deposit_epoch
+        )?;
 
         let vault_shares_before = self.checked_vault_shares(vault)?;
         let total_vault_shares_before = vault.total_shares;
         let user_vault_shares_before = vault.investor_shares;
         let protocol_shares_before = vault.get_protocol_shares();
 
-        let (manager_profit_share, protocol_profit_share) =
-            self.apply_profit_share(vault_equity, vault)?;
-        let profit_share = manager_profit_share.saturating_add(protocol_profit_share);
-        let protocol_shares_after = vault.get_protocol_shares();
+        // the entry fee isn't queued for shares, so it accrues to the existing shareholders
+        let entry_fee = vault.entry_fee(amount)?;
+        let queued_amount = amount.safe_sub(entry_fee)?;
+        self.epoch_deposit_amount = self.epoch_deposit_amount.safe_add(queued_amount)?;
+        self.epoch_deposit_epoch = vault.current_epoch;
+        vault.epoch_pending_deposits = vault.epoch_pending_deposits.safe_add(queued_amount)?;
+
+        self.total_deposits = self.total_deposits.saturating_add(amount);
+        self.net_deposits = self.net_deposits.safe_add(amount.cast()?)?;
+
+        vault.total_deposits = vault.total_deposits.saturating_add(amount);
+        vault.net_deposits = vault.net_deposits.safe_add(amount.cast()?)?;
 
         emit!(InvestorRecord {
             ts: now,
             vault: vault.pubkey,
             depositor_authority: self.authority,
-            action: InvestorAction::FeePayment,
-            amount: 0,
+            action: InvestorAction::Deposit,
+            amount,
             usdc_mint: vault.usdc_mint,
             sol_mint: vault.sol_mint,
             vault_equity_before: vault_equity,
             vault_shares_before,
             user_vault_shares_before,
             total_vault_shares_before,
-            vault_shares_after: self.vault_shares,
+            vault_shares_after: vault_shares_before,
             total_vault_shares_after: vault.total_shares,
             user_vault_shares_after: vault.investor_shares,
-            protocol_profit_share,
-            protocol_fee: protocol_fee_payment,
-            protocol_fee_shares,
-            manager_profit_share,
-            management_fee: management_fee_payment,
-            management_fee_shares,
+            protocol_profit_share: 0,
+            protocol_fee: 0,
+            protocol_fee_shares: 0,
+            manager_profit_share: 0,
+            management_fee: 0,
+            management_fee_shares: 0,
             protocol_shares_before,
-            protocol_shares_after
+            protocol_shares_after: protocol_shares_before,
+            entry_fee,
+            exit_fee: 0,
+            lockup_penalty: 0
         });
 
-        Ok(profit_share)
+        Ok(())
     }
-}
 
-#[cfg(test)]
-mod vault_v1_tests {
-    use crate::constants::{PERCENTAGE_PRECISION_U64, QUOTE_PRECISION_U64};
-    use crate::math::casting::Cast;
-    use crate::math::shares_to_amount;
-    use anchor_lang::prelude::Pubkey;
+    fn cancel_queued_withdraw_request(
+        &mut self,
+        vault_equity: u64,
+        vault: &mut Vault,
+        now: i64,
+    ) -> Result<()> {
+        validate!(
+            self.epoch_withdraw_queued(),
+            ErrorCode::InvalidVaultWithdraw,
+            "only a withdraw request that has not been settled can be canceled"
+        )?;
 
-    use crate::{Investor, Vault, WithdrawUnit};
+        let vault_shares_before: u128 = self.checked_vault_shares(vault)?;
+        let protocol_shares_before = vault.get_protocol_shares();
 
-    #[test]
-    fn base_init() {
+        vault.epoch_pending_withdraw_shares = vault
+            .epoch_pending_withdraw_shares
+            .saturating_sub(self.last_withdraw_request.shares);
+        self.last_withdraw_request.reset(now)?;
+
+        emit!(InvestorRecord {
+            ts: now,
+            vault: vault.pubkey,
+            depositor_authority: self.authority,
+            action: InvestorAction::CancelWithdrawRequest,
+            amount: 0,
+            usdc_mint: vault.usdc_mint,
+            sol_mint: vault.sol_mint,
+            vault_equity_before: vault_equity,
+            vault_shares_before,
+            user_vault_shares_before: vault.investor_shares,
+            total_vault_shares_before: vault.total_shares,
+            vault_shares_after: vault_shares_before,
+            total_vault_shares_after: vault.total_shares,
+            user_vault_shares_after: vault.investor_shares,
+            protocol_profit_share: 0,
+            protocol_fee: 0,
+            protocol_fee_shares: 0,
+            manager_profit_share: 0,
+            management_fee: 0,
+            management_fee_shares: 0,
+            protocol_shares_before,
+            protocol_shares_after: protocol_shares_before,
+            entry_fee: 0,
+            exit_fee: 0,
+            lockup_penalty: 0
+        });
+
+        Ok(())
+    }
+
+    fn process_epoch_withdraw(
+        &mut self,
+        vault_equity: u64,
+        vault: &mut Vault,
+        now: i64,
+        action: InvestorAction,
+    ) -> Result<(u64, bool)> {
+        validate!(
+            self.last_withdraw_request.shares == 0 && self.last_withdraw_request.value > 0,
+            ErrorCode::EpochNotSettled,
+            "withdraw request must be settled by settle_epoch before withdrawing"
+        )?;
+        self.last_withdraw_request
+            .check_redeem_period_finished(vault, now)?;
+
+        let vault_shares_before: u128 = self.checked_vault_shares(vault)?;
+        let protocol_shares_before = vault.get_protocol_shares();
+        let withdraw_value = self.last_withdraw_request.value;
+        // the exit fee and lockup penalty are no longer owed, so they return to the vault equity for the remaining shareholders
+        let exit_fee = vault.exit_fee(withdraw_value)?;
+        let lockup_penalty = self.lockup_penalty(withdraw_value, vault)?;
+        let withdraw_amount = withdraw_value
+            .safe_sub(exit_fee)?
+            .safe_sub(lockup_penalty)?;
+
+        self.total_withdraws = self.total_withdraws.saturating_add(withdraw_amount);
+        self.net_deposits = self.net_deposits.safe_sub(withdraw_amount.cast()?)?;
+
+        vault.total_withdraws = vault.total_withdraws.saturating_add(withdraw_amount);
+        vault.net_deposits = vault.net_deposits.safe_sub(withdraw_amount.cast()?)?;
+        vault.total_withdraw_requested = vault.total_withdraw_requested.safe_sub(withdraw_value)?;
+        vault.epoch_redemptions_owed = vault.epoch_redemptions_owed.safe_sub(withdraw_value)?;
+
+        self.last_withdraw_request.reset(now)?;
+
+        emit!(InvestorRecord {
+            ts: now,
+            vault: vault.pubkey,
+            depositor_authority: self.authority,
+            action,
+            amount: withdraw_amount,
+            usdc_mint: vault.usdc_mint,
+            sol_mint: vault.sol_mint,
+            vault_equity_before: vault_equity,
+            vault_shares_before,
+            user_vault_shares_before: vault.investor_shares,
+            total_vault_shares_before: vault.total_shares,
+            vault_shares_after: vault_shares_before,
+            total_vault_shares_after: vault.total_shares,
+            user_vault_shares_after: vault.investor_shares,
+            protocol_profit_share: 0,
+            protocol_fee: 0,
+            protocol_fee_shares: 0,
+            manager_profit_share: 0,
+            management_fee: 0,
+            management_fee_shares: 0,
+            protocol_shares_before,
+            protocol_shares_after: protocol_shares_before,
+            entry_fee: 0,
+            exit_fee,
+            lockup_penalty
+        });
+
+        let finishing_liquidation = vault.liquidator == self.authority;
+
+        Ok((withdraw_amount, finishing_liquidation))
+    }
+
+    pub fn apply_profit_share(
+        &mut self,
+        vault_equity: u64,
+        vault: &mut Vault,
+    ) -> Result<(u64, u64)> {
+        validate!(
+            !self.last_withdraw_request.pending(),
+            ErrorCode::InvalidVaultDeposit,
+            "Cannot apply profit share to investor with pending withdraw request"
+        )?;
+
+        let total_amount = shares_to_amount(self.vault_shares, vault.total_shares, vault_equity)?;
+
+        let (manager_profit_share, protocol_profit_share) =
+            self.calculate_profit_share_and_update(total_amount, vault)?;
+        let manager_profit_share: u64 = manager_profit_share.cast()?;
+        let protocol_profit_share: u64 = protocol_profit_share.cast()?;
+        let profit_share = manager_profit_share
+            .safe_add(protocol_profit_share)?
+            .cast()?;
+
+        msg!(
+            "investor_shares: {}/{}",
+            self.vault_shares,
+            vault.total_shares
+        );
+        msg!("investor_equity: {}/{}", total_amount, vault_equity);
+
+        let profit_share_shares: u128 =
+            amount_to_shares(profit_share, vault.total_shares, vault_equity)?;
+
+        self.decrease_vault_shares(profit_share_shares, vault)?;
+
+        vault.investor_shares = vault.investor_shares.safe_sub(profit_share_shares)?;
+        vault.manager_total_profit_share = vault
+            .manager_total_profit_share
+            .saturating_add(manager_profit_share);
+
+        vault.protocol_total_profit_share = vault
+            .protocol_total_profit_share
+            .saturating_add(protocol_profit_share.cast()?);
+        let protocol_profit_share_shares: u128 = amount_to_shares(
+            protocol_profit_share.cast()?,
+            vault.total_shares,
+            vault_equity,
+        )?;
+        vault.protocol_profit_and_fee_shares = vault
+            .protocol_profit_and_fee_shares
+            .saturating_add(protocol_profit_share_shares);
+
+        Ok((manager_profit_share, protocol_profit_share))
+    }
+
+    pub fn realize_profits(
+        &mut self,
+        vault_equity: u64,
+        vault: &mut Vault,
+        now: i64,
+    ) -> Result<u64> {
+        let VaultFee {
+            management_fee_payment,
+            management_fee_shares,
+            protocol_fee_payment,
+            protocol_fee_shares,
+        } = vault.apply_fee(vault_equity, now)?;
+
+        let vault_shares_before = self.checked_vault_shares(vault)?;
+        let total_vault_shares_before = vault.total_shares;
+        let user_vault_shares_before = vault.investor_shares;
+        let protocol_shares_before = vault.get_protocol_shares();
+
+        let (manager_profit_share, protocol_profit_share) =
+            self.apply_profit_share(vault_equity, vault)?;
+        let profit_share = manager_profit_share.saturating_add(protocol_profit_share);
+        let protocol_shares_after = vault.get_protocol_shares();
+
+        emit!(InvestorRecord {
+            ts: now,
+            vault: vault.pubkey,
+            depositor_authority: self.authority,
+            action: InvestorAction::FeePayment,
+            amount: 0,
+            usdc_mint: vault.usdc_mint,
+            sol_mint: vault.sol_mint,
+            vault_equity_before: vault_equity,
+            vault_shares_before,
+            user_vault_shares_before,
+            total_vault_shares_before,
+            vault_shares_after: self.vault_shares,
+            total_vault_shares_after: vault.total_shares,
+            user_vault_shares_after: vault.investor_shares,
+            protocol_profit_share,
+            protocol_fee: protocol_fee_payment,
+            protocol_fee_shares,
+            manager_profit_share,
+            management_fee: management_fee_payment,
+            management_fee_shares,
+            protocol_shares_before,
+            protocol_shares_after,
+            entry_fee: 0,
+            exit_fee: 0,
+            lockup_penalty: 0
+        });
+
+        Ok(profit_share)
+    }
+
+    /// Value of the investor's shares at `vault_equity` before profit share. Neither account is mutated.
+    pub fn equity(&self, vault_equity: u64, vault: &Vault) -> Result<u64> {
+        let mut vault = *vault;
+        let mut investor = *self;
+        let vault_equity = investor.claim_epoch(vault_equity, &mut vault)?;
+        investor.apply_rebase(&mut vault, vault_equity)?;
+
+        Ok(shares_to_amount(
+            investor.vault_shares,
+            vault.total_shares,
+            vault_equity,
+        )?)
+    }
+
+    /// Shares [`Investor::deposit`] would mint for `amount`. Neither account is mutated.
+    /// In epoch mode deposits are priced when the epoch settles, so this is an estimate at the current equity.
+    pub fn preview_deposit(
+        &self,
+        amount: u64,
+        vault_equity: u64,
+        vault: &Vault,
+        now: i64,
+    ) -> Result<DepositPreview> {
+        let mut vault = *vault;
+        let mut investor = *self;
+        let vault_equity = investor.claim_epoch(vault_equity, &mut vault)?;
+        investor.check_deposit(amount, vault_equity, &vault, now)?;
+
+        investor.apply_rebase(&mut vault, vault_equity)?;
+        if !vault.epoch_mode() {
+            vault.apply_fee(vault_equity, now)?;
+            investor.apply_profit_share(vault_equity, &mut vault)?;
+        }
+
+        let entry_fee = vault.entry_fee(amount)?;
+        let shares = amount_to_shares(
+            amount.safe_sub(entry_fee)?,
+            vault.total_shares,
+            vault_equity,
+        )?;
+
+        Ok(DepositPreview { shares, entry_fee })
+    }
+
+    /// USDC a withdrawal would pay after profit share and the exit fee. Neither account is mutated.
+    /// If a withdraw request is pending, `withdraw_amount` and `withdraw_unit` are ignored and the pending request is priced.
+    /// A request queued for an unsettled epoch is estimated at the current equity.
+    pub fn preview_withdraw(
+        &self,
+        withdraw_amount: u64,
+        withdraw_unit: WithdrawUnit,
+        vault_equity: u64,
+        vault: &Vault,
+        now: i64,
+    ) -> Result<WithdrawPreview> {
+        let mut vault = *vault;
+        let mut investor = *self;
+        let vault_equity = investor.claim_epoch(vault_equity, &mut vault)?;
+        let rebase_divisor = investor.apply_rebase(&mut vault, vault_equity)?;
+        if !vault.epoch_mode() {
+            vault.apply_fee(vault_equity, now)?;
+        }
+
+        let (withdraw_value, shares) = if investor.epoch_withdraw_queued() {
+            let shares = investor.last_withdraw_request.shares;
+            (
+                shares_to_amount(shares, vault.total_shares, vault_equity)?,
+                shares,
+            )
+        } else if investor.last_withdraw_request.pending() {
+            (
+                investor
+                    .last_withdraw_request
+                    .withdraw_value(&vault, vault_equity)?,
+                investor.last_withdraw_request.shares,
+            )
+        } else {
+            investor.apply_profit_share(vault_equity, &mut vault)?;
+            withdraw_unit.get_withdraw_value_and_shares(
+                withdraw_amount,
+                vault_equity,
+                investor.vault_shares,
+                vault.total_shares,
+                rebase_divisor,
+            )?
+        };
+
+        let exit_fee = vault.exit_fee(withdraw_value)?;
+        if !investor.last_withdraw_request.pending() {
+            validate!(
+                !vault.in_lockup(investor.first_deposit_ts, now) || vault.lockup_penalty_bps > 0,
+                ErrorCode::InvestorLockedUp,
+                "investor is locked up until {}",
+                investor
+                    .first_deposit_ts
+                    .saturating_add(vault.lockup_duration)
+            )?;
+            investor.last_withdraw_request.ts = now;
+        }
+        let lockup_penalty = investor.lockup_penalty(withdraw_value, &vault)?;
+
+        Ok(WithdrawPreview {
+            shares,
+            amount: withdraw_value
+                .safe_sub(exit_fee)?
+                .safe_sub(lockup_penalty)?,
+            exit_fee,
+            lockup_penalty,
+        })
+    }
+}
+
+#[cfg(test)]
+mod vault_v1_tests {
+    use crate::constants::{
+        PERCENTAGE_PRECISION_U64, PRICE_PRECISION_U64, QUOTE_PRECISION, QUOTE_PRECISION_U64,
+    };
+    use crate::error::ErrorCode;
+    use crate::math::casting::Cast;
+    use crate::math::shares_to_amount;
+    use anchor_lang::prelude::Pubkey;
+
+    use crate::state::SharePrice;
+    use crate::{Investor, Vault, VaultState, WithdrawUnit};
+
+    #[test]
+    fn base_init() {
         let now = 1337;
         let vd = Investor::new(Pubkey::default(), Pubkey::default(), Pubkey::default(), now);
         assert_eq!(vd.vault_shares_base, 0);
@@ -681,7 +1436,7 @@ mod vault_v1_tests {
         )
         .unwrap();
 
-        let withdraw_amount = vd.withdraw(vault_equity, &mut vault, now + 20).unwrap();
+        let (withdraw_amount, _) = vd.withdraw(vault_equity, &mut vault, now + 20).unwrap();
         assert_eq!(vd.vault_shares_base, 0);
         assert_eq!(withdraw_amount, amount);
     }
@@ -722,7 +1477,7 @@ mod vault_v1_tests {
         assert_eq!(vd.last_withdraw_request.value, 100_000_000);
         assert_eq!(vd.last_withdraw_request.ts, now + 20);
 
-        let withdraw_amount = vd.withdraw(vault_equity, &mut vault, now + 20).unwrap();
+        let (withdraw_amount, _) = vd.withdraw(vault_equity, &mut vault, now + 20).unwrap();
         // 100M shares minus 50M shares of profit and 15% or 7.5M profit share = 42.5M shares
         assert_eq!(vd.checked_vault_shares(&vault).unwrap(), 42_500_000);
         assert_eq!(vault.investor_shares, 42_500_000);
@@ -789,7 +1544,7 @@ mod vault_v1_tests {
         assert_eq!(vd.last_withdraw_request.value, 100_000_000);
         assert_eq!(vd.last_withdraw_request.ts, now + 20);
 
-        let withdraw_amount = vd.withdraw(vault_equity, &mut vault, now + 20).unwrap();
+        let (withdraw_amount, _) = vd.withdraw(vault_equity, &mut vault, now + 20).unwrap();
         assert_eq!(vd.checked_vault_shares(&vault).unwrap(), 45_000_000);
         assert_eq!(vd.vault_shares_base, 0);
         assert_eq!(vault.investor_shares, 45_000_000);
@@ -852,7 +1607,7 @@ mod vault_v1_tests {
         assert_eq!(vd.last_withdraw_request.value, 185_000_000);
         assert_eq!(vd.last_withdraw_request.ts, now + 20);
 
-        let withdraw_amount = vd.withdraw(vault_equity, &mut vault, now + 20).unwrap();
+        let (withdraw_amount, _) = vd.withdraw(vault_equity, &mut vault, now + 20).unwrap();
         let profit = amount;
         let equity_minus_fee = amount + profit - (profit as f64 * 0.15).round() as u64;
         assert_eq!(vd.checked_vault_shares(&vault).unwrap(), 0);
@@ -946,7 +1701,7 @@ mod vault_v1_tests {
         assert_eq!(vd.last_withdraw_request.value, 190_000_000);
         assert_eq!(vd.last_withdraw_request.ts, now + 20);
 
-        let withdraw_amount = vd.withdraw(vault_equity, &mut vault, now + 20).unwrap();
+        let (withdraw_amount, _) = vd.withdraw(vault_equity, &mut vault, now + 20).unwrap();
         let profit = amount;
         let equity_minus_fee = amount + profit - (profit as f64 * 0.10).round() as u64;
         assert_eq!(vd.checked_vault_shares(&vault).unwrap(), 0);
@@ -1052,7 +1807,7 @@ mod vault_v1_tests {
         assert_eq!(vd.last_withdraw_request.ts, now + 20);
         // assert_eq!(vd.last_withdraw_request.shares, 100000000);
 
-        let withdraw_amount = vd.withdraw(vault_equity, &mut vault, now + 20).unwrap();
+        let (withdraw_amount, _) = vd.withdraw(vault_equity, &mut vault, now + 20).unwrap();
         // assert_eq!(vd.checked_vault_shares(vault).unwrap(), 0);
         // assert_eq!(vd.vault_shares_base, 0);
         // assert_eq!(vault.investor_shares, 0);
@@ -1127,7 +1882,7 @@ mod vault_v1_tests {
 
         vault_equity *= 5; // up 400%
 
-        let withdraw_amount = vd
+        let (withdraw_amount, _) = vd
             .withdraw(vault_equity, &mut vault, now + 20 + 3600)
             .unwrap();
         // assert_eq!(vd.checked_vault_shares(vault).unwrap(), 0);
@@ -1203,7 +1958,7 @@ mod vault_v1_tests {
 
         vault_equity /= 5; // down 80%
 
-        let withdraw_amount = vd
+        let (withdraw_amount, _) = vd
             .withdraw(vault_equity, &mut vault, now + 20 + 3600)
             .unwrap();
         // assert_eq!(vd.checked_vault_shares(vault).unwrap(), 0);
@@ -1221,4 +1976,863 @@ mod vault_v1_tests {
             vd.cumulative_profit_share_amount
         );
     }
+
+    #[test]
+    fn test_drawdown_halts_trading() {
+        let now = 1000;
+        let mut vault = Vault {
+            redeem_period: 60 * 60 * 24,
+            max_drawdown: (PERCENTAGE_PRECISION_U64 / 10).cast().unwrap(), // 10%
+            ..Vault::default()
+        };
+
+        let vd = &mut Investor::new(Pubkey::default(), Pubkey::default(), Pubkey::default(), now);
+
+        let vault_equity: u64 = 100 * QUOTE_PRECISION_U64;
+        let amount: u64 = 100 * QUOTE_PRECISION_U64;
+        vd.deposit(amount, vault_equity, &mut vault, now).unwrap();
+
+        // deposits, withdrawals and the other cranks don't track the drawdown
+        vault.apply_rebase(400 * QUOTE_PRECISION_U64).unwrap();
+        assert_eq!(vault.high_water_mark_price, 0);
+        assert!(!vault.trading_halted);
+
+        vault.update_drawdown(200 * QUOTE_PRECISION_U64).unwrap();
+        assert_eq!(vault.high_water_mark_price, 1_000_000);
+
+        // 5% drawdown
+        vault.update_drawdown(190 * QUOTE_PRECISION_U64).unwrap();
+        assert!(!vault.trading_halted);
+        assert!(vault.check_trading_not_halted().is_ok());
+
+        // 15% drawdown
+        let vault_equity: u64 = 170 * QUOTE_PRECISION_U64;
+        vault.update_drawdown(vault_equity).unwrap();
+        assert!(vault.trading_halted);
+        assert!(vault.check_trading_not_halted().is_err());
+        assert_eq!(vault.high_water_mark_price, 1_000_000);
+
+        // investors can withdraw without waiting for the redeem period
+        vd.request_withdraw(
+            PERCENTAGE_PRECISION_U64,
+            WithdrawUnit::SharesPercent,
+            vault_equity,
+            &mut vault,
+            now,
+        )
+        .unwrap();
+        let (withdraw_amount, _) = vd.withdraw(vault_equity, &mut vault, now).unwrap();
+        assert_eq!(withdraw_amount, 85 * QUOTE_PRECISION_U64);
+
+        let vault_equity = vault_equity - withdraw_amount;
+        vault.resume_trading(vault_equity).unwrap();
+        assert!(!vault.trading_halted);
+        assert_eq!(vault.high_water_mark_price, 850_000);
+        assert!(vault.resume_trading(vault_equity).is_err());
+    }
+
+    #[test]
+    fn test_fundraising_refund() {
+        let now = 1000;
+        let deadline = now + 60 * 60 * 24;
+        let mut vault = Vault {
+            redeem_period: 60 * 60 * 24 * 7,
+            management_fee: (PERCENTAGE_PRECISION_U64 / 50).cast().unwrap(), // 2%
+            last_fee_update_ts: now,
+            state: VaultState::Fundraising,
+            fundraising_soft_cap: 1_000 * QUOTE_PRECISION_U64,
+            fundraising_deadline: deadline,
+            ..Vault::default()
+        };
+
+        let vd = &mut Investor::new(Pubkey::default(), Pubkey::default(), Pubkey::default(), now);
+
+        let amount: u64 = 100 * QUOTE_PRECISION_U64;
+        vd.deposit(amount, 0, &mut vault, now).unwrap();
+        assert_eq!(vault.state(deadline - 1), VaultState::Fundraising);
+        assert!(vault
+            .check_state(deadline - 1, &[VaultState::Active])
+            .is_err());
+
+        // soft cap missed, so investors are refunded without fees or a redeem period
+        assert_eq!(vault.state(deadline), VaultState::Closed);
+        vd.request_withdraw(
+            PERCENTAGE_PRECISION_U64,
+            WithdrawUnit::SharesPercent,
+            amount,
+            &mut vault,
+            deadline,
+        )
+        .unwrap();
+        let (withdraw_amount, _) = vd.withdraw(amount, &mut vault, deadline).unwrap();
+        assert_eq!(withdraw_amount, amount);
+        assert_eq!(vault.state, VaultState::Closed);
+    }
+
+    #[test]
+    fn test_fundraising_soft_cap() {
+        let now = 1000;
+        let deadline = now + 60 * 60 * 24;
+        let mut vault = Vault {
+            state: VaultState::Fundraising,
+            fundraising_soft_cap: 1_000 * QUOTE_PRECISION_U64,
+            fundraising_deadline: deadline,
+            ..Vault::default()
+        };
+
+        let vd = &mut Investor::new(Pubkey::default(), Pubkey::default(), Pubkey::default(), now);
+
+        // the manager's deposit counts once towards the soft cap
+        vault
+            .manager_deposit(500 * QUOTE_PRECISION_U64, 0, now)
+            .unwrap();
+        vd.deposit(
+            400 * QUOTE_PRECISION_U64,
+            500 * QUOTE_PRECISION_U64,
+            &mut vault,
+            now,
+        )
+        .unwrap();
+        assert_eq!(vault.net_deposits, 900 * QUOTE_PRECISION_U64 as i64);
+        assert!(!vault.fundraising_soft_cap_met());
+        assert_eq!(vault.state(deadline), VaultState::Closed);
+
+        vd.deposit(
+            100 * QUOTE_PRECISION_U64,
+            900 * QUOTE_PRECISION_U64,
+            &mut vault,
+            now,
+        )
+        .unwrap();
+        assert!(vault.fundraising_soft_cap_met());
+        assert_eq!(vault.state(deadline), VaultState::Active);
+    }
+
+    #[test]
+    fn test_wind_down_claim() {
+        let now = 1000;
+        let mut vault = Vault {
+            redeem_period: 60 * 60 * 24 * 7,
+            last_fee_update_ts: now,
+            ..Vault::default()
+        };
+
+        let vd1 = &mut Investor::new(Pubkey::default(), Pubkey::default(), Pubkey::default(), now);
+        let vd2 = &mut Investor::new(Pubkey::default(), Pubkey::default(), Pubkey::default(), now);
+
+        let amount: u64 = 100 * QUOTE_PRECISION_U64;
+        vd1.deposit(amount, 0, &mut vault, now).unwrap();
+        vd2.deposit(amount, amount, &mut vault, now).unwrap();
+
+        let vault_equity: u64 = 300 * QUOTE_PRECISION_U64;
+        vd1.request_withdraw(
+            PERCENTAGE_PRECISION_U64 / 2,
+            WithdrawUnit::SharesPercent,
+            vault_equity,
+            &mut vault,
+            now,
+        )
+        .unwrap();
+
+        vault.wind_down(now).unwrap();
+        assert_eq!(vault.state, VaultState::WindDown);
+        assert!(vault.wind_down(now).is_err());
+        assert!(vd1.claim_wind_down(&mut vault, now).is_err());
+
+        vault.finalize_wind_down(vault_equity, now).unwrap();
+        assert_eq!(vault.state, VaultState::Closed);
+
+        // the pending withdraw request is dropped and the whole position is paid without a redeem period
+        let claim_amount = vd1.claim_wind_down(&mut vault, now).unwrap();
+        assert_eq!(claim_amount, 150 * QUOTE_PRECISION_U64);
+        assert_eq!(vd1.checked_vault_shares(&vault).unwrap(), 0);
+        assert!(!vd1.last_withdraw_request.pending());
+        assert_eq!(vault.total_withdraw_requested, 0);
+
+        let claim_amount = vd2.claim_wind_down(&mut vault, now).unwrap();
+        assert_eq!(claim_amount, 150 * QUOTE_PRECISION_U64);
+        assert_eq!(vault.total_shares, 0);
+        assert_eq!(vault.investor_shares, 0);
+    }
+
+    #[test]
+    fn test_manager_min_stake() {
+        let now = 1000;
+        let mut vault = Vault {
+            last_fee_update_ts: now,
+            manager_min_stake: (PERCENTAGE_PRECISION_U64 / 10).cast().unwrap(), // 10%
+            ..Vault::default()
+        };
+
+        let amount: u64 = 100 * QUOTE_PRECISION_U64;
+        vault.manager_deposit(amount, 0, now).unwrap();
+
+        let vd = &mut Investor::new(Pubkey::default(), Pubkey::default(), Pubkey::default(), now);
+        vd.deposit(amount, amount, &mut vault, now).unwrap();
+
+        let vault_equity: u64 = 200 * QUOTE_PRECISION_U64;
+        // 5 of the 105 shares left would be below 10%
+        assert!(vault
+            .manager_request_withdraw(
+                95 * QUOTE_PRECISION_U64,
+                WithdrawUnit::Token,
+                vault_equity,
+                now
+            )
+            .is_err());
+        // 20 of the 120 shares left is above 10%
+        vault
+            .manager_request_withdraw(
+                80 * QUOTE_PRECISION_U64,
+                WithdrawUnit::Token,
+                vault_equity,
+                now,
+            )
+            .unwrap();
+        assert_eq!(
+            vault.last_manager_withdraw_request.value,
+            80 * QUOTE_PRECISION_U64
+        );
+    }
+
+    #[test]
+    fn test_protocol_withdraw_redeem_period() {
+        let now = 1000;
+        let redeem_period = 60 * 60 * 24;
+        let mut vault = Vault {
+            redeem_period,
+            last_fee_update_ts: now,
+            total_shares: 100 * QUOTE_PRECISION,
+            protocol_profit_and_fee_shares: 10 * QUOTE_PRECISION,
+            ..Vault::default()
+        };
+        let vault_equity: u64 = 100 * QUOTE_PRECISION_U64;
+
+        vault
+            .manager_request_withdraw(
+                10 * QUOTE_PRECISION_U64,
+                WithdrawUnit::Token,
+                vault_equity,
+                now,
+            )
+            .unwrap();
+        let protocol_request_ts = now + redeem_period;
+        vault
+            .protocol_request_withdraw(
+                5 * QUOTE_PRECISION_U64,
+                WithdrawUnit::Token,
+                vault_equity,
+                protocol_request_ts,
+            )
+            .unwrap();
+        assert_eq!(
+            vault.last_protocol_withdraw_request.value,
+            5 * QUOTE_PRECISION_U64
+        );
+
+        // the manager's redeem period has passed but the protocol's has not
+        assert!(vault
+            .protocol_withdraw(vault_equity, protocol_request_ts + 1)
+            .is_err());
+        let (amount, _) = vault
+            .protocol_withdraw(vault_equity, protocol_request_ts + redeem_period)
+            .unwrap();
+        assert_eq!(amount, 5 * QUOTE_PRECISION_U64);
+        assert_eq!(vault.get_protocol_shares(), 5 * QUOTE_PRECISION);
+    }
+
+    #[test]
+    fn test_liquidity_buffer() {
+        let now = 1000;
+        let mut vault = Vault {
+            redeem_period: 60 * 60 * 24,
+            liquidity_buffer: (PERCENTAGE_PRECISION_U64 / 10).cast().unwrap(), // 10%
+            ..Vault::default()
+        };
+
+        let vd = &mut Investor::new(Pubkey::default(), Pubkey::default(), Pubkey::default(), now);
+
+        let vault_equity: u64 = 100 * QUOTE_PRECISION_U64;
+        vd.deposit(vault_equity, 0, &mut vault, now).unwrap();
+        assert_eq!(
+            vault.liquidity_buffer(vault_equity).unwrap(),
+            10 * QUOTE_PRECISION_U64
+        );
+
+        // a pending withdraw request is reserved on top of the equity buffer
+        vd.request_withdraw(
+            20 * QUOTE_PRECISION_U64,
+            WithdrawUnit::Token,
+            vault_equity,
+            &mut vault,
+            now,
+        )
+        .unwrap();
+        assert_eq!(
+            vault.liquidity_buffer(vault_equity).unwrap(),
+            30 * QUOTE_PRECISION_U64
+        );
+        assert!(vault
+            .check_liquidity_buffer(30 * QUOTE_PRECISION_U64, vault_equity)
+            .is_ok());
+        assert!(vault
+            .check_liquidity_buffer(30 * QUOTE_PRECISION_U64 - 1, vault_equity)
+            .is_err());
+    }
+
+    #[test]
+    fn test_lockup_penalty() {
+        let now = 1000;
+        let lockup_duration = 60 * 60 * 24 * 30;
+        let mut vault = Vault {
+            lockup_duration,
+            ..Vault::default()
+        };
+
+        let vd = &mut Investor::new(Pubkey::default(), Pubkey::default(), Pubkey::default(), now);
+
+        let vault_equity: u64 = 100 * QUOTE_PRECISION_U64;
+        vd.deposit(vault_equity, 0, &mut vault, now).unwrap();
+        assert_eq!(vd.first_deposit_ts, now);
+
+        // without a penalty, requests during the lockup are rejected
+        assert!(vd
+            .request_withdraw(
+                PERCENTAGE_PRECISION_U64,
+                WithdrawUnit::SharesPercent,
+                vault_equity,
+                &mut vault,
+                now,
+            )
+            .is_err());
+
+        // with a penalty, it is withheld from the withdrawal and stays in the vault
+        vault.lockup_penalty_bps = 500; // 5%
+        vd.request_withdraw(
+            PERCENTAGE_PRECISION_U64 / 2,
+            WithdrawUnit::SharesPercent,
+            vault_equity,
+            &mut vault,
+            now,
+        )
+        .unwrap();
+        let (withdraw_amount, _) = vd
+            .withdraw(vault_equity, &mut vault, now + lockup_duration)
+            .unwrap();
+        assert_eq!(withdraw_amount, 475 * QUOTE_PRECISION_U64 / 10);
+
+        // requests after the lockup are not penalized
+        let vault_equity = vault_equity - withdraw_amount;
+        let now = now + lockup_duration;
+        vd.request_withdraw(
+            PERCENTAGE_PRECISION_U64,
+            WithdrawUnit::SharesPercent,
+            vault_equity,
+            &mut vault,
+            now,
+        )
+        .unwrap();
+        let (withdraw_amount, _) = vd.withdraw(vault_equity, &mut vault, now).unwrap();
+        assert_eq!(withdraw_amount, vault_equity);
+    }
+
+    #[test]
+    fn test_liquidation_window() {
+        let now = 1000;
+        let mut vault = Vault::default();
+        vault.set_liquidation_delegate(Pubkey::new_unique(), now);
+
+        // vaults without a configured window use the default
+        assert_eq!(vault.get_liquidation_window(), 60 * 60);
+        assert!(vault.check_can_exit_liquidation(now + 60 * 60).is_err());
+        assert!(vault.check_can_exit_liquidation(now + 60 * 60 + 1).is_ok());
+
+        vault.liquidation_window = 60 * 10;
+        assert!(vault.check_can_exit_liquidation(now + 60 * 10).is_err());
+        assert!(vault.check_can_exit_liquidation(now + 60 * 10 + 1).is_ok());
+        assert!(vault.liquidation_expired(now + 60 * 10 + 1));
+
+        // the window can't shrink under an appointed liquidator, even once it has expired
+        assert_eq!(
+            vault.update_liquidation_window(60 * 15),
+            Err(ErrorCode::VaultInLiquidation)
+        );
+        vault.reset_liquidation_delegate();
+        vault.liquidation_window = 60 * 30;
+        for (name, liquidation_window, expected) in [
+            ("below minimum", 60 * 5, Err(ErrorCode::InvalidVaultUpdate)),
+            ("not shorter", 60 * 30, Err(ErrorCode::InvalidVaultUpdate)),
+            ("shorter", 60 * 15, Ok(())),
+        ] {
+            assert_eq!(
+                vault.update_liquidation_window(liquidation_window),
+                expected,
+                "{}",
+                name
+            );
+        }
+        assert_eq!(vault.get_liquidation_window(), 60 * 15);
+    }
+
+    #[test]
+    fn test_unfulfilled_withdraw_value() {
+        let mut vault = Vault::default();
+        assert_eq!(vault.unfulfilled_withdraw_value(100, 40), 60);
+        assert_eq!(vault.unfulfilled_withdraw_value(100, 150), 0);
+
+        // USDC reserved for the epoch can't fulfill a withdraw request
+        vault.epoch_pending_deposits = 30;
+        vault.epoch_redemptions_owed = 20;
+        assert_eq!(vault.unfulfilled_withdraw_value(100, 150), 0);
+        assert_eq!(vault.unfulfilled_withdraw_value(100, 120), 30);
+        assert_eq!(vault.unfulfilled_withdraw_value(100, 40), 100);
+    }
+
+    #[test]
+    fn test_entry_exit_fees() {
+        let now = 1000;
+        let mut vault = Vault::default();
+
+        let vd1 = &mut Investor::new(Pubkey::default(), Pubkey::default(), Pubkey::default(), now);
+        let vd2 = &mut Investor::new(Pubkey::default(), Pubkey::default(), Pubkey::default(), now);
+
+        let amount: u64 = 100 * QUOTE_PRECISION_U64;
+        vd1.deposit(amount, 0, &mut vault, now).unwrap();
+
+        vault.entry_fee_bps = 100; // 1%
+        vault.exit_fee_bps = 100; // 1%
+
+        // the $1 entry fee mints no shares
+        let n_shares = vd2.deposit(amount, amount, &mut vault, now).unwrap();
+        assert_eq!(n_shares, 99_000_000);
+        assert_eq!(vault.total_shares, 199_000_000);
+        assert_eq!(vault.total_deposits, 2 * amount);
+
+        let vault_equity = 2 * amount;
+        vd2.request_withdraw(
+            PERCENTAGE_PRECISION_U64,
+            WithdrawUnit::SharesPercent,
+            vault_equity,
+            &mut vault,
+            now,
+        )
+        .unwrap();
+        assert_eq!(vd2.last_withdraw_request.value, 99_497_487);
+
+        // the 1% exit fee is withheld but the shares are burned
+        let (withdraw_amount, _) = vd2.withdraw(vault_equity, &mut vault, now).unwrap();
+        assert_eq!(withdraw_amount, 99_497_487 - 994_974);
+        assert_eq!(vd2.checked_vault_shares(&vault).unwrap(), 0);
+        assert_eq!(vault.total_shares, 100_000_000);
+
+        // both fees stay in the vault for the remaining investor
+        let vd1_amount = shares_to_amount(
+            vd1.checked_vault_shares(&vault).unwrap(),
+            vault.total_shares,
+            vault_equity - withdraw_amount,
+        )
+        .unwrap();
+        assert_eq!(vd1_amount, 101_497_487);
+    }
+
+    #[test]
+    fn test_share_price() {
+        let now = 1000;
+        let slot = 100;
+        let mut vault = Vault {
+            pubkey: Pubkey::new_unique(),
+            ..Vault::default()
+        };
+        let mut share_price = SharePrice {
+            vault: vault.pubkey,
+            max_staleness_slots: 10,
+            ..SharePrice::default()
+        };
+
+        // an empty vault is priced at one USDC per share
+        share_price.update(&vault, 0, slot, now).unwrap();
+        assert_eq!(share_price.price, PRICE_PRECISION_U64);
+
+        let vd = &mut Investor::new(vault.pubkey, Pubkey::default(), Pubkey::default(), now);
+        vd.deposit(100 * QUOTE_PRECISION_U64, 0, &mut vault, now)
+            .unwrap();
+        vault.shares_base = 1;
+        share_price
+            .update(&vault, 150 * QUOTE_PRECISION_U64, slot, now)
+            .unwrap();
+        assert_eq!(share_price.price, 1_500_000);
+        assert_eq!(share_price.equity, 150 * QUOTE_PRECISION_U64);
+        assert_eq!(share_price.total_shares, 100 * QUOTE_PRECISION);
+        assert_eq!(share_price.shares_base, 1);
+        assert_eq!((share_price.slot, share_price.ts), (slot, now));
+
+        assert_eq!(share_price.get_price(slot + 10).unwrap(), 1_500_000);
+        assert!(!share_price.is_stale(slot + 10));
+        assert!(share_price.is_stale(slot + 11));
+        assert_eq!(
+            share_price
+                .get_price(slot + 11)
+                .err()
+                .map(anchor_lang::error::Error::from),
+            Some(anchor_lang::error::Error::from(ErrorCode::StaleSharePrice))
+        );
+
+        // the share price of another vault can't be updated
+        let other_vault = Vault {
+            pubkey: Pubkey::new_unique(),
+            ..vault
+        };
+        assert_eq!(
+            share_price.update(&other_vault, 0, slot, now).err(),
+            Some(anchor_lang::error::Error::from(
+                ErrorCode::InvalidSharePrice
+            ))
+        );
+    }
+
+    #[test]
+    fn test_preview_deposit_withdraw() {
+        let now = 1000;
+        let mut vault = Vault {
+            entry_fee_bps: 100,                           // 1%
+            exit_fee_bps: 100,                            // 1%
+            max_tokens: 1_000 * QUOTE_PRECISION_U64,      // $1,000
+            min_deposit_amount: 10 * QUOTE_PRECISION_U64, // $10
+            ..Vault::default()
+        };
+
+        let vd1 = &mut Investor::new(Pubkey::default(), Pubkey::default(), Pubkey::default(), now);
+        let vd2 = &mut Investor::new(Pubkey::default(), Pubkey::default(), Pubkey::default(), now);
+
+        let amount: u64 = 100 * QUOTE_PRECISION_U64;
+        vd1.deposit(amount, 0, &mut vault, now).unwrap();
+        let vault_equity = amount;
+
+        // the preview matches the deposit
+        let preview = vd2
+            .preview_deposit(amount, vault_equity, &vault, now)
+            .unwrap();
+        assert_eq!(preview.entry_fee, QUOTE_PRECISION_U64);
+        let n_shares = vd2.deposit(amount, vault_equity, &mut vault, now).unwrap();
+        assert_eq!(preview.shares, n_shares);
+        let vault_equity = 2 * amount;
+
+        // the preview is rejected wherever the deposit would be
+        for (name, amount, state, expected) in [
+            (
+                "below min deposit",
+                10 * QUOTE_PRECISION_U64 - 1,
+                VaultState::Active,
+                ErrorCode::InvalidVaultDeposit,
+            ),
+            (
+                "at capacity",
+                800 * QUOTE_PRECISION_U64,
+                VaultState::Active,
+                ErrorCode::VaultIsAtCapacity,
+            ),
+            (
+                "winding down",
+                amount,
+                VaultState::WindDown,
+                ErrorCode::InvalidVaultState,
+            ),
+            (
+                "closed",
+                amount,
+                VaultState::Closed,
+                ErrorCode::InvalidVaultState,
+            ),
+        ] {
+            let mut vault = Vault { state, ..vault };
+            assert_eq!(
+                vd2.preview_deposit(amount, vault_equity, &vault, now).err(),
+                Some(anchor_lang::error::Error::from(expected)),
+                "{}",
+                name
+            );
+            let mut investor = *vd2;
+            assert_eq!(
+                investor
+                    .deposit(amount, vault_equity, &mut vault, now)
+                    .err(),
+                Some(anchor_lang::error::Error::from(expected)),
+                "{}",
+                name
+            );
+        }
+
+        // the preview matches the withdraw request and the withdrawal
+        let preview = vd2
+            .preview_withdraw(
+                PERCENTAGE_PRECISION_U64,
+                WithdrawUnit::SharesPercent,
+                vault_equity,
+                &vault,
+                now,
+            )
+            .unwrap();
+        vd2.request_withdraw(
+            PERCENTAGE_PRECISION_U64,
+            WithdrawUnit::SharesPercent,
+            vault_equity,
+            &mut vault,
+            now,
+        )
+        .unwrap();
+        assert_eq!(preview.shares, vd2.last_withdraw_request.shares);
+        assert_eq!(
+            preview.exit_fee,
+            vault.exit_fee(vd2.last_withdraw_request.value).unwrap()
+        );
+
+        // a pending request is priced instead of the given amount
+        let pending_preview = vd2
+            .preview_withdraw(1, WithdrawUnit::Token, vault_equity, &vault, now)
+            .unwrap();
+        assert_eq!(pending_preview, preview);
+
+        assert_eq!(
+            vd2.preview_deposit(amount, vault_equity, &vault, now).err(),
+            Some(anchor_lang::error::Error::from(
+                ErrorCode::WithdrawInProgress
+            ))
+        );
+
+        let (withdraw_amount, _) = vd2.withdraw(vault_equity, &mut vault, now).unwrap();
+        assert_eq!(withdraw_amount, preview.amount);
+    }
+
+    #[test]
+    fn test_slippage_and_deadline_guards() {
+        let now = 1000;
+        let mut vault = Vault::default();
+
+        assert!(Investor::check_deadline(None, now).is_ok());
+        assert!(Investor::check_deadline(Some(now), now).is_ok());
+        assert_eq!(
+            Investor::check_deadline(Some(now - 1), now),
+            Err(ErrorCode::DeadlineExceeded)
+        );
+
+        for (name, n_shares, min_shares_out, expected) in [
+            ("no min", 0, None, None),
+            ("at min", 100, Some(100), None),
+            (
+                "below min",
+                99,
+                Some(100),
+                Some(ErrorCode::SlippageExceeded),
+            ),
+        ] {
+            assert_eq!(
+                Investor::check_min_shares_out(n_shares, min_shares_out, &vault).err(),
+                expected,
+                "{}",
+                name
+            );
+        }
+
+        for (name, amount, min_amount_out, expected) in [
+            ("no min", 0, None, None),
+            ("at min", 100, Some(100), None),
+            (
+                "below min",
+                99,
+                Some(100),
+                Some(ErrorCode::SlippageExceeded),
+            ),
+        ] {
+            assert_eq!(
+                Investor::check_min_amount_out(amount, min_amount_out).err(),
+                expected,
+                "{}",
+                name
+            );
+        }
+
+        // queued deposits mint no shares until the epoch settles
+        vault.epoch_duration = 60;
+        assert!(Investor::check_min_shares_out(0, None, &vault).is_ok());
+        assert_eq!(
+            Investor::check_min_shares_out(0, Some(0), &vault),
+            Err(ErrorCode::InvalidVaultDeposit)
+        );
+    }
+
+    #[test]
+    fn test_epoch_reserved_usdc() {
+        let mut vault = Vault {
+            epoch_pending_deposits: 30,
+            epoch_redemptions_owed: 20,
+            ..Vault::default()
+        };
+        assert!(vault.check_epoch_reserved_usdc(50).is_ok());
+        assert!(vault.check_epoch_reserved_usdc(49).is_err());
+
+        vault.epoch_pending_deposits = 0;
+        assert!(vault.check_epoch_reserved_usdc(20).is_ok());
+        assert!(vault.check_epoch_reserved_usdc(19).is_err());
+    }
+
+    #[test]
+    fn test_epoch_withdraw_in_kind() {
+        let now = 1000;
+        let epoch_duration = 100;
+        let amount: u64 = 100 * QUOTE_PRECISION_U64;
+        let mut vault = Vault {
+            epoch_duration,
+            epoch_start_ts: now,
+            last_fee_update_ts: now,
+            ..Vault::default()
+        };
+        let vd = &mut Investor::new(Pubkey::default(), Pubkey::default(), Pubkey::default(), now);
+
+        vd.deposit(amount, 0, &mut vault, now).unwrap();
+        vault.settle_epoch(0, now + epoch_duration).unwrap();
+        vd.claim_epoch(amount, &mut vault).unwrap();
+        vd.request_withdraw(
+            PERCENTAGE_PRECISION_U64,
+            WithdrawUnit::SharesPercent,
+            amount,
+            &mut vault,
+            now + epoch_duration,
+        )
+        .unwrap();
+        vault
+            .settle_epoch(amount, now + 2 * epoch_duration)
+            .unwrap();
+        assert_eq!(vault.epoch_redemptions_owed, amount);
+
+        // the settled redemption is reserved in USDC, which the vault equity leaves out
+        let now = now + 2 * epoch_duration;
+        assert_eq!(
+            vd.withdraw_in_kind(0, &mut vault, now).unwrap_err(),
+            anchor_lang::error::Error::from(ErrorCode::InKindEpochMode)
+        );
+        assert_eq!(vault.epoch_redemptions_owed, amount);
+        assert_eq!(vd.last_withdraw_request.value, amount);
+
+        let (withdraw_amount, _) = vd.withdraw(0, &mut vault, now).unwrap();
+        assert_eq!(withdraw_amount, amount);
+        assert_eq!(vault.epoch_redemptions_owed, 0);
+    }
+
+    #[test]
+    fn test_epoch_withdraw_profit_share() {
+        let now = 1000;
+        let epoch_duration = 100;
+        let amount: u64 = 100 * QUOTE_PRECISION_U64;
+
+        for (
+            name,
+            withdraw_percent,
+            investor_shares_after,
+            withdraw_value,
+            vault_equity_after,
+            manager_amount_after,
+            protocol_amount_after,
+        ) in [
+            // 15% of $200 profit at the settle price is $30 or 10M of the 50M retained shares
+            (
+                "partial",
+                PERCENTAGE_PRECISION_U64 / 2,
+                40_000_000,
+                150 * QUOTE_PRECISION_U64,
+                150 * QUOTE_PRECISION_U64,
+                20_000_001,
+                9_999_999,
+            ),
+            // nothing is retained, so the $30 is withheld from the redemption value
+            (
+                "full",
+                PERCENTAGE_PRECISION_U64,
+                0,
+                270 * QUOTE_PRECISION_U64,
+                30 * QUOTE_PRECISION_U64,
+                20 * QUOTE_PRECISION_U64,
+                10 * QUOTE_PRECISION_U64,
+            ),
+        ] {
+            let mut vault = Vault {
+                epoch_duration,
+                epoch_start_ts: now,
+                last_fee_update_ts: now,
+                profit_share: 100_000,         // 10% profit share
+                protocol_profit_share: 50_000, // 5% profit share
+                ..Vault::default()
+            };
+            let vd =
+                &mut Investor::new(Pubkey::default(), Pubkey::default(), Pubkey::default(), now);
+
+            vd.deposit(amount, 0, &mut vault, now).unwrap();
+            vault.settle_epoch(0, now + epoch_duration).unwrap();
+            vd.claim_epoch(amount, &mut vault).unwrap();
+            assert_eq!(
+                vd.checked_vault_shares(&vault).unwrap(),
+                100_000_000,
+                "{}",
+                name
+            );
+
+            // requested at $2 per share, so no profit share is taken yet
+            vd.request_withdraw(
+                withdraw_percent,
+                WithdrawUnit::SharesPercent,
+                2 * amount,
+                &mut vault,
+                now + epoch_duration,
+            )
+            .unwrap();
+            assert_eq!(
+                vd.checked_vault_shares(&vault).unwrap(),
+                100_000_000,
+                "{}",
+                name
+            );
+            assert_eq!(vd.profit_share_fee_paid, 0, "{}", name);
+
+            // settled at $3 per share
+            vault
+                .settle_epoch(3 * amount, now + 2 * epoch_duration)
+                .unwrap();
+            let vault_equity = vd
+                .claim_epoch(3 * amount - vault.epoch_redemptions_owed, &mut vault)
+                .unwrap();
+
+            assert_eq!(
+                vd.profit_share_fee_paid,
+                30 * QUOTE_PRECISION_U64,
+                "{}",
+                name
+            );
+            assert_eq!(
+                vd.checked_vault_shares(&vault).unwrap(),
+                investor_shares_after,
+                "{}",
+                name
+            );
+            assert_eq!(vault.investor_shares, investor_shares_after, "{}", name);
+            assert_eq!(vd.last_withdraw_request.value, withdraw_value, "{}", name);
+            assert_eq!(vault.epoch_redemptions_owed, withdraw_value, "{}", name);
+            assert_eq!(vault.total_withdraw_requested, withdraw_value, "{}", name);
+            assert_eq!(vault_equity, vault_equity_after, "{}", name);
+
+            // the manager and protocol own their $20 and $10 of the remaining equity, less rounding
+            let manager_amount = shares_to_amount(
+                vault.get_manager_shares().unwrap(),
+                vault.total_shares,
+                vault_equity,
+            )
+            .unwrap();
+            let protocol_amount = shares_to_amount(
+                vault.get_protocol_shares(),
+                vault.total_shares,
+                vault_equity,
+            )
+            .unwrap();
+            assert_eq!(manager_amount, manager_amount_after, "{}", name);
+            assert_eq!(protocol_amount, protocol_amount_after, "{}", name);
+        }
+    }
 }
@@ -1,8 +1,8 @@
-use crate::constants::PRICE_PRECISION_U64;
+use crate::constants::{BPS_PRECISION, PRICE_PRECISION_U64};
 use crate::error::ErrorCode;
 use crate::math::*;
 use crate::state::withdraw_request::WithdrawRequest;
-use crate::state::{MarketPosition, MarketRegistry, Vault};
+use crate::state::{LiquidationFill, MarketPosition, MarketRegistry, Vault};
 use crate::validate;
 use anchor_lang::prelude::*;
 use anchor_spl::token::TokenAccount;
@@ -33,6 +33,8 @@ pub trait MarketMapProvider<'a> {
     ) -> Result<()>;
 
     fn market_position(&self, vault: &Vault, market: Pubkey) -> Result<MarketPosition>;
+
+    fn liquidation_fill(&self, vault: &Pubkey, before: &MarketPosition) -> Result<LiquidationFill>;
 }
 
 impl<'a: 'info, 'info, T: anchor_lang::Bumps> MarketMapProvider<'a>
@@ -50,22 +52,7 @@ impl<'a: 'info, 'info, T: anchor_lang::Bumps> MarketMapProvider<'a>
         )?;
 
         let account_data = account.try_borrow_data()?;
-        let (header_bytes, bytes) = account_data.split_at(std::mem::size_of::<MarketHeader>());
-        let header = Box::new(
-            MarketHeader::load_bytes(header_bytes)
-                .ok_or(anchor_lang::error::Error::from(
-                    ErrorCode::MarketDeserializationError,
-                ))?
-                .to_owned(),
-        );
-        if header.quote_params.mint_key != registry.usdc_mint
-            || header.base_params.mint_key != registry.sol_mint
-        {
-            return Err(ErrorCode::SolMarketMissing.into());
-        }
-        let market = load_with_dispatch(&header.market_size_params, bytes)?;
-        let ladder = market.inner.get_ladder(1);
-        let tick_price = ladder.bids.first().map_or(0, |bid| bid.price_in_ticks);
+        let (tick_price, header) = load_sol_usdc_market_data(registry, &account_data)?;
 
         Ok((account.key(), tick_price, header))
     }
@@ -76,68 +63,7 @@ impl<'a: 'info, 'info, T: anchor_lang::Bumps> MarketMapProvider<'a>
         vault_usdc: &Account<TokenAccount>,
         registry: &MarketRegistry,
     ) -> Result<u64> {
-        let mut equity = 0;
-
-        let sol_mint = registry.sol_mint;
-        let usdc_mint = registry.usdc_mint;
-
-        let (_, sol_tick_price, sol_header) = self.load_sol_usdc_market(registry)?;
-        let sol_price = ticks_to_price_precision(&sol_header, sol_tick_price);
-
-        // usdc has 6 decimals which is the same as PRICE_PRECISION
-        let vault_usdc_units_precision = vault_usdc.amount;
-        equity += vault_usdc_units_precision;
-
-        let remaining_accounts_iter = &mut self.remaining_accounts.iter().peekable();
-        for position in vault.positions {
-            if position.is_available() {
-                continue;
-            }
-            // assert this key in the remaining accounts matches the vault's MarketPosition
-            let account_info = MarketMap::find(&position.market, remaining_accounts_iter)?;
-
-            let account_data = account_info.try_borrow_data()?;
-            let (header_bytes, bytes) = account_data.split_at(std::mem::size_of::<MarketHeader>());
-            let header = Box::new(MarketHeader::load_bytes(header_bytes).ok_or(
-                anchor_lang::error::Error::from(ErrorCode::MarketDeserializationError),
-            )?);
-            let market = load_with_dispatch(&header.market_size_params, bytes)?;
-            let tick_price = market
-                .inner
-                .get_ladder(1)
-                .bids
-                .first()
-                .map_or(0, |bid| bid.price_in_ticks);
-            let price = ticks_to_price_precision(&header, tick_price);
-
-            if let Some(trader_state) = market.inner.get_trader_state(&vault.pubkey) {
-                let quote_mint = header.quote_params.mint_key;
-                let usdc_price_precision = if quote_mint == usdc_mint {
-                    price
-                } else if quote_mint == sol_mint {
-                    sol_to_usdc_denom(price, sol_price)
-                } else {
-                    return Err(ErrorCode::UnrecognizedQuoteMint.into());
-                };
-                let base_lots =
-                    trader_state.base_lots_locked.as_u64() + trader_state.base_lots_free.as_u64();
-                let quote_lots =
-                    trader_state.quote_lots_locked.as_u64() + trader_state.quote_lots_free.as_u64();
-
-                let base_units_precision =
-                    base_lots_to_raw_base_units_precision(&header, base_lots);
-                // both are multiplied by PRICE_PRECISION so multiply by one to make it multiplied once in total.
-                let base_quote_units_precision =
-                    base_units_precision * usdc_price_precision / PRICE_PRECISION_U64;
-
-                let quote_units_precision =
-                    quote_lots_to_quote_units_precision(&header, quote_lots);
-                let total_quote_units_precision =
-                    base_quote_units_precision + quote_units_precision;
-                equity += total_quote_units_precision;
-            }
-        }
-        Ok(equity)
+        equity_from_account_infos(vault, vault_usdc.amount, registry, self.remaining_accounts)
     }
 
     fn check_cant_withdraw(
@@ -152,51 +78,285 @@ impl<'a: 'info, 'info, T: anchor_lang::Bumps> MarketMapProvider<'a>
         )?;
 
         let account_data = account.try_borrow_data()?;
-        let (header_bytes, _) = account_data.split_at(std::mem::size_of::<MarketHeader>());
+        check_cant_withdraw_from_market_data(
+            withdraw_request,
+            vault_usdc_token_account.amount,
+            registry,
+            &account_data,
+        )
+    }
+
+    fn market_position(&self, vault: &Vault, market: Pubkey) -> Result<MarketPosition> {
+        market_position_from_account_infos(&vault.pubkey, market, self.remaining_accounts)
+    }
+
+    /// Measure the take order placed since `before` was read from the same market.
+    /// The taker fee is charged on the gross proceeds, so it is derived from the net quote received.
+    fn liquidation_fill(&self, vault: &Pubkey, before: &MarketPosition) -> Result<LiquidationFill> {
+        let account_info = MarketMap::find(
+            &before.market,
+            &mut self.remaining_accounts.iter().peekable(),
+        )?;
+        let account_data = account_info.try_borrow_data()?;
+        let (header_bytes, bytes) = account_data.split_at(std::mem::size_of::<MarketHeader>());
         let header = Box::new(MarketHeader::load_bytes(header_bytes).ok_or(
             anchor_lang::error::Error::from(ErrorCode::MarketDeserializationError),
         )?);
-        if header.quote_params.mint_key != registry.usdc_mint
-            || header.base_params.mint_key != registry.sol_mint
-        {
-            return Err(ErrorCode::SolMarketMissing.into());
-        }
+        let market_wrapper = load_with_dispatch(&header.market_size_params, bytes)?;
+        let trader_state =
+            market_wrapper
+                .inner
+                .get_trader_state(vault)
+                .ok_or(anchor_lang::error::Error::from(
+                    ErrorCode::TraderStateNotFound,
+                ))?;
 
-        let quote_lots_available =
-            quote_atoms_to_quote_lots_rounded_down(&header, vault_usdc_token_account.amount);
-        let quote_lots_requested =
-            quote_atoms_to_quote_lots_rounded_down(&header, withdraw_request.value);
-        let cant_withdraw = quote_lots_available < quote_lots_requested;
+        let base_lots_sold = before
+            .base_lots_free
+            .saturating_sub(trader_state.base_lots_free.as_u64());
+        let quote_lots_received = trader_state
+            .quote_lots_free
+            .as_u64()
+            .saturating_sub(before.quote_lots_free);
+        let quote_received = quote_lots_to_quote_atoms(&header, quote_lots_received);
 
-        validate!(
-            cant_withdraw,
-            ErrorCode::InvestorCanWithdraw,
-            "Investor can withdraw without liquidating the vault"
-        )?;
+        let fee_bps = market_wrapper.inner.get_taker_fee_bps();
+        let fees = quote_received
+            .cast::<u128>()?
+            .safe_mul(fee_bps.cast()?)?
+            .safe_div(BPS_PRECISION.safe_sub(fee_bps.cast()?)?)?
+            .cast::<u64>()?;
 
-        Ok(())
+        Ok(LiquidationFill {
+            base_lots_sold,
+            avg_price: fill_price_precision(
+                &header,
+                base_lots_sold,
+                quote_received.safe_add(fees)?,
+            ),
+            quote_received,
+            fees,
+        })
     }
+}
+
+/// Top bid in ticks and header of the SOL/USDC market from its raw account data.
+fn load_sol_usdc_market_data(
+    registry: &MarketRegistry,
+    data: &[u8],
+) -> Result<(u64, Box<MarketHeader>)> {
+    let (header_bytes, bytes) = data.split_at(std::mem::size_of::<MarketHeader>());
+    let header = Box::new(
+        MarketHeader::load_bytes(header_bytes)
+            .ok_or(anchor_lang::error::Error::from(
+                ErrorCode::MarketDeserializationError,
+            ))?
+            .to_owned(),
+    );
+    if header.quote_params.mint_key != registry.usdc_mint
+        || header.base_params.mint_key != registry.sol_mint
+    {
+        return Err(ErrorCode::SolMarketMissing.into());
+    }
+    let market = load_with_dispatch(&header.market_size_params, bytes)?;
+    let ladder = market.inner.get_ladder(1);
+    let tick_price = ladder.bids.first().map_or(0, |bid| bid.price_in_ticks);
+
+    Ok((tick_price, header))
+}
+
+/// Errors unless the vault USDC is short of `withdraw_request` by at least a quote lot
+/// of the SOL/USDC market, given its raw account data.
+fn check_cant_withdraw_from_market_data(
+    withdraw_request: &WithdrawRequest,
+    vault_usdc_amount: u64,
+    registry: &MarketRegistry,
+    sol_usdc_data: &[u8],
+) -> Result<()> {
+    let (header_bytes, _) = sol_usdc_data.split_at(std::mem::size_of::<MarketHeader>());
+    let header = Box::new(MarketHeader::load_bytes(header_bytes).ok_or(
+        anchor_lang::error::Error::from(ErrorCode::MarketDeserializationError),
+    )?);
+    if header.quote_params.mint_key != registry.usdc_mint
+        || header.base_params.mint_key != registry.sol_mint
+    {
+        return Err(ErrorCode::SolMarketMissing.into());
+    }
+
+    let quote_lots_available = quote_atoms_to_quote_lots_rounded_down(&header, vault_usdc_amount);
+    let quote_lots_requested =
+        quote_atoms_to_quote_lots_rounded_down(&header, withdraw_request.value);
+    let cant_withdraw = quote_lots_available < quote_lots_requested;
+
+    validate!(
+        cant_withdraw,
+        ErrorCode::InvestorCanWithdraw,
+        "Investor can withdraw without liquidating the vault"
+    )?;
+
+    Ok(())
+}
+
+/// Vault equity in USDC from the raw data of Phoenix market accounts, keyed by market.
+///
+/// `markets` must contain the SOL/USDC market and every market the vault has a position in,
+/// ordered as in `vault.positions`. This is the order the program requires of the remaining accounts,
+/// so on-chain and off-chain callers get identical results for the same account data.
+pub fn calculate_equity(
+    vault: &Vault,
+    vault_usdc_amount: u64,
+    registry: &MarketRegistry,
+    markets: &[(Pubkey, &[u8])],
+) -> Result<u64> {
+    let mut equity = 0;
+
+    let sol_mint = registry.sol_mint;
+    let usdc_mint = registry.usdc_mint;
+
+    let (_, sol_usdc_data) = markets
+        .iter()
+        .find(|(key, _)| key == &registry.sol_usdc_market)
+        .ok_or(ErrorCode::MarketMissingInRemainingAccounts)?;
+    let (sol_tick_price, sol_header) = load_sol_usdc_market_data(registry, sol_usdc_data)?;
+    let sol_price = ticks_to_price_precision(&sol_header, sol_tick_price);
+
+    // usdc has 6 decimals which is the same as PRICE_PRECISION
+    // exclude USDC queued or owed by epoch settlement since it isn't owned by current shareholders
+    let vault_usdc_units_precision = vault_usdc_amount.saturating_sub(vault.epoch_reserved_usdc());
+    equity += vault_usdc_units_precision;
+
+    let markets_iter = &mut markets.iter();
+    for position in vault.positions {
+        if position.is_available() {
+            continue;
+        }
+        // assert this key in the markets matches the vault's MarketPosition
+        let (_, account_data) = markets_iter
+            .find(|(key, _)| key == &position.market)
+            .ok_or(ErrorCode::MarketMissingInRemainingAccounts)?;
 
-    fn market_position(&self, vault: &Vault, market: Pubkey) -> Result<MarketPosition> {
-        let account_info =
-            MarketMap::find(&market, &mut self.remaining_accounts.iter().peekable())?;
-        let account_data = account_info.try_borrow_data()?;
         let (header_bytes, bytes) = account_data.split_at(std::mem::size_of::<MarketHeader>());
         let header = Box::new(MarketHeader::load_bytes(header_bytes).ok_or(
             anchor_lang::error::Error::from(ErrorCode::MarketDeserializationError),
         )?);
-        let market_wrapper = load_with_dispatch(&header.market_size_params, bytes)?;
-        let trader_state = market_wrapper.inner.get_trader_state(&vault.pubkey).ok_or(
-            anchor_lang::error::Error::from(ErrorCode::TraderStateNotFound),
-        )?;
-        Ok(MarketPosition {
-            market,
-            quote_lots_free: trader_state.quote_lots_free.as_u64(),
-            quote_lots_locked: trader_state.quote_lots_locked.as_u64(),
-            base_lots_free: trader_state.base_lots_free.as_u64(),
-            base_lots_locked: trader_state.base_lots_locked.as_u64(),
-        })
+        let market = load_with_dispatch(&header.market_size_params, bytes)?;
+        let tick_price = market
+            .inner
+            .get_ladder(1)
+            .bids
+            .first()
+            .map_or(0, |bid| bid.price_in_ticks);
+        let price = ticks_to_price_precision(&header, tick_price);
+
+        if let Some(trader_state) = market.inner.get_trader_state(&vault.pubkey) {
+            let quote_mint = header.quote_params.mint_key;
+            let usdc_price_precision = if quote_mint == usdc_mint {
+                price
+            } else if quote_mint == sol_mint {
+                sol_to_usdc_denom(price, sol_price)
+            } else {
+                return Err(ErrorCode::UnrecognizedQuoteMint.into());
+            };
+            let base_lots =
+                trader_state.base_lots_locked.as_u64() + trader_state.base_lots_free.as_u64();
+            let quote_lots =
+                trader_state.quote_lots_locked.as_u64() + trader_state.quote_lots_free.as_u64();
+
+            let base_units_precision = base_lots_to_raw_base_units_precision(&header, base_lots);
+            // both are multiplied by PRICE_PRECISION so multiply by one to make it multiplied once in total.
+            let base_quote_units_precision =
+                base_units_precision * usdc_price_precision / PRICE_PRECISION_U64;
+
+            // quote lots of SOL-quoted markets are SOL, so they are valued at the SOL price too
+            let quote_units_precision = quote_lots_to_quote_units_precision(&header, quote_lots);
+            let quote_units_precision = if quote_mint == sol_mint {
+                sol_to_usdc_denom(quote_units_precision, sol_price)
+            } else {
+                quote_units_precision
+            };
+            let total_quote_units_precision = base_quote_units_precision + quote_units_precision;
+            equity += total_quote_units_precision;
+        }
     }
+    Ok(equity)
+}
+
+/// [`calculate_equity`] over the remaining accounts of an instruction.
+/// Only the markets the valuation reads are borrowed.
+pub fn equity_from_account_infos(
+    vault: &Vault,
+    vault_usdc_amount: u64,
+    registry: &MarketRegistry,
+    remaining_accounts: &[AccountInfo],
+) -> Result<u64> {
+    let accounts: Vec<&AccountInfo> = remaining_accounts
+        .iter()
+        .filter(|account| {
+            account.key == &registry.sol_usdc_market
+                || vault
+                    .positions
+                    .iter()
+                    .any(|position| !position.is_available() && &position.market == account.key)
+        })
+        .collect();
+    let account_data = accounts
+        .iter()
+        .map(|account| account.try_borrow_data())
+        .collect::<std::result::Result<Vec<_>, _>>()?;
+    let markets: Vec<(Pubkey, &[u8])> = accounts
+        .iter()
+        .zip(account_data.iter())
+        .map(|(account, data)| (*account.key, &data[..]))
+        .collect();
+
+    calculate_equity(vault, vault_usdc_amount, registry, &markets)
+}
+
+/// Position of `vault` on `market` from the remaining accounts of an instruction.
+pub fn market_position_from_account_infos(
+    vault: &Pubkey,
+    market: Pubkey,
+    remaining_accounts: &[AccountInfo],
+) -> Result<MarketPosition> {
+    let account_info = remaining_accounts
+        .iter()
+        .find(|account| account.key == &market)
+        .ok_or(ErrorCode::MarketMissingInRemainingAccounts)?;
+    let account_data = account_info.try_borrow_data()?;
+    let (header_bytes, bytes) = account_data.split_at(std::mem::size_of::<MarketHeader>());
+    let header = Box::new(MarketHeader::load_bytes(header_bytes).ok_or(
+        anchor_lang::error::Error::from(ErrorCode::MarketDeserializationError),
+    )?);
+    let market_wrapper = load_with_dispatch(&header.market_size_params, bytes)?;
+    let trader_state =
+        market_wrapper
+            .inner
+            .get_trader_state(vault)
+            .ok_or(anchor_lang::error::Error::from(
+                ErrorCode::TraderStateNotFound,
+            ))?;
+    Ok(MarketPosition {
+        market,
+        quote_lots_free: trader_state.quote_lots_free.as_u64(),
+        quote_lots_locked: trader_state.quote_lots_locked.as_u64(),
+        base_lots_free: trader_state.base_lots_free.as_u64(),
+        base_lots_locked: trader_state.base_lots_locked.as_u64(),
+    })
+}
+
+/// [`market_position_from_account_infos`] for event records, which read an empty position
+/// on a market the vault has no trader state on because it hasn't deposited to it yet.
+pub fn market_position_or_empty(
+    vault: &Pubkey,
+    market: Pubkey,
+    remaining_accounts: &[AccountInfo],
+) -> MarketPosition {
+    market_position_from_account_infos(vault, market, remaining_accounts).unwrap_or(
+        MarketPosition {
+            market,
+            ..MarketPosition::default()
+        },
+    )
 }
 
 pub struct MarketMap;
@@ -214,3 +374,408 @@ impl MarketMap {
         Err(ErrorCode::MarketMissingInRemainingAccounts.into())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{create_account_info, TestMarket, TestMarketParams, TestTraderLots};
+
+    struct Setup {
+        registry: MarketRegistry,
+        vault: Vault,
+        sol_usdc: TestMarket,
+        jup_sol: TestMarket,
+    }
+
+    /// SOL/USDC with a $150 top bid under an ask, and JUP/SOL with a 0.05 SOL bid,
+    /// so JUP is worth $7.50. The vault has a position in each market it has lots on.
+    fn setup(sol_usdc_lots: Option<TestTraderLots>, jup_sol_lots: Option<TestTraderLots>) -> Setup {
+        let mut registry = MarketRegistry {
+            usdc_mint: Pubkey::new_unique(),
+            sol_mint: Pubkey::new_unique(),
+            ..MarketRegistry::default()
+        };
+        let mut vault = Vault {
+            pubkey: Pubkey::new_unique(),
+            ..Vault::default()
+        };
+        let maker = Pubkey::new_unique();
+
+        let sol_usdc = TestMarket::new(TestMarketParams {
+            base_mint: registry.sol_mint,
+            quote_mint: registry.usdc_mint,
+            ..TestMarketParams::default()
+        })
+        .with_ladder(
+            &maker,
+            &[(15_000, 1_000), (14_000, 10_000)],
+            &[(15_100, 1_000)],
+        );
+        let jup_sol = TestMarket::new(TestMarketParams {
+            base_mint: Pubkey::new_unique(),
+            quote_mint: registry.sol_mint,
+            base_decimals: 6,
+            quote_decimals: 9,
+            ..TestMarketParams::default()
+        })
+        .with_bid(&maker, 5, 1_000);
+        registry.sol_usdc_market = sol_usdc.key;
+
+        let sol_usdc = with_position(sol_usdc, &mut vault, 0, sol_usdc_lots);
+        let jup_sol = with_position(jup_sol, &mut vault, 1, jup_sol_lots);
+
+        Setup {
+            registry,
+            vault,
+            sol_usdc,
+            jup_sol,
+        }
+    }
+
+    /// Gives the vault `lots` on `market`, and a position for them at `index`.
+    fn with_position(
+        market: TestMarket,
+        vault: &mut Vault,
+        index: usize,
+        lots: Option<TestTraderLots>,
+    ) -> TestMarket {
+        match lots {
+            Some(lots) => {
+                vault.positions[index] = MarketPosition {
+                    market: market.key,
+                    base_lots_free: lots.base_lots_free,
+                    base_lots_locked: lots.base_lots_locked,
+                    quote_lots_free: lots.quote_lots_free,
+                    quote_lots_locked: lots.quote_lots_locked,
+                };
+                market.with_trader(&vault.pubkey, lots)
+            }
+            None => market,
+        }
+    }
+
+    /// Values `vault` over account infos of `markets` in order, as the program sees
+    /// remaining accounts.
+    fn equity(
+        vault: &Vault,
+        vault_usdc_amount: u64,
+        registry: &MarketRegistry,
+        markets: &[&TestMarket],
+    ) -> Result<u64> {
+        let owner = Pubkey::default();
+        let mut lamports = vec![0; markets.len()];
+        let mut data: Vec<Vec<u8>> = markets.iter().map(|market| market.data.clone()).collect();
+        let account_infos: Vec<AccountInfo> = markets
+            .iter()
+            .zip(lamports.iter_mut())
+            .zip(data.iter_mut())
+            .map(|((market, lamports), data)| {
+                create_account_info(&market.key, lamports, data, &owner)
+            })
+            .collect();
+        equity_from_account_infos(vault, vault_usdc_amount, registry, &account_infos)
+    }
+
+    #[test]
+    fn test_market_position_or_empty() {
+        let vault = Pubkey::new_unique();
+        let lots = TestTraderLots {
+            base_lots_free: 1_000,
+            quote_lots_locked: 50_000,
+            ..TestTraderLots::default()
+        };
+        let market = TestMarket::new(TestMarketParams::default());
+        let owner = Pubkey::default();
+        let empty = MarketPosition {
+            market: market.key,
+            ..MarketPosition::default()
+        };
+        for (name, vault_lots, in_accounts, expected) in [
+            ("market missing", Some(lots), false, empty),
+            ("not deposited to yet", None, true, empty),
+            (
+                "deposited",
+                Some(lots),
+                true,
+                MarketPosition {
+                    market: market.key,
+                    base_lots_free: 1_000,
+                    quote_lots_locked: 50_000,
+                    ..MarketPosition::default()
+                },
+            ),
+        ] {
+            let mut data = match vault_lots {
+                Some(lots) => {
+                    TestMarket {
+                        key: market.key,
+                        data: market.data.clone(),
+                    }
+                    .with_trader(&vault, lots)
+                    .data
+                }
+                None => market.data.clone(),
+            };
+            let mut lamports = 0;
+            let account_info = create_account_info(&market.key, &mut lamports, &mut data, &owner);
+            let accounts = if in_accounts {
+                vec![account_info]
+            } else {
+                vec![]
+            };
+            assert_eq!(
+                market_position_or_empty(&vault, market.key, &accounts),
+                expected,
+                "{}",
+                name
+            );
+        }
+    }
+
+    #[test]
+    fn test_equity() {
+        let sol_usdc_lots = TestTraderLots {
+            base_lots_free: 1_000,
+            base_lots_locked: 1_000,
+            quote_lots_free: 100_000,
+            quote_lots_locked: 50_000,
+        };
+        let jup_sol_lots = TestTraderLots {
+            base_lots_free: 10_000,
+            quote_lots_free: 100_000,
+            ..TestTraderLots::default()
+        };
+        for (name, vault_usdc_amount, epoch_pending_deposits, sol_usdc, jup_sol, expected) in [
+            ("usdc only", 1_000_000_000, 0, None, None, 1_000_000_000),
+            (
+                "epoch deposits excluded",
+                1_000_000_000,
+                100_000_000,
+                None,
+                None,
+                900_000_000,
+            ),
+            (
+                "epoch deposits beyond balance",
+                100_000_000,
+                200_000_000,
+                None,
+                None,
+                0,
+            ),
+            // 2 SOL at the $150 top bid and $1.50 of quote lots
+            (
+                "sol/usdc free and locked",
+                0,
+                0,
+                Some(sol_usdc_lots),
+                None,
+                301_500_000,
+            ),
+            (
+                "jup/sol base through sol/usdc",
+                0,
+                0,
+                None,
+                Some(TestTraderLots {
+                    base_lots_free: 10_000,
+                    base_lots_locked: 10_000,
+                    ..TestTraderLots::default()
+                }),
+                150_000_000,
+            ),
+            (
+                "jup/sol quote at the sol price",
+                0,
+                0,
+                None,
+                Some(TestTraderLots {
+                    quote_lots_free: 100_000,
+                    quote_lots_locked: 100_000,
+                    ..TestTraderLots::default()
+                }),
+                300_000_000,
+            ),
+            // $900 + $301.50 + 10 JUP at $7.50 + 1 SOL at $150
+            (
+                "all",
+                1_000_000_000,
+                100_000_000,
+                Some(sol_usdc_lots),
+                Some(jup_sol_lots),
+                1_426_500_000,
+            ),
+        ] {
+            let mut setup = setup(sol_usdc, jup_sol);
+            setup.vault.epoch_pending_deposits = epoch_pending_deposits;
+            assert_eq!(
+                equity(
+                    &setup.vault,
+                    vault_usdc_amount,
+                    &setup.registry,
+                    &[&setup.sol_usdc, &setup.jup_sol],
+                )
+                .unwrap(),
+                expected,
+                "{}",
+                name
+            );
+        }
+    }
+
+    #[test]
+    fn test_equity_errors() {
+        let lots = TestTraderLots {
+            base_lots_free: 1_000,
+            ..TestTraderLots::default()
+        };
+        let setup = setup(Some(lots), Some(lots));
+        let Setup {
+            registry,
+            vault,
+            sol_usdc,
+            jup_sol,
+        } = &setup;
+
+        let usdc_quoted = TestMarket::new(TestMarketParams {
+            base_mint: Pubkey::new_unique(),
+            quote_mint: registry.usdc_mint,
+            ..TestMarketParams::default()
+        });
+        let other_quoted = TestMarket::new(TestMarketParams {
+            base_mint: Pubkey::new_unique(),
+            quote_mint: Pubkey::new_unique(),
+            ..TestMarketParams::default()
+        })
+        .with_trader(&vault.pubkey, lots);
+        let mut unrecognized = *vault;
+        unrecognized.positions[2] = MarketPosition {
+            market: other_quoted.key,
+            base_lots_free: 1_000,
+            ..MarketPosition::default()
+        };
+        let swapped_mints = MarketRegistry {
+            usdc_mint: registry.sol_mint,
+            sol_mint: registry.usdc_mint,
+            ..*registry
+        };
+        let not_sol_usdc = MarketRegistry {
+            sol_usdc_market: usdc_quoted.key,
+            ..*registry
+        };
+
+        for (name, vault, registry, markets, error_code) in [
+            (
+                "sol/usdc missing",
+                vault,
+                registry,
+                vec![jup_sol],
+                ErrorCode::MarketMissingInRemainingAccounts,
+            ),
+            (
+                "position market missing",
+                vault,
+                registry,
+                vec![sol_usdc],
+                ErrorCode::MarketMissingInRemainingAccounts,
+            ),
+            (
+                "markets out of position order",
+                vault,
+                registry,
+                vec![jup_sol, sol_usdc],
+                ErrorCode::MarketMissingInRemainingAccounts,
+            ),
+            (
+                "sol/usdc mints swapped",
+                vault,
+                &swapped_mints,
+                vec![sol_usdc, jup_sol],
+                ErrorCode::SolMarketMissing,
+            ),
+            (
+                "registry market isn't sol/usdc",
+                vault,
+                &not_sol_usdc,
+                vec![&usdc_quoted, sol_usdc, jup_sol],
+                ErrorCode::SolMarketMissing,
+            ),
+            (
+                "unrecognized quote mint",
+                &unrecognized,
+                registry,
+                vec![sol_usdc, jup_sol, &other_quoted],
+                ErrorCode::UnrecognizedQuoteMint,
+            ),
+        ] {
+            assert_eq!(
+                equity(vault, 0, registry, &markets).unwrap_err(),
+                anchor_lang::error::Error::from(error_code),
+                "{}",
+                name
+            );
+        }
+    }
+
+    #[test]
+    fn test_check_cant_withdraw() {
+        let setup = setup(None, None);
+        // SOL/USDC quote lots are 10 USDC atoms
+        for (name, vault_usdc_amount, request_value, error_code) in [
+            (
+                "vault usdc covers the request",
+                1_000_000_000,
+                500_000_000,
+                Some(ErrorCode::InvestorCanWithdraw),
+            ),
+            (
+                "vault usdc equals the request",
+                500_000_000,
+                500_000_000,
+                Some(ErrorCode::InvestorCanWithdraw),
+            ),
+            ("short by a lot", 499_999_990, 500_000_000, None),
+            (
+                "short by less than a lot",
+                500_000_000,
+                500_000_009,
+                Some(ErrorCode::InvestorCanWithdraw),
+            ),
+            ("empty vault", 0, 10, None),
+            (
+                "empty vault, request under a lot",
+                0,
+                9,
+                Some(ErrorCode::InvestorCanWithdraw),
+            ),
+        ] {
+            let request = WithdrawRequest {
+                value: request_value,
+                ..WithdrawRequest::default()
+            };
+            let result = check_cant_withdraw_from_market_data(
+                &request,
+                vault_usdc_amount,
+                &setup.registry,
+                &setup.sol_usdc.data,
+            );
+            assert_eq!(
+                result.err(),
+                error_code.map(anchor_lang::error::Error::from),
+                "{}",
+                name
+            );
+        }
+
+        // the check is only made against the SOL/USDC market
+        let request = WithdrawRequest {
+            value: 500_000_000,
+            ..WithdrawRequest::default()
+        };
+        assert_eq!(
+            check_cant_withdraw_from_market_data(&request, 0, &setup.registry, &setup.jup_sol.data)
+                .unwrap_err(),
+            anchor_lang::error::Error::from(ErrorCode::SolMarketMissing)
+        );
+    }
+}
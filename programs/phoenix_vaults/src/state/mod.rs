@@ -1,9 +1,13 @@
 pub mod events;
+#[cfg(test)]
+mod invariant_tests;
 mod investor;
 mod market;
 mod market_position;
 mod market_registry;
+mod nav_history;
 mod programs;
+mod share_price;
 mod traits;
 mod types;
 pub mod vault;
@@ -15,7 +19,9 @@ pub use investor::*;
 pub use market::*;
 pub use market_position::*;
 pub use market_registry::*;
+pub use nav_history::*;
 pub use programs::*;
+pub use share_price::*;
 pub use traits::*;
 pub use types::*;
 pub use vault::*;
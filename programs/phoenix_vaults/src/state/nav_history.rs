@@ -0,0 +1,189 @@
+use anchor_lang::prelude::*;
+use bytemuck::Zeroable;
+use drift_macros::assert_no_slop;
+use static_assertions::const_assert_eq;
+
+use crate::constants::MIN_NAV_SAMPLE_INTERVAL;
+use crate::error::ErrorCode;
+use crate::state::events::VaultRecord;
+use crate::state::Vault;
+use crate::{validate, Size};
+
+pub const NAV_HISTORY_LENGTH: usize = 128;
+
+#[assert_no_slop]
+#[derive(
+    Default, AnchorSerialize, AnchorDeserialize, Copy, Clone, Eq, PartialEq, Debug, Zeroable,
+)]
+pub struct NavSample {
+    /// Vault total shares when sampled
+    pub total_shares: u128,
+    /// Unix timestamp of the sample
+    pub ts: i64,
+    /// Vault equity in USDC when sampled
+    pub equity: u64,
+    /// Vault shares base when sampled
+    pub shares_base: u32,
+    pub padding: [u8; 12],
+}
+
+/// Ring buffer of vault equity and shares samples to chart the share price without an indexer.
+/// Samples are appended at most once per [`MIN_NAV_SAMPLE_INTERVAL`] by the permissionless `update_nav_history` crank
+/// and whenever a deposit or withdrawal computes the vault equity. Within the interval the latest sample is updated in place.
+#[assert_no_slop]
+#[account(zero_copy(unsafe))]
+#[derive(Eq, PartialEq, Debug)]
+#[repr(C)]
+pub struct NavHistory {
+    /// The vault sampled
+    pub vault: Pubkey,
+    /// Index the next sample is written to
+    pub head: u64,
+    /// Number of samples written, up to [`NAV_HISTORY_LENGTH`]
+    pub len: u64,
+    /// Timestamp the most recent sample was appended at. Updates in place don't move it.
+    pub last_sample_ts: i64,
+    pub padding: [u8; 8],
+    pub samples: [NavSample; NAV_HISTORY_LENGTH],
+}
+
+impl NavHistory {
+    pub fn seeds(vault: &Pubkey) -> [&[u8]; 2] {
+        [b"nav_history", vault.as_ref()]
+    }
+}
+
+impl Size for NavHistory {
+    const SIZE: usize = 64 + 48 * NAV_HISTORY_LENGTH + 8;
+}
+const_assert_eq!(NavHistory::SIZE, std::mem::size_of::<NavHistory>() + 8);
+
+impl NavHistory {
+    pub fn append(&mut self, sample: NavSample) {
+        let index = self.head as usize % NAV_HISTORY_LENGTH;
+        self.samples[index] = sample;
+        self.head = ((index + 1) % NAV_HISTORY_LENGTH) as u64;
+        self.len = self.len.saturating_add(1).min(NAV_HISTORY_LENGTH as u64);
+        self.last_sample_ts = sample.ts;
+    }
+
+    /// Overwrite the most recent sample, appending if there is none.
+    pub fn update_latest(&mut self, sample: NavSample) {
+        if self.len == 0 {
+            return self.append(sample);
+        }
+        let index = (self.head as usize + NAV_HISTORY_LENGTH - 1) % NAV_HISTORY_LENGTH;
+        self.samples[index] = sample;
+    }
+
+    /// The most recent sample, if any
+    pub fn latest(&self) -> Option<&NavSample> {
+        if self.len == 0 {
+            return None;
+        }
+        let index = (self.head as usize + NAV_HISTORY_LENGTH - 1) % NAV_HISTORY_LENGTH;
+        Some(&self.samples[index])
+    }
+
+    /// Samples ordered from oldest to newest
+    pub fn iter(&self) -> impl Iterator<Item = &NavSample> {
+        let start =
+            (self.head as usize + NAV_HISTORY_LENGTH - self.len as usize) % NAV_HISTORY_LENGTH;
+        (0..self.len as usize).map(move |i| &self.samples[(start + i) % NAV_HISTORY_LENGTH])
+    }
+
+    /// Whether `min_interval` has passed since the last sample was appended
+    pub fn sample_due(&self, now: i64, min_interval: i64) -> bool {
+        self.len == 0 || now.saturating_sub(self.last_sample_ts) >= min_interval
+    }
+
+    pub fn check_sample_interval(&self, now: i64, min_interval: i64) -> Result<()> {
+        validate!(
+            self.sample_due(now, min_interval),
+            ErrorCode::NavSampleTooSoon,
+            "last sample at {}, next sample allowed after {} seconds",
+            self.last_sample_ts,
+            min_interval
+        )?;
+        Ok(())
+    }
+
+    /// Sample the vault share price and emit a [`VaultRecord`].
+    /// Within [`MIN_NAV_SAMPLE_INTERVAL`] of the last appended sample the latest sample is updated in place,
+    /// so deposits and withdrawals can't flood the ring buffer.
+    pub fn record(&mut self, vault: &Vault, vault_equity: u64, now: i64) -> Result<()> {
+        validate!(
+            self.vault == vault.pubkey,
+            ErrorCode::InvalidNavHistory,
+            "nav history is for vault {:?}",
+            self.vault
+        )?;
+
+        let sample = NavSample {
+            total_shares: vault.total_shares,
+            ts: now,
+            equity: vault_equity,
+            shares_base: vault.shares_base,
+            padding: [0; 12],
+        };
+        if self.sample_due(now, MIN_NAV_SAMPLE_INTERVAL) {
+            self.append(sample);
+        } else {
+            self.update_latest(sample);
+        }
+
+        emit!(VaultRecord {
+            ts: now,
+            vault: vault.pubkey,
+            vault_equity,
+            total_shares: vault.total_shares,
+            investor_shares: vault.investor_shares,
+            shares_base: vault.shares_base,
+        });
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_record_interval() {
+        let now = 1000;
+        let vault = Vault::default();
+        let mut nav_history = NavHistory::zeroed();
+
+        nav_history.record(&vault, 100, now).unwrap();
+        assert_eq!(nav_history.len, 1);
+        assert_eq!(nav_history.last_sample_ts, now);
+
+        // samples within the interval overwrite the latest one
+        for i in 1..NAV_HISTORY_LENGTH as i64 * 2 {
+            nav_history.record(&vault, 100 + i as u64, now + i).unwrap();
+        }
+        assert_eq!(nav_history.len, 1);
+        assert_eq!(nav_history.last_sample_ts, now);
+        let latest = nav_history.latest().unwrap();
+        assert_eq!(latest.ts, now + NAV_HISTORY_LENGTH as i64 * 2 - 1);
+        assert_eq!(latest.equity, 100 + NAV_HISTORY_LENGTH as u64 * 2 - 1);
+
+        // the interval is measured from when the latest sample was appended, not last updated
+        let now = now + MIN_NAV_SAMPLE_INTERVAL;
+        nav_history.record(&vault, 500, now).unwrap();
+        assert_eq!(nav_history.len, 2);
+        assert_eq!(nav_history.last_sample_ts, now);
+        assert_eq!(
+            nav_history.iter().map(|s| s.equity).collect::<Vec<_>>(),
+            vec![100 + NAV_HISTORY_LENGTH as u64 * 2 - 1, 500]
+        );
+
+        assert!(nav_history
+            .check_sample_interval(now + MIN_NAV_SAMPLE_INTERVAL - 1, MIN_NAV_SAMPLE_INTERVAL)
+            .is_err());
+        assert!(nav_history
+            .check_sample_interval(now + MIN_NAV_SAMPLE_INTERVAL, MIN_NAV_SAMPLE_INTERVAL)
+            .is_ok());
+    }
+}
@@ -0,0 +1,87 @@
+use anchor_lang::prelude::*;
+use drift_macros::assert_no_slop;
+use static_assertions::const_assert_eq;
+
+use crate::constants::PRICE_PRECISION_U64;
+use crate::error::{ErrorCode, VaultResult};
+use crate::state::Vault;
+use crate::{validate, Size};
+
+/// Latest vault share price, so other programs can price vault shares
+/// without recomputing equity over every market the vault trades.
+/// Refreshed by the permissionless `update_share_price` crank.
+#[assert_no_slop]
+#[account(zero_copy(unsafe))]
+#[derive(Default, Eq, PartialEq, Debug)]
+#[repr(C)]
+pub struct SharePrice {
+    /// The vault priced
+    pub vault: Pubkey,
+    /// Vault total shares at the last update
+    pub total_shares: u128,
+    /// Vault equity in USDC at the last update
+    pub equity: u64,
+    /// USDC per share, precision: PRICE_PRECISION
+    pub price: u64,
+    /// Slot of the last update
+    pub slot: u64,
+    /// Unix timestamp of the last update
+    pub ts: i64,
+    /// Number of slots after the last update before the price is considered stale
+    pub max_staleness_slots: u64,
+    /// Vault shares base at the last update
+    pub shares_base: u32,
+    pub padding1: u32,
+    pub padding: [u64; 4],
+}
+
+impl SharePrice {
+    pub fn seeds(vault: &Pubkey) -> [&[u8]; 2] {
+        [b"share_price", vault.as_ref()]
+    }
+}
+
+impl Size for SharePrice {
+    const SIZE: usize = 128 + 8;
+}
+const_assert_eq!(SharePrice::SIZE, std::mem::size_of::<SharePrice>() + 8);
+
+impl SharePrice {
+    pub fn update(&mut self, vault: &Vault, vault_equity: u64, slot: u64, now: i64) -> Result<()> {
+        validate!(
+            self.vault == vault.pubkey,
+            ErrorCode::InvalidSharePrice,
+            "share price is for vault {:?}",
+            self.vault
+        )?;
+
+        let price = vault
+            .share_price(vault_equity)?
+            .unwrap_or(PRICE_PRECISION_U64);
+
+        self.total_shares = vault.total_shares;
+        self.equity = vault_equity;
+        self.price = price;
+        self.shares_base = vault.shares_base;
+        self.slot = slot;
+        self.ts = now;
+
+        Ok(())
+    }
+
+    pub fn is_stale(&self, current_slot: u64) -> bool {
+        current_slot.saturating_sub(self.slot) > self.max_staleness_slots
+    }
+
+    /// Share price for consumers, erroring if the last update is older than `max_staleness_slots`.
+    pub fn get_price(&self, current_slot: u64) -> VaultResult<u64> {
+        validate!(
+            !self.is_stale(current_slot),
+            ErrorCode::StaleSharePrice,
+            "share price last updated at slot {}, current slot {}",
+            self.slot,
+            current_slot
+        )?;
+        Ok(self.price)
+    }
+}
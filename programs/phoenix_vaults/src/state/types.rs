@@ -1,5 +1,34 @@
 use anchor_lang::prelude::*;
 
+/// Lifecycle of a [`Vault`](crate::state::Vault).
+/// Every instruction checks the state with `Vault::check_state` and rejects actions the state doesn't allow.
+#[derive(Default, Debug, Clone, Copy, AnchorSerialize, AnchorDeserialize, PartialEq, Eq)]
+pub enum VaultState {
+    /// Normal operation
+    #[default]
+    Active,
+    /// Deposits are accepted until the fundraising deadline, but the delegate can't trade.
+    /// At the deadline the vault becomes active if the soft cap is met, otherwise it closes so investors can be refunded.
+    Fundraising,
+    /// New deposits and risk-increasing orders are blocked, and any investor can liquidate without waiting the redeem period.
+    WindDown,
+    /// Only final withdrawals are allowed
+    Closed,
+}
+
+/// Result of the take order a liquidation places on a market, measured from the vault trader state.
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LiquidationFill {
+    pub base_lots_sold: u64,
+    /// Quote units per raw base unit (multiplied by PRICE_PRECISION) before fees
+    pub avg_price: u64,
+    /// Quote atoms received after fees
+    pub quote_received: u64,
+    /// Taker fees in quote atoms
+    pub fees: u64,
+}
+
+#[derive(Default)]
 pub struct VaultFee {
     pub management_fee_payment: i64,
     pub management_fee_shares: i64,
@@ -7,6 +36,28 @@ pub struct VaultFee {
     pub protocol_fee_shares: i64,
 }
 
+/// Return data of the `preview_deposit` instruction
+#[derive(Default, Debug, Clone, Copy, AnchorSerialize, AnchorDeserialize, PartialEq, Eq)]
+pub struct DepositPreview {
+    /// Vault shares minted for the deposit
+    pub shares: u128,
+    /// Entry fee withheld from the deposit
+    pub entry_fee: u64,
+}
+
+/// Return data of the `preview_withdraw` instruction
+#[derive(Default, Debug, Clone, Copy, AnchorSerialize, AnchorDeserialize, PartialEq, Eq)]
+pub struct WithdrawPreview {
+    /// Vault shares burned by the withdrawal
+    pub shares: u128,
+    /// USDC paid to the investor after profit share and the exit fee
+    pub amount: u64,
+    /// Exit fee withheld from the withdrawal
+    pub exit_fee: u64,
+    /// Early exit penalty withheld from a withdrawal requested during the investor's lockup
+    pub lockup_penalty: u64,
+}
+
 #[derive(Debug, Clone, Copy, AnchorSerialize, AnchorDeserialize, PartialEq, Eq)]
 pub struct MarketTransferParams {
     pub quote_lots: u64,
@@ -1,10 +1,16 @@
 use crate::constants::{
-    ONE_YEAR, PERCENTAGE_PRECISION, PERCENTAGE_PRECISION_I128, TIME_FOR_LIQUIDATION,
+    BPS_PRECISION, DEFAULT_LIQUIDATION_WINDOW, MIN_LIQUIDATION_WINDOW, ONE_YEAR,
+    PERCENTAGE_PRECISION, PERCENTAGE_PRECISION_I128, PRICE_PRECISION,
 };
 use crate::error::{ErrorCode, VaultResult};
-use crate::math::{amount_to_shares, calculate_rebase_info, shares_to_amount, Cast, SafeMath};
+use crate::math::{
+    amount_to_shares, calculate_rebase_info, get_proportion_u128, shares_to_amount, Cast, SafeMath,
+};
 use crate::state::withdraw_request::WithdrawRequest;
-use crate::state::{InvestorAction, InvestorRecord, MarketPosition, VaultFee, WithdrawUnit};
+use crate::state::{
+    AppointLiquidatorRecord, EpochRecord, FeeRecord, ManagerAction, ManagerRecord, MarketPosition,
+    ProtocolAction, ProtocolRecord, VaultFee, VaultState, WithdrawUnit,
+};
 use crate::{validate, Size};
 use anchor_lang::prelude::*;
 use drift_macros::assert_no_slop;
@@ -117,6 +123,77 @@ pub struct Vault {
     /// The bump for the vault PDA
     pub bump: u8,
     pub padding: [u8; 6],
+
+    /// Shares requested for withdraw during the current epoch, burned by the next `settle_epoch`.
+    pub epoch_pending_withdraw_shares: u128,
+    /// Shares minted by the last `settle_epoch` that investors have yet to claim.
+    pub epoch_unclaimed_deposit_shares: u128,
+    /// Shares burned by the last `settle_epoch` that investors have yet to claim the value of.
+    pub epoch_unclaimed_withdraw_shares: u128,
+    /// The length (in seconds) of a subscription and redemption window.
+    /// If zero, deposits and withdraw requests are priced immediately at the current equity.
+    /// This can only be set when the vault is initialized.
+    pub epoch_duration: i64,
+    /// When the current epoch started
+    pub epoch_start_ts: i64,
+    /// The number of epochs settled since the vault was initialized
+    pub current_epoch: u64,
+    /// USDC deposited during the current epoch, not yet priced into shares.
+    pub epoch_pending_deposits: u64,
+    /// USDC deposits priced by the last `settle_epoch` that investors have yet to claim shares for.
+    pub epoch_unclaimed_deposits: u64,
+    /// Value of the withdraw shares burned by the last `settle_epoch` that investors have yet to claim.
+    pub epoch_unclaimed_withdraw_value: u64,
+    /// USDC priced by a `settle_epoch` and owed to investors until they withdraw it.
+    pub epoch_redemptions_owed: u64,
+    /// The fee (in basis points) withheld from investor deposits.
+    /// It stays in the vault to accrue to the remaining shareholders.
+    /// This is only updatable to lesser values.
+    pub entry_fee_bps: u16,
+    /// The fee (in basis points) withheld from investor withdrawals.
+    /// It stays in the vault to accrue to the remaining shareholders.
+    /// This is only updatable to lesser values.
+    pub exit_fee_bps: u16,
+    /// The drawdown of the share price from its high-water mark (multiplied by PERCENTAGE_PRECISION)
+    /// that halts delegate trading. If zero, trading is never halted.
+    pub max_drawdown: u32,
+    /// The highest share price (USDC per share multiplied by PRICE_PRECISION) sampled by `update_nav_history`.
+    pub high_water_mark_price: u64,
+    /// Set when the share price drawdown exceeds `max_drawdown`.
+    /// The delegate can't place orders or deposit to markets, and investors can withdraw without waiting the redeem period,
+    /// until the manager and protocol call `resume_trading`.
+    pub trading_halted: bool,
+    /// The lifecycle state. Use [`Vault::state`] since fundraising ends at its deadline without an instruction.
+    pub state: VaultState,
+    pub padding2: [u8; 2],
+    /// The minimum percentage of `total_shares` (multiplied by PERCENTAGE_PRECISION) the manager must hold
+    /// while investor shares are outstanding. This is only updatable to greater values.
+    pub manager_min_stake: u32,
+    /// Total deposits required by the fundraising deadline for the vault to become active.
+    /// If zero, the vault is active from initialization.
+    pub fundraising_soft_cap: u64,
+    /// When fundraising ends
+    pub fundraising_deadline: i64,
+    /// The vault equity snapshotted by `finalize_wind_down`, which investors claim pro-rata once the vault is closed.
+    pub final_equity: u64,
+    /// The total shares snapshotted alongside `final_equity`. Zero until the wind-down is finalized.
+    pub final_total_shares: u128,
+    /// The percentage of equity (multiplied by PERCENTAGE_PRECISION) kept in the vault USDC token account
+    /// on top of outstanding withdraw requests, which `market_deposit` can't draw from.
+    /// This is only updatable to greater values.
+    pub liquidity_buffer: u32,
+    /// The penalty (in basis points) withheld from withdrawals requested during an investor's lockup.
+    /// It stays in the vault to accrue to the remaining shareholders.
+    /// If zero, withdraw requests during the lockup are rejected.
+    pub lockup_penalty_bps: u16,
+    pub padding3: [u8; 2],
+    /// Seconds from an investor's first deposit during which withdraw requests are penalized or rejected.
+    /// If zero, there is no lockup. This is only updatable to lesser values.
+    pub lockup_duration: i64,
+    /// Seconds an appointed liquidator has to liquidate before the vault can exit liquidation
+    /// or another liquidator can be appointed. This is only updatable to lesser values.
+    pub liquidation_window: i64,
+    pub padding4: [u64; 1],
 }
 
 impl Vault {
@@ -125,13 +202,21 @@ impl Vault {
     }
 }
 
+// vaults created at the original 616 + 64 * 8 + 8 bytes are grown by `migrate_vault`
 impl Size for Vault {
-    const SIZE: usize = 616 + 64 * 8 + 8;
+    const SIZE: usize = 616 + 64 * 8 + 200 + 8;
 }
 const_assert_eq!(Vault::SIZE, std::mem::size_of::<Vault>() + 8);
 
 impl Vault {
     pub fn apply_fee(&mut self, vault_equity: u64, now: i64) -> Result<VaultFee> {
+        self.update_state(now);
+        // fees only accrue while the vault is trading
+        if !matches!(self.state, VaultState::Active | VaultState::WindDown) {
+            self.last_fee_update_ts = now;
+            return Ok(VaultFee::default());
+        }
+
         let depositor_equity =
             shares_to_amount(self.investor_shares, self.total_shares, vault_equity)?
                 .cast::<i128>()?;
@@ -319,6 +404,43 @@ impl Vault {
         })
     }
 
+    /// Apply the time-based management and protocol fees outside of any user action.
+    pub fn crystallize_fees(&mut self, vault_equity: u64, now: i64) -> Result<()> {
+        self.apply_rebase(vault_equity)?;
+
+        let total_vault_shares_before = self.total_shares;
+        let user_vault_shares_before = self.investor_shares;
+        let manager_shares_before = self.get_manager_shares()?;
+        let protocol_shares_before = self.get_protocol_shares();
+
+        let VaultFee {
+            management_fee_payment,
+            management_fee_shares,
+            protocol_fee_payment,
+            protocol_fee_shares,
+        } = self.apply_fee(vault_equity, now)?;
+
+        emit!(FeeRecord {
+            ts: now,
+            vault: self.pubkey,
+            vault_equity,
+            total_vault_shares_before,
+            user_vault_shares_before,
+            manager_shares_before,
+            protocol_shares_before,
+            total_vault_shares_after: self.total_shares,
+            user_vault_shares_after: self.investor_shares,
+            manager_shares_after: self.get_manager_shares()?,
+            protocol_shares_after: self.get_protocol_shares(),
+            protocol_fee: protocol_fee_payment,
+            protocol_fee_shares,
+            management_fee: management_fee_payment,
+            management_fee_shares,
+        });
+
+        Ok(())
+    }
+
     pub fn get_manager_shares(&self) -> VaultResult<u128> {
         self.total_shares
             .safe_sub(self.investor_shares)?
@@ -329,6 +451,38 @@ impl Vault {
         self.protocol_profit_and_fee_shares
     }
 
+    /// The manager must keep `manager_min_stake` of the shares left once `burned_shares` are withdrawn,
+    /// unless every investor has exited or the vault is closed and no longer carries risk.
+    pub fn check_manager_min_stake(
+        &self,
+        manager_shares: u128,
+        burned_shares: u128,
+    ) -> VaultResult {
+        if self.manager_min_stake == 0
+            || self.investor_shares == 0
+            || self.state == VaultState::Closed
+        {
+            return Ok(());
+        }
+
+        let total_shares = self.total_shares.safe_sub(burned_shares)?;
+        let min_manager_shares = get_proportion_u128(
+            total_shares,
+            self.manager_min_stake.cast()?,
+            PERCENTAGE_PRECISION,
+        )?;
+        validate!(
+            manager_shares >= min_manager_shares,
+            ErrorCode::ManagerStakeTooLow,
+            "manager shares {} < minimum stake {} of total shares {}",
+            manager_shares,
+            min_manager_shares,
+            total_shares
+        )?;
+
+        Ok(())
+    }
+
     pub fn get_profit_share(&self) -> VaultResult<u32> {
         self.profit_share.safe_add(self.protocol_profit_share)
     }
@@ -346,6 +500,23 @@ impl Vault {
                 self.protocol_profit_and_fee_shares = self
                     .protocol_profit_and_fee_shares
                     .safe_div(_rebase_divisor)?;
+                self.epoch_pending_withdraw_shares = self
+                    .epoch_pending_withdraw_shares
+                    .safe_div(_rebase_divisor)?;
+                self.epoch_unclaimed_deposit_shares = self
+                    .epoch_unclaimed_deposit_shares
+                    .safe_div(_rebase_divisor)?;
+                self.epoch_unclaimed_withdraw_shares = self
+                    .epoch_unclaimed_withdraw_shares
+                    .safe_div(_rebase_divisor)?;
+                self.final_total_shares = self.final_total_shares.safe_div(_rebase_divisor)?;
+
+                // each share is now worth `_rebase_divisor` of the old shares
+                self.high_water_mark_price = self
+                    .high_water_mark_price
+                    .cast::<u128>()?
+                    .safe_mul(_rebase_divisor)?
+                    .cast()?;
 
                 rebase_divisor = Some(_rebase_divisor);
 
@@ -385,29 +556,26 @@ impl Vault {
         let vault_shares_after = self.get_manager_shares()?;
         let protocol_shares_after = self.get_protocol_shares();
 
-        emit!(InvestorRecord {
+        emit!(ManagerRecord {
             ts: now,
             vault: self.pubkey,
-            depositor_authority: self.manager,
-            action: InvestorAction::Deposit,
-            amount: 0,
-            usdc_mint: self.usdc_mint,
-            sol_mint: self.sol_mint,
+            manager: self.manager,
+            action: ManagerAction::Deposit,
+            amount,
+            n_shares,
             vault_equity_before: vault_equity,
-            vault_shares_before,
-            user_vault_shares_before,
+            manager_shares_before: vault_shares_before,
             total_vault_shares_before,
-            vault_shares_after,
+            user_vault_shares_before,
+            protocol_shares_before,
+            manager_shares_after: vault_shares_after,
             total_vault_shares_after: self.total_shares,
             user_vault_shares_after: self.investor_shares,
-            protocol_profit_share: 0,
+            protocol_shares_after,
             protocol_fee: protocol_fee_payment,
             protocol_fee_shares,
-            manager_profit_share: 0,
             management_fee: management_fee_payment,
             management_fee_shares,
-            protocol_shares_before,
-            protocol_shares_after,
         });
 
         Ok(())
@@ -451,6 +619,7 @@ impl Vault {
             n_shares,
             vault_shares_before,
         )?;
+        self.check_manager_min_stake(vault_shares_before.safe_sub(n_shares)?, n_shares)?;
 
         let total_vault_shares_before = self.total_shares;
         let user_vault_shares_before = self.investor_shares;
@@ -467,29 +636,26 @@ impl Vault {
         let vault_shares_after: u128 = self.get_manager_shares()?;
         let protocol_shares_after: u128 = self.get_protocol_shares();
 
-        emit!(InvestorRecord {
+        emit!(ManagerRecord {
             ts: now,
             vault: self.pubkey,
-            depositor_authority: self.manager,
-            action: InvestorAction::WithdrawRequest,
-            amount: self.last_manager_withdraw_request.value,
-            usdc_mint: self.usdc_mint,
-            sol_mint: self.sol_mint,
+            manager: self.manager,
+            action: ManagerAction::WithdrawRequest,
+            amount: withdraw_value,
+            n_shares,
             vault_equity_before: vault_equity,
-            vault_shares_before,
-            user_vault_shares_before,
+            manager_shares_before: vault_shares_before,
             total_vault_shares_before,
-            vault_shares_after,
+            user_vault_shares_before,
+            protocol_shares_before,
+            manager_shares_after: vault_shares_after,
             total_vault_shares_after: self.total_shares,
             user_vault_shares_after: self.investor_shares,
-            protocol_profit_share: 0,
+            protocol_shares_after,
             protocol_fee: protocol_fee_payment,
             protocol_fee_shares,
-            manager_profit_share: 0,
             management_fee: management_fee_payment,
             management_fee_shares,
-            protocol_shares_before,
-            protocol_shares_after,
         });
 
         Ok(())
@@ -523,29 +689,26 @@ impl Vault {
         let vault_shares_after = self.get_manager_shares()?;
         let protocol_shares_after: u128 = self.get_protocol_shares();
 
-        emit!(InvestorRecord {
+        emit!(ManagerRecord {
             ts: now,
             vault: self.pubkey,
-            depositor_authority: self.manager,
-            action: InvestorAction::CancelWithdrawRequest,
-            amount: 0,
-            usdc_mint: self.usdc_mint,
-            sol_mint: self.sol_mint,
+            manager: self.manager,
+            action: ManagerAction::CancelWithdrawRequest,
+            amount: self.last_manager_withdraw_request.value,
+            n_shares: vault_shares_lost,
             vault_equity_before: vault_equity,
-            vault_shares_before,
-            user_vault_shares_before,
+            manager_shares_before: vault_shares_before,
             total_vault_shares_before,
-            vault_shares_after,
+            user_vault_shares_before,
+            protocol_shares_before,
+            manager_shares_after: vault_shares_after,
             total_vault_shares_after: self.total_shares,
             user_vault_shares_after: self.investor_shares,
-            protocol_profit_share: 0,
+            protocol_shares_after,
             protocol_fee: protocol_fee_payment,
             protocol_fee_shares,
-            manager_profit_share: 0,
             management_fee: management_fee_payment,
             management_fee_shares,
-            protocol_shares_before,
-            protocol_shares_after,
         });
 
         self.total_withdraw_requested = self
@@ -611,29 +774,26 @@ impl Vault {
         let vault_shares_after = self.get_manager_shares()?;
         let protocol_shares_after: u128 = self.get_protocol_shares();
 
-        emit!(InvestorRecord {
+        emit!(ManagerRecord {
             ts: now,
             vault: self.pubkey,
-            depositor_authority: self.manager,
-            action: InvestorAction::Withdraw,
-            amount: 0,
-            usdc_mint: self.usdc_mint,
-            sol_mint: self.sol_mint,
+            manager: self.manager,
+            action: ManagerAction::Withdraw,
+            amount: n_tokens,
+            n_shares,
             vault_equity_before: vault_equity,
-            vault_shares_before,
-            user_vault_shares_before,
+            manager_shares_before: vault_shares_before,
             total_vault_shares_before,
-            vault_shares_after,
+            user_vault_shares_before,
+            protocol_shares_before,
+            manager_shares_after: vault_shares_after,
             total_vault_shares_after: self.total_shares,
             user_vault_shares_after: self.investor_shares,
-            protocol_profit_share: 0,
+            protocol_shares_after,
             protocol_fee: protocol_fee_payment,
             protocol_fee_shares,
-            manager_profit_share: 0,
             management_fee: management_fee_payment,
             management_fee_shares,
-            protocol_shares_before,
-            protocol_shares_after,
         });
 
         self.total_withdraw_requested = self
@@ -650,13 +810,43 @@ impl Vault {
         self.liquidator != Pubkey::default()
     }
 
+    /// Seconds a liquidator has to liquidate before another can be appointed.
+    /// Vaults initialized before the window was configurable use the default.
+    pub fn get_liquidation_window(&self) -> i64 {
+        if self.liquidation_window == 0 {
+            DEFAULT_LIQUIDATION_WINDOW
+        } else {
+            self.liquidation_window
+        }
+    }
+
+    /// Shortens the liquidation window. Rejected while a liquidator is appointed,
+    /// so the window they were appointed under can't shrink beneath them.
+    pub fn update_liquidation_window(&mut self, liquidation_window: i64) -> VaultResult {
+        validate!(
+            !self.in_liquidation(),
+            ErrorCode::VaultInLiquidation,
+            "liquidation window can't change while the vault is in liquidation"
+        )?;
+        validate!(
+            liquidation_window >= MIN_LIQUIDATION_WINDOW
+                && liquidation_window < self.get_liquidation_window(),
+            ErrorCode::InvalidVaultUpdate,
+            "new liquidation window must be less than existing liquidation window and >= {} seconds",
+            MIN_LIQUIDATION_WINDOW
+        )?;
+        self.liquidation_window = liquidation_window;
+
+        Ok(())
+    }
+
     pub fn liquidation_expired(&self, now: i64) -> bool {
-        now.saturating_sub(self.liquidation_start_ts) > TIME_FOR_LIQUIDATION
+        now.saturating_sub(self.liquidation_start_ts) > self.get_liquidation_window()
     }
 
     pub fn check_can_exit_liquidation(&self, now: i64) -> VaultResult {
         validate!(
-            now.saturating_sub(self.liquidation_start_ts) > TIME_FOR_LIQUIDATION,
+            now.saturating_sub(self.liquidation_start_ts) > self.get_liquidation_window(),
             ErrorCode::VaultInLiquidation,
             "vault is in liquidation"
         )?;
@@ -672,7 +862,7 @@ impl Vault {
         )?;
 
         validate!(
-            now.saturating_sub(self.liquidation_start_ts) <= TIME_FOR_LIQUIDATION,
+            now.saturating_sub(self.liquidation_start_ts) <= self.get_liquidation_window(),
             ErrorCode::LiquidationExpired,
             "Investor liquidation expired"
         )?;
@@ -692,7 +882,7 @@ impl Vault {
         )?;
 
         validate!(
-            now.saturating_sub(self.liquidation_start_ts) > TIME_FOR_LIQUIDATION,
+            now.saturating_sub(self.liquidation_start_ts) > self.get_liquidation_window(),
             ErrorCode::DelegateNotAvailableForLiquidation,
             "vault liquidation not expired"
         )?;
@@ -703,6 +893,13 @@ impl Vault {
     pub fn set_liquidation_delegate(&mut self, liquidation_delegate: Pubkey, now: i64) {
         self.liquidator = liquidation_delegate;
         self.liquidation_start_ts = now;
+
+        emit!(AppointLiquidatorRecord {
+            ts: now,
+            vault: self.pubkey,
+            liquidator: liquidation_delegate,
+            liquidation_window: self.get_liquidation_window(),
+        });
     }
 
     pub fn reset_liquidation_delegate(&mut self) {
@@ -725,7 +922,7 @@ impl Vault {
             protocol_fee_shares,
         } = self.apply_fee(vault_equity, now)?;
 
-        let vault_shares_before: u128 = self.get_manager_shares()?;
+        let manager_shares_before: u128 = self.get_manager_shares()?;
         let protocol_shares_before: u128 = self.get_protocol_shares();
 
         let (withdraw_value, n_shares) = withdraw_unit.get_withdraw_value_and_shares(
@@ -754,32 +951,29 @@ impl Vault {
         )?;
         self.total_withdraw_requested = self.total_withdraw_requested.safe_add(withdraw_value)?;
 
-        let vault_shares_after: u128 = self.get_manager_shares()?;
+        let manager_shares_after: u128 = self.get_manager_shares()?;
         let protocol_shares_after: u128 = self.get_protocol_shares();
 
-        emit!(InvestorRecord {
+        emit!(ProtocolRecord {
             ts: now,
             vault: self.pubkey,
-            depositor_authority: self.manager,
-            action: InvestorAction::WithdrawRequest,
-            amount: self.last_manager_withdraw_request.value,
-            usdc_mint: self.usdc_mint,
-            sol_mint: self.sol_mint,
+            protocol: self.protocol,
+            action: ProtocolAction::WithdrawRequest,
+            amount: withdraw_value,
+            n_shares,
             vault_equity_before: vault_equity,
-            vault_shares_before,
-            user_vault_shares_before,
+            protocol_shares_before,
             total_vault_shares_before,
-            vault_shares_after,
+            user_vault_shares_before,
+            manager_shares_before,
+            protocol_shares_after,
             total_vault_shares_after: self.total_shares,
             user_vault_shares_after: self.investor_shares,
-            protocol_profit_share: 0,
+            manager_shares_after,
             protocol_fee: protocol_fee_payment,
             protocol_fee_shares,
-            manager_profit_share: 0,
             management_fee: management_fee_payment,
             management_fee_shares,
-            protocol_shares_before,
-            protocol_shares_after,
         });
 
         Ok(())
@@ -788,7 +982,7 @@ impl Vault {
     pub fn protocol_cancel_withdraw_request(&mut self, vault_equity: u64, now: i64) -> Result<()> {
         self.apply_rebase(vault_equity)?;
 
-        let vault_shares_before: u128 = self.get_manager_shares()?;
+        let manager_shares_before: u128 = self.get_manager_shares()?;
         let total_vault_shares_before = self.total_shares;
         let user_vault_shares_before = self.investor_shares;
         let protocol_shares_before: u128 = self.get_protocol_shares();
@@ -810,44 +1004,41 @@ impl Vault {
             .protocol_profit_and_fee_shares
             .safe_sub(vault_shares_lost)?;
 
-        let vault_shares_after = self.get_manager_shares()?;
+        let manager_shares_after = self.get_manager_shares()?;
         let protocol_shares_after: u128 = self.get_protocol_shares();
 
-        self.total_withdraw_requested = self
-            .total_withdraw_requested
-            .safe_sub(self.last_protocol_withdraw_request.value)?;
-        self.last_protocol_withdraw_request.reset(now)?;
-
-        emit!(InvestorRecord {
+        emit!(ProtocolRecord {
             ts: now,
             vault: self.pubkey,
-            depositor_authority: self.manager,
-            action: InvestorAction::CancelWithdrawRequest,
-            amount: 0,
-            usdc_mint: self.usdc_mint,
-            sol_mint: self.sol_mint,
+            protocol: self.protocol,
+            action: ProtocolAction::CancelWithdrawRequest,
+            amount: self.last_protocol_withdraw_request.value,
+            n_shares: vault_shares_lost,
             vault_equity_before: vault_equity,
-            vault_shares_before,
-            user_vault_shares_before,
+            protocol_shares_before,
             total_vault_shares_before,
-            vault_shares_after,
+            user_vault_shares_before,
+            manager_shares_before,
+            protocol_shares_after,
             total_vault_shares_after: self.total_shares,
             user_vault_shares_after: self.investor_shares,
-            protocol_profit_share: 0,
+            manager_shares_after,
             protocol_fee: protocol_fee_payment,
             protocol_fee_shares,
-            manager_profit_share: 0,
             management_fee: management_fee_payment,
             management_fee_shares,
-            protocol_shares_before,
-            protocol_shares_after,
         });
 
+        self.total_withdraw_requested = self
+            .total_withdraw_requested
+            .safe_sub(self.last_protocol_withdraw_request.value)?;
+        self.last_protocol_withdraw_request.reset(now)?;
+
         Ok(())
     }
 
     pub fn protocol_withdraw(&mut self, vault_equity: u64, now: i64) -> Result<(u64, bool)> {
-        self.last_manager_withdraw_request
+        self.last_protocol_withdraw_request
             .check_redeem_period_finished(self, now)?;
 
         self.apply_rebase(vault_equity)?;
@@ -859,7 +1050,7 @@ impl Vault {
             protocol_fee_shares,
         } = self.apply_fee(vault_equity, now)?;
 
-        let vault_shares_before: u128 = self.get_protocol_shares();
+        let manager_shares_before: u128 = self.get_manager_shares()?;
         let total_vault_shares_before = self.total_shares;
         let user_vault_shares_before = self.investor_shares;
         let protocol_shares_before: u128 = self.get_protocol_shares();
@@ -878,7 +1069,7 @@ impl Vault {
         let n_tokens = amount.min(self.last_protocol_withdraw_request.value);
 
         validate!(
-            vault_shares_before >= n_shares,
+            protocol_shares_before >= n_shares,
             ErrorCode::InsufficientVaultShares
         )?;
 
@@ -889,8 +1080,8 @@ impl Vault {
         validate!(
             protocol_shares_before >= n_shares,
             ErrorCode::InvalidVaultWithdrawSize,
-            "vault_shares_before={} < n_shares={}",
-            vault_shares_before,
+            "protocol_shares_before={} < n_shares={}",
+            protocol_shares_before,
             n_shares
         )?;
 
@@ -898,32 +1089,29 @@ impl Vault {
         self.protocol_profit_and_fee_shares =
             self.protocol_profit_and_fee_shares.safe_sub(n_shares)?;
 
-        let vault_shares_after = self.get_protocol_shares();
+        let manager_shares_after = self.get_manager_shares()?;
         let protocol_shares_after = self.get_protocol_shares();
 
-        emit!(InvestorRecord {
+        emit!(ProtocolRecord {
             ts: now,
             vault: self.pubkey,
-            depositor_authority: self.manager,
-            action: InvestorAction::Withdraw,
-            amount: 0,
-            usdc_mint: self.usdc_mint,
-            sol_mint: self.sol_mint,
+            protocol: self.protocol,
+            action: ProtocolAction::Withdraw,
+            amount: n_tokens,
+            n_shares,
             vault_equity_before: vault_equity,
-            vault_shares_before,
-            user_vault_shares_before,
+            protocol_shares_before,
             total_vault_shares_before,
-            vault_shares_after,
+            user_vault_shares_before,
+            manager_shares_before,
+            protocol_shares_after,
             total_vault_shares_after: self.total_shares,
             user_vault_shares_after: self.investor_shares,
-            protocol_profit_share: 0,
+            manager_shares_after,
             protocol_fee: protocol_fee_payment,
             protocol_fee_shares,
-            manager_profit_share: 0,
             management_fee: management_fee_payment,
             management_fee_shares,
-            protocol_shares_before,
-            protocol_shares_after,
         });
 
         self.total_withdraw_requested = self
@@ -936,6 +1124,338 @@ impl Vault {
         Ok((n_tokens, finishing_liquidation))
     }
 
+    pub fn entry_fee(&self, amount: u64) -> VaultResult<u64> {
+        amount
+            .cast::<u128>()?
+            .safe_mul(self.entry_fee_bps.cast()?)?
+            .safe_div(BPS_PRECISION)?
+            .cast()
+    }
+
+    pub fn exit_fee(&self, amount: u64) -> VaultResult<u64> {
+        amount
+            .cast::<u128>()?
+            .safe_mul(self.exit_fee_bps.cast()?)?
+            .safe_div(BPS_PRECISION)?
+            .cast()
+    }
+
+    /// Whether `ts` falls within the lockup of an investor whose first deposit was at `first_deposit_ts`.
+    pub fn in_lockup(&self, first_deposit_ts: i64, ts: i64) -> bool {
+        self.lockup_duration > 0
+            && first_deposit_ts > 0
+            && ts < first_deposit_ts.saturating_add(self.lockup_duration)
+    }
+
+    pub fn lockup_penalty(&self, amount: u64) -> VaultResult<u64> {
+        amount
+            .cast::<u128>()?
+            .safe_mul(self.lockup_penalty_bps.cast()?)?
+            .safe_div(BPS_PRECISION)?
+            .cast()
+    }
+
+    pub fn epoch_mode(&self) -> bool {
+        self.epoch_duration > 0
+    }
+
+    pub fn epoch_ended(&self, now: i64) -> bool {
+        now.saturating_sub(self.epoch_start_ts) >= self.epoch_duration
+    }
+
+    /// USDC in the vault token account that is not part of the vault equity:
+    /// deposits waiting to be priced and redemptions already priced by `settle_epoch`.
+    pub fn epoch_reserved_usdc(&self) -> u64 {
+        self.epoch_pending_deposits
+            .saturating_add(self.epoch_redemptions_owed)
+    }
+
+    /// Market deposits can't draw on the USDC reserved for epoch settlement.
+    pub fn check_epoch_reserved_usdc(&self, vault_usdc: u64) -> VaultResult {
+        let reserved = self.epoch_reserved_usdc();
+        validate!(
+            vault_usdc >= reserved,
+            ErrorCode::EpochReservedUsdc,
+            "vault USDC {} is below the {} reserved for epoch settlement",
+            vault_usdc,
+            reserved
+        )?;
+        Ok(())
+    }
+
+    /// The part of `withdraw_value` the vault USDC token account can't pay after reserving epoch USDC.
+    pub fn unfulfilled_withdraw_value(&self, withdraw_value: u64, vault_usdc: u64) -> u64 {
+        withdraw_value.saturating_sub(vault_usdc.saturating_sub(self.epoch_reserved_usdc()))
+    }
+
+    /// The lifecycle state at `now`, which ends fundraising once its deadline has passed.
+    pub fn state(&self, now: i64) -> VaultState {
+        if self.state == VaultState::Fundraising && now >= self.fundraising_deadline {
+            if self.fundraising_soft_cap_met() {
+                VaultState::Active
+            } else {
+                VaultState::Closed
+            }
+        } else {
+            self.state
+        }
+    }
+
+    /// `net_deposits` already includes the manager's deposits.
+    pub fn fundraising_soft_cap_met(&self) -> bool {
+        self.net_deposits >= 0 && self.net_deposits as u64 >= self.fundraising_soft_cap
+    }
+
+    /// Persist the end of fundraising. Fees accrue from the deadline if the vault becomes active.
+    pub fn update_state(&mut self, now: i64) {
+        let state = self.state(now);
+        if self.state == VaultState::Fundraising && state != VaultState::Fundraising {
+            msg!("fundraising ended: {:?}", state);
+            self.state = state;
+            self.last_fee_update_ts = self.fundraising_deadline;
+        }
+    }
+
+    pub fn check_state(&self, now: i64, allowed: &[VaultState]) -> VaultResult {
+        let state = self.state(now);
+        validate!(
+            allowed.contains(&state),
+            ErrorCode::InvalidVaultState,
+            "vault is {:?}, expected one of {:?}",
+            state,
+            allowed
+        )?;
+        Ok(())
+    }
+
+    /// Begin winding down the vault. The delegate may only reduce positions from here on.
+    pub fn wind_down(&mut self, now: i64) -> VaultResult {
+        self.check_state(now, &[VaultState::Fundraising, VaultState::Active])?;
+        self.update_state(now);
+        self.state = VaultState::WindDown;
+        Ok(())
+    }
+
+    /// Snapshot the final equity once every market position has been converted to USDC and close the vault.
+    /// Fees accrue up to the snapshot, then no more are charged.
+    pub fn finalize_wind_down(&mut self, vault_equity: u64, now: i64) -> Result<()> {
+        self.check_state(now, &[VaultState::WindDown])?;
+        validate!(
+            self.positions.iter().all(|pos| pos.is_available()),
+            ErrorCode::WindDownPositionsOpen,
+            "every market position must be liquidated before the wind-down is finalized"
+        )?;
+
+        self.apply_rebase(vault_equity)?;
+        self.apply_fee(vault_equity, now)?;
+
+        self.final_equity = vault_equity;
+        self.final_total_shares = self.total_shares;
+        self.state = VaultState::Closed;
+
+        msg!(
+            "wind-down finalized: equity={}, total_shares={}",
+            self.final_equity,
+            self.final_total_shares
+        );
+
+        Ok(())
+    }
+
+    /// The equity of the outstanding shares at the ratio snapshotted by `finalize_wind_down`.
+    pub fn wind_down_equity(&self) -> Result<u64> {
+        validate!(
+            self.state == VaultState::Closed && self.final_total_shares > 0,
+            ErrorCode::WindDownNotFinalized,
+            "vault wind-down has not been finalized"
+        )?;
+        Ok(self
+            .final_equity
+            .cast::<u128>()?
+            .safe_mul(self.total_shares)?
+            .safe_div(self.final_total_shares)?
+            .cast()?)
+    }
+
+    /// USDC the vault token account must hold: every outstanding withdraw request, since requests mature
+    /// within the redeem period and investor requests are only tracked in aggregate,
+    /// deposits queued for an epoch, and `liquidity_buffer` of the equity.
+    pub fn liquidity_buffer(&self, vault_equity: u64) -> VaultResult<u64> {
+        let equity_buffer = get_proportion_u128(
+            vault_equity.cast()?,
+            self.liquidity_buffer.cast()?,
+            PERCENTAGE_PRECISION,
+        )?
+        .cast::<u64>()?;
+        self.total_withdraw_requested
+            .safe_add(self.epoch_pending_deposits)?
+            .safe_add(equity_buffer)
+    }
+
+    pub fn check_liquidity_buffer(&self, vault_usdc: u64, vault_equity: u64) -> VaultResult {
+        let buffer = self.liquidity_buffer(vault_equity)?;
+        validate!(
+            vault_usdc >= buffer,
+            ErrorCode::LiquidityBufferBreached,
+            "vault USDC {} is below the liquidity buffer {}",
+            vault_usdc,
+            buffer
+        )?;
+        Ok(())
+    }
+
+    /// USDC per share (multiplied by PRICE_PRECISION), or `None` if there are no shares.
+    pub fn share_price(&self, vault_equity: u64) -> VaultResult<Option<u64>> {
+        if self.total_shares == 0 {
+            return Ok(None);
+        }
+        let price = vault_equity
+            .cast::<u128>()?
+            .safe_mul(PRICE_PRECISION)?
+            .safe_div(self.total_shares)?
+            .cast()?;
+        Ok(Some(price))
+    }
+
+    /// Raise the share price high-water mark, or halt delegate trading if the drawdown from it exceeds `max_drawdown`.
+    /// Only `update_nav_history` calls this, so the spot equity of any other instruction can't move the high-water mark
+    /// or trip the halt, and samples are at least [`MIN_NAV_SAMPLE_INTERVAL`] apart.
+    ///
+    /// [`MIN_NAV_SAMPLE_INTERVAL`]: crate::constants::MIN_NAV_SAMPLE_INTERVAL
+    pub fn update_drawdown(&mut self, vault_equity: u64) -> Result<()> {
+        let price = match self.share_price(vault_equity)? {
+            Some(price) => price,
+            None => return Ok(()),
+        };
+
+        if price >= self.high_water_mark_price {
+            self.high_water_mark_price = price;
+            return Ok(());
+        }
+
+        if self.max_drawdown == 0 || self.trading_halted {
+            return Ok(());
+        }
+
+        let drawdown = self
+            .high_water_mark_price
+            .safe_sub(price)?
+            .cast::<u128>()?
+            .safe_mul(PERCENTAGE_PRECISION)?
+            .safe_div(self.high_water_mark_price.cast()?)?;
+        if drawdown >= self.max_drawdown.cast()? {
+            msg!(
+                "share price {} is down {} from high-water mark {}, halting trading",
+                price,
+                drawdown,
+                self.high_water_mark_price
+            );
+            self.trading_halted = true;
+        }
+
+        Ok(())
+    }
+
+    pub fn check_trading_not_halted(&self) -> VaultResult {
+        // the liquidator acts as delegate and must be able to unwind positions
+        validate!(
+            !self.trading_halted || self.in_liquidation(),
+            ErrorCode::TradingHalted,
+            "share price drawdown exceeded {}, manager and protocol must resume trading",
+            self.max_drawdown
+        )?;
+        Ok(())
+    }
+
+    /// Resume delegate trading after a drawdown halt. The high-water mark resets to the current share price.
+    pub fn resume_trading(&mut self, vault_equity: u64) -> Result<()> {
+        validate!(
+            self.trading_halted,
+            ErrorCode::TradingNotHalted,
+            "trading is not halted"
+        )?;
+        self.trading_halted = false;
+        self.high_water_mark_price = self.share_price(vault_equity)?.unwrap_or(0);
+        Ok(())
+    }
+
+    /// Price every deposit and withdraw request queued during the epoch at one equity snapshot.
+    /// Fees are applied once, then withdraw shares are burned and deposit shares are minted in bulk.
+    /// Investors claim their slice of the settled shares and redemption value with [`Investor::claim_epoch`].
+    pub fn settle_epoch(&mut self, vault_equity: u64, now: i64) -> Result<()> {
+        validate!(
+            self.epoch_mode(),
+            ErrorCode::EpochModeDisabled,
+            "vault does not use epochs"
+        )?;
+        validate!(
+            self.epoch_ended(now),
+            ErrorCode::EpochNotEnded,
+            "epoch ends at {}",
+            self.epoch_start_ts.saturating_add(self.epoch_duration)
+        )?;
+        validate!(
+            self.epoch_unclaimed_deposits == 0 && self.epoch_unclaimed_withdraw_shares == 0,
+            ErrorCode::EpochUnclaimed,
+            "investors must claim the last epoch before settling the next"
+        )?;
+
+        self.apply_rebase(vault_equity)?;
+        let VaultFee {
+            management_fee_payment,
+            management_fee_shares,
+            protocol_fee_payment,
+            protocol_fee_shares,
+        } = self.apply_fee(vault_equity, now)?;
+
+        let total_vault_shares_before = self.total_shares;
+        let withdraw_shares = self.epoch_pending_withdraw_shares.min(self.investor_shares);
+        let withdraw_value = shares_to_amount(withdraw_shares, self.total_shares, vault_equity)?;
+        let deposits = self.epoch_pending_deposits;
+        let deposit_shares = amount_to_shares(deposits, self.total_shares, vault_equity)?;
+
+        self.total_shares = self
+            .total_shares
+            .safe_sub(withdraw_shares)?
+            .safe_add(deposit_shares)?;
+        self.investor_shares = self
+            .investor_shares
+            .safe_sub(withdraw_shares)?
+            .safe_add(deposit_shares)?;
+
+        self.epoch_unclaimed_deposits = deposits;
+        self.epoch_unclaimed_deposit_shares = deposit_shares;
+        self.epoch_unclaimed_withdraw_shares = withdraw_shares;
+        self.epoch_unclaimed_withdraw_value = withdraw_value;
+        self.epoch_redemptions_owed = self.epoch_redemptions_owed.safe_add(withdraw_value)?;
+        self.total_withdraw_requested = self.total_withdraw_requested.safe_add(withdraw_value)?;
+
+        self.epoch_pending_deposits = 0;
+        self.epoch_pending_withdraw_shares = 0;
+
+        emit!(EpochRecord {
+            ts: now,
+            vault: self.pubkey,
+            epoch: self.current_epoch,
+            vault_equity,
+            deposits,
+            deposit_shares,
+            withdraw_shares,
+            withdraw_value,
+            total_vault_shares_before,
+            total_vault_shares_after: self.total_shares,
+            protocol_fee: protocol_fee_payment,
+            protocol_fee_shares,
+            management_fee: management_fee_payment,
+            management_fee_shares,
+        });
+
+        self.current_epoch = self.current_epoch.safe_add(1)?;
+        self.epoch_start_ts = now;
+
+        Ok(())
+    }
+
     pub fn profit_share(&self) -> u32 {
         self.profit_share.saturating_add(self.protocol_profit_share)
     }
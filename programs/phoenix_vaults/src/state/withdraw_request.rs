@@ -1,6 +1,7 @@
 use crate::error::{ErrorCode, VaultResult};
 use crate::math::safe_math::SafeMath;
 use crate::math::vault::{amount_to_shares, shares_to_amount};
+use crate::state::VaultState;
 use crate::{validate, Vault};
 use anchor_lang::prelude::*;
 use anchor_lang::solana_program;
@@ -27,6 +28,16 @@ impl WithdrawRequest {
         self.shares != 0 || self.value != 0
     }
 
+    /// The USDC owed by this request at the current vault equity.
+    /// A request settled by `settle_epoch` has already had its shares burned, so it is owed its fixed value.
+    pub fn withdraw_value(&self, vault: &Vault, vault_equity: u64) -> VaultResult<u64> {
+        if self.shares == 0 {
+            return Ok(self.value);
+        }
+        let amount = shares_to_amount(self.shares, vault.total_shares, vault_equity)?;
+        Ok(amount.min(self.value))
+    }
+
     pub fn rebase(&mut self, rebase_divisor: u128) -> VaultResult {
         self.shares = self.shares.safe_div(rebase_divisor)?;
         Ok(())
@@ -109,6 +120,11 @@ impl WithdrawRequest {
     }
 
     pub fn check_redeem_period_finished(&self, vault: &Vault, now: i64) -> VaultResult {
+        // a vault winding down or closed has no redeem period
+        if matches!(vault.state(now), VaultState::WindDown | VaultState::Closed) {
+            return Ok(());
+        }
+
         let time_since_withdraw_request = now.safe_sub(self.ts)?;
 
         validate!(
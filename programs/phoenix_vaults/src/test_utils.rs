@@ -0,0 +1,195 @@
+//! In-memory Phoenix markets, so valuation can be tested over real market account data
+//! without a validator. Enabled for this crate's tests and by the `test-utils` feature.
+
+use anchor_lang::prelude::*;
+use phoenix::program::{
+    get_market_size, load_with_dispatch_init, load_with_dispatch_mut, MarketHeader,
+    MarketSizeParams, TokenParams,
+};
+use phoenix::quantities::{
+    BaseAtomsPerBaseLot, BaseLots, BaseLotsPerBaseUnit, QuoteAtomsPerBaseUnitPerTick,
+    QuoteAtomsPerQuoteLot, QuoteLots, QuoteLotsPerBaseUnitPerTick, WrapperU64,
+};
+use phoenix::state::{OrderPacket, Side};
+
+/// Market parameters, defaulting to those `tests/phoenix.rs` bootstraps localnet markets with.
+#[derive(Clone, Copy, Debug)]
+pub struct TestMarketParams {
+    pub base_mint: Pubkey,
+    pub quote_mint: Pubkey,
+    pub base_decimals: u32,
+    pub quote_decimals: u32,
+    pub num_base_lots_per_base_unit: u64,
+    pub num_quote_lots_per_quote_unit: u64,
+    pub tick_size_in_quote_lots_per_base_unit: u64,
+    pub raw_base_units_per_base_unit: u32,
+    pub taker_fee_bps: u64,
+    pub market_size_params: MarketSizeParams,
+}
+
+impl Default for TestMarketParams {
+    fn default() -> Self {
+        Self {
+            base_mint: Pubkey::default(),
+            quote_mint: Pubkey::default(),
+            base_decimals: 9,
+            quote_decimals: 6,
+            num_base_lots_per_base_unit: 1_000,
+            num_quote_lots_per_quote_unit: 100_000,
+            tick_size_in_quote_lots_per_base_unit: 1_000,
+            raw_base_units_per_base_unit: 1,
+            taker_fee_bps: 1,
+            market_size_params: MarketSizeParams {
+                bids_size: 512,
+                asks_size: 512,
+                num_seats: 128,
+            },
+        }
+    }
+}
+
+/// Free and locked lots of a trader on a [`TestMarket`]
+#[derive(Clone, Copy, Debug, Default)]
+pub struct TestTraderLots {
+    pub base_lots_free: u64,
+    pub base_lots_locked: u64,
+    pub quote_lots_free: u64,
+    pub quote_lots_locked: u64,
+}
+
+/// Raw account data of a Phoenix market: the `MarketHeader` followed by the market itself.
+pub struct TestMarket {
+    pub key: Pubkey,
+    pub data: Vec<u8>,
+}
+
+impl TestMarket {
+    pub fn new(params: TestMarketParams) -> Self {
+        let market_size_params = params.market_size_params;
+        let base_atoms_per_base_unit =
+            10u64.pow(params.base_decimals) * params.raw_base_units_per_base_unit as u64;
+        let quote_atoms_per_quote_unit = 10u64.pow(params.quote_decimals);
+        let base_lot_size = base_atoms_per_base_unit / params.num_base_lots_per_base_unit;
+        let quote_lot_size = quote_atoms_per_quote_unit / params.num_quote_lots_per_quote_unit;
+
+        let header = MarketHeader::new(
+            market_size_params,
+            TokenParams {
+                decimals: params.base_decimals,
+                vault_bump: 0,
+                mint_key: params.base_mint,
+                vault_key: Pubkey::default(),
+            },
+            BaseAtomsPerBaseLot::new(base_lot_size),
+            TokenParams {
+                decimals: params.quote_decimals,
+                vault_bump: 0,
+                mint_key: params.quote_mint,
+                vault_key: Pubkey::default(),
+            },
+            QuoteAtomsPerQuoteLot::new(quote_lot_size),
+            QuoteAtomsPerBaseUnitPerTick::new(
+                params.tick_size_in_quote_lots_per_base_unit * quote_lot_size,
+            ),
+            Pubkey::default(),
+            Pubkey::default(),
+            Pubkey::default(),
+            params.raw_base_units_per_base_unit,
+        );
+
+        let header_size = std::mem::size_of::<MarketHeader>();
+        let market_size = get_market_size(&market_size_params).unwrap();
+        let mut data = vec![0u8; header_size + market_size];
+        data[..header_size].copy_from_slice(bytemuck::bytes_of(&header));
+
+        let market = load_with_dispatch_init(&market_size_params, &mut data[header_size..])
+            .unwrap()
+            .inner;
+        market.initialize_with_params(
+            QuoteLotsPerBaseUnitPerTick::new(params.tick_size_in_quote_lots_per_base_unit),
+            BaseLotsPerBaseUnit::new(params.num_base_lots_per_base_unit),
+        );
+        market.set_fee(params.taker_fee_bps);
+
+        Self {
+            key: Pubkey::new_unique(),
+            data,
+        }
+    }
+
+    pub fn header(&self) -> &MarketHeader {
+        bytemuck::from_bytes(&self.data[..std::mem::size_of::<MarketHeader>()])
+    }
+
+    /// Registers `trader` if needed and overwrites its lots.
+    pub fn with_trader(mut self, trader: &Pubkey, lots: TestTraderLots) -> Self {
+        let header_size = std::mem::size_of::<MarketHeader>();
+        let market_size_params = self.header().market_size_params;
+        let market = load_with_dispatch_mut(&market_size_params, &mut self.data[header_size..])
+            .unwrap()
+            .inner;
+        market.get_or_register_trader(trader).unwrap();
+        let trader_state = market.get_trader_state_mut(trader).unwrap();
+        trader_state.base_lots_free = BaseLots::new(lots.base_lots_free);
+        trader_state.base_lots_locked = BaseLots::new(lots.base_lots_locked);
+        trader_state.quote_lots_free = QuoteLots::new(lots.quote_lots_free);
+        trader_state.quote_lots_locked = QuoteLots::new(lots.quote_lots_locked);
+        self
+    }
+
+    /// Rests a post-only bid from `maker`, which sets the top of book the vault is valued at.
+    pub fn with_bid(self, maker: &Pubkey, price_in_ticks: u64, num_base_lots: u64) -> Self {
+        self.with_order(maker, Side::Bid, price_in_ticks, num_base_lots)
+    }
+
+    /// Rests a post-only ask from `maker`.
+    pub fn with_ask(self, maker: &Pubkey, price_in_ticks: u64, num_base_lots: u64) -> Self {
+        self.with_order(maker, Side::Ask, price_in_ticks, num_base_lots)
+    }
+
+    /// Rests a level of `(price_in_ticks, num_base_lots)` from `maker` for each bid and ask.
+    pub fn with_ladder(mut self, maker: &Pubkey, bids: &[(u64, u64)], asks: &[(u64, u64)]) -> Self {
+        for &(price_in_ticks, num_base_lots) in bids {
+            self = self.with_bid(maker, price_in_ticks, num_base_lots);
+        }
+        for &(price_in_ticks, num_base_lots) in asks {
+            self = self.with_ask(maker, price_in_ticks, num_base_lots);
+        }
+        self
+    }
+
+    /// Rests a post-only order from `maker`. Panics if it would cross the book.
+    pub fn with_order(
+        mut self,
+        maker: &Pubkey,
+        side: Side,
+        price_in_ticks: u64,
+        num_base_lots: u64,
+    ) -> Self {
+        let header_size = std::mem::size_of::<MarketHeader>();
+        let market_size_params = self.header().market_size_params;
+        let market = load_with_dispatch_mut(&market_size_params, &mut self.data[header_size..])
+            .unwrap()
+            .inner;
+        market.get_or_register_trader(maker).unwrap();
+        market
+            .place_order(
+                maker,
+                OrderPacket::new_post_only_default(side, price_in_ticks, num_base_lots),
+                &mut |_| {},
+                &mut || (0, 0),
+            )
+            .unwrap();
+        self
+    }
+}
+
+/// Account info over borrowed account data, as the program sees remaining accounts.
+pub fn create_account_info<'a>(
+    key: &'a Pubkey,
+    lamports: &'a mut u64,
+    data: &'a mut [u8],
+    owner: &'a Pubkey,
+) -> AccountInfo<'a> {
+    AccountInfo::new(key, false, false, lamports, data, owner, false, 0)
+}
@@ -0,0 +1,1374 @@
+//! In-process test environment for the integration suites.
+//!
+//! phoenix-vaults runs natively on a `ProgramTest` bank next to the Phoenix and seat manager
+//! programs loaded from `deps/`, so the suites need no running validator. [`TestEnv::new`]
+//! bootstraps the USDC, SOL and JUP mints, the SOL/USDC and JUP/SOL markets, the market registry,
+//! and a maker quoting both books.
+
+#![allow(dead_code)]
+
+use std::collections::HashMap;
+use std::path::Path;
+
+use anchor_spl::associated_token::get_associated_token_address;
+use anchor_spl::associated_token::spl_associated_token_account::instruction::create_associated_token_account_idempotent;
+use anchor_spl::token::spl_token;
+use anchor_spl::token::spl_token::solana_program::program_pack::Pack;
+use anchor_spl::token::spl_token::state::{Account as TokenAccount, Mint};
+use phoenix::program::instruction_builders::{
+    create_cancel_all_orders_instruction, create_change_market_status_instruction,
+    create_initialize_market_instructions_default, create_name_successor_instruction,
+    create_new_order_instruction, create_new_order_with_free_funds_instruction,
+};
+use phoenix::program::status::MarketStatus;
+use phoenix::program::{MarketHeader, MarketSizeParams};
+use phoenix::state::{OrderPacket, Side};
+use phoenix_seat_manager::get_seat_manager_address;
+use phoenix_seat_manager::instruction_builders::{
+    create_claim_market_authority_instruction, create_claim_seat_instruction,
+};
+use phoenix_vaults::accounts;
+use phoenix_vaults::constants::ONE_DAY;
+use phoenix_vaults::error::ErrorCode;
+use phoenix_vaults::instructions::{MarketLookupTableParams, PlaceOrderParams, VaultParams};
+use phoenix_vaults::math::market::quote_atoms_to_quote_lots_rounded_down;
+use phoenix_vaults::state::{
+    Investor, MarketPosition, MarketRegistry, MarketTransferParams, Vault, WithdrawUnit,
+};
+use phoenix_vaults_client::instructions as ix;
+use phoenix_vaults_client::{
+    deserialize_account, deserialize_market_header, encode_name, equity_remaining_accounts,
+    get_investor_address, get_market_registry_address, get_vault_address, get_vault_token_address,
+    market_remaining_accounts, vault_equity, PhoenixMarketAccounts,
+};
+use solana_program_test::{processor, BanksClientError, ProgramTest, ProgramTestContext};
+use solana_sdk::account::Account;
+use solana_sdk::account_info::AccountInfo;
+use solana_sdk::bpf_loader;
+use solana_sdk::clock::Clock;
+use solana_sdk::entrypoint::ProgramResult;
+use solana_sdk::instruction::{AccountMeta, Instruction, InstructionError};
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::rent::Rent;
+use solana_sdk::signature::Keypair;
+use solana_sdk::signer::Signer;
+use solana_sdk::system_instruction;
+use solana_sdk::transaction::{Transaction, TransactionError};
+
+pub const USDC_DECIMALS: u8 = 6;
+pub const SOL_DECIMALS: u8 = 9;
+pub const JUP_DECIMALS: u8 = 9;
+
+/// Both markets use Phoenix's default lot sizes: 1000 base lots per unit,
+/// 100_000 quote lots per unit, and a tick of 1000 quote lots per base unit.
+pub const BASE_LOTS_PER_UNIT: u64 = 1_000;
+
+/// The maker's SOL/USDC quotes in ticks of $0.01
+pub const SOL_USDC_BID: u64 = 10_000;
+pub const SOL_USDC_ASK: u64 = 10_100;
+/// The maker's JUP/SOL quotes in ticks of 0.01 SOL
+pub const JUP_SOL_BID: u64 = 5;
+pub const JUP_SOL_ASK: u64 = 6;
+
+/// SOL the vault buys on SOL/USDC in [`TestEnv::trade_into_markets`], in base lots
+pub const SOL_BOUGHT: u64 = 10 * BASE_LOTS_PER_UNIT;
+/// SOL the vault moves from SOL/USDC to JUP/SOL, in SOL/USDC base lots
+pub const SOL_MOVED: u64 = 5 * BASE_LOTS_PER_UNIT;
+/// JUP the vault buys on JUP/SOL, in base lots
+pub const JUP_BOUGHT: u64 = 50 * BASE_LOTS_PER_UNIT;
+
+pub const REDEEM_PERIOD: i64 = ONE_DAY;
+
+pub fn usdc(amount: u64) -> u64 {
+    amount * 10_u64.pow(USDC_DECIMALS as u32)
+}
+
+pub fn sol(amount: u64) -> u64 {
+    amount * 10_u64.pow(SOL_DECIMALS as u32)
+}
+
+pub fn jup(amount: u64) -> u64 {
+    amount * 10_u64.pow(JUP_DECIMALS as u32)
+}
+
+/// Anchor's entrypoint wants the account slice to live as long as the accounts,
+/// which the native processor signature doesn't promise, so the slice is leaked for the test's lifetime.
+fn process_instruction<'info>(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo<'info>],
+    data: &[u8],
+) -> ProgramResult {
+    let accounts = Box::leak(Box::new(accounts.to_vec()));
+    phoenix_vaults::entry(program_id, accounts, data)
+}
+
+fn add_deployed_program(program_test: &mut ProgramTest, program_id: Pubkey, file: &str) {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("../../deps")
+        .join(file);
+    let data = std::fs::read(&path)
+        .unwrap_or_else(|e| panic!("Failed to read {}: {:?}", path.display(), e));
+    program_test.add_account(
+        program_id,
+        Account {
+            lamports: Rent::default().minimum_balance(data.len()).max(1),
+            data,
+            owner: bpf_loader::id(),
+            executable: true,
+            rent_epoch: 0,
+        },
+    );
+}
+
+/// Asserts that a transaction failed with a phoenix-vaults error.
+pub fn assert_vault_error(result: Result<(), BanksClientError>, error: ErrorCode) {
+    match result {
+        Err(e) => assert_eq!(
+            e.unwrap(),
+            TransactionError::InstructionError(0, InstructionError::Custom(error.into())),
+            "expected {:?}",
+            error
+        ),
+        Ok(()) => panic!("expected {:?}, but the transaction succeeded", error),
+    }
+}
+
+/// Vault params with a one day redeem period and no fees.
+pub fn vault_params(name: &str, protocol: &Pubkey) -> VaultParams {
+    VaultParams {
+        name: encode_name(name).unwrap(),
+        redeem_period: REDEEM_PERIOD,
+        max_tokens: 0,
+        management_fee: 0,
+        min_deposit_amount: 0,
+        profit_share: 0,
+        hurdle_rate: 0,
+        permissioned: false,
+        protocol: *protocol,
+        protocol_fee: 0,
+        protocol_profit_share: 0,
+        epoch_duration: 0,
+        entry_fee_bps: 0,
+        exit_fee_bps: 0,
+        max_drawdown: 0,
+        manager_min_stake: 0,
+        liquidity_buffer: 0,
+        lockup_duration: 0,
+        lockup_penalty_bps: 0,
+        liquidation_window: 0,
+        fundraising_soft_cap: 0,
+        fundraising_deadline: 0,
+    }
+}
+
+/// A vault and the keypairs of its roles. The manager is also the delegate.
+pub struct TestVault {
+    pub key: Pubkey,
+    pub manager: Keypair,
+    pub protocol: Keypair,
+}
+
+pub struct TestEnv {
+    pub context: ProgramTestContext,
+    pub mint_authority: Keypair,
+    /// Quotes both markets, see [`TestEnv::post_default_quotes`]
+    pub maker: Keypair,
+    pub usdc_mint: Pubkey,
+    pub sol_mint: Pubkey,
+    pub jup_mint: Pubkey,
+    pub sol_usdc_market: Pubkey,
+    pub jup_sol_market: Pubkey,
+}
+
+impl TestEnv {
+    pub async fn new() -> Self {
+        let mut program_test = ProgramTest::new(
+            "phoenix_vaults",
+            phoenix_vaults::ID,
+            processor!(process_instruction),
+        );
+        program_test.prefer_bpf(false);
+        // liquidations chain several Phoenix CPIs
+        program_test.set_compute_max_units(1_400_000);
+        add_deployed_program(&mut program_test, phoenix::ID, "phoenix.so");
+        add_deployed_program(
+            &mut program_test,
+            phoenix_seat_manager::ID,
+            "phoenix_seat_manager.so",
+        );
+
+        let context = program_test.start_with_context().await;
+        let mut env = Self {
+            context,
+            mint_authority: Keypair::new(),
+            maker: Keypair::new(),
+            usdc_mint: Pubkey::default(),
+            sol_mint: Pubkey::default(),
+            jup_mint: Pubkey::default(),
+            sol_usdc_market: Pubkey::default(),
+            jup_sol_market: Pubkey::default(),
+        };
+        env.usdc_mint = env.create_mint(USDC_DECIMALS).await;
+        env.sol_mint = env.create_mint(SOL_DECIMALS).await;
+        env.jup_mint = env.create_mint(JUP_DECIMALS).await;
+        env.sol_usdc_market = env.create_market(env.sol_mint, env.usdc_mint).await;
+        env.jup_sol_market = env.create_market(env.jup_mint, env.sol_mint).await;
+        env.initialize_market_registry().await;
+        env.setup_maker().await;
+        env
+    }
+
+    pub fn payer(&self) -> Pubkey {
+        self.context.payer.pubkey()
+    }
+
+    /// Signs with the payer and `signers` against a fresh blockhash,
+    /// so that repeating an instruction never trips the status cache.
+    pub async fn process(
+        &mut self,
+        ixs: &[Instruction],
+        signers: &[&Keypair],
+    ) -> Result<(), BanksClientError> {
+        let blockhash = self.context.get_new_latest_blockhash().await?;
+        let mut all_signers = vec![&self.context.payer];
+        all_signers.extend_from_slice(signers);
+        let tx = Transaction::new_signed_with_payer(
+            ixs,
+            Some(&self.context.payer.pubkey()),
+            all_signers.as_slice(),
+            blockhash,
+        );
+        self.context.banks_client.process_transaction(tx).await
+    }
+
+    pub async fn send(&mut self, ixs: &[Instruction], signers: &[&Keypair]) {
+        self.process(ixs, signers).await.unwrap();
+    }
+
+    //
+    // Clock
+    //
+
+    pub async fn clock(&mut self) -> Clock {
+        self.context
+            .banks_client
+            .get_sysvar::<Clock>()
+            .await
+            .unwrap()
+    }
+
+    pub async fn now(&mut self) -> i64 {
+        self.clock().await.unix_timestamp
+    }
+
+    /// Moves the clock `seconds` forward on the next slot.
+    pub async fn warp(&mut self, seconds: i64) {
+        let before = self.clock().await;
+        self.context.warp_to_slot(before.slot + 1).unwrap();
+        let mut clock = self.clock().await;
+        clock.unix_timestamp = before.unix_timestamp + seconds;
+        self.context.set_sysvar(&clock);
+    }
+
+    //
+    // Accounts
+    //
+
+    pub async fn account_data(&mut self, key: &Pubkey) -> Vec<u8> {
+        self.context
+            .banks_client
+            .get_account(*key)
+            .await
+            .unwrap()
+            .unwrap_or_else(|| panic!("Account not found: {:?}", key))
+            .data
+    }
+
+    pub async fn vault(&mut self, key: &Pubkey) -> Vault {
+        deserialize_account(&self.account_data(key).await).unwrap()
+    }
+
+    pub async fn investor(&mut self, vault: &TestVault, authority: &Keypair) -> Investor {
+        let key = get_investor_address(&vault.key, &authority.pubkey());
+        deserialize_account(&self.account_data(&key).await).unwrap()
+    }
+
+    pub async fn registry(&mut self) -> MarketRegistry {
+        deserialize_account(&self.account_data(&get_market_registry_address()).await).unwrap()
+    }
+
+    pub async fn market_header(&mut self, market: &Pubkey) -> MarketHeader {
+        deserialize_market_header(&self.account_data(market).await).unwrap()
+    }
+
+    pub async fn token_balance(&mut self, token_account: &Pubkey) -> u64 {
+        TokenAccount::unpack(&self.account_data(token_account).await)
+            .unwrap()
+            .amount
+    }
+
+    pub async fn usdc_balance(&mut self, owner: &Pubkey) -> u64 {
+        let token_account = get_associated_token_address(owner, &self.usdc_mint);
+        self.token_balance(&token_account).await
+    }
+
+    /// The vault's position on `market` as the vault last recorded it.
+    pub async fn position(&mut self, vault: &TestVault, market: &Pubkey) -> MarketPosition {
+        self.vault(&vault.key)
+            .await
+            .positions
+            .into_iter()
+            .find(|position| &position.market == market)
+            .unwrap_or_else(|| panic!("Vault has no position on {:?}", market))
+    }
+
+    /// Vault equity as the program values it.
+    pub async fn equity(&mut self, vault: &TestVault) -> u64 {
+        let vault = self.vault(&vault.key).await;
+        let registry = self.registry().await;
+        let vault_usdc_amount = self.token_balance(&vault.usdc_token_account).await;
+        let mut markets = HashMap::new();
+        for meta in equity_remaining_accounts(&vault, &registry) {
+            let data = self.account_data(&meta.pubkey).await;
+            markets.insert(meta.pubkey, data);
+        }
+        vault_equity(&vault, vault_usdc_amount, &registry, &markets).unwrap()
+    }
+
+    //
+    // Tokens
+    //
+
+    pub async fn create_mint(&mut self, decimals: u8) -> Pubkey {
+        let mint = Keypair::new();
+        let rent = Rent::default().minimum_balance(Mint::LEN);
+        let ixs = [
+            system_instruction::create_account(
+                &self.payer(),
+                &mint.pubkey(),
+                rent,
+                Mint::LEN as u64,
+                &spl_token::ID,
+            ),
+            spl_token::instruction::initialize_mint(
+                &spl_token::ID,
+                &mint.pubkey(),
+                &self.mint_authority.pubkey(),
+                None,
+                decimals,
+            )
+            .unwrap(),
+        ];
+        self.send(&ixs, &[&mint]).await;
+        mint.pubkey()
+    }
+
+    /// Creates the associated token account of `owner` if it doesn't exist, `owner` can be a PDA.
+    pub async fn create_token_account(&mut self, owner: &Pubkey, mint: &Pubkey) -> Pubkey {
+        let ix =
+            create_associated_token_account_idempotent(&self.payer(), owner, mint, &spl_token::ID);
+        self.send(&[ix], &[]).await;
+        get_associated_token_address(owner, mint)
+    }
+
+    pub async fn mint_to(&mut self, owner: &Pubkey, mint: &Pubkey, amount: u64) {
+        let token_account = self.create_token_account(owner, mint).await;
+        let ix = spl_token::instruction::mint_to(
+            &spl_token::ID,
+            mint,
+            &token_account,
+            &self.mint_authority.pubkey(),
+            &[],
+            amount,
+        )
+        .unwrap();
+        let mint_authority = self.mint_authority.insecure_clone();
+        self.send(&[ix], &[&mint_authority]).await;
+    }
+
+    pub async fn transfer_lamports(&mut self, to: &Pubkey, lamports: u64) {
+        let ix = system_instruction::transfer(&self.payer(), to, lamports);
+        self.send(&[ix], &[]).await;
+    }
+
+    //
+    // Markets
+    //
+
+    /// Replicates the Phoenix and seat manager bootstrap of `tests/phoenix.rs` with default lot sizes.
+    async fn create_market(&mut self, base_mint: Pubkey, quote_mint: Pubkey) -> Pubkey {
+        let market = Keypair::new();
+        let payer = self.payer();
+        let seat_manager = get_seat_manager_address(&market.pubkey()).0;
+
+        let mut ixs = create_initialize_market_instructions_default(
+            &market.pubkey(),
+            &base_mint,
+            &quote_mint,
+            &payer,
+            MarketSizeParams {
+                bids_size: 512,
+                asks_size: 512,
+                num_seats: 128,
+            },
+            100_000,
+            BASE_LOTS_PER_UNIT,
+            1_000,
+            1,
+            None,
+        )
+        .unwrap();
+        ixs.push(create_name_successor_instruction(
+            &payer,
+            &market.pubkey(),
+            &seat_manager,
+        ));
+        ixs.push(create_change_market_status_instruction(
+            &payer,
+            &market.pubkey(),
+            MarketStatus::Active,
+        ));
+        self.send(&ixs, &[&market]).await;
+
+        // the seat manager becomes the market authority
+        let ix = create_claim_market_authority_instruction(&market.pubkey(), &payer);
+        self.send(&[ix], &[]).await;
+
+        market.pubkey()
+    }
+
+    async fn initialize_market_registry(&mut self) {
+        let payer = self.payer();
+        let ix = ix::initialize_market_registry(
+            accounts::InitializeMarketRegistry {
+                authority: payer,
+                market_registry: get_market_registry_address(),
+                payer,
+                rent: solana_sdk::sysvar::rent::ID,
+                system_program: solana_sdk::system_program::ID,
+            },
+            MarketLookupTableParams {
+                sol_usdc_market: self.sol_usdc_market,
+                usdc_mint: self.usdc_mint,
+                sol_mint: self.sol_mint,
+            },
+            vec![],
+        );
+        self.send(&[ix], &[]).await;
+    }
+
+    /// Funds the maker, claims its seats and posts [`TestEnv::post_default_quotes`].
+    async fn setup_maker(&mut self) {
+        let maker = self.maker.pubkey();
+        self.transfer_lamports(&maker, sol(10)).await;
+        let (usdc_mint, sol_mint, jup_mint) = (self.usdc_mint, self.sol_mint, self.jup_mint);
+        self.mint_to(&maker, &usdc_mint, usdc(1_000_000)).await;
+        self.mint_to(&maker, &sol_mint, sol(10_000)).await;
+        self.mint_to(&maker, &jup_mint, jup(1_000_000)).await;
+
+        for market in [self.sol_usdc_market, self.jup_sol_market] {
+            let ix = create_claim_seat_instruction(&maker, &market);
+            let signer = self.maker_signer();
+            self.send(&[ix], &[&signer]).await;
+        }
+        self.post_default_quotes().await;
+    }
+
+    fn maker_signer(&self) -> Keypair {
+        self.maker.insecure_clone()
+    }
+
+    /// SOL/USDC: 100 SOL bid at $100 and offered at $101.
+    /// JUP/SOL: 10,000 JUP bid at 0.05 SOL and offered at 0.06 SOL.
+    pub async fn post_default_quotes(&mut self) {
+        let sol_usdc = self.sol_usdc_market;
+        let jup_sol = self.jup_sol_market;
+        self.maker_order(&sol_usdc, Side::Bid, SOL_USDC_BID, 100 * BASE_LOTS_PER_UNIT)
+            .await;
+        self.maker_order(&sol_usdc, Side::Ask, SOL_USDC_ASK, 100 * BASE_LOTS_PER_UNIT)
+            .await;
+        self.maker_order(
+            &jup_sol,
+            Side::Bid,
+            JUP_SOL_BID,
+            10_000 * BASE_LOTS_PER_UNIT,
+        )
+        .await;
+        self.maker_order(
+            &jup_sol,
+            Side::Ask,
+            JUP_SOL_ASK,
+            10_000 * BASE_LOTS_PER_UNIT,
+        )
+        .await;
+    }
+
+    /// Posts a maker order funded from the maker's token accounts.
+    pub async fn maker_order(
+        &mut self,
+        market: &Pubkey,
+        side: Side,
+        price_in_ticks: u64,
+        base_lots: u64,
+    ) {
+        let header = self.market_header(market).await;
+        let ix = create_new_order_instruction(
+            market,
+            &self.maker.pubkey(),
+            &header.base_params.mint_key,
+            &header.quote_params.mint_key,
+            &OrderPacket::new_post_only_default(side, price_in_ticks, base_lots),
+        );
+        let signer = self.maker_signer();
+        self.send(&[ix], &[&signer]).await;
+    }
+
+    pub async fn maker_cancel_all(&mut self, market: &Pubkey) {
+        let header = self.market_header(market).await;
+        let ix = create_cancel_all_orders_instruction(
+            market,
+            &self.maker.pubkey(),
+            &header.base_params.mint_key,
+            &header.quote_params.mint_key,
+        );
+        let signer = self.maker_signer();
+        self.send(&[ix], &[&signer]).await;
+    }
+
+    //
+    // Vault setup
+    //
+
+    pub async fn create_vault(&mut self, name: &str) -> TestVault {
+        self.create_vault_with(name, |params| params).await
+    }
+
+    /// Creates the vault token accounts, initializes the vault with `configure`d [`vault_params`],
+    /// and creates USDC token accounts for the manager and protocol.
+    pub async fn create_vault_with(
+        &mut self,
+        name: &str,
+        configure: impl FnOnce(VaultParams) -> VaultParams,
+    ) -> TestVault {
+        let manager = Keypair::new();
+        let protocol = Keypair::new();
+        let params = configure(vault_params(name, &protocol.pubkey()));
+        let key = get_vault_address(&params.name);
+
+        let (usdc_mint, sol_mint, jup_mint) = (self.usdc_mint, self.sol_mint, self.jup_mint);
+        for mint in [usdc_mint, sol_mint, jup_mint] {
+            self.create_token_account(&key, &mint).await;
+        }
+        self.create_token_account(&manager.pubkey(), &usdc_mint)
+            .await;
+        self.create_token_account(&protocol.pubkey(), &usdc_mint)
+            .await;
+
+        let ix = ix::initialize_vault(
+            accounts::InitializeVault {
+                vault: key,
+                manager: manager.pubkey(),
+                usdc_token_account: get_vault_token_address(&key, &usdc_mint),
+                usdc_mint,
+                sol_token_account: get_vault_token_address(&key, &sol_mint),
+                sol_mint,
+                payer: self.payer(),
+                rent: solana_sdk::sysvar::rent::ID,
+                system_program: solana_sdk::system_program::ID,
+                token_program: anchor_spl::token::ID,
+                associated_token_program: anchor_spl::associated_token::ID,
+            },
+            params,
+            vec![],
+        );
+        self.send(&[ix], &[&manager]).await;
+
+        TestVault {
+            key,
+            manager,
+            protocol,
+        }
+    }
+
+    /// Creates an investor holding `usdc_amount` USDC.
+    pub async fn create_investor(&mut self, vault: &TestVault, usdc_amount: u64) -> Keypair {
+        let authority = Keypair::new();
+        let usdc_mint = self.usdc_mint;
+        self.mint_to(&authority.pubkey(), &usdc_mint, usdc_amount)
+            .await;
+        let ix = ix::initialize_investor(
+            accounts::InitializeInvestor {
+                vault: vault.key,
+                investor: get_investor_address(&vault.key, &authority.pubkey()),
+                authority: authority.pubkey(),
+                payer: self.payer(),
+                rent: solana_sdk::sysvar::rent::ID,
+                system_program: solana_sdk::system_program::ID,
+            },
+            vec![],
+        );
+        self.send(&[ix], &[]).await;
+        authority
+    }
+
+    async fn equity_accounts(&mut self, vault: &TestVault) -> (Vault, Vec<AccountMeta>) {
+        let vault = self.vault(&vault.key).await;
+        let registry = self.registry().await;
+        let remaining_accounts = equity_remaining_accounts(&vault, &registry);
+        (vault, remaining_accounts)
+    }
+
+    async fn market_accounts(
+        &mut self,
+        vault: &TestVault,
+        market: &Pubkey,
+    ) -> (Vault, PhoenixMarketAccounts, Vec<AccountMeta>) {
+        let vault_state = self.vault(&vault.key).await;
+        let registry = self.registry().await;
+        let header = self.market_header(market).await;
+        let remaining_accounts = market_remaining_accounts(&vault_state, &registry, *market);
+        (
+            vault_state,
+            PhoenixMarketAccounts::new(&vault.key, market, &header),
+            remaining_accounts,
+        )
+    }
+
+    //
+    // Investor instructions
+    //
+
+    pub async fn investor_deposit(
+        &mut self,
+        vault: &TestVault,
+        authority: &Keypair,
+        amount: u64,
+    ) -> Result<(), BanksClientError> {
+        let (vault_state, remaining_accounts) = self.equity_accounts(vault).await;
+        let ix = ix::investor_deposit(
+            accounts::InvestorDeposit {
+                vault: vault.key,
+                investor: get_investor_address(&vault.key, &authority.pubkey()),
+                authority: authority.pubkey(),
+                market_registry: get_market_registry_address(),
+                nav_history: None,
+                investor_quote_token_account: get_associated_token_address(
+                    &authority.pubkey(),
+                    &vault_state.usdc_mint,
+                ),
+                vault_quote_token_account: vault_state.usdc_token_account,
+                token_program: anchor_spl::token::ID,
+            },
+            amount,
+            None,
+            None,
+            remaining_accounts,
+        );
+        self.process(&[ix], &[authority]).await
+    }
+
+    pub async fn investor_request_withdraw(
+        &mut self,
+        vault: &TestVault,
+        authority: &Keypair,
+        amount: u64,
+        unit: WithdrawUnit,
+    ) -> Result<(), BanksClientError> {
+        let (vault_state, remaining_accounts) = self.equity_accounts(vault).await;
+        let ix = ix::investor_request_withdraw(
+            accounts::InvestorRequestWithdraw {
+                vault: vault.key,
+                investor: get_investor_address(&vault.key, &authority.pubkey()),
+                authority: authority.pubkey(),
+                market_registry: get_market_registry_address(),
+                vault_usdc_token_account: vault_state.usdc_token_account,
+            },
+            amount,
+            unit,
+            remaining_accounts,
+        );
+        self.process(&[ix], &[authority]).await
+    }
+
+    pub async fn investor_withdraw(
+        &mut self,
+        vault: &TestVault,
+        authority: &Keypair,
+    ) -> Result<(), BanksClientError> {
+        let (vault_state, remaining_accounts) = self.equity_accounts(vault).await;
+        let sol_usdc = self.sol_usdc_accounts(vault).await;
+        let ix = ix::investor_withdraw(
+            accounts::InvestorWithdraw {
+                vault: vault.key,
+                investor: get_investor_address(&vault.key, &authority.pubkey()),
+                authority: authority.pubkey(),
+                market_registry: get_market_registry_address(),
+                nav_history: None,
+                investor_quote_token_account: get_associated_token_address(
+                    &authority.pubkey(),
+                    &vault_state.usdc_mint,
+                ),
+                phoenix: phoenix::ID,
+                log_authority: sol_usdc.log_authority,
+                market: sol_usdc.market,
+                seat: sol_usdc.seat,
+                base_mint: sol_usdc.base_mint,
+                quote_mint: sol_usdc.quote_mint,
+                vault_base_token_account: vault_state.sol_token_account,
+                vault_quote_token_account: vault_state.usdc_token_account,
+                market_base_token_account: sol_usdc.market_base_token_account,
+                market_quote_token_account: sol_usdc.market_quote_token_account,
+                token_program: anchor_spl::token::ID,
+            },
+            None,
+            None,
+            remaining_accounts,
+        );
+        self.process(&[ix], &[authority]).await
+    }
+
+    pub async fn appoint_investor_liquidator(
+        &mut self,
+        vault: &TestVault,
+        authority: &Keypair,
+    ) -> Result<(), BanksClientError> {
+        let (vault_state, remaining_accounts) = self.equity_accounts(vault).await;
+        let ix = ix::appoint_investor_liquidator(
+            accounts::AppointInvestorLiquidator {
+                vault: vault.key,
+                investor: get_investor_address(&vault.key, &authority.pubkey()),
+                authority: authority.pubkey(),
+                market_registry: get_market_registry_address(),
+                vault_quote_token_account: vault_state.usdc_token_account,
+            },
+            remaining_accounts,
+        );
+        self.process(&[ix], &[authority]).await
+    }
+
+    pub async fn investor_liquidate_usdc_market(
+        &mut self,
+        vault: &TestVault,
+        authority: &Keypair,
+        market: &Pubkey,
+    ) -> Result<(), BanksClientError> {
+        let (vault_state, market, remaining_accounts) = self.market_accounts(vault, market).await;
+        let ix = ix::investor_liquidate_usdc_market(
+            accounts::InvestorLiquidateUsdcMarket {
+                vault: vault.key,
+                investor: get_investor_address(&vault.key, &authority.pubkey()),
+                authority: authority.pubkey(),
+                market_registry: get_market_registry_address(),
+                investor_usdc_token_account: get_associated_token_address(
+                    &authority.pubkey(),
+                    &vault_state.usdc_mint,
+                ),
+                phoenix: phoenix::ID,
+                log_authority: market.log_authority,
+                market: market.market,
+                seat: market.seat,
+                base_mint: market.base_mint,
+                usdc_mint: vault_state.usdc_mint,
+                vault_base_token_account: market.vault_base_token_account,
+                vault_usdc_token_account: vault_state.usdc_token_account,
+                market_base_token_account: market.market_base_token_account,
+                market_usdc_token_account: market.market_quote_token_account,
+                token_program: anchor_spl::token::ID,
+            },
+            remaining_accounts,
+        );
+        self.process(&[ix], &[authority]).await
+    }
+
+    pub async fn investor_liquidate_sol_market(
+        &mut self,
+        vault: &TestVault,
+        authority: &Keypair,
+        market: &Pubkey,
+    ) -> Result<(), BanksClientError> {
+        let (vault_state, market, remaining_accounts) = self.market_accounts(vault, market).await;
+        let sol_usdc = self.sol_usdc_accounts(vault).await;
+        let ix = ix::investor_liquidate_sol_market(
+            accounts::InvestorLiquidateSolMarket {
+                vault: vault.key,
+                investor: get_investor_address(&vault.key, &authority.pubkey()),
+                authority: authority.pubkey(),
+                market_registry: get_market_registry_address(),
+                investor_usdc_token_account: get_associated_token_address(
+                    &authority.pubkey(),
+                    &vault_state.usdc_mint,
+                ),
+                phoenix: phoenix::ID,
+                log_authority: market.log_authority,
+                market: market.market,
+                seat: market.seat,
+                base_mint: market.base_mint,
+                sol_mint: vault_state.sol_mint,
+                usdc_mint: vault_state.usdc_mint,
+                vault_base_token_account: market.vault_base_token_account,
+                vault_sol_token_account: vault_state.sol_token_account,
+                vault_usdc_token_account: vault_state.usdc_token_account,
+                market_base_token_account: market.market_base_token_account,
+                market_sol_token_account: market.market_quote_token_account,
+                sol_usdc_market: sol_usdc.market,
+                sol_usdc_market_seat: sol_usdc.seat,
+                sol_usdc_market_sol_token_account: sol_usdc.market_base_token_account,
+                sol_usdc_market_usdc_token_account: sol_usdc.market_quote_token_account,
+                token_program: anchor_spl::token::ID,
+            },
+            remaining_accounts,
+        );
+        self.process(&[ix], &[authority]).await
+    }
+
+    //
+    // Manager instructions
+    //
+
+    pub async fn manager_deposit(
+        &mut self,
+        vault: &TestVault,
+        amount: u64,
+    ) -> Result<(), BanksClientError> {
+        let (vault_state, remaining_accounts) = self.equity_accounts(vault).await;
+        let ix = ix::manager_deposit(
+            accounts::ManagerDeposit {
+                vault: vault.key,
+                manager: vault.manager.pubkey(),
+                market_registry: get_market_registry_address(),
+                nav_history: None,
+                manager_quote_token_account: get_associated_token_address(
+                    &vault.manager.pubkey(),
+                    &vault_state.usdc_mint,
+                ),
+                vault_quote_token_account: vault_state.usdc_token_account,
+                token_program: anchor_spl::token::ID,
+            },
+            amount,
+            remaining_accounts,
+        );
+        self.process(&[ix], &[&vault.manager]).await
+    }
+
+    pub async fn manager_request_withdraw(
+        &mut self,
+        vault: &TestVault,
+        amount: u64,
+        unit: WithdrawUnit,
+    ) -> Result<(), BanksClientError> {
+        let (vault_state, remaining_accounts) = self.equity_accounts(vault).await;
+        let ix = ix::manager_request_withdraw(
+            accounts::ManagerRequestWithdraw {
+                vault: vault.key,
+                manager: vault.manager.pubkey(),
+                market_registry: get_market_registry_address(),
+                vault_usdc_token_account: vault_state.usdc_token_account,
+            },
+            amount,
+            unit,
+            remaining_accounts,
+        );
+        self.process(&[ix], &[&vault.manager]).await
+    }
+
+    pub async fn manager_withdraw(&mut self, vault: &TestVault) -> Result<(), BanksClientError> {
+        let (vault_state, remaining_accounts) = self.equity_accounts(vault).await;
+        let sol_usdc = self.sol_usdc_accounts(vault).await;
+        let ix = ix::manager_withdraw(
+            accounts::ManagerWithdraw {
+                vault: vault.key,
+                manager: vault.manager.pubkey(),
+                market_registry: get_market_registry_address(),
+                nav_history: None,
+                manager_quote_token_account: get_associated_token_address(
+                    &vault.manager.pubkey(),
+                    &vault_state.usdc_mint,
+                ),
+                phoenix: phoenix::ID,
+                log_authority: sol_usdc.log_authority,
+                market: sol_usdc.market,
+                seat: sol_usdc.seat,
+                base_mint: sol_usdc.base_mint,
+                quote_mint: sol_usdc.quote_mint,
+                vault_base_token_account: vault_state.sol_token_account,
+                vault_quote_token_account: vault_state.usdc_token_account,
+                market_base_token_account: sol_usdc.market_base_token_account,
+                market_quote_token_account: sol_usdc.market_quote_token_account,
+                token_program: anchor_spl::token::ID,
+            },
+            remaining_accounts,
+        );
+        self.process(&[ix], &[&vault.manager]).await
+    }
+
+    pub async fn appoint_manager_liquidator(
+        &mut self,
+        vault: &TestVault,
+    ) -> Result<(), BanksClientError> {
+        let (vault_state, remaining_accounts) = self.equity_accounts(vault).await;
+        let ix = ix::appoint_manager_liquidator(
+            accounts::AppointManagerLiquidator {
+                vault: vault.key,
+                manager: vault.manager.pubkey(),
+                market_registry: get_market_registry_address(),
+                vault_quote_token_account: vault_state.usdc_token_account,
+            },
+            remaining_accounts,
+        );
+        self.process(&[ix], &[&vault.manager]).await
+    }
+
+    pub async fn manager_liquidate_usdc_market(
+        &mut self,
+        vault: &TestVault,
+        market: &Pubkey,
+    ) -> Result<(), BanksClientError> {
+        let (vault_state, market, remaining_accounts) = self.market_accounts(vault, market).await;
+        let ix = ix::manager_liquidate_usdc_market(
+            accounts::ManagerLiquidateUsdcMarket {
+                vault: vault.key,
+                manager: vault.manager.pubkey(),
+                market_registry: get_market_registry_address(),
+                manager_usdc_token_account: get_associated_token_address(
+                    &vault.manager.pubkey(),
+                    &vault_state.usdc_mint,
+                ),
+                phoenix: phoenix::ID,
+                log_authority: market.log_authority,
+                market: market.market,
+                seat: market.seat,
+                base_mint: market.base_mint,
+                usdc_mint: vault_state.usdc_mint,
+                vault_base_token_account: market.vault_base_token_account,
+                vault_usdc_token_account: vault_state.usdc_token_account,
+                market_base_token_account: market.market_base_token_account,
+                market_usdc_token_account: market.market_quote_token_account,
+                token_program: anchor_spl::token::ID,
+            },
+            remaining_accounts,
+        );
+        self.process(&[ix], &[&vault.manager]).await
+    }
+
+    pub async fn manager_liquidate_sol_market(
+        &mut self,
+        vault: &TestVault,
+        market: &Pubkey,
+    ) -> Result<(), BanksClientError> {
+        let (vault_state, market, remaining_accounts) = self.market_accounts(vault, market).await;
+        let sol_usdc = self.sol_usdc_accounts(vault).await;
+        let ix = ix::manager_liquidate_sol_market(
+            accounts::ManagerLiquidateSolMarket {
+                vault: vault.key,
+                manager: vault.manager.pubkey(),
+                market_registry: get_market_registry_address(),
+                manager_usdc_token_account: get_associated_token_address(
+                    &vault.manager.pubkey(),
+                    &vault_state.usdc_mint,
+                ),
+                phoenix: phoenix::ID,
+                log_authority: market.log_authority,
+                market: market.market,
+                seat: market.seat,
+                base_mint: market.base_mint,
+                sol_mint: vault_state.sol_mint,
+                usdc_mint: vault_state.usdc_mint,
+                vault_base_token_account: market.vault_base_token_account,
+                vault_sol_token_account: vault_state.sol_token_account,
+                vault_usdc_token_account: vault_state.usdc_token_account,
+                market_base_token_account: market.market_base_token_account,
+                market_sol_token_account: market.market_quote_token_account,
+                sol_usdc_market: sol_usdc.market,
+                sol_usdc_market_seat: sol_usdc.seat,
+                sol_usdc_market_sol_token_account: sol_usdc.market_base_token_account,
+                sol_usdc_market_usdc_token_account: sol_usdc.market_quote_token_account,
+                token_program: anchor_spl::token::ID,
+            },
+            remaining_accounts,
+        );
+        self.process(&[ix], &[&vault.manager]).await
+    }
+
+    //
+    // Protocol instructions
+    //
+
+    pub async fn protocol_request_withdraw(
+        &mut self,
+        vault: &TestVault,
+        amount: u64,
+        unit: WithdrawUnit,
+    ) -> Result<(), BanksClientError> {
+        let (vault_state, remaining_accounts) = self.equity_accounts(vault).await;
+        let ix = ix::protocol_request_withdraw(
+            accounts::ProtocolRequestWithdraw {
+                vault: vault.key,
+                protocol: vault.protocol.pubkey(),
+                market_registry: get_market_registry_address(),
+                vault_usdc_token_account: vault_state.usdc_token_account,
+            },
+            amount,
+            unit,
+            remaining_accounts,
+        );
+        self.process(&[ix], &[&vault.protocol]).await
+    }
+
+    pub async fn protocol_withdraw(&mut self, vault: &TestVault) -> Result<(), BanksClientError> {
+        let (vault_state, remaining_accounts) = self.equity_accounts(vault).await;
+        let sol_usdc = self.sol_usdc_accounts(vault).await;
+        let ix = ix::protocol_withdraw(
+            accounts::ProtocolWithdraw {
+                vault: vault.key,
+                protocol: vault.protocol.pubkey(),
+                market_registry: get_market_registry_address(),
+                nav_history: None,
+                protocol_quote_token_account: get_associated_token_address(
+                    &vault.protocol.pubkey(),
+                    &vault_state.usdc_mint,
+                ),
+                phoenix: phoenix::ID,
+                log_authority: sol_usdc.log_authority,
+                market: sol_usdc.market,
+                seat: sol_usdc.seat,
+                base_mint: sol_usdc.base_mint,
+                quote_mint: sol_usdc.quote_mint,
+                vault_base_token_account: vault_state.sol_token_account,
+                vault_quote_token_account: vault_state.usdc_token_account,
+                market_base_token_account: sol_usdc.market_base_token_account,
+                market_quote_token_account: sol_usdc.market_quote_token_account,
+                token_program: anchor_spl::token::ID,
+            },
+            remaining_accounts,
+        );
+        self.process(&[ix], &[&vault.protocol]).await
+    }
+
+    pub async fn appoint_protocol_liquidator(
+        &mut self,
+        vault: &TestVault,
+    ) -> Result<(), BanksClientError> {
+        let (vault_state, remaining_accounts) = self.equity_accounts(vault).await;
+        let ix = ix::appoint_protocol_liquidator(
+            accounts::AppointProtocolLiquidator {
+                vault: vault.key,
+                protocol: vault.protocol.pubkey(),
+                market_registry: get_market_registry_address(),
+                vault_quote_token_account: vault_state.usdc_token_account,
+            },
+            remaining_accounts,
+        );
+        self.process(&[ix], &[&vault.protocol]).await
+    }
+
+    pub async fn protocol_liquidate_usdc_market(
+        &mut self,
+        vault: &TestVault,
+        market: &Pubkey,
+    ) -> Result<(), BanksClientError> {
+        let (vault_state, market, remaining_accounts) = self.market_accounts(vault, market).await;
+        let ix = ix::protocol_liquidate_usdc_market(
+            accounts::ProtocolLiquidateUsdcMarket {
+                vault: vault.key,
+                protocol: vault.protocol.pubkey(),
+                market_registry: get_market_registry_address(),
+                protocol_usdc_token_account: get_associated_token_address(
+                    &vault.protocol.pubkey(),
+                    &vault_state.usdc_mint,
+                ),
+                phoenix: phoenix::ID,
+                log_authority: market.log_authority,
+                market: market.market,
+                seat: market.seat,
+                base_mint: market.base_mint,
+                usdc_mint: vault_state.usdc_mint,
+                vault_base_token_account: market.vault_base_token_account,
+                vault_usdc_token_account: vault_state.usdc_token_account,
+                market_base_token_account: market.market_base_token_account,
+                market_usdc_token_account: market.market_quote_token_account,
+                token_program: anchor_spl::token::ID,
+            },
+            remaining_accounts,
+        );
+        self.process(&[ix], &[&vault.protocol]).await
+    }
+
+    pub async fn protocol_liquidate_sol_market(
+        &mut self,
+        vault: &TestVault,
+        market: &Pubkey,
+    ) -> Result<(), BanksClientError> {
+        let (vault_state, market, remaining_accounts) = self.market_accounts(vault, market).await;
+        let sol_usdc = self.sol_usdc_accounts(vault).await;
+        let ix = ix::protocol_liquidate_sol_market(
+            accounts::ProtocolLiquidateSolMarket {
+                vault: vault.key,
+                protocol: vault.protocol.pubkey(),
+                market_registry: get_market_registry_address(),
+                protocol_usdc_token_account: get_associated_token_address(
+                    &vault.protocol.pubkey(),
+                    &vault_state.usdc_mint,
+                ),
+                phoenix: phoenix::ID,
+                log_authority: market.log_authority,
+                market: market.market,
+                seat: market.seat,
+                base_mint: market.base_mint,
+                sol_mint: vault_state.sol_mint,
+                usdc_mint: vault_state.usdc_mint,
+                vault_base_token_account: market.vault_base_token_account,
+                vault_sol_token_account: vault_state.sol_token_account,
+                vault_usdc_token_account: vault_state.usdc_token_account,
+                market_base_token_account: market.market_base_token_account,
+                market_sol_token_account: market.market_quote_token_account,
+                sol_usdc_market: sol_usdc.market,
+                sol_usdc_market_seat: sol_usdc.seat,
+                sol_usdc_market_sol_token_account: sol_usdc.market_base_token_account,
+                sol_usdc_market_usdc_token_account: sol_usdc.market_quote_token_account,
+                token_program: anchor_spl::token::ID,
+            },
+            remaining_accounts,
+        );
+        self.process(&[ix], &[&vault.protocol]).await
+    }
+
+    //
+    // Vault instructions
+    //
+
+    /// Grows a vault created by an earlier program version to the current size.
+    pub async fn migrate_vault(&mut self, vault: &TestVault) -> Result<(), BanksClientError> {
+        let ix = ix::migrate_vault(
+            accounts::MigrateVault {
+                vault: vault.key,
+                payer: self.payer(),
+                system_program: solana_sdk::system_program::ID,
+            },
+            vec![],
+        );
+        self.process(&[ix], &[]).await
+    }
+
+    /// Cranks fees, then profit share for `investors` (authorities).
+    pub async fn crystallize_fees(
+        &mut self,
+        vault: &TestVault,
+        investors: &[&Keypair],
+    ) -> Result<(), BanksClientError> {
+        let investors: Vec<Pubkey> = investors
+            .iter()
+            .map(|authority| get_investor_address(&vault.key, &authority.pubkey()))
+            .collect();
+        self.crystallize_fees_with(vault, &investors).await
+    }
+
+    /// Cranks fees, then profit share for the `investors` accounts, whichever vault they belong to.
+    pub async fn crystallize_fees_with(
+        &mut self,
+        vault: &TestVault,
+        investors: &[Pubkey],
+    ) -> Result<(), BanksClientError> {
+        let (vault_state, mut remaining_accounts) = self.equity_accounts(vault).await;
+        remaining_accounts.extend(
+            investors
+                .iter()
+                .map(|investor| AccountMeta::new(*investor, false)),
+        );
+        let ix = ix::crystallize_fees(
+            accounts::CrystallizeFees {
+                vault: vault.key,
+                market_registry: get_market_registry_address(),
+                vault_usdc_token_account: vault_state.usdc_token_account,
+            },
+            remaining_accounts,
+        );
+        self.process(&[ix], &[]).await
+    }
+
+    async fn sol_usdc_accounts(&mut self, vault: &TestVault) -> PhoenixMarketAccounts {
+        let market = self.sol_usdc_market;
+        let header = self.market_header(&market).await;
+        PhoenixMarketAccounts::new(&vault.key, &market, &header)
+    }
+
+    /// Claims the vault's seat on `market`, signed by the manager as delegate.
+    pub async fn claim_seat(
+        &mut self,
+        vault: &TestVault,
+        market: &Pubkey,
+    ) -> Result<(), BanksClientError> {
+        let (_, market, _) = self.market_accounts(vault, market).await;
+        let ix = ix::claim_seat(
+            accounts::ClaimSeat {
+                vault: vault.key,
+                delegate: vault.manager.pubkey(),
+                phoenix: phoenix::ID,
+                log_authority: market.log_authority,
+                market: market.market,
+                seat_manager: market.seat_manager,
+                seat_deposit_collector: market.seat_deposit_collector,
+                payer: self.payer(),
+                seat: market.seat,
+                system_program: solana_sdk::system_program::ID,
+                phoenix_seat_manager: phoenix_seat_manager::ID,
+            },
+            vec![],
+        );
+        self.process(&[ix], &[&vault.manager]).await
+    }
+
+    pub async fn market_deposit(
+        &mut self,
+        vault: &TestVault,
+        market: &Pubkey,
+        params: MarketTransferParams,
+    ) -> Result<(), BanksClientError> {
+        let (_, market, remaining_accounts) = self.market_accounts(vault, market).await;
+        let ix = ix::market_deposit(
+            accounts::MarketDeposit {
+                vault: vault.key,
+                delegate: vault.manager.pubkey(),
+                market_registry: get_market_registry_address(),
+                phoenix: phoenix::ID,
+                log_authority: market.log_authority,
+                market: market.market,
+                seat: market.seat,
+                base_mint: market.base_mint,
+                quote_mint: market.quote_mint,
+                vault_base_token_account: market.vault_base_token_account,
+                vault_quote_token_account: market.vault_quote_token_account,
+                market_base_token_account: market.market_base_token_account,
+                market_quote_token_account: market.market_quote_token_account,
+                token_program: anchor_spl::token::ID,
+            },
+            params,
+            remaining_accounts,
+        );
+        self.process(&[ix], &[&vault.manager]).await
+    }
+
+    pub async fn market_withdraw(
+        &mut self,
+        vault: &TestVault,
+        market: &Pubkey,
+        params: MarketTransferParams,
+    ) -> Result<(), BanksClientError> {
+        let (_, market, remaining_accounts) = self.market_accounts(vault, market).await;
+        let ix = ix::market_withdraw(
+            accounts::MarketWithdraw {
+                vault: vault.key,
+                delegate: vault.manager.pubkey(),
+                phoenix: phoenix::ID,
+                log_authority: market.log_authority,
+                market: market.market,
+                base_mint: market.base_mint,
+                quote_mint: market.quote_mint,
+                vault_base_token_account: market.vault_base_token_account,
+                vault_quote_token_account: market.vault_quote_token_account,
+                market_base_token_account: market.market_base_token_account,
+                market_quote_token_account: market.market_quote_token_account,
+                token_program: anchor_spl::token::ID,
+            },
+            params,
+            remaining_accounts,
+        );
+        self.process(&[ix], &[&vault.manager]).await
+    }
+
+    /// Places a limit order with the vault's free funds, which crosses the book if marketable.
+    pub async fn place_limit_order(
+        &mut self,
+        vault: &TestVault,
+        market: &Pubkey,
+        side: Side,
+        price_in_ticks: u64,
+        base_lots: u64,
+    ) -> Result<(), BanksClientError> {
+        let (_, market, remaining_accounts) = self.market_accounts(vault, market).await;
+        let order = create_new_order_with_free_funds_instruction(
+            &market.market,
+            &vault.key,
+            &OrderPacket::new_limit_order_default(side, price_in_ticks, base_lots),
+        )
+        .data;
+        let ix = ix::place_limit_order(
+            accounts::PlaceLimitOrder {
+                vault: vault.key,
+                delegate: vault.manager.pubkey(),
+                phoenix: phoenix::ID,
+                log_authority: market.log_authority,
+                market: market.market,
+                seat: market.seat,
+                base_mint: market.base_mint,
+                quote_mint: market.quote_mint,
+                vault_base_token_account: market.vault_base_token_account,
+                vault_quote_token_account: market.vault_quote_token_account,
+                market_base_token_account: market.market_base_token_account,
+                market_quote_token_account: market.market_quote_token_account,
+                token_program: anchor_spl::token::ID,
+            },
+            PlaceOrderParams { order },
+            remaining_accounts,
+        );
+        self.process(&[ix], &[&vault.manager]).await
+    }
+
+    /// Moves all of the vault's USDC into positions, as the delegate would:
+    /// deposits it to SOL/USDC and buys [`SOL_BOUGHT`] at the ask,
+    /// moves [`SOL_MOVED`] to JUP/SOL and buys [`JUP_BOUGHT`] at the ask.
+    ///
+    /// The vault is left with no USDC in its token account, the rest of its USDC and some SOL free on SOL/USDC,
+    /// and some SOL and the JUP free on JUP/SOL, so every withdraw needs a liquidation.
+    pub async fn trade_into_markets(&mut self, vault: &TestVault) {
+        let sol_usdc = self.sol_usdc_market;
+        let jup_sol = self.jup_sol_market;
+        self.claim_seat(vault, &sol_usdc).await.unwrap();
+        self.claim_seat(vault, &jup_sol).await.unwrap();
+
+        let vault_state = self.vault(&vault.key).await;
+        let usdc_amount = self.token_balance(&vault_state.usdc_token_account).await;
+        let sol_usdc_header = self.market_header(&sol_usdc).await;
+        let quote_lots = quote_atoms_to_quote_lots_rounded_down(&sol_usdc_header, usdc_amount);
+        self.market_deposit(
+            vault,
+            &sol_usdc,
+            MarketTransferParams {
+                quote_lots,
+                base_lots: 0,
+            },
+        )
+        .await
+        .unwrap();
+        self.place_limit_order(vault, &sol_usdc, Side::Bid, SOL_USDC_ASK, SOL_BOUGHT)
+            .await
+            .unwrap();
+        self.market_withdraw(
+            vault,
+            &sol_usdc,
+            MarketTransferParams {
+                quote_lots: 0,
+                base_lots: SOL_MOVED,
+            },
+        )
+        .await
+        .unwrap();
+
+        // SOL is the quote of JUP/SOL
+        let sol_amount = self.token_balance(&vault_state.sol_token_account).await;
+        let jup_sol_header = self.market_header(&jup_sol).await;
+        let quote_lots = quote_atoms_to_quote_lots_rounded_down(&jup_sol_header, sol_amount);
+        self.market_deposit(
+            vault,
+            &jup_sol,
+            MarketTransferParams {
+                quote_lots,
+                base_lots: 0,
+            },
+        )
+        .await
+        .unwrap();
+        self.place_limit_order(vault, &jup_sol, Side::Bid, JUP_SOL_ASK, JUP_BOUGHT)
+            .await
+            .unwrap();
+    }
+}
@@ -0,0 +1,265 @@
+use phoenix_vaults::constants::ONE_YEAR;
+use phoenix_vaults::error::ErrorCode;
+use phoenix_vaults::instructions::VaultParams;
+use phoenix_vaults::math::shares_to_amount;
+use phoenix_vaults::state::withdraw_request::WithdrawRequest;
+use phoenix_vaults::state::WithdrawUnit;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signer::Signer;
+
+mod fixtures;
+use crate::fixtures::*;
+
+/// What a withdraw pays for `request` at the current equity, as `*_withdraw` computes it without fees.
+async fn expected_payout(env: &mut TestEnv, vault: &TestVault, request: &WithdrawRequest) -> u64 {
+    let equity = env.equity(vault).await;
+    let total_shares = env.vault(&vault.key).await.total_shares;
+    shares_to_amount(request.shares, total_shares, equity)
+        .unwrap()
+        .min(request.value)
+}
+
+async fn assert_liquidation_finished(env: &mut TestEnv, vault: &TestVault) {
+    let state = env.vault(&vault.key).await;
+    assert_eq!(state.liquidator, Pubkey::default());
+    assert_eq!(state.liquidation_start_ts, 0);
+}
+
+#[tokio::test]
+async fn investor_liquidates_usdc_market() {
+    let mut env = TestEnv::new().await;
+    let vault = env.create_vault("investor usdc liquidation").await;
+    let alice = env.create_investor(&vault, usdc(1_500)).await;
+    let bob = env.create_investor(&vault, usdc(500)).await;
+    env.investor_deposit(&vault, &alice, usdc(1_500))
+        .await
+        .unwrap();
+    env.investor_deposit(&vault, &bob, usdc(500)).await.unwrap();
+    env.trade_into_markets(&vault).await;
+
+    env.investor_request_withdraw(&vault, &alice, usdc(1_200), WithdrawUnit::Token)
+        .await
+        .unwrap();
+    env.investor_request_withdraw(&vault, &bob, usdc(100), WithdrawUnit::Token)
+        .await
+        .unwrap();
+    assert_vault_error(
+        env.appoint_investor_liquidator(&vault, &alice).await,
+        ErrorCode::CannotWithdrawBeforeRedeemPeriodEnd,
+    );
+
+    env.warp(REDEEM_PERIOD + 1).await;
+    env.appoint_investor_liquidator(&vault, &alice)
+        .await
+        .unwrap();
+    let state = env.vault(&vault.key).await;
+    assert_eq!(state.liquidator, alice.pubkey());
+    assert_eq!(state.liquidation_start_ts, env.now().await);
+    // one liquidator at a time
+    assert_vault_error(
+        env.appoint_investor_liquidator(&vault, &bob).await,
+        ErrorCode::DelegateNotAvailableForLiquidation,
+    );
+    let sol_usdc = env.sol_usdc_market;
+    assert_vault_error(
+        env.investor_liquidate_usdc_market(&vault, &bob, &sol_usdc)
+            .await,
+        ErrorCode::InvalidLiquidator,
+    );
+
+    env.investor_liquidate_usdc_market(&vault, &alice, &sol_usdc)
+        .await
+        .unwrap();
+    assert!(env.usdc_balance(&vault.key).await >= usdc(1_200));
+
+    let request = env.investor(&vault, &alice).await.last_withdraw_request;
+    let payout = expected_payout(&mut env, &vault, &request).await;
+    env.investor_withdraw(&vault, &alice).await.unwrap();
+    assert_eq!(env.usdc_balance(&alice.pubkey()).await, payout);
+    assert!(!env
+        .investor(&vault, &alice)
+        .await
+        .last_withdraw_request
+        .pending());
+    assert_liquidation_finished(&mut env, &vault).await;
+
+    // the next investor can liquidate once the vault USDC is paid out
+    env.appoint_investor_liquidator(&vault, &bob).await.unwrap();
+    env.investor_liquidate_usdc_market(&vault, &bob, &sol_usdc)
+        .await
+        .unwrap();
+    let request = env.investor(&vault, &bob).await.last_withdraw_request;
+    let payout = expected_payout(&mut env, &vault, &request).await;
+    env.investor_withdraw(&vault, &bob).await.unwrap();
+    assert_eq!(env.usdc_balance(&bob.pubkey()).await, payout);
+    assert_liquidation_finished(&mut env, &vault).await;
+}
+
+#[tokio::test]
+async fn investor_liquidates_sol_market() {
+    let mut env = TestEnv::new().await;
+    let vault = env.create_vault("investor sol liquidation").await;
+    let investor = env.create_investor(&vault, usdc(2_000)).await;
+    env.investor_deposit(&vault, &investor, usdc(2_000))
+        .await
+        .unwrap();
+    env.trade_into_markets(&vault).await;
+
+    // more than the SOL free on JUP/SOL is worth, so some JUP is sold
+    env.investor_request_withdraw(&vault, &investor, usdc(300), WithdrawUnit::Token)
+        .await
+        .unwrap();
+    env.warp(REDEEM_PERIOD + 1).await;
+    env.appoint_investor_liquidator(&vault, &investor)
+        .await
+        .unwrap();
+
+    let jup_sol = env.jup_sol_market;
+    env.investor_liquidate_sol_market(&vault, &investor, &jup_sol)
+        .await
+        .unwrap();
+    assert!(env.position(&vault, &jup_sol).await.base_lots_free < JUP_BOUGHT);
+
+    let request = env.investor(&vault, &investor).await.last_withdraw_request;
+    let payout = expected_payout(&mut env, &vault, &request).await;
+    env.investor_withdraw(&vault, &investor).await.unwrap();
+    assert_eq!(env.usdc_balance(&investor.pubkey()).await, payout);
+    assert_liquidation_finished(&mut env, &vault).await;
+}
+
+#[tokio::test]
+async fn manager_liquidates_usdc_market() {
+    let mut env = TestEnv::new().await;
+    let vault = env.create_vault("manager usdc liquidation").await;
+    let manager = vault.manager.pubkey();
+    let usdc_mint = env.usdc_mint;
+    env.mint_to(&manager, &usdc_mint, usdc(2_000)).await;
+    env.manager_deposit(&vault, usdc(2_000)).await.unwrap();
+    env.trade_into_markets(&vault).await;
+
+    env.manager_request_withdraw(&vault, usdc(1_200), WithdrawUnit::Token)
+        .await
+        .unwrap();
+    env.warp(REDEEM_PERIOD + 1).await;
+    env.appoint_manager_liquidator(&vault).await.unwrap();
+    assert_eq!(env.vault(&vault.key).await.liquidator, manager);
+
+    let sol_usdc = env.sol_usdc_market;
+    env.manager_liquidate_usdc_market(&vault, &sol_usdc)
+        .await
+        .unwrap();
+
+    let request = env.vault(&vault.key).await.last_manager_withdraw_request;
+    let payout = expected_payout(&mut env, &vault, &request).await;
+    env.manager_withdraw(&vault).await.unwrap();
+    assert_eq!(env.usdc_balance(&manager).await, payout);
+    assert_liquidation_finished(&mut env, &vault).await;
+}
+
+#[tokio::test]
+async fn manager_liquidates_sol_market() {
+    let mut env = TestEnv::new().await;
+    let vault = env.create_vault("manager sol liquidation").await;
+    let manager = vault.manager.pubkey();
+    let usdc_mint = env.usdc_mint;
+    env.mint_to(&manager, &usdc_mint, usdc(2_000)).await;
+    env.manager_deposit(&vault, usdc(2_000)).await.unwrap();
+    env.trade_into_markets(&vault).await;
+
+    env.manager_request_withdraw(&vault, usdc(300), WithdrawUnit::Token)
+        .await
+        .unwrap();
+    env.warp(REDEEM_PERIOD + 1).await;
+    env.appoint_manager_liquidator(&vault).await.unwrap();
+
+    let jup_sol = env.jup_sol_market;
+    env.manager_liquidate_sol_market(&vault, &jup_sol)
+        .await
+        .unwrap();
+    assert!(env.position(&vault, &jup_sol).await.base_lots_free < JUP_BOUGHT);
+
+    let request = env.vault(&vault.key).await.last_manager_withdraw_request;
+    let payout = expected_payout(&mut env, &vault, &request).await;
+    env.manager_withdraw(&vault).await.unwrap();
+    assert_eq!(env.usdc_balance(&manager).await, payout);
+    assert_liquidation_finished(&mut env, &vault).await;
+}
+
+/// A vault whose protocol has earned a year of 10% protocol fees on a $2000 deposit,
+/// with the deposit traded into both markets.
+async fn protocol_fee_vault(env: &mut TestEnv, name: &str) -> TestVault {
+    let vault = env
+        .create_vault_with(name, |params| VaultParams {
+            protocol_fee: 100_000,
+            ..params
+        })
+        .await;
+    let investor = env.create_investor(&vault, usdc(2_000)).await;
+    env.investor_deposit(&vault, &investor, usdc(2_000))
+        .await
+        .unwrap();
+    env.warp(ONE_YEAR as i64).await;
+    env.crystallize_fees(&vault, &[]).await.unwrap();
+    assert!(env.vault(&vault.key).await.protocol_profit_and_fee_shares > 0);
+    env.trade_into_markets(&vault).await;
+    vault
+}
+
+#[tokio::test]
+async fn protocol_liquidates_usdc_market() {
+    let mut env = TestEnv::new().await;
+    let vault = protocol_fee_vault(&mut env, "protocol usdc liquidation").await;
+    let protocol = vault.protocol.pubkey();
+
+    env.protocol_request_withdraw(&vault, usdc(150), WithdrawUnit::Token)
+        .await
+        .unwrap();
+    env.warp(REDEEM_PERIOD + 1).await;
+    env.appoint_protocol_liquidator(&vault).await.unwrap();
+    assert_eq!(env.vault(&vault.key).await.liquidator, protocol);
+
+    // the free USDC on SOL/USDC covers the request, so nothing is sold
+    let sol_usdc = env.sol_usdc_market;
+    let base_lots_before = env.position(&vault, &sol_usdc).await.base_lots_free;
+    env.protocol_liquidate_usdc_market(&vault, &sol_usdc)
+        .await
+        .unwrap();
+    assert_eq!(
+        env.position(&vault, &sol_usdc).await.base_lots_free,
+        base_lots_before
+    );
+
+    // settle the fees accrued over the redeem period so the withdraw charges none
+    env.crystallize_fees(&vault, &[]).await.unwrap();
+    let request = env.vault(&vault.key).await.last_protocol_withdraw_request;
+    let payout = expected_payout(&mut env, &vault, &request).await;
+    env.protocol_withdraw(&vault).await.unwrap();
+    assert_eq!(env.usdc_balance(&protocol).await, payout);
+    assert_liquidation_finished(&mut env, &vault).await;
+}
+
+#[tokio::test]
+async fn protocol_liquidates_sol_market() {
+    let mut env = TestEnv::new().await;
+    let vault = protocol_fee_vault(&mut env, "protocol sol liquidation").await;
+    let protocol = vault.protocol.pubkey();
+
+    env.protocol_request_withdraw(&vault, usdc(150), WithdrawUnit::Token)
+        .await
+        .unwrap();
+    env.warp(REDEEM_PERIOD + 1).await;
+    env.appoint_protocol_liquidator(&vault).await.unwrap();
+
+    let jup_sol = env.jup_sol_market;
+    env.protocol_liquidate_sol_market(&vault, &jup_sol)
+        .await
+        .unwrap();
+    assert!(env.usdc_balance(&vault.key).await > 0);
+
+    env.crystallize_fees(&vault, &[]).await.unwrap();
+    let request = env.vault(&vault.key).await.last_protocol_withdraw_request;
+    let payout = expected_payout(&mut env, &vault, &request).await;
+    env.protocol_withdraw(&vault).await.unwrap();
+    assert_eq!(env.usdc_balance(&protocol).await, payout);
+    assert_liquidation_finished(&mut env, &vault).await;
+}
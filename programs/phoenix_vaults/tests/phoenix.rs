@@ -230,7 +230,9 @@ async fn bootstrap_market(cfg: BootstrapMarketConfig<'_>) -> anyhow::Result<()>
     Ok(())
 }
 
+/// Deploys the markets to a running local validator, run by `test.sh` for the anchor tests.
 #[tokio::test]
+#[ignore = "needs a running local validator"]
 async fn bootstrap_markets() -> anyhow::Result<()> {
     let payer = Keypair::from_bytes(&MOCK_MARKET_AUTHORITY_KEYPAIR).unwrap();
     let authority = Keypair::from_bytes(&MOCK_MARKET_AUTHORITY_KEYPAIR).unwrap();
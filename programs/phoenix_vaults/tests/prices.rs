@@ -25,6 +25,7 @@ struct MarketInfo {
 }
 
 #[tokio::test]
+#[ignore = "needs mainnet RPC"]
 async fn market_prices() -> anyhow::Result<()> {
     const RPC_URL: &str = "https://api.mainnet-beta.solana.com";
     // const RPC_URL: &str = "https://mainnet.helius-rpc.com/?api-key=0b810c4e-acb6-49a3-b2cd-90e671480ca8";
@@ -0,0 +1,286 @@
+use phoenix::state::Side;
+use phoenix_vaults::constants::{ONE_YEAR, PERCENTAGE_PRECISION};
+use phoenix_vaults::error::ErrorCode;
+use phoenix_vaults::instructions::VaultParams;
+use phoenix_vaults::math::{amount_to_shares, calculate_rebase_info};
+use phoenix_vaults::state::{MarketTransferParams, Size, Vault, VaultState, WithdrawUnit};
+use phoenix_vaults_client::{deserialize_account, get_investor_address};
+use solana_sdk::signer::Signer;
+
+mod fixtures;
+use crate::fixtures::*;
+
+#[tokio::test]
+async fn investor_deposit_and_withdraw() {
+    let mut env = TestEnv::new().await;
+    let vault = env.create_vault("deposit and withdraw").await;
+    let investor = env.create_investor(&vault, usdc(1_000)).await;
+
+    env.investor_deposit(&vault, &investor, usdc(1_000))
+        .await
+        .unwrap();
+    let state = env.investor(&vault, &investor).await;
+    assert_eq!(state.unchecked_vault_shares(), usdc(1_000) as u128);
+    assert_eq!(env.usdc_balance(&vault.key).await, usdc(1_000));
+
+    // half the shares
+    env.investor_request_withdraw(&vault, &investor, 500_000, WithdrawUnit::SharesPercent)
+        .await
+        .unwrap();
+    assert_vault_error(
+        env.investor_withdraw(&vault, &investor).await,
+        ErrorCode::CannotWithdrawBeforeRedeemPeriodEnd,
+    );
+
+    env.warp(REDEEM_PERIOD).await;
+    // the vault holds the USDC, so there is nothing to liquidate
+    assert_vault_error(
+        env.appoint_investor_liquidator(&vault, &investor).await,
+        ErrorCode::InvestorCanWithdraw,
+    );
+
+    env.investor_withdraw(&vault, &investor).await.unwrap();
+    assert_eq!(env.usdc_balance(&investor.pubkey()).await, usdc(500));
+    assert_eq!(env.usdc_balance(&vault.key).await, usdc(500));
+    let state = env.investor(&vault, &investor).await;
+    assert_eq!(state.unchecked_vault_shares(), usdc(500) as u128);
+    assert!(!state.last_withdraw_request.pending());
+}
+
+#[tokio::test]
+async fn delegate_trades_into_markets() {
+    let mut env = TestEnv::new().await;
+    let vault = env.create_vault("trades").await;
+    let investor = env.create_investor(&vault, usdc(1_000)).await;
+    env.investor_deposit(&vault, &investor, usdc(1_000))
+        .await
+        .unwrap();
+
+    env.trade_into_markets(&vault).await;
+    assert_eq!(env.usdc_balance(&vault.key).await, 0);
+
+    let sol_usdc = env.sol_usdc_market;
+    let jup_sol = env.jup_sol_market;
+    let sol_position = env.position(&vault, &sol_usdc).await;
+    assert_eq!(sol_position.base_lots_free, SOL_BOUGHT - SOL_MOVED);
+    assert!(sol_position.quote_lots_free > 0);
+    let jup_position = env.position(&vault, &jup_sol).await;
+    assert_eq!(jup_position.base_lots_free, JUP_BOUGHT);
+
+    // bought at the ask and valued at the bid
+    let equity = env.equity(&vault).await;
+    assert!(equity > 0 && equity < usdc(1_000));
+
+    // the next deposit is priced at the equity the client computes
+    let second = env.create_investor(&vault, usdc(500)).await;
+    let total_shares = env.vault(&vault.key).await.total_shares;
+    env.investor_deposit(&vault, &second, usdc(500))
+        .await
+        .unwrap();
+    let state = env.investor(&vault, &second).await;
+    assert_eq!(
+        state.unchecked_vault_shares(),
+        amount_to_shares(usdc(500), total_shares, equity).unwrap()
+    );
+}
+
+#[tokio::test]
+async fn management_and_protocol_fees_accrue() {
+    let mut env = TestEnv::new().await;
+    let vault = env
+        .create_vault_with("fees", |params| VaultParams {
+            management_fee: 20_000,
+            protocol_fee: 10_000,
+            ..params
+        })
+        .await;
+    let investor = env.create_investor(&vault, usdc(1_000)).await;
+    env.investor_deposit(&vault, &investor, usdc(1_000))
+        .await
+        .unwrap();
+
+    env.warp(ONE_YEAR as i64 / 2).await;
+    let before = env.vault(&vault.key).await;
+    env.crystallize_fees(&vault, &[]).await.unwrap();
+    let now = env.now().await;
+
+    // all shares are the investor's, so the depositor equity is the deposit
+    let since = (now - before.last_fee_update_ts) as i128;
+    let total_fee = 30_000_i128;
+    let total_fee_payment =
+        usdc(1_000) as i128 * total_fee / PERCENTAGE_PRECISION as i128 * since / ONE_YEAR as i128;
+    let after = env.vault(&vault.key).await;
+    assert_eq!(
+        after.manager_total_fee as i128,
+        total_fee_payment * 20_000 / total_fee
+    );
+    assert_eq!(
+        after.protocol_total_fee as i128,
+        total_fee_payment * 10_000 / total_fee
+    );
+    assert!(after.protocol_profit_and_fee_shares > 0);
+    assert!(after.total_shares > before.total_shares);
+    assert_eq!(after.investor_shares, before.investor_shares);
+    assert_eq!(after.last_fee_update_ts, now);
+}
+
+#[tokio::test]
+async fn protocol_fee_accrues_to_protocol_shares() {
+    let mut env = TestEnv::new().await;
+    let vault = env
+        .create_vault_with("protocol fee", |params| VaultParams {
+            protocol_fee: 100_000,
+            ..params
+        })
+        .await;
+    let investor = env.create_investor(&vault, usdc(1_000)).await;
+    env.investor_deposit(&vault, &investor, usdc(1_000))
+        .await
+        .unwrap();
+
+    env.warp(ONE_YEAR as i64).await;
+    let before = env.vault(&vault.key).await;
+    env.crystallize_fees(&vault, &[]).await.unwrap();
+    let now = env.now().await;
+
+    let depositor_equity = usdc(1_000) as u128;
+    let since = (now - before.last_fee_update_ts) as u128;
+    let payment = depositor_equity * 100_000 / PERCENTAGE_PRECISION * since / ONE_YEAR;
+    let factor = depositor_equity * PERCENTAGE_PRECISION / (depositor_equity - payment);
+    let total_shares = before.total_shares * factor / PERCENTAGE_PRECISION;
+
+    let after = env.vault(&vault.key).await;
+    assert_eq!(after.protocol_total_fee as u128, payment);
+    assert_eq!(after.manager_total_fee, 0);
+    assert_eq!(after.total_shares, total_shares);
+    assert_eq!(
+        after.protocol_profit_and_fee_shares,
+        total_shares - before.total_shares
+    );
+}
+
+#[tokio::test]
+async fn crystallize_fees_rebases_shares() {
+    let mut env = TestEnv::new().await;
+    let vault = env.create_vault("rebase").await;
+    let investor = env.create_investor(&vault, usdc(1_000)).await;
+    env.investor_deposit(&vault, &investor, usdc(1_000))
+        .await
+        .unwrap();
+
+    // spend nearly all the USDC on SOL
+    let sol_usdc = env.sol_usdc_market;
+    env.claim_seat(&vault, &sol_usdc).await.unwrap();
+    env.market_deposit(
+        &vault,
+        &sol_usdc,
+        MarketTransferParams {
+            // $1000 in lots of 10 atoms
+            quote_lots: 100_000_000,
+            base_lots: 0,
+        },
+    )
+    .await
+    .unwrap();
+    env.place_limit_order(&vault, &sol_usdc, Side::Bid, SOL_USDC_ASK, 9_899)
+        .await
+        .unwrap();
+
+    // SOL collapses to $0.01
+    env.maker_cancel_all(&sol_usdc).await;
+    env.maker_order(&sol_usdc, Side::Bid, 1, 100 * BASE_LOTS_PER_UNIT)
+        .await;
+
+    let before = env.vault(&vault.key).await;
+    let investor_shares_before = env
+        .investor(&vault, &investor)
+        .await
+        .unchecked_vault_shares();
+    let equity = env.equity(&vault).await;
+    assert!((equity as u128) < before.total_shares / 10);
+    let (expo, divisor) = calculate_rebase_info(before.total_shares, equity).unwrap();
+    assert!(expo > 0);
+
+    env.crystallize_fees(&vault, &[&investor]).await.unwrap();
+
+    let after = env.vault(&vault.key).await;
+    assert_eq!(after.shares_base, before.shares_base + expo);
+    assert_eq!(after.total_shares, before.total_shares / divisor);
+    assert_eq!(after.investor_shares, before.investor_shares / divisor);
+    let state = env.investor(&vault, &investor).await;
+    assert_eq!(state.vault_shares_base, after.shares_base);
+    assert_eq!(
+        state.unchecked_vault_shares(),
+        investor_shares_before / divisor
+    );
+}
+
+#[tokio::test]
+async fn crystallize_fees_rejects_investors_of_other_vaults() {
+    let mut env = TestEnv::new().await;
+    let vault = env.create_vault("crystallize").await;
+    let other_vault = env.create_vault("other").await;
+    let investor = env.create_investor(&other_vault, usdc(1_000)).await;
+
+    let other_investor = get_investor_address(&other_vault.key, &investor.pubkey());
+    assert_vault_error(
+        env.crystallize_fees_with(&vault, &[other_investor]).await,
+        ErrorCode::InvalidInvestorAccount,
+    );
+    env.crystallize_fees(&vault, &[]).await.unwrap();
+}
+
+#[tokio::test]
+async fn migrate_vault_grows_legacy_vaults() {
+    let mut env = TestEnv::new().await;
+    let vault = env
+        .create_vault_with("migrate", |params| VaultParams {
+            liquidation_window: 60 * 30,
+            ..params
+        })
+        .await;
+    let investor = env.create_investor(&vault, usdc(1_000)).await;
+    let before = env.vault(&vault.key).await;
+
+    // a vault created before the appended fields is 200 bytes short
+    let legacy_size = Vault::SIZE - 200;
+    let mut account = env
+        .context
+        .banks_client
+        .get_account(vault.key)
+        .await
+        .unwrap()
+        .unwrap();
+    account.data.truncate(legacy_size);
+    account.lamports = env
+        .context
+        .banks_client
+        .get_rent()
+        .await
+        .unwrap()
+        .minimum_balance(legacy_size);
+    env.context.set_account(&vault.key, &account.into());
+    assert!(deserialize_account::<Vault>(&env.account_data(&vault.key).await).is_err());
+
+    env.migrate_vault(&vault).await.unwrap();
+    assert_eq!(env.account_data(&vault.key).await.len(), Vault::SIZE);
+    let after = env.vault(&vault.key).await;
+    assert_eq!(after.name, before.name);
+    assert_eq!(after.manager, before.manager);
+    assert_eq!(after.usdc_token_account, before.usdc_token_account);
+    assert_eq!(after.bump, before.bump);
+    // the appended fields are zeroed, so the vault is active with the default liquidation window
+    assert_eq!(after.state, VaultState::Active);
+    assert_eq!(after.epoch_duration, 0);
+    assert_eq!(after.liquidation_window, 0);
+
+    // migrating again is a no-op
+    env.migrate_vault(&vault).await.unwrap();
+    assert_eq!(env.vault(&vault.key).await, after);
+
+    env.investor_deposit(&vault, &investor, usdc(1_000))
+        .await
+        .unwrap();
+    let state = env.investor(&vault, &investor).await;
+    assert_eq!(state.unchecked_vault_shares(), usdc(1_000) as u128);
+}
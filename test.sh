@@ -84,7 +84,7 @@ bkg anchor localnet
 sleep 5
 
 # run bootstrap.sh
-cargo test --package phoenix-vaults --test phoenix bootstrap_markets -- --exact --nocapture
+cargo test --package phoenix-vaults --test phoenix bootstrap_markets -- --exact --nocapture --ignored
 
 if [[ $no_test == false ]]; then
   yarn anchor-tests
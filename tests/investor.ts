@@ -146,6 +146,17 @@ describe('phoenixVaults', () => {
 			protocol: protocol.publicKey,
 			protocolFee: new BN(0),
 			protocolProfitShare: 100_000,
+			epochDuration: new BN(0),
+			entryFeeBps: 0,
+			exitFeeBps: 0,
+			maxDrawdown: 0,
+			managerMinStake: 0,
+			liquidityBuffer: 0,
+			lockupDuration: new BN(0),
+			lockupPenaltyBps: 0,
+			liquidationWindow: new BN(0),
+			fundraisingSoftCap: new BN(0),
+			fundraisingDeadline: new BN(0),
 		};
 		await program.methods
 			.initializeVault(config)
@@ -203,7 +214,7 @@ describe('phoenixVaults', () => {
 		});
 
 		const ix = await program.methods
-			.investorDeposit(usdcAmount)
+			.investorDeposit(usdcAmount, null, null)
 			.accounts({
 				vault: vaultKey,
 				investor,
@@ -310,7 +321,7 @@ describe('phoenixVaults', () => {
 		});
 
 		const ix = await program.methods
-			.investorWithdraw()
+			.investorWithdraw(null, null)
 			.accounts({
 				vault: vaultKey,
 				investor,
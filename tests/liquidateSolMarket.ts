@@ -190,6 +190,17 @@ describe('phoenixVaults', () => {
 			protocol: protocol.publicKey,
 			protocolFee: new BN(0),
 			protocolProfitShare: 100_000,
+			epochDuration: new BN(0),
+			entryFeeBps: 0,
+			exitFeeBps: 0,
+			maxDrawdown: 0,
+			managerMinStake: 0,
+			liquidityBuffer: 0,
+			lockupDuration: new BN(0),
+			lockupPenaltyBps: 0,
+			liquidationWindow: new BN(0),
+			fundraisingSoftCap: new BN(0),
+			fundraisingDeadline: new BN(0),
 		};
 		await program.methods
 			.initializeVault(config)
@@ -325,7 +336,11 @@ describe('phoenixVaults', () => {
 		await sendAndConfirm(conn, payer, [createAtaIx, mintToIx], [mintAuth]);
 
 		const ix = await program.methods
-			.investorDeposit(new BN(usdcToDeposit * QUOTE_PRECISION.toNumber()))
+			.investorDeposit(
+				new BN(usdcToDeposit * QUOTE_PRECISION.toNumber()),
+				null,
+				null
+			)
 			.accounts({
 				vault: vaultKey,
 				investor,
@@ -386,6 +401,7 @@ describe('phoenixVaults', () => {
 			.accounts({
 				vault: vaultKey,
 				delegate: manager.publicKey,
+				marketRegistry,
 				phoenix: PHOENIX_PROGRAM_ID,
 				logAuthority: getLogAuthority(),
 				market: solUsdcMarket,
@@ -740,6 +756,7 @@ describe('phoenixVaults', () => {
 			.accounts({
 				vault: vaultKey,
 				delegate: manager.publicKey,
+				marketRegistry,
 				phoenix: PHOENIX_PROGRAM_ID,
 				logAuthority: getLogAuthority(),
 				market: jupSolMarket,
@@ -1095,7 +1112,7 @@ describe('phoenixVaults', () => {
 			};
 		});
 		const withdrawIx = await program.methods
-			.investorWithdraw()
+			.investorWithdraw(null, null)
 			.accounts({
 				vault: vaultKey,
 				investor,
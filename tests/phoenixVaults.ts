@@ -185,6 +185,17 @@ describe('phoenixVaults', () => {
 			protocol: protocol.publicKey,
 			protocolFee: new BN(0),
 			protocolProfitShare: 100_000,
+			epochDuration: new BN(0),
+			entryFeeBps: 0,
+			exitFeeBps: 0,
+			maxDrawdown: 0,
+			managerMinStake: 0,
+			liquidityBuffer: 0,
+			lockupDuration: new BN(0),
+			lockupPenaltyBps: 0,
+			liquidationWindow: new BN(0),
+			fundraisingSoftCap: new BN(0),
+			fundraisingDeadline: new BN(0),
 		};
 		await program.methods
 			.initializeVault(config)
@@ -211,6 +222,13 @@ describe('phoenixVaults', () => {
 			profitShare: null,
 			hurdleRate: null,
 			permissioned: null,
+			entryFeeBps: null,
+			exitFeeBps: null,
+			maxDrawdown: null,
+			managerMinStake: null,
+			liquidityBuffer: null,
+			lockupDuration: null,
+			liquidationWindow: null,
 		};
 		try {
 			const changeToDelegate = await program.methods
@@ -322,7 +340,7 @@ describe('phoenixVaults', () => {
 		await sendAndConfirm(conn, payer, [createAtaIx, mintToIx], [mintAuth]);
 
 		const ix = await program.methods
-			.investorDeposit(usdcAmount)
+			.investorDeposit(usdcAmount, null, null)
 			.accounts({
 				vault: vaultKey,
 				investor,
@@ -383,6 +401,7 @@ describe('phoenixVaults', () => {
 			.accounts({
 				vault: vaultKey,
 				delegate: manager.publicKey,
+				marketRegistry,
 				phoenix: PHOENIX_PROGRAM_ID,
 				logAuthority: getLogAuthority(),
 				market: solUsdcMarket,
@@ -1100,7 +1119,7 @@ describe('phoenixVaults', () => {
 
 	it('Withdraw', async () => {
 		const withdrawIx = await program.methods
-			.investorWithdraw()
+			.investorWithdraw(null, null)
 			.accounts({
 				vault: vaultKey,
 				investor,
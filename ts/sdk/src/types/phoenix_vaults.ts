@@ -162,7 +162,12 @@ export type PhoenixVaults = {
 		},
 		{
 			name: 'investorDeposit';
-			docs: ['Investor deposits funds to the vault USDC token account.'];
+			docs: [
+				'Investor deposits funds to the vault USDC token account.',
+				'',
+				'Fails with `SlippageExceeded` if fewer than `min_shares_out` shares are minted,',
+				'or with `DeadlineExceeded` if executed after the `deadline` unix timestamp.'
+			];
 			accounts: [
 				{
 					name: 'vault';
@@ -184,6 +189,12 @@ export type PhoenixVaults = {
 					isMut: false;
 					isSigner: false;
 				},
+				{
+					name: 'navHistory';
+					isMut: true;
+					isSigner: false;
+					isOptional: true;
+				},
 				{
 					name: 'investorQuoteTokenAccount';
 					isMut: true;
@@ -204,6 +215,18 @@ export type PhoenixVaults = {
 				{
 					name: 'amount';
 					type: 'u64';
+				},
+				{
+					name: 'minSharesOut';
+					type: {
+						option: 'u128';
+					};
+				},
+				{
+					name: 'deadline';
+					type: {
+						option: 'i64';
+					};
 				}
 			];
 		},
@@ -216,7 +239,10 @@ export type PhoenixVaults = {
 				'acquire permission to liquidate the vault market positions.',
 				'',
 				'Then call `liquidate_usdc_market` or `liquidate_sol_market` to forcefully swap a vault market position back to USDC,',
-				'and then withdraw back to the investor.'
+				'and then withdraw back to the investor.',
+				'',
+				'Fails with `SlippageExceeded` if less than `min_amount_out` USDC is paid out,',
+				'or with `DeadlineExceeded` if executed after the `deadline` unix timestamp.'
 			];
 			accounts: [
 				{
@@ -239,6 +265,12 @@ export type PhoenixVaults = {
 					isMut: false;
 					isSigner: false;
 				},
+				{
+					name: 'navHistory';
+					isMut: true;
+					isSigner: false;
+					isOptional: true;
+				},
 				{
 					name: 'investorQuoteTokenAccount';
 					isMut: true;
@@ -300,6 +332,93 @@ export type PhoenixVaults = {
 					isSigner: false;
 				}
 			];
+			args: [
+				{
+					name: 'minAmountOut';
+					type: {
+						option: 'u64';
+					};
+				},
+				{
+					name: 'deadline';
+					type: {
+						option: 'i64';
+					};
+				}
+			];
+		},
+		{
+			name: 'investorWithdrawInKind';
+			docs: [
+				'Investor withdraws a pro-rata slice of every vault market position and of the vault token balances,',
+				'instead of waiting for the vault to be liquidated to USDC.',
+				'',
+				'The remaining accounts are the SOL/USDC market followed by, for each vault market position in order:',
+				'market, vault base token account, investor base token account, market base vault, market quote vault.'
+			];
+			accounts: [
+				{
+					name: 'vault';
+					isMut: true;
+					isSigner: false;
+				},
+				{
+					name: 'investor';
+					isMut: true;
+					isSigner: false;
+				},
+				{
+					name: 'authority';
+					isMut: false;
+					isSigner: true;
+				},
+				{
+					name: 'marketRegistry';
+					isMut: false;
+					isSigner: false;
+				},
+				{
+					name: 'navHistory';
+					isMut: true;
+					isSigner: false;
+					isOptional: true;
+				},
+				{
+					name: 'investorUsdcTokenAccount';
+					isMut: true;
+					isSigner: false;
+				},
+				{
+					name: 'investorSolTokenAccount';
+					isMut: true;
+					isSigner: false;
+				},
+				{
+					name: 'vaultUsdcTokenAccount';
+					isMut: true;
+					isSigner: false;
+				},
+				{
+					name: 'vaultSolTokenAccount';
+					isMut: true;
+					isSigner: false;
+				},
+				{
+					name: 'phoenix';
+					isMut: false;
+					isSigner: false;
+				},
+				{
+					name: 'logAuthority';
+					isMut: false;
+					isSigner: false;
+				},
+				{
+					name: 'tokenProgram';
+					isMut: false;
+					isSigner: false;
+				}
+			];
 			args: [];
 		},
 		{
@@ -540,7 +659,9 @@ export type PhoenixVaults = {
 		{
 			name: 'marketDeposit';
 			docs: [
-				'Vault delegate deposits vault assets from the USDC or SOL token account to a Phoenix market.'
+				'Vault delegate deposits vault assets from the USDC or SOL token account to a Phoenix market.',
+				"USDC can't be deposited below the vault liquidity buffer, so the remaining accounts must include",
+				'the markets needed for equity when depositing from the USDC token account.'
 			];
 			accounts: [
 				{
@@ -561,6 +682,11 @@ export type PhoenixVaults = {
 						'Is manager by default, but can be delegated to another pubkey using `update_delegate`'
 					];
 				},
+				{
+					name: 'marketRegistry';
+					isMut: false;
+					isSigner: false;
+				},
 				{
 					name: 'phoenix';
 					isMut: false;
@@ -1438,6 +1564,31 @@ export type PhoenixVaults = {
 				}
 			];
 		},
+		{
+			name: 'migrateVault';
+			docs: [
+				'Permissionless instruction to grow a vault created by an earlier version of the program to the current [`Vault`] size.',
+				'Other instructions fail on the vault until it is migrated.'
+			];
+			accounts: [
+				{
+					name: 'vault';
+					isMut: true;
+					isSigner: false;
+				},
+				{
+					name: 'payer';
+					isMut: true;
+					isSigner: true;
+				},
+				{
+					name: 'systemProgram';
+					isMut: false;
+					isSigner: false;
+				}
+			];
+			args: [];
+		},
 		{
 			name: 'managerWithdraw';
 			accounts: [
@@ -1456,6 +1607,12 @@ export type PhoenixVaults = {
 					isMut: false;
 					isSigner: false;
 				},
+				{
+					name: 'navHistory';
+					isMut: true;
+					isSigner: false;
+					isOptional: true;
+				},
 				{
 					name: 'managerQuoteTokenAccount';
 					isMut: true;
@@ -1537,6 +1694,12 @@ export type PhoenixVaults = {
 					isMut: false;
 					isSigner: false;
 				},
+				{
+					name: 'navHistory';
+					isMut: true;
+					isSigner: false;
+					isOptional: true;
+				},
 				{
 					name: 'managerQuoteTokenAccount';
 					isMut: true;
@@ -1641,6 +1804,12 @@ export type PhoenixVaults = {
 					isMut: false;
 					isSigner: false;
 				},
+				{
+					name: 'navHistory';
+					isMut: true;
+					isSigner: false;
+					isOptional: true;
+				},
 				{
 					name: 'protocolQuoteTokenAccount';
 					isMut: true;
@@ -1768,1234 +1937,3755 @@ export type PhoenixVaults = {
 			args: [];
 		},
 		{
-			name: 'cancelAllOrders';
+			name: 'settleEpoch';
+			docs: [
+				'Permissionless crank that prices the deposits and withdraw requests queued during an epoch',
+				'at one equity snapshot, and lets the [`Investor`] accounts in the remaining accounts claim the result.'
+			];
 			accounts: [
 				{
 					name: 'vault';
 					isMut: true;
 					isSigner: false;
-					docs: [
-						'If delegate has authority to sign for vault, then any Phoenix CPI is valid.',
-						'Phoenix CPI validates that opaque instruction data is a [`PhoenixInstruction`],',
-						'so this is safe since any Phoenix CPI is secure.'
-					];
 				},
 				{
-					name: 'delegate';
+					name: 'marketRegistry';
 					isMut: false;
-					isSigner: true;
-					docs: [
-						'Is manager by default, but can be delegated to another pubkey using `update_delegate`'
-					];
+					isSigner: false;
 				},
 				{
-					name: 'phoenix';
+					name: 'vaultUsdcTokenAccount';
+					isMut: false;
+					isSigner: false;
+				}
+			];
+			args: [];
+		},
+		{
+			name: 'crystallizeFees';
+			docs: [
+				'Permissionless crank that applies the time-based management and protocol fees,',
+				'and processes profit share for the [`Investor`] accounts in the remaining accounts.'
+			];
+			accounts: [
+				{
+					name: 'vault';
+					isMut: true;
+					isSigner: false;
+				},
+				{
+					name: 'marketRegistry';
 					isMut: false;
 					isSigner: false;
 				},
 				{
-					name: 'logAuthority';
+					name: 'vaultUsdcTokenAccount';
+					isMut: false;
+					isSigner: false;
+				}
+			];
+			args: [];
+		},
+		{
+			name: 'initializeNavHistory';
+			docs: [
+				'Create the [`NavHistory`] ring buffer of share price samples for a [`Vault`].'
+			];
+			accounts: [
+				{
+					name: 'vault';
 					isMut: false;
 					isSigner: false;
 				},
 				{
-					name: 'market';
+					name: 'navHistory';
+					isMut: true;
+					isSigner: false;
+				},
+				{
+					name: 'payer';
 					isMut: true;
+					isSigner: true;
+				},
+				{
+					name: 'rent';
+					isMut: false;
+					isSigner: false;
+				},
+				{
+					name: 'systemProgram';
+					isMut: false;
 					isSigner: false;
 				}
 			];
 			args: [];
 		},
 		{
-			name: 'cancelMultipleOrdersById';
+			name: 'updateNavHistory';
+			docs: [
+				'Permissionless crank that appends a sample of the vault equity and shares to the [`NavHistory`].'
+			];
 			accounts: [
 				{
 					name: 'vault';
 					isMut: true;
 					isSigner: false;
-					docs: [
-						'If delegate has authority to sign for vault, then any Phoenix CPI is valid.',
-						'Phoenix CPI validates that opaque instruction data is a [`PhoenixInstruction`],',
-						'so this is safe since any Phoenix CPI is secure.'
-					];
 				},
 				{
-					name: 'delegate';
+					name: 'navHistory';
+					isMut: true;
+					isSigner: false;
+				},
+				{
+					name: 'marketRegistry';
 					isMut: false;
-					isSigner: true;
-					docs: [
-						'Is manager by default, but can be delegated to another pubkey using `update_delegate`'
-					];
+					isSigner: false;
 				},
 				{
-					name: 'phoenix';
+					name: 'vaultUsdcTokenAccount';
+					isMut: false;
+					isSigner: false;
+				}
+			];
+			args: [];
+		},
+		{
+			name: 'initializeSharePrice';
+			docs: [
+				'Manager creates the [`SharePrice`] account other programs read to price vault shares.'
+			];
+			accounts: [
+				{
+					name: 'vault';
 					isMut: false;
 					isSigner: false;
 				},
 				{
-					name: 'logAuthority';
+					name: 'manager';
 					isMut: false;
+					isSigner: true;
+				},
+				{
+					name: 'sharePrice';
+					isMut: true;
 					isSigner: false;
 				},
 				{
-					name: 'market';
+					name: 'payer';
 					isMut: true;
+					isSigner: true;
+				},
+				{
+					name: 'rent';
+					isMut: false;
+					isSigner: false;
+				},
+				{
+					name: 'systemProgram';
+					isMut: false;
 					isSigner: false;
 				}
 			];
 			args: [
 				{
-					name: 'params';
-					type: {
-						defined: 'CancelMultipleOrdersParams';
-					};
+					name: 'maxStalenessSlots';
+					type: 'u64';
 				}
 			];
-		}
-	];
-	accounts: [
+		},
 		{
-			name: 'investor';
-			type: {
-				kind: 'struct';
-				fields: [
-					{
-						name: 'vault';
-						docs: ['The vault deposited into'];
-						type: 'publicKey';
-					},
-					{
-						name: 'pubkey';
-						docs: [
-							"The vault depositor account's pubkey. It is a pda of vault and authority"
-						];
-						type: 'publicKey';
-					},
-					{
-						name: 'authority';
-						docs: [
-							'The authority is the address w permission to deposit/withdraw'
-						];
-						type: 'publicKey';
-					},
-					{
-						name: 'vaultShares';
-						docs: [
-							"share of vault owned by this depositor. vault_shares / vault.total_shares is depositor's ownership of vault_equity"
-						];
-						type: 'u128';
-					},
-					{
-						name: 'lastWithdrawRequest';
-						docs: ['last withdraw request'];
-						type: {
-							defined: 'WithdrawRequest';
-						};
-					},
-					{
-						name: 'lastValidTs';
-						docs: ['creation ts of vault depositor'];
-						type: 'i64';
-					},
-					{
-						name: 'netDeposits';
-						docs: ['lifetime net deposits of vault depositor for the vault'];
-						type: 'i64';
-					},
-					{
-						name: 'totalDeposits';
-						docs: ['lifetime total deposits'];
-						type: 'u64';
-					},
-					{
-						name: 'totalWithdraws';
-						docs: ['lifetime total withdraws'];
-						type: 'u64';
-					},
-					{
-						name: 'cumulativeProfitShareAmount';
-						docs: [
-							'the token amount of gains the vault depositor has paid performance fees on (in other words, cumulative profit)'
-						];
-						type: 'i64';
-					},
-					{
-						name: 'profitShareFeePaid';
-						docs: [
-							'the token amount profit share paid out to the manager and protocol'
-						];
-						type: 'u64';
-					},
-					{
-						name: 'vaultSharesBase';
-						docs: ['the exponent for vault_shares decimal places'];
-						type: 'u32';
-					},
-					{
-						name: 'padding1';
-						type: 'u32';
-					},
-					{
-						name: 'padding';
-						type: {
-							array: ['u64', 8];
-						};
-					}
-				];
-			};
+			name: 'updateSharePrice';
+			docs: [
+				'Permissionless crank that refreshes the [`SharePrice`] with the current vault equity.'
+			];
+			accounts: [
+				{
+					name: 'vault';
+					isMut: false;
+					isSigner: false;
+				},
+				{
+					name: 'sharePrice';
+					isMut: true;
+					isSigner: false;
+				},
+				{
+					name: 'marketRegistry';
+					isMut: false;
+					isSigner: false;
+				},
+				{
+					name: 'vaultUsdcTokenAccount';
+					isMut: false;
+					isSigner: false;
+				}
+			];
+			args: [];
 		},
 		{
-			name: 'marketRegistry';
+			name: 'resumeTrading';
 			docs: [
-				'DriftVaults validates vault user positions against the remaining accounts provided for those markets.',
-				'If the remaining accounts do not contain every market the user has a position in, then the instruction errors.',
-				'For Phoenix, we use our MarketRegistry as the official source of truth for the "list of markets",',
-				"and we can get the TraderState for the vault within each market to determine the vault's positions.",
-				'If the remaining accounts do not contain every market in the MarketRegistry that the vault has a position in,',
-				'then the instruction will error.'
+				'Manager and protocol resume delegate trading after the share price drawdown exceeded the vault `max_drawdown`.'
 			];
-			type: {
-				kind: 'struct';
-				fields: [
-					{
-						name: 'authority';
-						docs: [
-							'Authority over this account. This is a program admin-level keypair.'
-						];
-						type: 'publicKey';
-					},
-					{
-						name: 'solUsdcMarket';
-						docs: ['Phoenix SOL/USDC market'];
-						type: 'publicKey';
-					},
-					{
-						name: 'usdcMint';
-						docs: [
-							'Phoenix markets are denominated in USDC or SOL, so we must pre-define this'
-						];
-						type: 'publicKey';
-					},
-					{
-						name: 'solMint';
-						docs: [
-							'Phoenix markets are denominated in USDC or SOL, so we must pre-define this'
-						];
-						type: 'publicKey';
-					}
-				];
-			};
+			accounts: [
+				{
+					name: 'vault';
+					isMut: true;
+					isSigner: false;
+				},
+				{
+					name: 'manager';
+					isMut: false;
+					isSigner: true;
+				},
+				{
+					name: 'protocol';
+					isMut: false;
+					isSigner: true;
+				},
+				{
+					name: 'marketRegistry';
+					isMut: false;
+					isSigner: false;
+				},
+				{
+					name: 'vaultUsdcTokenAccount';
+					isMut: false;
+					isSigner: false;
+				}
+			];
+			args: [];
 		},
 		{
-			name: 'vault';
-			type: {
-				kind: 'struct';
-				fields: [
-					{
-						name: 'name';
-						docs: [
-							'The name of the vault. Vault pubkey is derived from this name.'
-						];
-						type: {
-							array: ['u8', 32];
-						};
-					},
-					{
-						name: 'pubkey';
-						docs: [
-							"The vault's pubkey. It is a PDA also used as the authority token accounts"
-						];
-						type: 'publicKey';
-					},
-					{
-						name: 'manager';
-						docs: [
-							'The manager of the vault who has ability to update vault config,',
-							'and earns a profit share or management fee.'
-						];
-						type: 'publicKey';
-					},
-					{
-						name: 'usdcMint';
-						docs: ['The Phoenix USDC mint.'];
-						type: 'publicKey';
-					},
-					{
-						name: 'solMint';
-						docs: ['The Phoenix (wrapped) SOL mint.'];
-						type: 'publicKey';
-					},
-					{
-						name: 'usdcTokenAccount';
-						docs: [
-							'The USDC token account investor transfer with,',
-							'and the vault transfer to Phoenix markets with.'
-						];
-						type: 'publicKey';
-					},
-					{
-						name: 'solTokenAccount';
-						docs: [
-							'The SOL token account investor transfer with,',
-							'and the vault transfer to Phoenix markets with.'
-						];
-						type: 'publicKey';
-					},
-					{
-						name: 'liquidator';
-						docs: [
-							'The delegate (investor) handling liquidation for an investor to withdraw their funds.'
-						];
-						type: 'publicKey';
-					},
-					{
-						name: 'delegate';
-						docs: [
-							'The delegate is the "portfolio manager", "trader", or "bot" that trades the vault assets.',
-							'It can swap 100% of vault tokens.',
-							'This is the manager by default.'
-						];
-						type: 'publicKey';
-					},
-					{
-						name: 'investorShares';
-						docs: ['The sum of all shares held by the investors'];
-						type: 'u128';
-					},
+			name: 'windDown';
+			docs: [
+				'Manager or protocol begins winding down the vault. Deposits stop and the delegate may only reduce positions.'
+			];
+			accounts: [
+				{
+					name: 'vault';
+					isMut: true;
+					isSigner: false;
+				},
+				{
+					name: 'authority';
+					isMut: false;
+					isSigner: true;
+				}
+			];
+			args: [];
+		},
+		{
+			name: 'windDownLiquidateMarket';
+			docs: [
+				"Permissionless crank that cancels all orders and sells a winding down vault's position in a market to its quote token.",
+				'The market must also be passed in the remaining accounts.'
+			];
+			accounts: [
+				{
+					name: 'vault';
+					isMut: true;
+					isSigner: false;
+				},
+				{
+					name: 'phoenix';
+					isMut: false;
+					isSigner: false;
+				},
+				{
+					name: 'logAuthority';
+					isMut: false;
+					isSigner: false;
+				},
+				{
+					name: 'market';
+					isMut: true;
+					isSigner: false;
+				},
+				{
+					name: 'seat';
+					isMut: false;
+					isSigner: false;
+				},
+				{
+					name: 'baseMint';
+					isMut: false;
+					isSigner: false;
+				},
+				{
+					name: 'quoteMint';
+					isMut: false;
+					isSigner: false;
+				},
+				{
+					name: 'vaultBaseTokenAccount';
+					isMut: true;
+					isSigner: false;
+				},
+				{
+					name: 'vaultQuoteTokenAccount';
+					isMut: true;
+					isSigner: false;
+				},
+				{
+					name: 'marketBaseTokenAccount';
+					isMut: true;
+					isSigner: false;
+				},
+				{
+					name: 'marketQuoteTokenAccount';
+					isMut: true;
+					isSigner: false;
+				},
+				{
+					name: 'tokenProgram';
+					isMut: false;
+					isSigner: false;
+				}
+			];
+			args: [];
+		},
+		{
+			name: 'finalizeWindDown';
+			docs: [
+				'Permissionless crank that snapshots the final equity and closes the vault once every market position is USDC.'
+			];
+			accounts: [
+				{
+					name: 'vault';
+					isMut: true;
+					isSigner: false;
+				},
+				{
+					name: 'marketRegistry';
+					isMut: false;
+					isSigner: false;
+				},
+				{
+					name: 'vaultUsdcTokenAccount';
+					isMut: false;
+					isSigner: false;
+				},
+				{
+					name: 'vaultSolTokenAccount';
+					isMut: false;
+					isSigner: false;
+				}
+			];
+			args: [];
+		},
+		{
+			name: 'investorClaimWindDown';
+			docs: [
+				'Investor claims their pro-rata USDC of the final equity of a wound down vault.'
+			];
+			accounts: [
+				{
+					name: 'vault';
+					isMut: true;
+					isSigner: false;
+				},
+				{
+					name: 'investor';
+					isMut: true;
+					isSigner: false;
+				},
+				{
+					name: 'authority';
+					isMut: false;
+					isSigner: true;
+				},
+				{
+					name: 'investorQuoteTokenAccount';
+					isMut: true;
+					isSigner: false;
+				},
+				{
+					name: 'vaultQuoteTokenAccount';
+					isMut: true;
+					isSigner: false;
+				},
+				{
+					name: 'tokenProgram';
+					isMut: false;
+					isSigner: false;
+				}
+			];
+			args: [];
+		},
+		{
+			name: 'viewVaultEquity';
+			docs: [
+				'Read-only instruction that returns the [`Vault`] equity as return data.'
+			];
+			accounts: [
+				{
+					name: 'vault';
+					isMut: false;
+					isSigner: false;
+				},
+				{
+					name: 'marketRegistry';
+					isMut: false;
+					isSigner: false;
+				},
+				{
+					name: 'vaultUsdcTokenAccount';
+					isMut: false;
+					isSigner: false;
+				}
+			];
+			args: [];
+		},
+		{
+			name: 'viewInvestorEquity';
+			docs: [
+				'Read-only instruction that returns the equity of an [`Investor`] as return data.'
+			];
+			accounts: [
+				{
+					name: 'vault';
+					isMut: false;
+					isSigner: false;
+				},
+				{
+					name: 'investor';
+					isMut: false;
+					isSigner: false;
+				},
+				{
+					name: 'marketRegistry';
+					isMut: false;
+					isSigner: false;
+				},
+				{
+					name: 'vaultUsdcTokenAccount';
+					isMut: false;
+					isSigner: false;
+				}
+			];
+			args: [];
+		},
+		{
+			name: 'previewDeposit';
+			docs: [
+				'Read-only instruction that returns the shares an [`Investor`] deposit would mint as return data.',
+				'Fails wherever `investor_deposit` would, e.g. if the vault is at capacity or no longer accepts deposits.'
+			];
+			accounts: [
+				{
+					name: 'vault';
+					isMut: false;
+					isSigner: false;
+				},
+				{
+					name: 'investor';
+					isMut: false;
+					isSigner: false;
+				},
+				{
+					name: 'marketRegistry';
+					isMut: false;
+					isSigner: false;
+				},
+				{
+					name: 'vaultUsdcTokenAccount';
+					isMut: false;
+					isSigner: false;
+				}
+			];
+			args: [
+				{
+					name: 'amount';
+					type: 'u64';
+				}
+			];
+		},
+		{
+			name: 'previewWithdraw';
+			docs: [
+				'Read-only instruction that returns the USDC an [`Investor`] withdrawal would pay as return data.'
+			];
+			accounts: [
+				{
+					name: 'vault';
+					isMut: false;
+					isSigner: false;
+				},
+				{
+					name: 'investor';
+					isMut: false;
+					isSigner: false;
+				},
+				{
+					name: 'marketRegistry';
+					isMut: false;
+					isSigner: false;
+				},
+				{
+					name: 'vaultUsdcTokenAccount';
+					isMut: false;
+					isSigner: false;
+				}
+			];
+			args: [
+				{
+					name: 'withdrawAmount';
+					type: 'u64';
+				},
+				{
+					name: 'withdrawUnit';
+					type: {
+						defined: 'WithdrawUnit';
+					};
+				}
+			];
+		},
+		{
+			name: 'cancelAllOrders';
+			accounts: [
+				{
+					name: 'vault';
+					isMut: true;
+					isSigner: false;
+					docs: [
+						'If delegate has authority to sign for vault, then any Phoenix CPI is valid.',
+						'Phoenix CPI validates that opaque instruction data is a [`PhoenixInstruction`],',
+						'so this is safe since any Phoenix CPI is secure.'
+					];
+				},
+				{
+					name: 'delegate';
+					isMut: false;
+					isSigner: true;
+					docs: [
+						'Is manager by default, but can be delegated to another pubkey using `update_delegate`'
+					];
+				},
+				{
+					name: 'phoenix';
+					isMut: false;
+					isSigner: false;
+				},
+				{
+					name: 'logAuthority';
+					isMut: false;
+					isSigner: false;
+				},
+				{
+					name: 'market';
+					isMut: true;
+					isSigner: false;
+				}
+			];
+			args: [];
+		},
+		{
+			name: 'cancelMultipleOrdersById';
+			accounts: [
+				{
+					name: 'vault';
+					isMut: true;
+					isSigner: false;
+					docs: [
+						'If delegate has authority to sign for vault, then any Phoenix CPI is valid.',
+						'Phoenix CPI validates that opaque instruction data is a [`PhoenixInstruction`],',
+						'so this is safe since any Phoenix CPI is secure.'
+					];
+				},
+				{
+					name: 'delegate';
+					isMut: false;
+					isSigner: true;
+					docs: [
+						'Is manager by default, but can be delegated to another pubkey using `update_delegate`'
+					];
+				},
+				{
+					name: 'phoenix';
+					isMut: false;
+					isSigner: false;
+				},
+				{
+					name: 'logAuthority';
+					isMut: false;
+					isSigner: false;
+				},
+				{
+					name: 'market';
+					isMut: true;
+					isSigner: false;
+				}
+			];
+			args: [
+				{
+					name: 'params';
+					type: {
+						defined: 'CancelMultipleOrdersParams';
+					};
+				}
+			];
+		}
+	];
+	accounts: [
+		{
+			name: 'investor';
+			type: {
+				kind: 'struct';
+				fields: [
+					{
+						name: 'vault';
+						docs: ['The vault deposited into'];
+						type: 'publicKey';
+					},
+					{
+						name: 'pubkey';
+						docs: [
+							"The vault depositor account's pubkey. It is a pda of vault and authority"
+						];
+						type: 'publicKey';
+					},
+					{
+						name: 'authority';
+						docs: [
+							'The authority is the address w permission to deposit/withdraw'
+						];
+						type: 'publicKey';
+					},
+					{
+						name: 'vaultShares';
+						docs: [
+							"share of vault owned by this depositor. vault_shares / vault.total_shares is depositor's ownership of vault_equity"
+						];
+						type: 'u128';
+					},
+					{
+						name: 'lastWithdrawRequest';
+						docs: ['last withdraw request'];
+						type: {
+							defined: 'WithdrawRequest';
+						};
+					},
+					{
+						name: 'lastValidTs';
+						docs: ['creation ts of vault depositor'];
+						type: 'i64';
+					},
+					{
+						name: 'netDeposits';
+						docs: ['lifetime net deposits of vault depositor for the vault'];
+						type: 'i64';
+					},
+					{
+						name: 'totalDeposits';
+						docs: ['lifetime total deposits'];
+						type: 'u64';
+					},
+					{
+						name: 'totalWithdraws';
+						docs: ['lifetime total withdraws'];
+						type: 'u64';
+					},
+					{
+						name: 'cumulativeProfitShareAmount';
+						docs: [
+							'the token amount of gains the vault depositor has paid performance fees on (in other words, cumulative profit)'
+						];
+						type: 'i64';
+					},
+					{
+						name: 'profitShareFeePaid';
+						docs: [
+							'the token amount profit share paid out to the manager and protocol'
+						];
+						type: 'u64';
+					},
+					{
+						name: 'vaultSharesBase';
+						docs: ['the exponent for vault_shares decimal places'];
+						type: 'u32';
+					},
+					{
+						name: 'padding1';
+						type: 'u32';
+					},
+					{
+						name: 'epochDepositAmount';
+						docs: [
+							'USDC deposited during an epoch that has not been claimed as shares'
+						];
+						type: 'u64';
+					},
+					{
+						name: 'epochDepositEpoch';
+						docs: ['The epoch `epoch_deposit_amount` was deposited in'];
+						type: 'u64';
+					},
+					{
+						name: 'epochWithdrawEpoch';
+						docs: ['The epoch the last withdraw request was queued in'];
+						type: 'u64';
+					},
+					{
+						name: 'firstDepositTs';
+						docs: [
+							'When the investor first deposited, which starts the vault lockup'
+						];
+						type: 'i64';
+					},
+					{
+						name: 'padding';
+						type: {
+							array: ['u64', 4];
+						};
+					}
+				];
+			};
+		},
+		{
+			name: 'marketRegistry';
+			docs: [
+				'DriftVaults validates vault user positions against the remaining accounts provided for those markets.',
+				'If the remaining accounts do not contain every market the user has a position in, then the instruction errors.',
+				'For Phoenix, we use our MarketRegistry as the official source of truth for the "list of markets",',
+				"and we can get the TraderState for the vault within each market to determine the vault's positions.",
+				'If the remaining accounts do not contain every market in the MarketRegistry that the vault has a position in,',
+				'then the instruction will error.'
+			];
+			type: {
+				kind: 'struct';
+				fields: [
+					{
+						name: 'authority';
+						docs: [
+							'Authority over this account. This is a program admin-level keypair.'
+						];
+						type: 'publicKey';
+					},
+					{
+						name: 'solUsdcMarket';
+						docs: ['Phoenix SOL/USDC market'];
+						type: 'publicKey';
+					},
+					{
+						name: 'usdcMint';
+						docs: [
+							'Phoenix markets are denominated in USDC or SOL, so we must pre-define this'
+						];
+						type: 'publicKey';
+					},
+					{
+						name: 'solMint';
+						docs: [
+							'Phoenix markets are denominated in USDC or SOL, so we must pre-define this'
+						];
+						type: 'publicKey';
+					}
+				];
+			};
+		},
+		{
+			name: 'navHistory';
+			docs: [
+				'Ring buffer of vault equity and shares samples to chart the share price without an indexer.',
+				'Samples are appended at most once per [`MIN_NAV_SAMPLE_INTERVAL`] by the permissionless `update_nav_history` crank',
+				'and whenever a deposit or withdrawal computes the vault equity. Within the interval the latest sample is updated in place.'
+			];
+			type: {
+				kind: 'struct';
+				fields: [
+					{
+						name: 'vault';
+						docs: ['The vault sampled'];
+						type: 'publicKey';
+					},
+					{
+						name: 'head';
+						docs: ['Index the next sample is written to'];
+						type: 'u64';
+					},
+					{
+						name: 'len';
+						docs: ['Number of samples written, up to [`NAV_HISTORY_LENGTH`]'];
+						type: 'u64';
+					},
+					{
+						name: 'lastSampleTs';
+						docs: [
+							"Timestamp the most recent sample was appended at. Updates in place don't move it."
+						];
+						type: 'i64';
+					},
+					{
+						name: 'padding';
+						type: {
+							array: ['u8', 8];
+						};
+					},
+					{
+						name: 'samples';
+						type: {
+							array: [
+								{
+									defined: 'NavSample';
+								},
+								128
+							];
+						};
+					}
+				];
+			};
+		},
+		{
+			name: 'sharePrice';
+			docs: [
+				'Latest vault share price, so other programs can price vault shares',
+				'without recomputing equity over every market the vault trades.',
+				'Refreshed by the permissionless `update_share_price` crank.'
+			];
+			type: {
+				kind: 'struct';
+				fields: [
+					{
+						name: 'vault';
+						docs: ['The vault priced'];
+						type: 'publicKey';
+					},
+					{
+						name: 'totalShares';
+						docs: ['Vault total shares at the last update'];
+						type: 'u128';
+					},
+					{
+						name: 'equity';
+						docs: ['Vault equity in USDC at the last update'];
+						type: 'u64';
+					},
+					{
+						name: 'price';
+						docs: ['USDC per share, precision: PRICE_PRECISION'];
+						type: 'u64';
+					},
+					{
+						name: 'slot';
+						docs: ['Slot of the last update'];
+						type: 'u64';
+					},
+					{
+						name: 'ts';
+						docs: ['Unix timestamp of the last update'];
+						type: 'i64';
+					},
+					{
+						name: 'maxStalenessSlots';
+						docs: [
+							'Number of slots after the last update before the price is considered stale'
+						];
+						type: 'u64';
+					},
+					{
+						name: 'sharesBase';
+						docs: ['Vault shares base at the last update'];
+						type: 'u32';
+					},
+					{
+						name: 'padding1';
+						type: 'u32';
+					},
+					{
+						name: 'padding';
+						type: {
+							array: ['u64', 4];
+						};
+					}
+				];
+			};
+		},
+		{
+			name: 'vault';
+			type: {
+				kind: 'struct';
+				fields: [
+					{
+						name: 'name';
+						docs: [
+							'The name of the vault. Vault pubkey is derived from this name.'
+						];
+						type: {
+							array: ['u8', 32];
+						};
+					},
+					{
+						name: 'pubkey';
+						docs: [
+							"The vault's pubkey. It is a PDA also used as the authority token accounts"
+						];
+						type: 'publicKey';
+					},
+					{
+						name: 'manager';
+						docs: [
+							'The manager of the vault who has ability to update vault config,',
+							'and earns a profit share or management fee.'
+						];
+						type: 'publicKey';
+					},
+					{
+						name: 'usdcMint';
+						docs: ['The Phoenix USDC mint.'];
+						type: 'publicKey';
+					},
+					{
+						name: 'solMint';
+						docs: ['The Phoenix (wrapped) SOL mint.'];
+						type: 'publicKey';
+					},
+					{
+						name: 'usdcTokenAccount';
+						docs: [
+							'The USDC token account investor transfer with,',
+							'and the vault transfer to Phoenix markets with.'
+						];
+						type: 'publicKey';
+					},
+					{
+						name: 'solTokenAccount';
+						docs: [
+							'The SOL token account investor transfer with,',
+							'and the vault transfer to Phoenix markets with.'
+						];
+						type: 'publicKey';
+					},
+					{
+						name: 'liquidator';
+						docs: [
+							'The delegate (investor) handling liquidation for an investor to withdraw their funds.'
+						];
+						type: 'publicKey';
+					},
+					{
+						name: 'delegate';
+						docs: [
+							'The delegate is the "portfolio manager", "trader", or "bot" that trades the vault assets.',
+							'It can swap 100% of vault tokens.',
+							'This is the manager by default.'
+						];
+						type: 'publicKey';
+					},
+					{
+						name: 'investorShares';
+						docs: ['The sum of all shares held by the investors'];
+						type: 'u128';
+					},
+					{
+						name: 'totalShares';
+						docs: [
+							'The sum of all shares: investor deposits, manager deposits, manager profit/fee, and protocol profit/fee.',
+							'The manager deposits are total_shares - investor_shares - protocol_profit_and_fee_shares.'
+						];
+						type: 'u128';
+					},
+					{
+						name: 'lastFeeUpdateTs';
+						docs: ['Last fee update unix timestamp'];
+						type: 'i64';
+					},
+					{
+						name: 'liquidationStartTs';
+						docs: ['When the liquidation starts'];
+						type: 'i64';
+					},
+					{
+						name: 'redeemPeriod';
+						docs: [
+							'The period (in seconds) that an investor must wait after requesting a withdrawal to transfer funds.',
+							'The maximum is 90 days.',
+							'This is only updatable to lesser values.'
+						];
+						type: 'i64';
+					},
+					{
+						name: 'totalWithdrawRequested';
+						docs: ['The sum of all outstanding withdraw requests'];
+						type: 'u64';
+					},
+					{
+						name: 'maxTokens';
+						docs: [
+							'Max token capacity, once hit/passed vault will reject new deposits.',
+							'This is only updatable to lesser values.'
+						];
+						type: 'u64';
+					},
+					{
+						name: 'managementFee';
+						docs: [
+							'The annual fee charged on deposits by the manager.',
+							'Traditional funds typically charge 2% per year on assets under management.',
+							'This is only updatable to lesser values.'
+						];
+						type: 'i64';
+					},
+					{
+						name: 'initTs';
+						docs: ['Timestamp vault initialized'];
+						type: 'i64';
+					},
+					{
+						name: 'netDeposits';
+						docs: ['The net deposits for the vault'];
+						type: 'i64';
+					},
+					{
+						name: 'managerNetDeposits';
+						docs: ['The net deposits for the manager'];
+						type: 'i64';
+					},
+					{
+						name: 'totalDeposits';
+						docs: ['Total deposits'];
+						type: 'u64';
+					},
+					{
+						name: 'totalWithdraws';
+						docs: ['Total withdraws'];
+						type: 'u64';
+					},
+					{
+						name: 'managerTotalDeposits';
+						docs: ['Total deposits for the manager'];
+						type: 'u64';
+					},
+					{
+						name: 'managerTotalWithdraws';
+						docs: ['Total withdraws for the manager'];
+						type: 'u64';
+					},
+					{
+						name: 'managerTotalFee';
+						docs: ['Total management fee accrued by the manager'];
+						type: 'i64';
+					},
+					{
+						name: 'managerTotalProfitShare';
+						docs: ['Total profit share accrued by the manager'];
+						type: 'u64';
+					},
+					{
+						name: 'minDepositAmount';
+						docs: [
+							'The minimum deposit amount.',
+							'This is only updatable to lesser values.'
+						];
+						type: 'u64';
+					},
+					{
+						name: 'lastManagerWithdrawRequest';
+						type: {
+							defined: 'WithdrawRequest';
+						};
+					},
+					{
+						name: 'sharesBase';
+						docs: [
+							'The base 10 exponent of the shares (given massive share inflation can occur at near zero vault equity)'
+						];
+						type: 'u32';
+					},
+					{
+						name: 'profitShare';
+						docs: [
+							'Percentage the manager charges on all profits realized by depositors (multiplied by PERCENTAGE_PRECISION).',
+							'Traditional funds typically charge 20% of profits.',
+							'This is only updatable to lesser values.'
+						];
+						type: 'u32';
+					},
+					{
+						name: 'hurdleRate';
+						docs: [
+							'Vault manager only collect incentive fees during periods when returns are higher than this amount (multiplied by PERCENTAGE_PRECISION).'
+						];
+						type: 'u32';
+					},
+					{
+						name: 'protocolProfitShare';
+						docs: [
+							'Percentage the protocol charges on all profits realized by depositors: PERCENTAGE_PRECISION'
+						];
+						type: 'u32';
+					},
+					{
+						name: 'protocol';
+						docs: [
+							'The protocol, company, or entity that services the product using this vault.',
+							'The protocol is not allowed to deposit into the vault but can profit share and collect annual fees just like the manager.'
+						];
+						type: 'publicKey';
+					},
+					{
+						name: 'protocolProfitAndFeeShares';
+						docs: [
+							'The shares from profit share and annual fee unclaimed by the protocol.'
+						];
+						type: 'u128';
+					},
+					{
+						name: 'protocolFee';
+						docs: [
+							'The annual fee charged on deposits by the protocol (traditional hedge funds typically charge 2% per year on assets under management).',
+							"Unlike the management fee this can't be negative."
+						];
+						type: 'u64';
+					},
+					{
+						name: 'protocolTotalWithdraws';
+						docs: ['Total withdraws for the protocol'];
+						type: 'u64';
+					},
+					{
+						name: 'protocolTotalFee';
+						docs: [
+							'Total fee charged by the protocol (annual management fee + profit share).',
+							"Unlike the management fee this can't be negative."
+						];
+						type: 'u64';
+					},
+					{
+						name: 'protocolTotalProfitShare';
+						docs: ['Total profit share charged by the protocol'];
+						type: 'u64';
+					},
+					{
+						name: 'lastProtocolWithdrawRequest';
+						type: {
+							defined: 'WithdrawRequest';
+						};
+					},
+					{
+						name: 'positions';
+						type: {
+							array: [
+								{
+									defined: 'MarketPosition';
+								},
+								8
+							];
+						};
+					},
+					{
+						name: 'permissioned';
+						docs: ['Whether anyone can be an investor'];
+						type: 'bool';
+					},
+					{
+						name: 'bump';
+						docs: ['The bump for the vault PDA'];
+						type: 'u8';
+					},
+					{
+						name: 'padding';
+						type: {
+							array: ['u8', 6];
+						};
+					},
+					{
+						name: 'epochPendingWithdrawShares';
+						docs: [
+							'Shares requested for withdraw during the current epoch, burned by the next `settle_epoch`.'
+						];
+						type: 'u128';
+					},
+					{
+						name: 'epochUnclaimedDepositShares';
+						docs: [
+							'Shares minted by the last `settle_epoch` that investors have yet to claim.'
+						];
+						type: 'u128';
+					},
+					{
+						name: 'epochUnclaimedWithdrawShares';
+						docs: [
+							'Shares burned by the last `settle_epoch` that investors have yet to claim the value of.'
+						];
+						type: 'u128';
+					},
+					{
+						name: 'epochDuration';
+						docs: [
+							'The length (in seconds) of a subscription and redemption window.',
+							'If zero, deposits and withdraw requests are priced immediately at the current equity.',
+							'This can only be set when the vault is initialized.'
+						];
+						type: 'i64';
+					},
+					{
+						name: 'epochStartTs';
+						docs: ['When the current epoch started'];
+						type: 'i64';
+					},
+					{
+						name: 'currentEpoch';
+						docs: [
+							'The number of epochs settled since the vault was initialized'
+						];
+						type: 'u64';
+					},
+					{
+						name: 'epochPendingDeposits';
+						docs: [
+							'USDC deposited during the current epoch, not yet priced into shares.'
+						];
+						type: 'u64';
+					},
+					{
+						name: 'epochUnclaimedDeposits';
+						docs: [
+							'USDC deposits priced by the last `settle_epoch` that investors have yet to claim shares for.'
+						];
+						type: 'u64';
+					},
+					{
+						name: 'epochUnclaimedWithdrawValue';
+						docs: [
+							'Value of the withdraw shares burned by the last `settle_epoch` that investors have yet to claim.'
+						];
+						type: 'u64';
+					},
+					{
+						name: 'epochRedemptionsOwed';
+						docs: [
+							'USDC priced by a `settle_epoch` and owed to investors until they withdraw it.'
+						];
+						type: 'u64';
+					},
+					{
+						name: 'entryFeeBps';
+						docs: [
+							'The fee (in basis points) withheld from investor deposits.',
+							'It stays in the vault to accrue to the remaining shareholders.',
+							'This is only updatable to lesser values.'
+						];
+						type: 'u16';
+					},
+					{
+						name: 'exitFeeBps';
+						docs: [
+							'The fee (in basis points) withheld from investor withdrawals.',
+							'It stays in the vault to accrue to the remaining shareholders.',
+							'This is only updatable to lesser values.'
+						];
+						type: 'u16';
+					},
+					{
+						name: 'maxDrawdown';
+						docs: [
+							'The drawdown of the share price from its high-water mark (multiplied by PERCENTAGE_PRECISION)',
+							'that halts delegate trading. If zero, trading is never halted.'
+						];
+						type: 'u32';
+					},
+					{
+						name: 'highWaterMarkPrice';
+						docs: [
+							'The highest share price (USDC per share multiplied by PRICE_PRECISION) sampled by `update_nav_history`.'
+						];
+						type: 'u64';
+					},
+					{
+						name: 'tradingHalted';
+						docs: [
+							'Set when the share price drawdown exceeds `max_drawdown`.',
+							"The delegate can't place orders or deposit to markets, and investors can withdraw without waiting the redeem period,",
+							'until the manager and protocol call `resume_trading`.'
+						];
+						type: 'bool';
+					},
+					{
+						name: 'state';
+						docs: [
+							'The lifecycle state. Use [`Vault::state`] since fundraising ends at its deadline without an instruction.'
+						];
+						type: {
+							defined: 'VaultState';
+						};
+					},
+					{
+						name: 'padding2';
+						type: {
+							array: ['u8', 2];
+						};
+					},
+					{
+						name: 'managerMinStake';
+						docs: [
+							'The minimum percentage of `total_shares` (multiplied by PERCENTAGE_PRECISION) the manager must hold',
+							'while investor shares are outstanding. This is only updatable to greater values.'
+						];
+						type: 'u32';
+					},
+					{
+						name: 'fundraisingSoftCap';
+						docs: [
+							'Total deposits required by the fundraising deadline for the vault to become active.',
+							'If zero, the vault is active from initialization.'
+						];
+						type: 'u64';
+					},
+					{
+						name: 'fundraisingDeadline';
+						docs: ['When fundraising ends'];
+						type: 'i64';
+					},
+					{
+						name: 'finalEquity';
+						docs: [
+							'The vault equity snapshotted by `finalize_wind_down`, which investors claim pro-rata once the vault is closed.'
+						];
+						type: 'u64';
+					},
+					{
+						name: 'finalTotalShares';
+						docs: [
+							'The total shares snapshotted alongside `final_equity`. Zero until the wind-down is finalized.'
+						];
+						type: 'u128';
+					},
+					{
+						name: 'liquidityBuffer';
+						docs: [
+							'The percentage of equity (multiplied by PERCENTAGE_PRECISION) kept in the vault USDC token account',
+							"on top of outstanding withdraw requests, which `market_deposit` can't draw from.",
+							'This is only updatable to greater values.'
+						];
+						type: 'u32';
+					},
+					{
+						name: 'lockupPenaltyBps';
+						docs: [
+							"The penalty (in basis points) withheld from withdrawals requested during an investor's lockup.",
+							'It stays in the vault to accrue to the remaining shareholders.',
+							'If zero, withdraw requests during the lockup are rejected.'
+						];
+						type: 'u16';
+					},
+					{
+						name: 'padding3';
+						type: {
+							array: ['u8', 2];
+						};
+					},
+					{
+						name: 'lockupDuration';
+						docs: [
+							"Seconds from an investor's first deposit during which withdraw requests are penalized or rejected.",
+							'If zero, there is no lockup. This is only updatable to lesser values.'
+						];
+						type: 'i64';
+					},
+					{
+						name: 'liquidationWindow';
+						docs: [
+							'Seconds an appointed liquidator has to liquidate before the vault can exit liquidation',
+							'or another liquidator can be appointed. This is only updatable to lesser values.'
+						];
+						type: 'i64';
+					},
+					{
+						name: 'padding4';
+						type: {
+							array: ['u64', 1];
+						};
+					}
+				];
+			};
+		}
+	];
+	types: [
+		{
+			name: 'MarketLookupTableParams';
+			type: {
+				kind: 'struct';
+				fields: [
+					{
+						name: 'solUsdcMarket';
+						type: 'publicKey';
+					},
+					{
+						name: 'usdcMint';
+						type: 'publicKey';
+					},
+					{
+						name: 'solMint';
+						type: 'publicKey';
+					}
+				];
+			};
+		},
+		{
+			name: 'CancelMultipleOrdersParams';
+			type: {
+				kind: 'struct';
+				fields: [
+					{
+						name: 'orders';
+						type: {
+							vec: {
+								defined: 'CancelOrderParams';
+							};
+						};
+					}
+				];
+			};
+		},
+		{
+			name: 'CancelOrderParams';
+			type: {
+				kind: 'struct';
+				fields: [
+					{
+						name: 'side';
+						type: {
+							defined: 'Side';
+						};
+					},
+					{
+						name: 'priceInTicks';
+						type: 'u64';
+					},
+					{
+						name: 'orderSequenceNumber';
+						type: 'u64';
+					}
+				];
+			};
+		},
+		{
+			name: 'VaultParams';
+			type: {
+				kind: 'struct';
+				fields: [
+					{
+						name: 'name';
+						type: {
+							array: ['u8', 32];
+						};
+					},
+					{
+						name: 'redeemPeriod';
+						type: 'i64';
+					},
+					{
+						name: 'maxTokens';
+						type: 'u64';
+					},
+					{
+						name: 'managementFee';
+						type: 'i64';
+					},
+					{
+						name: 'minDepositAmount';
+						type: 'u64';
+					},
+					{
+						name: 'profitShare';
+						type: 'u32';
+					},
+					{
+						name: 'hurdleRate';
+						type: 'u32';
+					},
+					{
+						name: 'permissioned';
+						type: 'bool';
+					},
+					{
+						name: 'protocol';
+						type: 'publicKey';
+					},
+					{
+						name: 'protocolFee';
+						type: 'u64';
+					},
+					{
+						name: 'protocolProfitShare';
+						type: 'u32';
+					},
+					{
+						name: 'epochDuration';
+						docs: [
+							'If non-zero, deposits and withdraw requests are queued and priced once per epoch by `settle_epoch`.'
+						];
+						type: 'i64';
+					},
+					{
+						name: 'entryFeeBps';
+						docs: [
+							'Fee (in basis points) withheld from investor deposits for the remaining shareholders.'
+						];
+						type: 'u16';
+					},
+					{
+						name: 'exitFeeBps';
+						docs: [
+							'Fee (in basis points) withheld from investor withdrawals for the remaining shareholders.'
+						];
+						type: 'u16';
+					},
+					{
+						name: 'maxDrawdown';
+						docs: [
+							'Share price drawdown from its high-water mark (multiplied by PERCENTAGE_PRECISION) that halts delegate trading.',
+							'If zero, trading is never halted.'
+						];
+						type: 'u32';
+					},
+					{
+						name: 'managerMinStake';
+						docs: [
+							'The minimum percentage of total shares (multiplied by PERCENTAGE_PRECISION) the manager must hold',
+							'while investors remain in the vault. If zero, the manager can withdraw everything.'
+						];
+						type: 'u32';
+					},
+					{
+						name: 'liquidityBuffer';
+						docs: [
+							'The percentage of equity (multiplied by PERCENTAGE_PRECISION) reserved in the vault USDC token account,',
+							"on top of outstanding withdraw requests, that the delegate can't deposit to markets."
+						];
+						type: 'u32';
+					},
+					{
+						name: 'lockupDuration';
+						docs: [
+							"Seconds from an investor's first deposit during which withdraw requests are penalized,",
+							'or rejected if `lockup_penalty_bps` is zero. If zero, there is no lockup.'
+						];
+						type: 'i64';
+					},
+					{
+						name: 'lockupPenaltyBps';
+						type: 'u16';
+					},
+					{
+						name: 'liquidationWindow';
+						docs: [
+							'Seconds an appointed liquidator has to liquidate. If zero, the default of one hour.'
+						];
+						type: 'i64';
+					},
+					{
+						name: 'fundraisingSoftCap';
+						docs: [
+							'If non-zero, the vault raises funds until `fundraising_deadline` without trading,',
+							'and closes to refund investors if total deposits are below this soft cap.'
+						];
+						type: 'u64';
+					},
+					{
+						name: 'fundraisingDeadline';
+						type: 'i64';
+					}
+				];
+			};
+		},
+		{
+			name: 'PlaceOrderParams';
+			type: {
+				kind: 'struct';
+				fields: [
+					{
+						name: 'order';
+						type: 'bytes';
+					}
+				];
+			};
+		},
+		{
+			name: 'UpdateVaultParams';
+			type: {
+				kind: 'struct';
+				fields: [
+					{
+						name: 'redeemPeriod';
+						type: {
+							option: 'i64';
+						};
+					},
+					{
+						name: 'maxTokens';
+						type: {
+							option: 'u64';
+						};
+					},
+					{
+						name: 'managementFee';
+						type: {
+							option: 'i64';
+						};
+					},
+					{
+						name: 'minDepositAmount';
+						type: {
+							option: 'u64';
+						};
+					},
+					{
+						name: 'profitShare';
+						type: {
+							option: 'u32';
+						};
+					},
+					{
+						name: 'hurdleRate';
+						type: {
+							option: 'u32';
+						};
+					},
+					{
+						name: 'permissioned';
+						type: {
+							option: 'bool';
+						};
+					},
+					{
+						name: 'delegate';
+						type: {
+							option: 'publicKey';
+						};
+					},
+					{
+						name: 'entryFeeBps';
+						type: {
+							option: 'u16';
+						};
+					},
+					{
+						name: 'exitFeeBps';
+						type: {
+							option: 'u16';
+						};
+					},
+					{
+						name: 'maxDrawdown';
+						type: {
+							option: 'u32';
+						};
+					},
+					{
+						name: 'managerMinStake';
+						type: {
+							option: 'u32';
+						};
+					},
+					{
+						name: 'liquidityBuffer';
+						type: {
+							option: 'u32';
+						};
+					},
+					{
+						name: 'lockupDuration';
+						type: {
+							option: 'i64';
+						};
+					},
+					{
+						name: 'liquidationWindow';
+						type: {
+							option: 'i64';
+						};
+					}
+				];
+			};
+		},
+		{
+			name: 'MarketPosition';
+			type: {
+				kind: 'struct';
+				fields: [
+					{
+						name: 'market';
+						type: 'publicKey';
+					},
+					{
+						name: 'quoteLotsLocked';
+						type: 'u64';
+					},
+					{
+						name: 'quoteLotsFree';
+						type: 'u64';
+					},
+					{
+						name: 'baseLotsLocked';
+						type: 'u64';
+					},
+					{
+						name: 'baseLotsFree';
+						type: 'u64';
+					}
+				];
+			};
+		},
+		{
+			name: 'NavSample';
+			type: {
+				kind: 'struct';
+				fields: [
+					{
+						name: 'totalShares';
+						docs: ['Vault total shares when sampled'];
+						type: 'u128';
+					},
+					{
+						name: 'ts';
+						docs: ['Unix timestamp of the sample'];
+						type: 'i64';
+					},
+					{
+						name: 'equity';
+						docs: ['Vault equity in USDC when sampled'];
+						type: 'u64';
+					},
+					{
+						name: 'sharesBase';
+						docs: ['Vault shares base when sampled'];
+						type: 'u32';
+					},
+					{
+						name: 'padding';
+						type: {
+							array: ['u8', 12];
+						};
+					}
+				];
+			};
+		},
+		{
+			name: 'DepositPreview';
+			docs: ['Return data of the `preview_deposit` instruction'];
+			type: {
+				kind: 'struct';
+				fields: [
 					{
-						name: 'totalShares';
-						docs: [
-							'The sum of all shares: investor deposits, manager deposits, manager profit/fee, and protocol profit/fee.',
-							'The manager deposits are total_shares - investor_shares - protocol_profit_and_fee_shares.'
-						];
+						name: 'shares';
+						docs: ['Vault shares minted for the deposit'];
 						type: 'u128';
 					},
 					{
-						name: 'lastFeeUpdateTs';
-						docs: ['Last fee update unix timestamp'];
-						type: 'i64';
-					},
+						name: 'entryFee';
+						docs: ['Entry fee withheld from the deposit'];
+						type: 'u64';
+					}
+				];
+			};
+		},
+		{
+			name: 'WithdrawPreview';
+			docs: ['Return data of the `preview_withdraw` instruction'];
+			type: {
+				kind: 'struct';
+				fields: [
 					{
-						name: 'liquidationStartTs';
-						docs: ['When the liquidation starts'];
-						type: 'i64';
+						name: 'shares';
+						docs: ['Vault shares burned by the withdrawal'];
+						type: 'u128';
 					},
 					{
-						name: 'redeemPeriod';
+						name: 'amount';
 						docs: [
-							'The period (in seconds) that an investor must wait after requesting a withdrawal to transfer funds.',
-							'The maximum is 90 days.',
-							'This is only updatable to lesser values.'
+							'USDC paid to the investor after profit share and the exit fee'
 						];
-						type: 'i64';
+						type: 'u64';
 					},
 					{
-						name: 'totalWithdrawRequested';
-						docs: ['The sum of all outstanding withdraw requests'];
+						name: 'exitFee';
+						docs: ['Exit fee withheld from the withdrawal'];
 						type: 'u64';
 					},
 					{
-						name: 'maxTokens';
+						name: 'lockupPenalty';
 						docs: [
-							'Max token capacity, once hit/passed vault will reject new deposits.',
-							'This is only updatable to lesser values.'
+							"Early exit penalty withheld from a withdrawal requested during the investor's lockup"
 						];
 						type: 'u64';
-					},
+					}
+				];
+			};
+		},
+		{
+			name: 'MarketTransferParams';
+			type: {
+				kind: 'struct';
+				fields: [
 					{
-						name: 'managementFee';
-						docs: [
-							'The annual fee charged on deposits by the manager.',
-							'Traditional funds typically charge 2% per year on assets under management.',
-							'This is only updatable to lesser values.'
-						];
-						type: 'i64';
+						name: 'quoteLots';
+						type: 'u64';
 					},
 					{
-						name: 'initTs';
-						docs: ['Timestamp vault initialized'];
-						type: 'i64';
+						name: 'baseLots';
+						type: 'u64';
+					}
+				];
+			};
+		},
+		{
+			name: 'WithdrawRequest';
+			type: {
+				kind: 'struct';
+				fields: [
+					{
+						name: 'shares';
+						docs: ['request shares of vault withdraw'];
+						type: 'u128';
 					},
 					{
-						name: 'netDeposits';
-						docs: ['The net deposits for the vault'];
-						type: 'i64';
+						name: 'value';
+						docs: ['requested value in USDC of shares for withdraw'];
+						type: 'u64';
 					},
 					{
-						name: 'managerNetDeposits';
-						docs: ['The net deposits for the manager'];
+						name: 'ts';
+						docs: ['request ts of vault withdraw'];
 						type: 'i64';
-					},
+					}
+				];
+			};
+		},
+		{
+			name: 'Side';
+			type: {
+				kind: 'enum';
+				variants: [
 					{
-						name: 'totalDeposits';
-						docs: ['Total deposits'];
-						type: 'u64';
+						name: 'Bid';
 					},
 					{
-						name: 'totalWithdraws';
-						docs: ['Total withdraws'];
-						type: 'u64';
+						name: 'Ask';
+					}
+				];
+			};
+		},
+		{
+			name: 'InvestorAction';
+			type: {
+				kind: 'enum';
+				variants: [
+					{
+						name: 'Deposit';
 					},
 					{
-						name: 'managerTotalDeposits';
-						docs: ['Total deposits for the manager'];
-						type: 'u64';
+						name: 'WithdrawRequest';
 					},
 					{
-						name: 'managerTotalWithdraws';
-						docs: ['Total withdraws for the manager'];
-						type: 'u64';
+						name: 'CancelWithdrawRequest';
 					},
 					{
-						name: 'managerTotalFee';
-						docs: ['Total management fee accrued by the manager'];
-						type: 'i64';
+						name: 'Withdraw';
 					},
 					{
-						name: 'managerTotalProfitShare';
-						docs: ['Total profit share accrued by the manager'];
-						type: 'u64';
+						name: 'FeePayment';
 					},
 					{
-						name: 'minDepositAmount';
-						docs: [
-							'The minimum deposit amount.',
-							'This is only updatable to lesser values.'
-						];
-						type: 'u64';
+						name: 'WithdrawInKind';
 					},
 					{
-						name: 'lastManagerWithdrawRequest';
-						type: {
-							defined: 'WithdrawRequest';
-						};
+						name: 'WindDownClaim';
+					}
+				];
+			};
+		},
+		{
+			name: 'ManagerAction';
+			type: {
+				kind: 'enum';
+				variants: [
+					{
+						name: 'Deposit';
 					},
 					{
-						name: 'sharesBase';
-						docs: [
-							'The base 10 exponent of the shares (given massive share inflation can occur at near zero vault equity)'
-						];
-						type: 'u32';
+						name: 'WithdrawRequest';
 					},
 					{
-						name: 'profitShare';
-						docs: [
-							'Percentage the manager charges on all profits realized by depositors (multiplied by PERCENTAGE_PRECISION).',
-							'Traditional funds typically charge 20% of profits.',
-							'This is only updatable to lesser values.'
-						];
-						type: 'u32';
+						name: 'CancelWithdrawRequest';
 					},
 					{
-						name: 'hurdleRate';
-						docs: [
-							'Vault manager only collect incentive fees during periods when returns are higher than this amount (multiplied by PERCENTAGE_PRECISION).'
-						];
-						type: 'u32';
+						name: 'Withdraw';
+					}
+				];
+			};
+		},
+		{
+			name: 'ProtocolAction';
+			type: {
+				kind: 'enum';
+				variants: [
+					{
+						name: 'WithdrawRequest';
 					},
 					{
-						name: 'protocolProfitShare';
-						docs: [
-							'Percentage the protocol charges on all profits realized by depositors: PERCENTAGE_PRECISION'
-						];
-						type: 'u32';
+						name: 'CancelWithdrawRequest';
 					},
 					{
-						name: 'protocol';
-						docs: [
-							'The protocol, company, or entity that services the product using this vault.',
-							'The protocol is not allowed to deposit into the vault but can profit share and collect annual fees just like the manager.'
+						name: 'Withdraw';
+					}
+				];
+			};
+		},
+		{
+			name: 'DelegateAction';
+			docs: [
+				'The delegate instruction and the order parameters it was called with'
+			];
+			type: {
+				kind: 'enum';
+				variants: [
+					{
+						name: 'PlaceLimitOrder';
+						fields: [
+							{
+								name: 'order';
+								type: 'bytes';
+							}
 						];
-						type: 'publicKey';
 					},
 					{
-						name: 'protocolProfitAndFeeShares';
-						docs: [
-							'The shares from profit share and annual fee unclaimed by the protocol.'
-						];
-						type: 'u128';
+						name: 'CancelAllOrders';
 					},
 					{
-						name: 'protocolFee';
-						docs: [
-							'The annual fee charged on deposits by the protocol (traditional hedge funds typically charge 2% per year on assets under management).',
-							"Unlike the management fee this can't be negative."
+						name: 'CancelMultipleOrdersById';
+						fields: [
+							{
+								name: 'orders';
+								type: {
+									vec: {
+										defined: 'CancelOrderParams';
+									};
+								};
+							}
 						];
-						type: 'u64';
 					},
 					{
-						name: 'protocolTotalWithdraws';
-						docs: ['Total withdraws for the protocol'];
-						type: 'u64';
+						name: 'MarketDeposit';
+						fields: [
+							{
+								name: 'params';
+								type: {
+									defined: 'MarketTransferParams';
+								};
+							}
+						];
 					},
 					{
-						name: 'protocolTotalFee';
-						docs: [
-							'Total fee charged by the protocol (annual management fee + profit share).',
-							"Unlike the management fee this can't be negative."
+						name: 'MarketWithdraw';
+						fields: [
+							{
+								name: 'params';
+								type: {
+									defined: 'MarketTransferParams';
+								};
+							}
 						];
-						type: 'u64';
-					},
+					}
+				];
+			};
+		},
+		{
+			name: 'LiquidatorRole';
+			type: {
+				kind: 'enum';
+				variants: [
 					{
-						name: 'protocolTotalProfitShare';
-						docs: ['Total profit share charged by the protocol'];
-						type: 'u64';
+						name: 'Investor';
 					},
 					{
-						name: 'lastProtocolWithdrawRequest';
-						type: {
-							defined: 'WithdrawRequest';
-						};
+						name: 'Manager';
 					},
 					{
-						name: 'positions';
-						type: {
-							array: [
-								{
-									defined: 'MarketPosition';
-								},
-								8
-							];
-						};
+						name: 'Protocol';
+					}
+				];
+			};
+		},
+		{
+			name: 'VaultState';
+			docs: [
+				'Lifecycle of a [`Vault`](crate::state::Vault).',
+				"Every instruction checks the state with `Vault::check_state` and rejects actions the state doesn't allow."
+			];
+			type: {
+				kind: 'enum';
+				variants: [
+					{
+						name: 'Active';
 					},
 					{
-						name: 'permissioned';
-						docs: ['Whether anyone can be an investor'];
-						type: 'bool';
+						name: 'Fundraising';
 					},
 					{
-						name: 'bump';
-						docs: ['The bump for the vault PDA'];
-						type: 'u8';
+						name: 'WindDown';
 					},
 					{
-						name: 'padding';
-						type: {
-							array: ['u8', 6];
-						};
+						name: 'Closed';
+					}
+				];
+			};
+		},
+		{
+			name: 'WithdrawUnit';
+			type: {
+				kind: 'enum';
+				variants: [
+					{
+						name: 'Shares';
+					},
+					{
+						name: 'Token';
+					},
+					{
+						name: 'SharesPercent';
 					}
 				];
 			};
 		}
 	];
-	types: [
+	events: [
+		{
+			name: 'VaultRecord';
+			fields: [
+				{
+					name: 'ts';
+					type: 'i64';
+					index: false;
+				},
+				{
+					name: 'vault';
+					type: 'publicKey';
+					index: false;
+				},
+				{
+					name: 'vaultEquity';
+					type: 'u64';
+					index: false;
+				},
+				{
+					name: 'totalShares';
+					type: 'u128';
+					index: false;
+				},
+				{
+					name: 'investorShares';
+					type: 'u128';
+					index: false;
+				},
+				{
+					name: 'sharesBase';
+					type: 'u32';
+					index: false;
+				}
+			];
+		},
+		{
+			name: 'InvestorRecord';
+			fields: [
+				{
+					name: 'ts';
+					type: 'i64';
+					index: false;
+				},
+				{
+					name: 'vault';
+					type: 'publicKey';
+					index: false;
+				},
+				{
+					name: 'depositorAuthority';
+					type: 'publicKey';
+					index: false;
+				},
+				{
+					name: 'action';
+					type: {
+						defined: 'InvestorAction';
+					};
+					index: false;
+				},
+				{
+					name: 'amount';
+					type: 'u64';
+					index: false;
+				},
+				{
+					name: 'usdcMint';
+					type: 'publicKey';
+					index: false;
+				},
+				{
+					name: 'solMint';
+					type: 'publicKey';
+					index: false;
+				},
+				{
+					name: 'vaultSharesBefore';
+					type: 'u128';
+					index: false;
+				},
+				{
+					name: 'vaultSharesAfter';
+					type: 'u128';
+					index: false;
+				},
+				{
+					name: 'vaultEquityBefore';
+					type: 'u64';
+					index: false;
+				},
+				{
+					name: 'userVaultSharesBefore';
+					type: 'u128';
+					index: false;
+				},
+				{
+					name: 'totalVaultSharesBefore';
+					type: 'u128';
+					index: false;
+				},
+				{
+					name: 'protocolSharesBefore';
+					type: 'u128';
+					index: false;
+				},
+				{
+					name: 'userVaultSharesAfter';
+					type: 'u128';
+					index: false;
+				},
+				{
+					name: 'totalVaultSharesAfter';
+					type: 'u128';
+					index: false;
+				},
+				{
+					name: 'protocolSharesAfter';
+					type: 'u128';
+					index: false;
+				},
+				{
+					name: 'protocolProfitShare';
+					type: 'u64';
+					index: false;
+				},
+				{
+					name: 'protocolFee';
+					type: 'i64';
+					index: false;
+				},
+				{
+					name: 'protocolFeeShares';
+					type: 'i64';
+					index: false;
+				},
+				{
+					name: 'managerProfitShare';
+					type: 'u64';
+					index: false;
+				},
+				{
+					name: 'managementFee';
+					type: 'i64';
+					index: false;
+				},
+				{
+					name: 'managementFeeShares';
+					type: 'i64';
+					index: false;
+				},
+				{
+					name: 'entryFee';
+					type: 'u64';
+					index: false;
+				},
+				{
+					name: 'exitFee';
+					type: 'u64';
+					index: false;
+				},
+				{
+					name: 'lockupPenalty';
+					type: 'u64';
+					index: false;
+				}
+			];
+		},
+		{
+			name: 'ManagerRecord';
+			fields: [
+				{
+					name: 'ts';
+					type: 'i64';
+					index: false;
+				},
+				{
+					name: 'vault';
+					type: 'publicKey';
+					index: false;
+				},
+				{
+					name: 'manager';
+					type: 'publicKey';
+					index: false;
+				},
+				{
+					name: 'action';
+					type: {
+						defined: 'ManagerAction';
+					};
+					index: false;
+				},
+				{
+					name: 'amount';
+					type: 'u64';
+					index: false;
+				},
+				{
+					name: 'nShares';
+					type: 'u128';
+					index: false;
+				},
+				{
+					name: 'vaultEquityBefore';
+					type: 'u64';
+					index: false;
+				},
+				{
+					name: 'managerSharesBefore';
+					type: 'u128';
+					index: false;
+				},
+				{
+					name: 'totalVaultSharesBefore';
+					type: 'u128';
+					index: false;
+				},
+				{
+					name: 'userVaultSharesBefore';
+					type: 'u128';
+					index: false;
+				},
+				{
+					name: 'protocolSharesBefore';
+					type: 'u128';
+					index: false;
+				},
+				{
+					name: 'managerSharesAfter';
+					type: 'u128';
+					index: false;
+				},
+				{
+					name: 'totalVaultSharesAfter';
+					type: 'u128';
+					index: false;
+				},
+				{
+					name: 'userVaultSharesAfter';
+					type: 'u128';
+					index: false;
+				},
+				{
+					name: 'protocolSharesAfter';
+					type: 'u128';
+					index: false;
+				},
+				{
+					name: 'protocolFee';
+					type: 'i64';
+					index: false;
+				},
+				{
+					name: 'protocolFeeShares';
+					type: 'i64';
+					index: false;
+				},
+				{
+					name: 'managementFee';
+					type: 'i64';
+					index: false;
+				},
+				{
+					name: 'managementFeeShares';
+					type: 'i64';
+					index: false;
+				}
+			];
+		},
+		{
+			name: 'ProtocolRecord';
+			fields: [
+				{
+					name: 'ts';
+					type: 'i64';
+					index: false;
+				},
+				{
+					name: 'vault';
+					type: 'publicKey';
+					index: false;
+				},
+				{
+					name: 'protocol';
+					type: 'publicKey';
+					index: false;
+				},
+				{
+					name: 'action';
+					type: {
+						defined: 'ProtocolAction';
+					};
+					index: false;
+				},
+				{
+					name: 'amount';
+					type: 'u64';
+					index: false;
+				},
+				{
+					name: 'nShares';
+					type: 'u128';
+					index: false;
+				},
+				{
+					name: 'vaultEquityBefore';
+					type: 'u64';
+					index: false;
+				},
+				{
+					name: 'protocolSharesBefore';
+					type: 'u128';
+					index: false;
+				},
+				{
+					name: 'totalVaultSharesBefore';
+					type: 'u128';
+					index: false;
+				},
+				{
+					name: 'userVaultSharesBefore';
+					type: 'u128';
+					index: false;
+				},
+				{
+					name: 'managerSharesBefore';
+					type: 'u128';
+					index: false;
+				},
+				{
+					name: 'protocolSharesAfter';
+					type: 'u128';
+					index: false;
+				},
+				{
+					name: 'totalVaultSharesAfter';
+					type: 'u128';
+					index: false;
+				},
+				{
+					name: 'userVaultSharesAfter';
+					type: 'u128';
+					index: false;
+				},
+				{
+					name: 'managerSharesAfter';
+					type: 'u128';
+					index: false;
+				},
+				{
+					name: 'protocolFee';
+					type: 'i64';
+					index: false;
+				},
+				{
+					name: 'protocolFeeShares';
+					type: 'i64';
+					index: false;
+				},
+				{
+					name: 'managementFee';
+					type: 'i64';
+					index: false;
+				},
+				{
+					name: 'managementFeeShares';
+					type: 'i64';
+					index: false;
+				}
+			];
+		},
+		{
+			name: 'AppointLiquidatorRecord';
+			fields: [
+				{
+					name: 'ts';
+					type: 'i64';
+					index: false;
+				},
+				{
+					name: 'vault';
+					type: 'publicKey';
+					index: false;
+				},
+				{
+					name: 'liquidator';
+					type: 'publicKey';
+					index: false;
+				},
+				{
+					name: 'liquidationWindow';
+					type: 'i64';
+					index: false;
+				}
+			];
+		},
+		{
+			name: 'LiquidationRecord';
+			fields: [
+				{
+					name: 'ts';
+					type: 'i64';
+					index: false;
+				},
+				{
+					name: 'vault';
+					type: 'publicKey';
+					index: false;
+				},
+				{
+					name: 'liquidatorRole';
+					type: {
+						defined: 'LiquidatorRole';
+					};
+					index: false;
+				},
+				{
+					name: 'liquidator';
+					type: 'publicKey';
+					index: false;
+				},
+				{
+					name: 'market';
+					type: 'publicKey';
+					index: false;
+				},
+				{
+					name: 'liquidationWindow';
+					type: 'i64';
+					index: false;
+				},
+				{
+					name: 'baseLotsSold';
+					type: 'u64';
+					index: false;
+				},
+				{
+					name: 'avgPrice';
+					type: 'u64';
+					index: false;
+				},
+				{
+					name: 'quoteReceived';
+					type: 'u64';
+					index: false;
+				},
+				{
+					name: 'fees';
+					type: 'u64';
+					index: false;
+				},
+				{
+					name: 'withdrawRequestValueBefore';
+					type: 'u64';
+					index: false;
+				},
+				{
+					name: 'withdrawRequestValueAfter';
+					type: 'u64';
+					index: false;
+				}
+			];
+		},
+		{
+			name: 'DelegateActionRecord';
+			fields: [
+				{
+					name: 'ts';
+					type: 'i64';
+					index: false;
+				},
+				{
+					name: 'vault';
+					type: 'publicKey';
+					index: false;
+				},
+				{
+					name: 'delegate';
+					type: 'publicKey';
+					index: false;
+				},
+				{
+					name: 'market';
+					type: 'publicKey';
+					index: false;
+				},
+				{
+					name: 'action';
+					type: {
+						defined: 'DelegateAction';
+					};
+					index: false;
+				},
+				{
+					name: 'positionBefore';
+					type: {
+						defined: 'MarketPosition';
+					};
+					index: false;
+				},
+				{
+					name: 'positionAfter';
+					type: {
+						defined: 'MarketPosition';
+					};
+					index: false;
+				}
+			];
+		},
+		{
+			name: 'EpochRecord';
+			fields: [
+				{
+					name: 'ts';
+					type: 'i64';
+					index: false;
+				},
+				{
+					name: 'vault';
+					type: 'publicKey';
+					index: false;
+				},
+				{
+					name: 'epoch';
+					type: 'u64';
+					index: false;
+				},
+				{
+					name: 'vaultEquity';
+					type: 'u64';
+					index: false;
+				},
+				{
+					name: 'deposits';
+					type: 'u64';
+					index: false;
+				},
+				{
+					name: 'depositShares';
+					type: 'u128';
+					index: false;
+				},
+				{
+					name: 'withdrawShares';
+					type: 'u128';
+					index: false;
+				},
+				{
+					name: 'withdrawValue';
+					type: 'u64';
+					index: false;
+				},
+				{
+					name: 'totalVaultSharesBefore';
+					type: 'u128';
+					index: false;
+				},
+				{
+					name: 'totalVaultSharesAfter';
+					type: 'u128';
+					index: false;
+				},
+				{
+					name: 'protocolFee';
+					type: 'i64';
+					index: false;
+				},
+				{
+					name: 'protocolFeeShares';
+					type: 'i64';
+					index: false;
+				},
+				{
+					name: 'managementFee';
+					type: 'i64';
+					index: false;
+				},
+				{
+					name: 'managementFeeShares';
+					type: 'i64';
+					index: false;
+				}
+			];
+		},
+		{
+			name: 'FeeRecord';
+			fields: [
+				{
+					name: 'ts';
+					type: 'i64';
+					index: false;
+				},
+				{
+					name: 'vault';
+					type: 'publicKey';
+					index: false;
+				},
+				{
+					name: 'vaultEquity';
+					type: 'u64';
+					index: false;
+				},
+				{
+					name: 'totalVaultSharesBefore';
+					type: 'u128';
+					index: false;
+				},
+				{
+					name: 'userVaultSharesBefore';
+					type: 'u128';
+					index: false;
+				},
+				{
+					name: 'managerSharesBefore';
+					type: 'u128';
+					index: false;
+				},
+				{
+					name: 'protocolSharesBefore';
+					type: 'u128';
+					index: false;
+				},
+				{
+					name: 'totalVaultSharesAfter';
+					type: 'u128';
+					index: false;
+				},
+				{
+					name: 'userVaultSharesAfter';
+					type: 'u128';
+					index: false;
+				},
+				{
+					name: 'managerSharesAfter';
+					type: 'u128';
+					index: false;
+				},
+				{
+					name: 'protocolSharesAfter';
+					type: 'u128';
+					index: false;
+				},
+				{
+					name: 'protocolFee';
+					type: 'i64';
+					index: false;
+				},
+				{
+					name: 'protocolFeeShares';
+					type: 'i64';
+					index: false;
+				},
+				{
+					name: 'managementFee';
+					type: 'i64';
+					index: false;
+				},
+				{
+					name: 'managementFeeShares';
+					type: 'i64';
+					index: false;
+				}
+			];
+		}
+	];
+	errors: [
+		{
+			code: 6000;
+			name: 'Default';
+			msg: 'Default';
+		},
+		{
+			code: 6001;
+			name: 'InvalidVaultRebase';
+			msg: 'InvalidVaultRebase';
+		},
+		{
+			code: 6002;
+			name: 'InvalidVaultSharesDetected';
+			msg: 'InvalidVaultSharesDetected';
+		},
+		{
+			code: 6003;
+			name: 'CannotWithdrawBeforeRedeemPeriodEnd';
+			msg: 'CannotWithdrawBeforeRedeemPeriodEnd';
+		},
+		{
+			code: 6004;
+			name: 'InvalidVaultWithdraw';
+			msg: 'InvalidVaultWithdraw';
+		},
+		{
+			code: 6005;
+			name: 'InsufficientVaultShares';
+			msg: 'InsufficientVaultShares';
+		},
+		{
+			code: 6006;
+			name: 'InvalidVaultWithdrawSize';
+			msg: 'InvalidVaultWithdrawSize';
+		},
+		{
+			code: 6007;
+			name: 'InvalidVaultForNewDepositors';
+			msg: 'InvalidVaultForNewDepositors';
+		},
+		{
+			code: 6008;
+			name: 'VaultWithdrawRequestInProgress';
+			msg: 'VaultWithdrawRequestInProgress';
+		},
+		{
+			code: 6009;
+			name: 'VaultIsAtCapacity';
+			msg: 'VaultIsAtCapacity';
+		},
+		{
+			code: 6010;
+			name: 'InvalidVaultDepositorInitialization';
+			msg: 'InvalidVaultDepositorInitialization';
+		},
+		{
+			code: 6011;
+			name: 'DelegateNotAvailableForLiquidation';
+			msg: 'DelegateNotAvailableForLiquidation';
+		},
+		{
+			code: 6012;
+			name: 'InvalidLiquidator';
+			msg: 'InvalidLiquidator';
+		},
+		{
+			code: 6013;
+			name: 'LiquidationExpired';
+			msg: 'LiquidationExpired';
+		},
+		{
+			code: 6014;
+			name: 'InvalidEquityValue';
+			msg: 'InvalidEquityValue';
+		},
+		{
+			code: 6015;
+			name: 'VaultInLiquidation';
+			msg: 'VaultInLiquidation';
+		},
+		{
+			code: 6016;
+			name: 'InvestorCanWithdraw';
+			msg: 'InvestorCanWithdraw';
+		},
+		{
+			code: 6017;
+			name: 'InvalidVaultInitialization';
+			msg: 'InvalidVaultInitialization';
+		},
+		{
+			code: 6018;
+			name: 'InvalidVaultUpdate';
+			msg: 'InvalidVaultUpdate';
+		},
+		{
+			code: 6019;
+			name: 'PermissionedVault';
+			msg: 'PermissionedVault';
+		},
+		{
+			code: 6020;
+			name: 'WithdrawInProgress';
+			msg: 'WithdrawInProgress';
+		},
+		{
+			code: 6021;
+			name: 'SharesPercentTooLarge';
+			msg: 'SharesPercentTooLarge';
+		},
+		{
+			code: 6022;
+			name: 'InvalidVaultDeposit';
+			msg: 'InvalidVaultDeposit';
+		},
+		{
+			code: 6023;
+			name: 'OngoingLiquidation';
+			msg: 'OngoingLiquidation';
+		},
 		{
-			name: 'MarketLookupTableParams';
-			type: {
-				kind: 'struct';
-				fields: [
-					{
-						name: 'solUsdcMarket';
-						type: 'publicKey';
-					},
-					{
-						name: 'usdcMint';
-						type: 'publicKey';
-					},
-					{
-						name: 'solMint';
-						type: 'publicKey';
-					}
-				];
-			};
+			code: 6024;
+			name: 'VaultProtocolMissing';
+			msg: 'VaultProtocolMissing';
 		},
 		{
-			name: 'CancelMultipleOrdersParams';
-			type: {
-				kind: 'struct';
-				fields: [
-					{
-						name: 'orders';
-						type: {
-							vec: {
-								defined: 'CancelOrderParams';
-							};
-						};
-					}
-				];
-			};
+			code: 6025;
+			name: 'BnConversion';
+			msg: 'BnConversion';
 		},
 		{
-			name: 'CancelOrderParams';
-			type: {
-				kind: 'struct';
-				fields: [
-					{
-						name: 'side';
-						type: {
-							defined: 'Side';
-						};
-					},
-					{
-						name: 'priceInTicks';
-						type: 'u64';
-					},
-					{
-						name: 'orderSequenceNumber';
-						type: 'u64';
-					}
-				];
-			};
+			code: 6026;
+			name: 'MathError';
+			msg: 'MathError';
 		},
 		{
-			name: 'VaultParams';
-			type: {
-				kind: 'struct';
-				fields: [
-					{
-						name: 'name';
-						type: {
-							array: ['u8', 32];
-						};
-					},
-					{
-						name: 'redeemPeriod';
-						type: 'i64';
-					},
-					{
-						name: 'maxTokens';
-						type: 'u64';
-					},
-					{
-						name: 'managementFee';
-						type: 'i64';
-					},
-					{
-						name: 'minDepositAmount';
-						type: 'u64';
-					},
-					{
-						name: 'profitShare';
-						type: 'u32';
-					},
-					{
-						name: 'hurdleRate';
-						type: 'u32';
-					},
-					{
-						name: 'permissioned';
-						type: 'bool';
-					},
-					{
-						name: 'protocol';
-						type: 'publicKey';
-					},
-					{
-						name: 'protocolFee';
-						type: 'u64';
-					},
-					{
-						name: 'protocolProfitShare';
-						type: 'u32';
-					}
-				];
-			};
+			code: 6027;
+			name: 'CastError';
+			msg: 'CastError';
 		},
 		{
-			name: 'PlaceOrderParams';
-			type: {
-				kind: 'struct';
-				fields: [
-					{
-						name: 'order';
-						type: 'bytes';
-					}
-				];
-			};
+			code: 6028;
+			name: 'UnwrapError';
+			msg: 'UnwrapError';
 		},
 		{
-			name: 'UpdateVaultParams';
-			type: {
-				kind: 'struct';
-				fields: [
-					{
-						name: 'redeemPeriod';
-						type: {
-							option: 'i64';
-						};
-					},
-					{
-						name: 'maxTokens';
-						type: {
-							option: 'u64';
-						};
-					},
-					{
-						name: 'managementFee';
-						type: {
-							option: 'i64';
-						};
-					},
-					{
-						name: 'minDepositAmount';
-						type: {
-							option: 'u64';
-						};
-					},
-					{
-						name: 'profitShare';
-						type: {
-							option: 'u32';
-						};
-					},
-					{
-						name: 'hurdleRate';
-						type: {
-							option: 'u32';
-						};
-					},
-					{
-						name: 'permissioned';
-						type: {
-							option: 'bool';
-						};
-					},
-					{
-						name: 'delegate';
-						type: {
-							option: 'publicKey';
-						};
-					}
-				];
-			};
+			code: 6029;
+			name: 'MarketDeserializationError';
+			msg: 'MarketDeserializationError';
+		},
+		{
+			code: 6030;
+			name: 'UnrecognizedQuoteMint';
+			msg: 'UnrecognizedQuoteMint';
+		},
+		{
+			code: 6031;
+			name: 'SolMarketMissing';
+			msg: 'SolMarketMissing';
+		},
+		{
+			code: 6032;
+			name: 'MarketMissingInRemainingAccounts';
+			msg: 'MarketMissingInRemainingAccounts';
+		},
+		{
+			code: 6033;
+			name: 'MarketRegistryMismatch';
+			msg: 'MarketRegistryMismatch';
+		},
+		{
+			code: 6034;
+			name: 'OrderPacketDeserialization';
+			msg: 'OrderPacketDeserialization';
+		},
+		{
+			code: 6035;
+			name: 'OrderPacketMustUseDepositedFunds';
+			msg: 'OrderPacketMustUseDepositedFunds';
+		},
+		{
+			code: 6036;
+			name: 'InvalidPhoenixInstruction';
+			msg: 'InvalidPhoenixInstruction';
+		},
+		{
+			code: 6037;
+			name: 'OrderPacketMustBeTakeOnly';
+			msg: 'OrderPacketMustBeTakeOnly';
+		},
+		{
+			code: 6038;
+			name: 'BaseLotsMustBeZero';
+			msg: 'BaseLotsMustBeZero';
+		},
+		{
+			code: 6039;
+			name: 'TraderStateNotFound';
+			msg: 'TraderStateNotFound';
+		},
+		{
+			code: 6040;
+			name: 'MarketPositionNotFound';
+			msg: 'MarketPositionNotFound';
+		},
+		{
+			code: 6041;
+			name: 'InKindMarketAccountsMissing';
+			msg: 'InKindMarketAccountsMissing';
+		},
+		{
+			code: 6042;
+			name: 'InKindTokenAccountMismatch';
+			msg: 'InKindTokenAccountMismatch';
+		},
+		{
+			code: 6043;
+			name: 'InKindLotsLocked';
+			msg: 'InKindLotsLocked';
+		},
+		{
+			code: 6044;
+			name: 'EpochModeDisabled';
+			msg: 'EpochModeDisabled';
+		},
+		{
+			code: 6045;
+			name: 'EpochNotEnded';
+			msg: 'EpochNotEnded';
+		},
+		{
+			code: 6046;
+			name: 'EpochUnclaimed';
+			msg: 'EpochUnclaimed';
+		},
+		{
+			code: 6047;
+			name: 'EpochNotSettled';
+			msg: 'EpochNotSettled';
 		},
 		{
-			name: 'MarketPosition';
-			type: {
-				kind: 'struct';
-				fields: [
-					{
-						name: 'market';
-						type: 'publicKey';
-					},
-					{
-						name: 'quoteLotsLocked';
-						type: 'u64';
-					},
-					{
-						name: 'quoteLotsFree';
-						type: 'u64';
-					},
-					{
-						name: 'baseLotsLocked';
-						type: 'u64';
-					},
-					{
-						name: 'baseLotsFree';
-						type: 'u64';
-					}
-				];
-			};
+			code: 6048;
+			name: 'InvalidInvestorAccount';
+			msg: 'InvalidInvestorAccount';
 		},
 		{
-			name: 'MarketTransferParams';
-			type: {
-				kind: 'struct';
-				fields: [
-					{
-						name: 'quoteLots';
-						type: 'u64';
-					},
-					{
-						name: 'baseLots';
-						type: 'u64';
-					}
-				];
-			};
+			code: 6049;
+			name: 'EpochReservedUsdc';
+			msg: 'EpochReservedUsdc';
 		},
 		{
-			name: 'WithdrawRequest';
-			type: {
-				kind: 'struct';
-				fields: [
-					{
-						name: 'shares';
-						docs: ['request shares of vault withdraw'];
-						type: 'u128';
-					},
-					{
-						name: 'value';
-						docs: ['requested value in USDC of shares for withdraw'];
-						type: 'u64';
-					},
-					{
-						name: 'ts';
-						docs: ['request ts of vault withdraw'];
-						type: 'i64';
-					}
-				];
-			};
+			code: 6050;
+			name: 'NavSampleTooSoon';
+			msg: 'NavSampleTooSoon';
 		},
 		{
-			name: 'Side';
-			type: {
-				kind: 'enum';
-				variants: [
-					{
-						name: 'Bid';
+			code: 6051;
+			name: 'InvalidNavHistory';
+			msg: 'InvalidNavHistory';
+		},
+		{
+			code: 6052;
+			name: 'InvalidSharePrice';
+			msg: 'InvalidSharePrice';
+		},
+		{
+			code: 6053;
+			name: 'StaleSharePrice';
+			msg: 'StaleSharePrice';
+		},
+		{
+			code: 6054;
+			name: 'SlippageExceeded';
+			msg: 'SlippageExceeded';
+		},
+		{
+			code: 6055;
+			name: 'DeadlineExceeded';
+			msg: 'DeadlineExceeded';
+		},
+		{
+			code: 6056;
+			name: 'TradingHalted';
+			msg: 'TradingHalted';
+		},
+		{
+			code: 6057;
+			name: 'TradingNotHalted';
+			msg: 'TradingNotHalted';
+		},
+		{
+			code: 6058;
+			name: 'InvalidVaultState';
+			msg: 'InvalidVaultState';
+		},
+		{
+			code: 6059;
+			name: 'WindDownPositionsOpen';
+			msg: 'WindDownPositionsOpen';
+		},
+		{
+			code: 6060;
+			name: 'WindDownNotFinalized';
+			msg: 'WindDownNotFinalized';
+		},
+		{
+			code: 6061;
+			name: 'ManagerStakeTooLow';
+			msg: 'ManagerStakeTooLow';
+		},
+		{
+			code: 6062;
+			name: 'LiquidityBufferBreached';
+			msg: 'LiquidityBufferBreached';
+		},
+		{
+			code: 6063;
+			name: 'InvestorLockedUp';
+			msg: 'InvestorLockedUp';
+		},
+		{
+			code: 6064;
+			name: 'InKindEpochMode';
+			msg: 'InKindEpochMode';
+		}
+	];
+};
+
+export const IDL: PhoenixVaults = {
+	version: '0.1.0',
+	name: 'phoenix_vaults',
+	instructions: [
+		{
+			name: 'initializeVault',
+			docs: [
+				'The wallet that signs this instruction becomes the manager and therefore profits the management fee and profit share.',
+				'The manager can NOT be updated, so be careful who creates the vault.',
+				'',
+				'By default, the manager is also the delegate who has permission to trade on behalf of the vault.',
+				'',
+				'The delegate can be updated at anytime by calling `update_vault`.',
+			],
+			accounts: [
+				{
+					name: 'vault',
+					isMut: true,
+					isSigner: false,
+				},
+				{
+					name: 'manager',
+					isMut: false,
+					isSigner: true,
+				},
+				{
+					name: 'usdcTokenAccount',
+					isMut: true,
+					isSigner: false,
+				},
+				{
+					name: 'usdcMint',
+					isMut: false,
+					isSigner: false,
+				},
+				{
+					name: 'solTokenAccount',
+					isMut: true,
+					isSigner: false,
+				},
+				{
+					name: 'solMint',
+					isMut: false,
+					isSigner: false,
+				},
+				{
+					name: 'payer',
+					isMut: true,
+					isSigner: true,
+				},
+				{
+					name: 'rent',
+					isMut: false,
+					isSigner: false,
+				},
+				{
+					name: 'systemProgram',
+					isMut: false,
+					isSigner: false,
+				},
+				{
+					name: 'tokenProgram',
+					isMut: false,
+					isSigner: false,
+				},
+				{
+					name: 'associatedTokenProgram',
+					isMut: false,
+					isSigner: false,
+				},
+			],
+			args: [
+				{
+					name: 'params',
+					type: {
+						defined: 'VaultParams',
 					},
-					{
-						name: 'Ask';
-					}
-				];
-			};
+				},
+			],
+		},
+		{
+			name: 'initializeInvestor',
+			docs: [
+				'User creates an [`Investor`] account to invest with a [`Vault`].',
+			],
+			accounts: [
+				{
+					name: 'vault',
+					isMut: false,
+					isSigner: false,
+				},
+				{
+					name: 'investor',
+					isMut: true,
+					isSigner: false,
+				},
+				{
+					name: 'authority',
+					isMut: false,
+					isSigner: false,
+				},
+				{
+					name: 'payer',
+					isMut: true,
+					isSigner: true,
+				},
+				{
+					name: 'rent',
+					isMut: false,
+					isSigner: false,
+				},
+				{
+					name: 'systemProgram',
+					isMut: false,
+					isSigner: false,
+				},
+			],
+			args: [],
+		},
+		{
+			name: 'initializeMarketRegistry',
+			docs: [
+				'Admin function to create an on-chain source of truth for list of Phoenix markets.',
+				'This is called once after the first deploy of this program to a network.',
+			],
+			accounts: [
+				{
+					name: 'authority',
+					isMut: false,
+					isSigner: true,
+					docs: ['Admin-level keypair'],
+				},
+				{
+					name: 'marketRegistry',
+					isMut: true,
+					isSigner: false,
+				},
+				{
+					name: 'payer',
+					isMut: true,
+					isSigner: true,
+				},
+				{
+					name: 'rent',
+					isMut: false,
+					isSigner: false,
+				},
+				{
+					name: 'systemProgram',
+					isMut: false,
+					isSigner: false,
+				},
+			],
+			args: [
+				{
+					name: 'params',
+					type: {
+						defined: 'MarketLookupTableParams',
+					},
+				},
+			],
 		},
 		{
-			name: 'InvestorAction';
-			type: {
-				kind: 'enum';
-				variants: [
-					{
-						name: 'Deposit';
-					},
-					{
-						name: 'WithdrawRequest';
-					},
-					{
-						name: 'CancelWithdrawRequest';
+			name: 'investorDeposit',
+			docs: [
+				'Investor deposits funds to the vault USDC token account.',
+				'',
+				'Fails with `SlippageExceeded` if fewer than `min_shares_out` shares are minted,',
+				'or with `DeadlineExceeded` if executed after the `deadline` unix timestamp.',
+			],
+			accounts: [
+				{
+					name: 'vault',
+					isMut: true,
+					isSigner: false,
+				},
+				{
+					name: 'investor',
+					isMut: true,
+					isSigner: false,
+				},
+				{
+					name: 'authority',
+					isMut: false,
+					isSigner: true,
+				},
+				{
+					name: 'marketRegistry',
+					isMut: false,
+					isSigner: false,
+				},
+				{
+					name: 'navHistory',
+					isMut: true,
+					isSigner: false,
+					isOptional: true,
+				},
+				{
+					name: 'investorQuoteTokenAccount',
+					isMut: true,
+					isSigner: false,
+				},
+				{
+					name: 'vaultQuoteTokenAccount',
+					isMut: true,
+					isSigner: false,
+				},
+				{
+					name: 'tokenProgram',
+					isMut: false,
+					isSigner: false,
+				},
+			],
+			args: [
+				{
+					name: 'amount',
+					type: 'u64',
+				},
+				{
+					name: 'minSharesOut',
+					type: {
+						option: 'u128',
 					},
-					{
-						name: 'Withdraw';
+				},
+				{
+					name: 'deadline',
+					type: {
+						option: 'i64',
 					},
-					{
-						name: 'FeePayment';
-					}
-				];
-			};
+				},
+			],
 		},
 		{
-			name: 'WithdrawUnit';
-			type: {
-				kind: 'enum';
-				variants: [
-					{
-						name: 'Shares';
+			name: 'investorWithdraw',
+			docs: [
+				'Investor withdraws funds from the vault, assuming funds are in the vault USDC token account.',
+				'',
+				'If insufficient USDC in the vault_usdc_token_account, then the investor must call `appoint_liquidator` to',
+				'acquire permission to liquidate the vault market positions.',
+				'',
+				'Then call `liquidate_usdc_market` or `liquidate_sol_market` to forcefully swap a vault market position back to USDC,',
+				'and then withdraw back to the investor.',
+				'',
+				'Fails with `SlippageExceeded` if less than `min_amount_out` USDC is paid out,',
+				'or with `DeadlineExceeded` if executed after the `deadline` unix timestamp.',
+			],
+			accounts: [
+				{
+					name: 'vault',
+					isMut: true,
+					isSigner: false,
+				},
+				{
+					name: 'investor',
+					isMut: true,
+					isSigner: false,
+				},
+				{
+					name: 'authority',
+					isMut: false,
+					isSigner: true,
+				},
+				{
+					name: 'marketRegistry',
+					isMut: false,
+					isSigner: false,
+				},
+				{
+					name: 'navHistory',
+					isMut: true,
+					isSigner: false,
+					isOptional: true,
+				},
+				{
+					name: 'investorQuoteTokenAccount',
+					isMut: true,
+					isSigner: false,
+				},
+				{
+					name: 'phoenix',
+					isMut: false,
+					isSigner: false,
+				},
+				{
+					name: 'logAuthority',
+					isMut: false,
+					isSigner: false,
+				},
+				{
+					name: 'market',
+					isMut: true,
+					isSigner: false,
+				},
+				{
+					name: 'seat',
+					isMut: false,
+					isSigner: false,
+				},
+				{
+					name: 'baseMint',
+					isMut: false,
+					isSigner: false,
+				},
+				{
+					name: 'quoteMint',
+					isMut: false,
+					isSigner: false,
+				},
+				{
+					name: 'vaultBaseTokenAccount',
+					isMut: true,
+					isSigner: false,
+				},
+				{
+					name: 'vaultQuoteTokenAccount',
+					isMut: true,
+					isSigner: false,
+				},
+				{
+					name: 'marketBaseTokenAccount',
+					isMut: true,
+					isSigner: false,
+				},
+				{
+					name: 'marketQuoteTokenAccount',
+					isMut: true,
+					isSigner: false,
+				},
+				{
+					name: 'tokenProgram',
+					isMut: false,
+					isSigner: false,
+				},
+			],
+			args: [
+				{
+					name: 'minAmountOut',
+					type: {
+						option: 'u64',
 					},
-					{
-						name: 'Token';
+				},
+				{
+					name: 'deadline',
+					type: {
+						option: 'i64',
 					},
-					{
-						name: 'SharesPercent';
-					}
-				];
-			};
-		}
-	];
-	events: [
+				},
+			],
+		},
+		{
+			name: 'investorWithdrawInKind',
+			docs: [
+				'Investor withdraws a pro-rata slice of every vault market position and of the vault token balances,',
+				'instead of waiting for the vault to be liquidated to USDC.',
+				'',
+				'The remaining accounts are the SOL/USDC market followed by, for each vault market position in order:',
+				'market, vault base token account, investor base token account, market base vault, market quote vault.',
+			],
+			accounts: [
+				{
+					name: 'vault',
+					isMut: true,
+					isSigner: false,
+				},
+				{
+					name: 'investor',
+					isMut: true,
+					isSigner: false,
+				},
+				{
+					name: 'authority',
+					isMut: false,
+					isSigner: true,
+				},
+				{
+					name: 'marketRegistry',
+					isMut: false,
+					isSigner: false,
+				},
+				{
+					name: 'navHistory',
+					isMut: true,
+					isSigner: false,
+					isOptional: true,
+				},
+				{
+					name: 'investorUsdcTokenAccount',
+					isMut: true,
+					isSigner: false,
+				},
+				{
+					name: 'investorSolTokenAccount',
+					isMut: true,
+					isSigner: false,
+				},
+				{
+					name: 'vaultUsdcTokenAccount',
+					isMut: true,
+					isSigner: false,
+				},
+				{
+					name: 'vaultSolTokenAccount',
+					isMut: true,
+					isSigner: false,
+				},
+				{
+					name: 'phoenix',
+					isMut: false,
+					isSigner: false,
+				},
+				{
+					name: 'logAuthority',
+					isMut: false,
+					isSigner: false,
+				},
+				{
+					name: 'tokenProgram',
+					isMut: false,
+					isSigner: false,
+				},
+			],
+			args: [],
+		},
 		{
-			name: 'VaultRecord';
-			fields: [
+			name: 'claimSeat',
+			docs: [
+				'Vault delegate claims a seat on a Phoenix market to enable trading.',
+				'Call this before `place_limit_order`.',
+			],
+			accounts: [
 				{
-					name: 'ts';
-					type: 'i64';
-					index: false;
+					name: 'vault',
+					isMut: false,
+					isSigner: false,
+					docs: [
+						'If delegate has authority to sign for vault, then any Phoenix CPI is valid.',
+						'Phoenix CPI validates that opaque instruction data is a [`PhoenixInstruction`],',
+						'so this is safe since any Phoenix CPI is secure.',
+					],
 				},
 				{
-					name: 'spotMarketIndex';
-					type: 'u16';
-					index: false;
+					name: 'delegate',
+					isMut: false,
+					isSigner: true,
+					docs: [
+						'Either vault delegate or an investor liquidating this vault.',
+						'If an investor needs to call this, then they must call `appoint_liquidator` first.',
+					],
 				},
 				{
-					name: 'vaultEquityBefore';
-					type: 'u64';
-					index: false;
-				}
-			];
+					name: 'phoenix',
+					isMut: false,
+					isSigner: false,
+				},
+				{
+					name: 'logAuthority',
+					isMut: false,
+					isSigner: false,
+				},
+				{
+					name: 'market',
+					isMut: true,
+					isSigner: false,
+				},
+				{
+					name: 'seatManager',
+					isMut: true,
+					isSigner: false,
+				},
+				{
+					name: 'seatDepositCollector',
+					isMut: true,
+					isSigner: false,
+				},
+				{
+					name: 'payer',
+					isMut: true,
+					isSigner: true,
+				},
+				{
+					name: 'seat',
+					isMut: true,
+					isSigner: false,
+				},
+				{
+					name: 'systemProgram',
+					isMut: false,
+					isSigner: false,
+				},
+				{
+					name: 'phoenixSeatManager',
+					isMut: false,
+					isSigner: false,
+				},
+			],
+			args: [],
 		},
 		{
-			name: 'InvestorRecord';
-			fields: [
+			name: 'placeLimitOrder',
+			docs: ['Vault delegate places a limit order on behalf of the vault.'],
+			accounts: [
 				{
-					name: 'ts';
-					type: 'i64';
-					index: false;
+					name: 'vault',
+					isMut: true,
+					isSigner: false,
+					docs: [
+						'If delegate has authority to sign for vault, then any Phoenix CPI is valid.',
+						'Phoenix CPI validates that opaque instruction data is a [`PhoenixInstruction`],',
+						'so this is safe since any Phoenix CPI is secure.',
+					],
 				},
 				{
-					name: 'vault';
-					type: 'publicKey';
-					index: false;
+					name: 'delegate',
+					isMut: false,
+					isSigner: true,
+					docs: [
+						'Is manager by default, but can be delegated to another pubkey using `update_delegate`',
+					],
 				},
 				{
-					name: 'depositorAuthority';
-					type: 'publicKey';
-					index: false;
+					name: 'phoenix',
+					isMut: false,
+					isSigner: false,
 				},
 				{
-					name: 'action';
-					type: {
-						defined: 'InvestorAction';
-					};
-					index: false;
+					name: 'logAuthority',
+					isMut: false,
+					isSigner: false,
 				},
 				{
-					name: 'amount';
-					type: 'u64';
-					index: false;
+					name: 'market',
+					isMut: true,
+					isSigner: false,
 				},
 				{
-					name: 'usdcMint';
-					type: 'publicKey';
-					index: false;
+					name: 'seat',
+					isMut: false,
+					isSigner: false,
 				},
 				{
-					name: 'solMint';
-					type: 'publicKey';
-					index: false;
+					name: 'baseMint',
+					isMut: false,
+					isSigner: false,
 				},
 				{
-					name: 'vaultSharesBefore';
-					type: 'u128';
-					index: false;
+					name: 'quoteMint',
+					isMut: false,
+					isSigner: false,
 				},
 				{
-					name: 'vaultSharesAfter';
-					type: 'u128';
-					index: false;
+					name: 'vaultBaseTokenAccount',
+					isMut: true,
+					isSigner: false,
 				},
 				{
-					name: 'vaultEquityBefore';
-					type: 'u64';
-					index: false;
+					name: 'vaultQuoteTokenAccount',
+					isMut: true,
+					isSigner: false,
 				},
 				{
-					name: 'userVaultSharesBefore';
-					type: 'u128';
-					index: false;
+					name: 'marketBaseTokenAccount',
+					isMut: true,
+					isSigner: false,
 				},
 				{
-					name: 'totalVaultSharesBefore';
-					type: 'u128';
-					index: false;
+					name: 'marketQuoteTokenAccount',
+					isMut: true,
+					isSigner: false,
 				},
 				{
-					name: 'protocolSharesBefore';
-					type: 'u128';
-					index: false;
+					name: 'tokenProgram',
+					isMut: false,
+					isSigner: false,
 				},
+			],
+			args: [
 				{
-					name: 'userVaultSharesAfter';
-					type: 'u128';
-					index: false;
+					name: 'params',
+					type: {
+						defined: 'PlaceOrderParams',
+					},
 				},
+			],
+		},
+		{
+			name: 'investorRequestWithdraw',
+			docs: ['Investor request withdrawal of funds from the vault.'],
+			accounts: [
 				{
-					name: 'totalVaultSharesAfter';
-					type: 'u128';
-					index: false;
+					name: 'vault',
+					isMut: true,
+					isSigner: false,
 				},
 				{
-					name: 'protocolSharesAfter';
-					type: 'u128';
-					index: false;
+					name: 'investor',
+					isMut: true,
+					isSigner: false,
 				},
 				{
-					name: 'protocolProfitShare';
-					type: 'u64';
-					index: false;
+					name: 'authority',
+					isMut: false,
+					isSigner: true,
 				},
 				{
-					name: 'protocolFee';
-					type: 'i64';
-					index: false;
+					name: 'marketRegistry',
+					isMut: false,
+					isSigner: false,
+				},
+				{
+					name: 'vaultUsdcTokenAccount',
+					isMut: true,
+					isSigner: false,
+				},
+			],
+			args: [
+				{
+					name: 'withdrawAmount',
+					type: 'u64',
+				},
+				{
+					name: 'withdrawUnit',
+					type: {
+						defined: 'WithdrawUnit',
+					},
+				},
+			],
+		},
+		{
+			name: 'cancelWithdrawRequest',
+			accounts: [
+				{
+					name: 'vault',
+					isMut: true,
+					isSigner: false,
+				},
+				{
+					name: 'investor',
+					isMut: true,
+					isSigner: false,
 				},
 				{
-					name: 'protocolFeeShares';
-					type: 'i64';
-					index: false;
+					name: 'authority',
+					isMut: false,
+					isSigner: true,
 				},
 				{
-					name: 'managerProfitShare';
-					type: 'u64';
-					index: false;
+					name: 'marketRegistry',
+					isMut: false,
+					isSigner: false,
 				},
 				{
-					name: 'managementFee';
-					type: 'i64';
-					index: false;
+					name: 'vaultUsdcTokenAccount',
+					isMut: true,
+					isSigner: false,
 				},
-				{
-					name: 'managementFeeShares';
-					type: 'i64';
-					index: false;
-				}
-			];
-		}
-	];
-	errors: [
-		{
-			code: 6000;
-			name: 'Default';
-			msg: 'Default';
-		},
-		{
-			code: 6001;
-			name: 'InvalidVaultRebase';
-			msg: 'InvalidVaultRebase';
-		},
-		{
-			code: 6002;
-			name: 'InvalidVaultSharesDetected';
-			msg: 'InvalidVaultSharesDetected';
-		},
-		{
-			code: 6003;
-			name: 'CannotWithdrawBeforeRedeemPeriodEnd';
-			msg: 'CannotWithdrawBeforeRedeemPeriodEnd';
-		},
-		{
-			code: 6004;
-			name: 'InvalidVaultWithdraw';
-			msg: 'InvalidVaultWithdraw';
-		},
-		{
-			code: 6005;
-			name: 'InsufficientVaultShares';
-			msg: 'InsufficientVaultShares';
-		},
-		{
-			code: 6006;
-			name: 'InvalidVaultWithdrawSize';
-			msg: 'InvalidVaultWithdrawSize';
-		},
-		{
-			code: 6007;
-			name: 'InvalidVaultForNewDepositors';
-			msg: 'InvalidVaultForNewDepositors';
-		},
-		{
-			code: 6008;
-			name: 'VaultWithdrawRequestInProgress';
-			msg: 'VaultWithdrawRequestInProgress';
-		},
-		{
-			code: 6009;
-			name: 'VaultIsAtCapacity';
-			msg: 'VaultIsAtCapacity';
-		},
-		{
-			code: 6010;
-			name: 'InvalidVaultDepositorInitialization';
-			msg: 'InvalidVaultDepositorInitialization';
-		},
-		{
-			code: 6011;
-			name: 'DelegateNotAvailableForLiquidation';
-			msg: 'DelegateNotAvailableForLiquidation';
-		},
-		{
-			code: 6012;
-			name: 'InvalidLiquidator';
-			msg: 'InvalidLiquidator';
-		},
-		{
-			code: 6013;
-			name: 'LiquidationExpired';
-			msg: 'LiquidationExpired';
-		},
-		{
-			code: 6014;
-			name: 'InvalidEquityValue';
-			msg: 'InvalidEquityValue';
-		},
-		{
-			code: 6015;
-			name: 'VaultInLiquidation';
-			msg: 'VaultInLiquidation';
-		},
-		{
-			code: 6016;
-			name: 'InvestorCanWithdraw';
-			msg: 'InvestorCanWithdraw';
-		},
-		{
-			code: 6017;
-			name: 'InvalidVaultInitialization';
-			msg: 'InvalidVaultInitialization';
-		},
-		{
-			code: 6018;
-			name: 'InvalidVaultUpdate';
-			msg: 'InvalidVaultUpdate';
-		},
-		{
-			code: 6019;
-			name: 'PermissionedVault';
-			msg: 'PermissionedVault';
-		},
-		{
-			code: 6020;
-			name: 'WithdrawInProgress';
-			msg: 'WithdrawInProgress';
-		},
-		{
-			code: 6021;
-			name: 'SharesPercentTooLarge';
-			msg: 'SharesPercentTooLarge';
-		},
-		{
-			code: 6022;
-			name: 'InvalidVaultDeposit';
-			msg: 'InvalidVaultDeposit';
-		},
-		{
-			code: 6023;
-			name: 'OngoingLiquidation';
-			msg: 'OngoingLiquidation';
-		},
-		{
-			code: 6024;
-			name: 'VaultProtocolMissing';
-			msg: 'VaultProtocolMissing';
-		},
-		{
-			code: 6025;
-			name: 'BnConversion';
-			msg: 'BnConversion';
-		},
-		{
-			code: 6026;
-			name: 'MathError';
-			msg: 'MathError';
-		},
-		{
-			code: 6027;
-			name: 'CastError';
-			msg: 'CastError';
-		},
-		{
-			code: 6028;
-			name: 'UnwrapError';
-			msg: 'UnwrapError';
-		},
-		{
-			code: 6029;
-			name: 'MarketDeserializationError';
-			msg: 'MarketDeserializationError';
-		},
-		{
-			code: 6030;
-			name: 'UnrecognizedQuoteMint';
-			msg: 'UnrecognizedQuoteMint';
-		},
-		{
-			code: 6031;
-			name: 'SolMarketMissing';
-			msg: 'SolMarketMissing';
-		},
-		{
-			code: 6032;
-			name: 'MarketMissingInRemainingAccounts';
-			msg: 'MarketMissingInRemainingAccounts';
-		},
-		{
-			code: 6033;
-			name: 'MarketRegistryMismatch';
-			msg: 'MarketRegistryMismatch';
-		},
-		{
-			code: 6034;
-			name: 'OrderPacketDeserialization';
-			msg: 'OrderPacketDeserialization';
-		},
-		{
-			code: 6035;
-			name: 'OrderPacketMustUseDepositedFunds';
-			msg: 'OrderPacketMustUseDepositedFunds';
-		},
-		{
-			code: 6036;
-			name: 'InvalidPhoenixInstruction';
-			msg: 'InvalidPhoenixInstruction';
-		},
-		{
-			code: 6037;
-			name: 'OrderPacketMustBeTakeOnly';
-			msg: 'OrderPacketMustBeTakeOnly';
-		},
-		{
-			code: 6038;
-			name: 'BaseLotsMustBeZero';
-			msg: 'BaseLotsMustBeZero';
-		},
-		{
-			code: 6039;
-			name: 'TraderStateNotFound';
-			msg: 'TraderStateNotFound';
+			],
+			args: [],
 		},
 		{
-			code: 6040;
-			name: 'MarketPositionNotFound';
-			msg: 'MarketPositionNotFound';
-		}
-	];
-};
-
-export const IDL: PhoenixVaults = {
-	version: '0.1.0',
-	name: 'phoenix_vaults',
-	instructions: [
-		{
-			name: 'initializeVault',
+			name: 'marketDeposit',
 			docs: [
-				'The wallet that signs this instruction becomes the manager and therefore profits the management fee and profit share.',
-				'The manager can NOT be updated, so be careful who creates the vault.',
-				'',
-				'By default, the manager is also the delegate who has permission to trade on behalf of the vault.',
-				'',
-				'The delegate can be updated at anytime by calling `update_vault`.',
+				'Vault delegate deposits vault assets from the USDC or SOL token account to a Phoenix market.',
+				"USDC can't be deposited below the vault liquidity buffer, so the remaining accounts must include",
+				'the markets needed for equity when depositing from the USDC token account.',
 			],
 			accounts: [
 				{
-					name: 'vault',
-					isMut: true,
+					name: 'vault',
+					isMut: true,
+					isSigner: false,
+					docs: [
+						'If delegate has authority to sign for vault, then any Phoenix CPI is valid.',
+						'Phoenix CPI validates that opaque instruction data is a [`PhoenixInstruction`],',
+						'so this is safe since any Phoenix CPI is secure.',
+					],
+				},
+				{
+					name: 'delegate',
+					isMut: false,
+					isSigner: true,
+					docs: [
+						'Is manager by default, but can be delegated to another pubkey using `update_delegate`',
+					],
+				},
+				{
+					name: 'marketRegistry',
+					isMut: false,
 					isSigner: false,
 				},
 				{
-					name: 'manager',
+					name: 'phoenix',
 					isMut: false,
-					isSigner: true,
+					isSigner: false,
 				},
 				{
-					name: 'usdcTokenAccount',
+					name: 'logAuthority',
+					isMut: false,
+					isSigner: false,
+				},
+				{
+					name: 'market',
 					isMut: true,
 					isSigner: false,
 				},
 				{
-					name: 'usdcMint',
+					name: 'seat',
 					isMut: false,
 					isSigner: false,
 				},
 				{
-					name: 'solTokenAccount',
-					isMut: true,
+					name: 'baseMint',
+					isMut: false,
 					isSigner: false,
 				},
 				{
-					name: 'solMint',
+					name: 'quoteMint',
 					isMut: false,
 					isSigner: false,
 				},
 				{
-					name: 'payer',
+					name: 'vaultBaseTokenAccount',
 					isMut: true,
-					isSigner: true,
+					isSigner: false,
 				},
 				{
-					name: 'rent',
-					isMut: false,
+					name: 'vaultQuoteTokenAccount',
+					isMut: true,
 					isSigner: false,
 				},
 				{
-					name: 'systemProgram',
-					isMut: false,
+					name: 'marketBaseTokenAccount',
+					isMut: true,
 					isSigner: false,
 				},
 				{
-					name: 'tokenProgram',
-					isMut: false,
+					name: 'marketQuoteTokenAccount',
+					isMut: true,
 					isSigner: false,
 				},
 				{
-					name: 'associatedTokenProgram',
+					name: 'tokenProgram',
 					isMut: false,
 					isSigner: false,
 				},
@@ -3004,80 +5694,82 @@ export const IDL: PhoenixVaults = {
 				{
 					name: 'params',
 					type: {
-						defined: 'VaultParams',
+						defined: 'MarketTransferParams',
 					},
 				},
 			],
 		},
 		{
-			name: 'initializeInvestor',
+			name: 'marketWithdraw',
 			docs: [
-				'User creates an [`Investor`] account to invest with a [`Vault`].',
+				'Vault delegate withdraws vault Phoenix market back to the vault USDC or SOL token accounts.',
 			],
 			accounts: [
 				{
 					name: 'vault',
-					isMut: false,
+					isMut: true,
 					isSigner: false,
+					docs: [
+						'If delegate has authority to sign for vault, then any Phoenix CPI is valid.',
+						'Phoenix CPI validates that opaque instruction data is a [`PhoenixInstruction`],',
+						'so this is safe since any Phoenix CPI is secure.',
+					],
 				},
 				{
-					name: 'investor',
-					isMut: true,
+					name: 'delegate',
+					isMut: false,
+					isSigner: true,
+					docs: [
+						'Is manager by default, but can be delegated to another pubkey using `update_delegate`',
+					],
+				},
+				{
+					name: 'phoenix',
+					isMut: false,
 					isSigner: false,
 				},
 				{
-					name: 'authority',
+					name: 'logAuthority',
 					isMut: false,
 					isSigner: false,
 				},
 				{
-					name: 'payer',
+					name: 'market',
 					isMut: true,
-					isSigner: true,
+					isSigner: false,
 				},
 				{
-					name: 'rent',
+					name: 'baseMint',
 					isMut: false,
 					isSigner: false,
 				},
 				{
-					name: 'systemProgram',
+					name: 'quoteMint',
 					isMut: false,
 					isSigner: false,
 				},
-			],
-			args: [],
-		},
-		{
-			name: 'initializeMarketRegistry',
-			docs: [
-				'Admin function to create an on-chain source of truth for list of Phoenix markets.',
-				'This is called once after the first deploy of this program to a network.',
-			],
-			accounts: [
 				{
-					name: 'authority',
-					isMut: false,
-					isSigner: true,
-					docs: ['Admin-level keypair'],
+					name: 'vaultBaseTokenAccount',
+					isMut: true,
+					isSigner: false,
 				},
 				{
-					name: 'marketRegistry',
+					name: 'vaultQuoteTokenAccount',
 					isMut: true,
 					isSigner: false,
 				},
 				{
-					name: 'payer',
+					name: 'marketBaseTokenAccount',
 					isMut: true,
-					isSigner: true,
+					isSigner: false,
 				},
 				{
-					name: 'rent',
-					isMut: false,
+					name: 'marketQuoteTokenAccount',
+					isMut: true,
 					isSigner: false,
 				},
 				{
-					name: 'systemProgram',
+					name: 'tokenProgram',
 					isMut: false,
 					isSigner: false,
 				},
@@ -3086,14 +5778,16 @@ export const IDL: PhoenixVaults = {
 				{
 					name: 'params',
 					type: {
-						defined: 'MarketLookupTableParams',
+						defined: 'MarketTransferParams',
 					},
 				},
 			],
 		},
 		{
-			name: 'investorDeposit',
-			docs: ['Investor deposits funds to the vault USDC token account.'],
+			name: 'appointInvestorLiquidator',
+			docs: [
+				'Assign an investor as delegate to enable liquidation of market positions.',
+			],
 			accounts: [
 				{
 					name: 'vault',
@@ -3116,38 +5810,76 @@ export const IDL: PhoenixVaults = {
 					isSigner: false,
 				},
 				{
-					name: 'investorQuoteTokenAccount',
+					name: 'vaultQuoteTokenAccount',
+					isMut: true,
+					isSigner: false,
+				},
+			],
+			args: [],
+		},
+		{
+			name: 'appointManagerLiquidator',
+			docs: [
+				'Assign a vault manager as delegate to enable liquidation of market positions.',
+			],
+			accounts: [
+				{
+					name: 'vault',
 					isMut: true,
 					isSigner: false,
 				},
+				{
+					name: 'manager',
+					isMut: false,
+					isSigner: true,
+				},
+				{
+					name: 'marketRegistry',
+					isMut: false,
+					isSigner: false,
+				},
 				{
 					name: 'vaultQuoteTokenAccount',
 					isMut: true,
 					isSigner: false,
 				},
+			],
+			args: [],
+		},
+		{
+			name: 'appointProtocolLiquidator',
+			docs: [
+				'Assign a vault protocol as delegate to enable liquidation of market positions.',
+			],
+			accounts: [
 				{
-					name: 'tokenProgram',
+					name: 'vault',
+					isMut: true,
+					isSigner: false,
+				},
+				{
+					name: 'protocol',
+					isMut: false,
+					isSigner: true,
+				},
+				{
+					name: 'marketRegistry',
 					isMut: false,
 					isSigner: false,
 				},
-			],
-			args: [
 				{
-					name: 'amount',
-					type: 'u64',
+					name: 'vaultQuoteTokenAccount',
+					isMut: true,
+					isSigner: false,
 				},
 			],
+			args: [],
 		},
 		{
-			name: 'investorWithdraw',
+			name: 'investorLiquidateUsdcMarket',
 			docs: [
-				'Investor withdraws funds from the vault, assuming funds are in the vault USDC token account.',
-				'',
-				'If insufficient USDC in the vault_usdc_token_account, then the investor must call `appoint_liquidator` to',
-				'acquire permission to liquidate the vault market positions.',
-				'',
-				'Then call `liquidate_usdc_market` or `liquidate_sol_market` to forcefully swap a vault market position back to USDC,',
-				'and then withdraw back to the investor.',
+				'After `appoint_investor_liquidator` the investor can liquidate a USDC denominated market position',
+				'to fulfill their withdrawal request.',
 			],
 			accounts: [
 				{
@@ -3157,7 +5889,7 @@ export const IDL: PhoenixVaults = {
 				},
 				{
 					name: 'investor',
-					isMut: true,
+					isMut: false,
 					isSigner: false,
 				},
 				{
@@ -3171,7 +5903,7 @@ export const IDL: PhoenixVaults = {
 					isSigner: false,
 				},
 				{
-					name: 'investorQuoteTokenAccount',
+					name: 'investorUsdcTokenAccount',
 					isMut: true,
 					isSigner: false,
 				},
@@ -3201,7 +5933,7 @@ export const IDL: PhoenixVaults = {
 					isSigner: false,
 				},
 				{
-					name: 'quoteMint',
+					name: 'usdcMint',
 					isMut: false,
 					isSigner: false,
 				},
@@ -3211,7 +5943,7 @@ export const IDL: PhoenixVaults = {
 					isSigner: false,
 				},
 				{
-					name: 'vaultQuoteTokenAccount',
+					name: 'vaultUsdcTokenAccount',
 					isMut: true,
 					isSigner: false,
 				},
@@ -3221,7 +5953,7 @@ export const IDL: PhoenixVaults = {
 					isSigner: false,
 				},
 				{
-					name: 'marketQuoteTokenAccount',
+					name: 'marketUsdcTokenAccount',
 					isMut: true,
 					isSigner: false,
 				},
@@ -3234,100 +5966,36 @@ export const IDL: PhoenixVaults = {
 			args: [],
 		},
 		{
-			name: 'claimSeat',
+			name: 'investorLiquidateSolMarket',
 			docs: [
-				'Vault delegate claims a seat on a Phoenix market to enable trading.',
-				'Call this before `place_limit_order`.',
+				'After `appoint_investor_liquidator` the investor can liquidate a SOL denominated market position',
+				'to fulfill their withdrawal request.',
 			],
 			accounts: [
 				{
 					name: 'vault',
-					isMut: false,
+					isMut: true,
 					isSigner: false,
-					docs: [
-						'If delegate has authority to sign for vault, then any Phoenix CPI is valid.',
-						'Phoenix CPI validates that opaque instruction data is a [`PhoenixInstruction`],',
-						'so this is safe since any Phoenix CPI is secure.',
-					],
-				},
-				{
-					name: 'delegate',
-					isMut: false,
-					isSigner: true,
-					docs: [
-						'Either vault delegate or an investor liquidating this vault.',
-						'If an investor needs to call this, then they must call `appoint_liquidator` first.',
-					],
 				},
 				{
-					name: 'phoenix',
+					name: 'investor',
 					isMut: false,
 					isSigner: false,
 				},
 				{
-					name: 'logAuthority',
+					name: 'authority',
 					isMut: false,
-					isSigner: false,
-				},
-				{
-					name: 'market',
-					isMut: true,
-					isSigner: false,
-				},
-				{
-					name: 'seatManager',
-					isMut: true,
-					isSigner: false,
-				},
-				{
-					name: 'seatDepositCollector',
-					isMut: true,
-					isSigner: false,
-				},
-				{
-					name: 'payer',
-					isMut: true,
 					isSigner: true,
 				},
 				{
-					name: 'seat',
-					isMut: true,
-					isSigner: false,
-				},
-				{
-					name: 'systemProgram',
-					isMut: false,
-					isSigner: false,
-				},
-				{
-					name: 'phoenixSeatManager',
+					name: 'marketRegistry',
 					isMut: false,
 					isSigner: false,
 				},
-			],
-			args: [],
-		},
-		{
-			name: 'placeLimitOrder',
-			docs: ['Vault delegate places a limit order on behalf of the vault.'],
-			accounts: [
 				{
-					name: 'vault',
+					name: 'investorUsdcTokenAccount',
 					isMut: true,
 					isSigner: false,
-					docs: [
-						'If delegate has authority to sign for vault, then any Phoenix CPI is valid.',
-						'Phoenix CPI validates that opaque instruction data is a [`PhoenixInstruction`],',
-						'so this is safe since any Phoenix CPI is secure.',
-					],
-				},
-				{
-					name: 'delegate',
-					isMut: false,
-					isSigner: true,
-					docs: [
-						'Is manager by default, but can be delegated to another pubkey using `update_delegate`',
-					],
 				},
 				{
 					name: 'phoenix',
@@ -3355,90 +6023,74 @@ export const IDL: PhoenixVaults = {
 					isSigner: false,
 				},
 				{
-					name: 'quoteMint',
+					name: 'solMint',
 					isMut: false,
 					isSigner: false,
 				},
 				{
-					name: 'vaultBaseTokenAccount',
-					isMut: true,
+					name: 'usdcMint',
+					isMut: false,
 					isSigner: false,
 				},
 				{
-					name: 'vaultQuoteTokenAccount',
+					name: 'vaultBaseTokenAccount',
 					isMut: true,
 					isSigner: false,
 				},
 				{
-					name: 'marketBaseTokenAccount',
+					name: 'vaultSolTokenAccount',
 					isMut: true,
 					isSigner: false,
 				},
 				{
-					name: 'marketQuoteTokenAccount',
+					name: 'vaultUsdcTokenAccount',
 					isMut: true,
 					isSigner: false,
 				},
 				{
-					name: 'tokenProgram',
-					isMut: false,
+					name: 'marketBaseTokenAccount',
+					isMut: true,
 					isSigner: false,
 				},
-			],
-			args: [
-				{
-					name: 'params',
-					type: {
-						defined: 'PlaceOrderParams',
-					},
-				},
-			],
-		},
-		{
-			name: 'investorRequestWithdraw',
-			docs: ['Investor request withdrawal of funds from the vault.'],
-			accounts: [
 				{
-					name: 'vault',
+					name: 'marketSolTokenAccount',
 					isMut: true,
 					isSigner: false,
 				},
 				{
-					name: 'investor',
+					name: 'solUsdcMarket',
 					isMut: true,
 					isSigner: false,
 				},
 				{
-					name: 'authority',
-					isMut: false,
-					isSigner: true,
-				},
-				{
-					name: 'marketRegistry',
+					name: 'solUsdcMarketSeat',
 					isMut: false,
 					isSigner: false,
 				},
 				{
-					name: 'vaultUsdcTokenAccount',
+					name: 'solUsdcMarketSolTokenAccount',
 					isMut: true,
 					isSigner: false,
 				},
-			],
-			args: [
 				{
-					name: 'withdrawAmount',
-					type: 'u64',
+					name: 'solUsdcMarketUsdcTokenAccount',
+					isMut: true,
+					isSigner: false,
 				},
 				{
-					name: 'withdrawUnit',
-					type: {
-						defined: 'WithdrawUnit',
-					},
+					name: 'tokenProgram',
+					isMut: false,
+					isSigner: false,
 				},
 			],
+			args: [],
 		},
 		{
-			name: 'cancelWithdrawRequest',
+			name: 'managerLiquidateUsdcMarket',
+			docs: [
+				'After `appoint_manager_liquidator` the manager can liquidate a USDC denominated market position',
+				'to fulfill their withdrawal request.',
+			],
 			accounts: [
 				{
 					name: 'vault',
@@ -3446,12 +6098,7 @@ export const IDL: PhoenixVaults = {
 					isSigner: false,
 				},
 				{
-					name: 'investor',
-					isMut: true,
-					isSigner: false,
-				},
-				{
-					name: 'authority',
+					name: 'manager',
 					isMut: false,
 					isSigner: true,
 				},
@@ -3461,36 +6108,9 @@ export const IDL: PhoenixVaults = {
 					isSigner: false,
 				},
 				{
-					name: 'vaultUsdcTokenAccount',
-					isMut: true,
-					isSigner: false,
-				},
-			],
-			args: [],
-		},
-		{
-			name: 'marketDeposit',
-			docs: [
-				'Vault delegate deposits vault assets from the USDC or SOL token account to a Phoenix market.',
-			],
-			accounts: [
-				{
-					name: 'vault',
+					name: 'managerUsdcTokenAccount',
 					isMut: true,
 					isSigner: false,
-					docs: [
-						'If delegate has authority to sign for vault, then any Phoenix CPI is valid.',
-						'Phoenix CPI validates that opaque instruction data is a [`PhoenixInstruction`],',
-						'so this is safe since any Phoenix CPI is secure.',
-					],
-				},
-				{
-					name: 'delegate',
-					isMut: false,
-					isSigner: true,
-					docs: [
-						'Is manager by default, but can be delegated to another pubkey using `update_delegate`',
-					],
 				},
 				{
 					name: 'phoenix',
@@ -3518,7 +6138,7 @@ export const IDL: PhoenixVaults = {
 					isSigner: false,
 				},
 				{
-					name: 'quoteMint',
+					name: 'usdcMint',
 					isMut: false,
 					isSigner: false,
 				},
@@ -3528,7 +6148,7 @@ export const IDL: PhoenixVaults = {
 					isSigner: false,
 				},
 				{
-					name: 'vaultQuoteTokenAccount',
+					name: 'vaultUsdcTokenAccount',
 					isMut: true,
 					isSigner: false,
 				},
@@ -3538,7 +6158,7 @@ export const IDL: PhoenixVaults = {
 					isSigner: false,
 				},
 				{
-					name: 'marketQuoteTokenAccount',
+					name: 'marketUsdcTokenAccount',
 					isMut: true,
 					isSigner: false,
 				},
@@ -3548,195 +6168,127 @@ export const IDL: PhoenixVaults = {
 					isSigner: false,
 				},
 			],
-			args: [
-				{
-					name: 'params',
-					type: {
-						defined: 'MarketTransferParams',
-					},
-				},
-			],
+			args: [],
 		},
 		{
-			name: 'marketWithdraw',
+			name: 'managerLiquidateSolMarket',
 			docs: [
-				'Vault delegate withdraws vault Phoenix market back to the vault USDC or SOL token accounts.',
+				'After `appoint_manager_liquidator` the manager can liquidate a SOL denominated market position',
+				'to fulfill their withdrawal request.',
 			],
 			accounts: [
 				{
 					name: 'vault',
 					isMut: true,
 					isSigner: false,
-					docs: [
-						'If delegate has authority to sign for vault, then any Phoenix CPI is valid.',
-						'Phoenix CPI validates that opaque instruction data is a [`PhoenixInstruction`],',
-						'so this is safe since any Phoenix CPI is secure.',
-					],
 				},
 				{
-					name: 'delegate',
+					name: 'manager',
 					isMut: false,
 					isSigner: true,
-					docs: [
-						'Is manager by default, but can be delegated to another pubkey using `update_delegate`',
-					],
-				},
-				{
-					name: 'phoenix',
-					isMut: false,
-					isSigner: false,
 				},
 				{
-					name: 'logAuthority',
+					name: 'marketRegistry',
 					isMut: false,
 					isSigner: false,
 				},
 				{
-					name: 'market',
+					name: 'managerUsdcTokenAccount',
 					isMut: true,
 					isSigner: false,
 				},
 				{
-					name: 'baseMint',
+					name: 'phoenix',
 					isMut: false,
 					isSigner: false,
 				},
 				{
-					name: 'quoteMint',
+					name: 'logAuthority',
 					isMut: false,
 					isSigner: false,
 				},
 				{
-					name: 'vaultBaseTokenAccount',
+					name: 'market',
 					isMut: true,
 					isSigner: false,
 				},
 				{
-					name: 'vaultQuoteTokenAccount',
-					isMut: true,
+					name: 'seat',
+					isMut: false,
 					isSigner: false,
 				},
 				{
-					name: 'marketBaseTokenAccount',
-					isMut: true,
+					name: 'baseMint',
+					isMut: false,
 					isSigner: false,
 				},
 				{
-					name: 'marketQuoteTokenAccount',
-					isMut: true,
+					name: 'solMint',
+					isMut: false,
 					isSigner: false,
 				},
 				{
-					name: 'tokenProgram',
+					name: 'usdcMint',
 					isMut: false,
 					isSigner: false,
 				},
-			],
-			args: [
-				{
-					name: 'params',
-					type: {
-						defined: 'MarketTransferParams',
-					},
-				},
-			],
-		},
-		{
-			name: 'appointInvestorLiquidator',
-			docs: [
-				'Assign an investor as delegate to enable liquidation of market positions.',
-			],
-			accounts: [
 				{
-					name: 'vault',
+					name: 'vaultBaseTokenAccount',
 					isMut: true,
 					isSigner: false,
 				},
 				{
-					name: 'investor',
+					name: 'vaultSolTokenAccount',
 					isMut: true,
 					isSigner: false,
 				},
 				{
-					name: 'authority',
-					isMut: false,
-					isSigner: true,
-				},
-				{
-					name: 'marketRegistry',
-					isMut: false,
+					name: 'vaultUsdcTokenAccount',
+					isMut: true,
 					isSigner: false,
 				},
 				{
-					name: 'vaultQuoteTokenAccount',
+					name: 'marketBaseTokenAccount',
 					isMut: true,
 					isSigner: false,
 				},
-			],
-			args: [],
-		},
-		{
-			name: 'appointManagerLiquidator',
-			docs: [
-				'Assign a vault manager as delegate to enable liquidation of market positions.',
-			],
-			accounts: [
 				{
-					name: 'vault',
+					name: 'marketSolTokenAccount',
 					isMut: true,
 					isSigner: false,
 				},
 				{
-					name: 'manager',
-					isMut: false,
-					isSigner: true,
-				},
-				{
-					name: 'marketRegistry',
-					isMut: false,
+					name: 'solUsdcMarket',
+					isMut: true,
 					isSigner: false,
 				},
 				{
-					name: 'vaultQuoteTokenAccount',
-					isMut: true,
+					name: 'solUsdcMarketSeat',
+					isMut: false,
 					isSigner: false,
 				},
-			],
-			args: [],
-		},
-		{
-			name: 'appointProtocolLiquidator',
-			docs: [
-				'Assign a vault protocol as delegate to enable liquidation of market positions.',
-			],
-			accounts: [
 				{
-					name: 'vault',
+					name: 'solUsdcMarketSolTokenAccount',
 					isMut: true,
 					isSigner: false,
 				},
 				{
-					name: 'protocol',
-					isMut: false,
-					isSigner: true,
-				},
-				{
-					name: 'marketRegistry',
-					isMut: false,
+					name: 'solUsdcMarketUsdcTokenAccount',
+					isMut: true,
 					isSigner: false,
 				},
 				{
-					name: 'vaultQuoteTokenAccount',
-					isMut: true,
+					name: 'tokenProgram',
+					isMut: false,
 					isSigner: false,
 				},
 			],
 			args: [],
 		},
 		{
-			name: 'investorLiquidateUsdcMarket',
+			name: 'protocolLiquidateSolMarket',
 			docs: [
-				'After `appoint_investor_liquidator` the investor can liquidate a USDC denominated market position',
+				'After `appoint_protocol_liquidator` the protocol can liquidate a SOL denominated market position',
 				'to fulfill their withdrawal request.',
 			],
 			accounts: [
@@ -3746,12 +6298,7 @@ export const IDL: PhoenixVaults = {
 					isSigner: false,
 				},
 				{
-					name: 'investor',
-					isMut: false,
-					isSigner: false,
-				},
-				{
-					name: 'authority',
+					name: 'protocol',
 					isMut: false,
 					isSigner: true,
 				},
@@ -3761,7 +6308,7 @@ export const IDL: PhoenixVaults = {
 					isSigner: false,
 				},
 				{
-					name: 'investorUsdcTokenAccount',
+					name: 'protocolUsdcTokenAccount',
 					isMut: true,
 					isSigner: false,
 				},
@@ -3790,6 +6337,11 @@ export const IDL: PhoenixVaults = {
 					isMut: false,
 					isSigner: false,
 				},
+				{
+					name: 'solMint',
+					isMut: false,
+					isSigner: false,
+				},
 				{
 					name: 'usdcMint',
 					isMut: false,
@@ -3800,6 +6352,11 @@ export const IDL: PhoenixVaults = {
 					isMut: true,
 					isSigner: false,
 				},
+				{
+					name: 'vaultSolTokenAccount',
+					isMut: true,
+					isSigner: false,
+				},
 				{
 					name: 'vaultUsdcTokenAccount',
 					isMut: true,
@@ -3811,7 +6368,27 @@ export const IDL: PhoenixVaults = {
 					isSigner: false,
 				},
 				{
-					name: 'marketUsdcTokenAccount',
+					name: 'marketSolTokenAccount',
+					isMut: true,
+					isSigner: false,
+				},
+				{
+					name: 'solUsdcMarket',
+					isMut: true,
+					isSigner: false,
+				},
+				{
+					name: 'solUsdcMarketSeat',
+					isMut: false,
+					isSigner: false,
+				},
+				{
+					name: 'solUsdcMarketSolTokenAccount',
+					isMut: true,
+					isSigner: false,
+				},
+				{
+					name: 'solUsdcMarketUsdcTokenAccount',
 					isMut: true,
 					isSigner: false,
 				},
@@ -3824,9 +6401,9 @@ export const IDL: PhoenixVaults = {
 			args: [],
 		},
 		{
-			name: 'investorLiquidateSolMarket',
+			name: 'protocolLiquidateUsdcMarket',
 			docs: [
-				'After `appoint_investor_liquidator` the investor can liquidate a SOL denominated market position',
+				'After `appoint_protocol_liquidator` the protocol can liquidate a USDC denominated market position',
 				'to fulfill their withdrawal request.',
 			],
 			accounts: [
@@ -3836,12 +6413,7 @@ export const IDL: PhoenixVaults = {
 					isSigner: false,
 				},
 				{
-					name: 'investor',
-					isMut: false,
-					isSigner: false,
-				},
-				{
-					name: 'authority',
+					name: 'protocol',
 					isMut: false,
 					isSigner: true,
 				},
@@ -3851,7 +6423,7 @@ export const IDL: PhoenixVaults = {
 					isSigner: false,
 				},
 				{
-					name: 'investorUsdcTokenAccount',
+					name: 'protocolUsdcTokenAccount',
 					isMut: true,
 					isSigner: false,
 				},
@@ -3880,11 +6452,6 @@ export const IDL: PhoenixVaults = {
 					isMut: false,
 					isSigner: false,
 				},
-				{
-					name: 'solMint',
-					isMut: false,
-					isSigner: false,
-				},
 				{
 					name: 'usdcMint',
 					isMut: false,
@@ -3896,47 +6463,73 @@ export const IDL: PhoenixVaults = {
 					isSigner: false,
 				},
 				{
-					name: 'vaultSolTokenAccount',
+					name: 'vaultUsdcTokenAccount',
 					isMut: true,
 					isSigner: false,
 				},
 				{
-					name: 'vaultUsdcTokenAccount',
+					name: 'marketBaseTokenAccount',
 					isMut: true,
 					isSigner: false,
 				},
 				{
-					name: 'marketBaseTokenAccount',
+					name: 'marketUsdcTokenAccount',
 					isMut: true,
 					isSigner: false,
 				},
 				{
-					name: 'marketSolTokenAccount',
-					isMut: true,
+					name: 'tokenProgram',
+					isMut: false,
 					isSigner: false,
 				},
+			],
+			args: [],
+		},
+		{
+			name: 'updateVault',
+			docs: [
+				'Update the fees, profit share, min deposit, max capacity, delegate, and more.',
+			],
+			accounts: [
 				{
-					name: 'solUsdcMarket',
+					name: 'vault',
 					isMut: true,
 					isSigner: false,
 				},
 				{
-					name: 'solUsdcMarketSeat',
+					name: 'manager',
 					isMut: false,
-					isSigner: false,
+					isSigner: true,
 				},
+			],
+			args: [
 				{
-					name: 'solUsdcMarketSolTokenAccount',
+					name: 'params',
+					type: {
+						defined: 'UpdateVaultParams',
+					},
+				},
+			],
+		},
+		{
+			name: 'migrateVault',
+			docs: [
+				'Permissionless instruction to grow a vault created by an earlier version of the program to the current [`Vault`] size.',
+				'Other instructions fail on the vault until it is migrated.',
+			],
+			accounts: [
+				{
+					name: 'vault',
 					isMut: true,
 					isSigner: false,
 				},
 				{
-					name: 'solUsdcMarketUsdcTokenAccount',
+					name: 'payer',
 					isMut: true,
-					isSigner: false,
+					isSigner: true,
 				},
 				{
-					name: 'tokenProgram',
+					name: 'systemProgram',
 					isMut: false,
 					isSigner: false,
 				},
@@ -3944,11 +6537,7 @@ export const IDL: PhoenixVaults = {
 			args: [],
 		},
 		{
-			name: 'managerLiquidateUsdcMarket',
-			docs: [
-				'After `appoint_manager_liquidator` the manager can liquidate a USDC denominated market position',
-				'to fulfill their withdrawal request.',
-			],
+			name: 'managerWithdraw',
 			accounts: [
 				{
 					name: 'vault',
@@ -3966,7 +6555,13 @@ export const IDL: PhoenixVaults = {
 					isSigner: false,
 				},
 				{
-					name: 'managerUsdcTokenAccount',
+					name: 'navHistory',
+					isMut: true,
+					isSigner: false,
+					isOptional: true,
+				},
+				{
+					name: 'managerQuoteTokenAccount',
 					isMut: true,
 					isSigner: false,
 				},
@@ -3996,7 +6591,7 @@ export const IDL: PhoenixVaults = {
 					isSigner: false,
 				},
 				{
-					name: 'usdcMint',
+					name: 'quoteMint',
 					isMut: false,
 					isSigner: false,
 				},
@@ -4006,7 +6601,7 @@ export const IDL: PhoenixVaults = {
 					isSigner: false,
 				},
 				{
-					name: 'vaultUsdcTokenAccount',
+					name: 'vaultQuoteTokenAccount',
 					isMut: true,
 					isSigner: false,
 				},
@@ -4016,7 +6611,7 @@ export const IDL: PhoenixVaults = {
 					isSigner: false,
 				},
 				{
-					name: 'marketUsdcTokenAccount',
+					name: 'marketQuoteTokenAccount',
 					isMut: true,
 					isSigner: false,
 				},
@@ -4029,11 +6624,7 @@ export const IDL: PhoenixVaults = {
 			args: [],
 		},
 		{
-			name: 'managerLiquidateSolMarket',
-			docs: [
-				'After `appoint_manager_liquidator` the manager can liquidate a SOL denominated market position',
-				'to fulfill their withdrawal request.',
-			],
+			name: 'managerDeposit',
 			accounts: [
 				{
 					name: 'vault',
@@ -4051,87 +6642,173 @@ export const IDL: PhoenixVaults = {
 					isSigner: false,
 				},
 				{
-					name: 'managerUsdcTokenAccount',
+					name: 'navHistory',
 					isMut: true,
 					isSigner: false,
+					isOptional: true,
 				},
 				{
-					name: 'phoenix',
-					isMut: false,
+					name: 'managerQuoteTokenAccount',
+					isMut: true,
 					isSigner: false,
 				},
 				{
-					name: 'logAuthority',
+					name: 'vaultQuoteTokenAccount',
+					isMut: true,
+					isSigner: false,
+				},
+				{
+					name: 'tokenProgram',
 					isMut: false,
 					isSigner: false,
 				},
+			],
+			args: [
 				{
-					name: 'market',
+					name: 'amount',
+					type: 'u64',
+				},
+			],
+		},
+		{
+			name: 'managerRequestWithdraw',
+			accounts: [
+				{
+					name: 'vault',
 					isMut: true,
 					isSigner: false,
 				},
 				{
-					name: 'seat',
+					name: 'manager',
 					isMut: false,
-					isSigner: false,
+					isSigner: true,
 				},
 				{
-					name: 'baseMint',
+					name: 'marketRegistry',
 					isMut: false,
 					isSigner: false,
 				},
 				{
-					name: 'solMint',
-					isMut: false,
+					name: 'vaultUsdcTokenAccount',
+					isMut: true,
 					isSigner: false,
 				},
+			],
+			args: [
+				{
+					name: 'withdrawAmount',
+					type: 'u64',
+				},
 				{
-					name: 'usdcMint',
+					name: 'withdrawUnit',
+					type: {
+						defined: 'WithdrawUnit',
+					},
+				},
+			],
+		},
+		{
+			name: 'managerCancelWithdrawRequest',
+			accounts: [
+				{
+					name: 'vault',
+					isMut: true,
+					isSigner: false,
+				},
+				{
+					name: 'manager',
+					isMut: false,
+					isSigner: true,
+				},
+				{
+					name: 'marketRegistry',
 					isMut: false,
 					isSigner: false,
 				},
 				{
-					name: 'vaultBaseTokenAccount',
+					name: 'vaultUsdcTokenAccount',
 					isMut: true,
 					isSigner: false,
 				},
+			],
+			args: [],
+		},
+		{
+			name: 'protocolWithdraw',
+			accounts: [
 				{
-					name: 'vaultSolTokenAccount',
+					name: 'vault',
 					isMut: true,
 					isSigner: false,
 				},
 				{
-					name: 'vaultUsdcTokenAccount',
+					name: 'protocol',
+					isMut: false,
+					isSigner: true,
+				},
+				{
+					name: 'marketRegistry',
+					isMut: false,
+					isSigner: false,
+				},
+				{
+					name: 'navHistory',
 					isMut: true,
 					isSigner: false,
+					isOptional: true,
 				},
 				{
-					name: 'marketBaseTokenAccount',
+					name: 'protocolQuoteTokenAccount',
+					isMut: true,
+					isSigner: false,
+				},
+				{
+					name: 'phoenix',
+					isMut: false,
+					isSigner: false,
+				},
+				{
+					name: 'logAuthority',
+					isMut: false,
+					isSigner: false,
+				},
+				{
+					name: 'market',
 					isMut: true,
 					isSigner: false,
 				},
 				{
-					name: 'marketSolTokenAccount',
+					name: 'seat',
+					isMut: false,
+					isSigner: false,
+				},
+				{
+					name: 'baseMint',
+					isMut: false,
+					isSigner: false,
+				},
+				{
+					name: 'quoteMint',
+					isMut: false,
+					isSigner: false,
+				},
+				{
+					name: 'vaultBaseTokenAccount',
 					isMut: true,
 					isSigner: false,
 				},
 				{
-					name: 'solUsdcMarket',
+					name: 'vaultQuoteTokenAccount',
 					isMut: true,
 					isSigner: false,
 				},
 				{
-					name: 'solUsdcMarketSeat',
-					isMut: false,
-					isSigner: false,
-				},
-				{
-					name: 'solUsdcMarketSolTokenAccount',
+					name: 'marketBaseTokenAccount',
 					isMut: true,
 					isSigner: false,
 				},
 				{
-					name: 'solUsdcMarketUsdcTokenAccount',
+					name: 'marketQuoteTokenAccount',
 					isMut: true,
 					isSigner: false,
 				},
@@ -4144,11 +6821,7 @@ export const IDL: PhoenixVaults = {
 			args: [],
 		},
 		{
-			name: 'protocolLiquidateSolMarket',
-			docs: [
-				'After `appoint_protocol_liquidator` the protocol can liquidate a SOL denominated market position',
-				'to fulfill their withdrawal request.',
-			],
+			name: 'protocolRequestWithdraw',
 			accounts: [
 				{
 					name: 'vault',
@@ -4166,92 +6839,128 @@ export const IDL: PhoenixVaults = {
 					isSigner: false,
 				},
 				{
-					name: 'protocolUsdcTokenAccount',
+					name: 'vaultUsdcTokenAccount',
 					isMut: true,
 					isSigner: false,
 				},
+			],
+			args: [
 				{
-					name: 'phoenix',
-					isMut: false,
-					isSigner: false,
+					name: 'withdrawAmount',
+					type: 'u64',
 				},
 				{
-					name: 'logAuthority',
-					isMut: false,
-					isSigner: false,
+					name: 'withdrawUnit',
+					type: {
+						defined: 'WithdrawUnit',
+					},
 				},
+			],
+		},
+		{
+			name: 'protocolCancelWithdrawRequest',
+			accounts: [
 				{
-					name: 'market',
+					name: 'vault',
 					isMut: true,
 					isSigner: false,
 				},
 				{
-					name: 'seat',
-					isMut: false,
-					isSigner: false,
-				},
-				{
-					name: 'baseMint',
+					name: 'protocol',
 					isMut: false,
-					isSigner: false,
+					isSigner: true,
 				},
 				{
-					name: 'solMint',
+					name: 'marketRegistry',
 					isMut: false,
 					isSigner: false,
 				},
 				{
-					name: 'usdcMint',
-					isMut: false,
+					name: 'vaultUsdcTokenAccount',
+					isMut: true,
 					isSigner: false,
 				},
+			],
+			args: [],
+		},
+		{
+			name: 'settleEpoch',
+			docs: [
+				'Permissionless crank that prices the deposits and withdraw requests queued during an epoch',
+				'at one equity snapshot, and lets the [`Investor`] accounts in the remaining accounts claim the result.',
+			],
+			accounts: [
 				{
-					name: 'vaultBaseTokenAccount',
+					name: 'vault',
 					isMut: true,
 					isSigner: false,
 				},
 				{
-					name: 'vaultSolTokenAccount',
-					isMut: true,
+					name: 'marketRegistry',
+					isMut: false,
 					isSigner: false,
 				},
 				{
 					name: 'vaultUsdcTokenAccount',
-					isMut: true,
+					isMut: false,
 					isSigner: false,
 				},
+			],
+			args: [],
+		},
+		{
+			name: 'crystallizeFees',
+			docs: [
+				'Permissionless crank that applies the time-based management and protocol fees,',
+				'and processes profit share for the [`Investor`] accounts in the remaining accounts.',
+			],
+			accounts: [
 				{
-					name: 'marketBaseTokenAccount',
+					name: 'vault',
 					isMut: true,
 					isSigner: false,
 				},
 				{
-					name: 'marketSolTokenAccount',
-					isMut: true,
+					name: 'marketRegistry',
+					isMut: false,
 					isSigner: false,
 				},
 				{
-					name: 'solUsdcMarket',
-					isMut: true,
+					name: 'vaultUsdcTokenAccount',
+					isMut: false,
 					isSigner: false,
 				},
+			],
+			args: [],
+		},
+		{
+			name: 'initializeNavHistory',
+			docs: [
+				'Create the [`NavHistory`] ring buffer of share price samples for a [`Vault`].',
+			],
+			accounts: [
 				{
-					name: 'solUsdcMarketSeat',
+					name: 'vault',
 					isMut: false,
 					isSigner: false,
 				},
 				{
-					name: 'solUsdcMarketSolTokenAccount',
+					name: 'navHistory',
 					isMut: true,
 					isSigner: false,
 				},
 				{
-					name: 'solUsdcMarketUsdcTokenAccount',
+					name: 'payer',
 					isMut: true,
+					isSigner: true,
+				},
+				{
+					name: 'rent',
+					isMut: false,
 					isSigner: false,
 				},
 				{
-					name: 'tokenProgram',
+					name: 'systemProgram',
 					isMut: false,
 					isSigner: false,
 				},
@@ -4259,10 +6968,9 @@ export const IDL: PhoenixVaults = {
 			args: [],
 		},
 		{
-			name: 'protocolLiquidateUsdcMarket',
+			name: 'updateNavHistory',
 			docs: [
-				'After `appoint_protocol_liquidator` the protocol can liquidate a USDC denominated market position',
-				'to fulfill their withdrawal request.',
+				'Permissionless crank that appends a sample of the vault equity and shares to the [`NavHistory`].',
 			],
 			accounts: [
 				{
@@ -4271,9 +6979,9 @@ export const IDL: PhoenixVaults = {
 					isSigner: false,
 				},
 				{
-					name: 'protocol',
-					isMut: false,
-					isSigner: true,
+					name: 'navHistory',
+					isMut: true,
+					isSigner: false,
 				},
 				{
 					name: 'marketRegistry',
@@ -4281,62 +6989,80 @@ export const IDL: PhoenixVaults = {
 					isSigner: false,
 				},
 				{
-					name: 'protocolUsdcTokenAccount',
-					isMut: true,
+					name: 'vaultUsdcTokenAccount',
+					isMut: false,
 					isSigner: false,
 				},
+			],
+			args: [],
+		},
+		{
+			name: 'initializeSharePrice',
+			docs: [
+				'Manager creates the [`SharePrice`] account other programs read to price vault shares.',
+			],
+			accounts: [
 				{
-					name: 'phoenix',
+					name: 'vault',
 					isMut: false,
 					isSigner: false,
 				},
 				{
-					name: 'logAuthority',
+					name: 'manager',
 					isMut: false,
-					isSigner: false,
+					isSigner: true,
 				},
 				{
-					name: 'market',
+					name: 'sharePrice',
 					isMut: true,
 					isSigner: false,
 				},
 				{
-					name: 'seat',
-					isMut: false,
-					isSigner: false,
+					name: 'payer',
+					isMut: true,
+					isSigner: true,
 				},
 				{
-					name: 'baseMint',
+					name: 'rent',
 					isMut: false,
 					isSigner: false,
 				},
 				{
-					name: 'usdcMint',
+					name: 'systemProgram',
 					isMut: false,
 					isSigner: false,
 				},
+			],
+			args: [
 				{
-					name: 'vaultBaseTokenAccount',
-					isMut: true,
-					isSigner: false,
+					name: 'maxStalenessSlots',
+					type: 'u64',
 				},
+			],
+		},
+		{
+			name: 'updateSharePrice',
+			docs: [
+				'Permissionless crank that refreshes the [`SharePrice`] with the current vault equity.',
+			],
+			accounts: [
 				{
-					name: 'vaultUsdcTokenAccount',
-					isMut: true,
+					name: 'vault',
+					isMut: false,
 					isSigner: false,
 				},
 				{
-					name: 'marketBaseTokenAccount',
+					name: 'sharePrice',
 					isMut: true,
 					isSigner: false,
 				},
 				{
-					name: 'marketUsdcTokenAccount',
-					isMut: true,
+					name: 'marketRegistry',
+					isMut: false,
 					isSigner: false,
 				},
 				{
-					name: 'tokenProgram',
+					name: 'vaultUsdcTokenAccount',
 					isMut: false,
 					isSigner: false,
 				},
@@ -4344,9 +7070,9 @@ export const IDL: PhoenixVaults = {
 			args: [],
 		},
 		{
-			name: 'updateVault',
+			name: 'resumeTrading',
 			docs: [
-				'Update the fees, profit share, min deposit, max capacity, delegate, and more.',
+				'Manager and protocol resume delegate trading after the share price drawdown exceeded the vault `max_drawdown`.',
 			],
 			accounts: [
 				{
@@ -4359,18 +7085,29 @@ export const IDL: PhoenixVaults = {
 					isMut: false,
 					isSigner: true,
 				},
-			],
-			args: [
 				{
-					name: 'params',
-					type: {
-						defined: 'UpdateVaultParams',
-					},
+					name: 'protocol',
+					isMut: false,
+					isSigner: true,
+				},
+				{
+					name: 'marketRegistry',
+					isMut: false,
+					isSigner: false,
+				},
+				{
+					name: 'vaultUsdcTokenAccount',
+					isMut: false,
+					isSigner: false,
 				},
 			],
+			args: [],
 		},
 		{
-			name: 'managerWithdraw',
+			name: 'windDown',
+			docs: [
+				'Manager or protocol begins winding down the vault. Deposits stop and the delegate may only reduce positions.',
+			],
 			accounts: [
 				{
 					name: 'vault',
@@ -4378,17 +7115,22 @@ export const IDL: PhoenixVaults = {
 					isSigner: false,
 				},
 				{
-					name: 'manager',
+					name: 'authority',
 					isMut: false,
 					isSigner: true,
 				},
+			],
+			args: [],
+		},
+		{
+			name: 'windDownLiquidateMarket',
+			docs: [
+				"Permissionless crank that cancels all orders and sells a winding down vault's position in a market to its quote token.",
+				'The market must also be passed in the remaining accounts.',
+			],
+			accounts: [
 				{
-					name: 'marketRegistry',
-					isMut: false,
-					isSigner: false,
-				},
-				{
-					name: 'managerQuoteTokenAccount',
+					name: 'vault',
 					isMut: true,
 					isSigner: false,
 				},
@@ -4451,7 +7193,10 @@ export const IDL: PhoenixVaults = {
 			args: [],
 		},
 		{
-			name: 'managerDeposit',
+			name: 'finalizeWindDown',
+			docs: [
+				'Permissionless crank that snapshots the final equity and closes the vault once every market position is USDC.',
+			],
 			accounts: [
 				{
 					name: 'vault',
@@ -4459,50 +7204,72 @@ export const IDL: PhoenixVaults = {
 					isSigner: false,
 				},
 				{
-					name: 'manager',
+					name: 'marketRegistry',
 					isMut: false,
-					isSigner: true,
+					isSigner: false,
 				},
 				{
-					name: 'marketRegistry',
+					name: 'vaultUsdcTokenAccount',
 					isMut: false,
 					isSigner: false,
 				},
 				{
-					name: 'managerQuoteTokenAccount',
+					name: 'vaultSolTokenAccount',
+					isMut: false,
+					isSigner: false,
+				},
+			],
+			args: [],
+		},
+		{
+			name: 'investorClaimWindDown',
+			docs: [
+				'Investor claims their pro-rata USDC of the final equity of a wound down vault.',
+			],
+			accounts: [
+				{
+					name: 'vault',
 					isMut: true,
 					isSigner: false,
 				},
 				{
-					name: 'vaultQuoteTokenAccount',
+					name: 'investor',
 					isMut: true,
 					isSigner: false,
 				},
 				{
-					name: 'tokenProgram',
+					name: 'authority',
 					isMut: false,
+					isSigner: true,
+				},
+				{
+					name: 'investorQuoteTokenAccount',
+					isMut: true,
 					isSigner: false,
 				},
-			],
-			args: [
 				{
-					name: 'amount',
-					type: 'u64',
+					name: 'vaultQuoteTokenAccount',
+					isMut: true,
+					isSigner: false,
+				},
+				{
+					name: 'tokenProgram',
+					isMut: false,
+					isSigner: false,
 				},
 			],
+			args: [],
 		},
 		{
-			name: 'managerRequestWithdraw',
+			name: 'viewVaultEquity',
+			docs: [
+				'Read-only instruction that returns the [`Vault`] equity as return data.',
+			],
 			accounts: [
 				{
 					name: 'vault',
-					isMut: true,
-					isSigner: false,
-				},
-				{
-					name: 'manager',
 					isMut: false,
-					isSigner: true,
+					isSigner: false,
 				},
 				{
 					name: 'marketRegistry',
@@ -4511,35 +7278,27 @@ export const IDL: PhoenixVaults = {
 				},
 				{
 					name: 'vaultUsdcTokenAccount',
-					isMut: true,
+					isMut: false,
 					isSigner: false,
 				},
 			],
-			args: [
-				{
-					name: 'withdrawAmount',
-					type: 'u64',
-				},
-				{
-					name: 'withdrawUnit',
-					type: {
-						defined: 'WithdrawUnit',
-					},
-				},
-			],
+			args: [],
 		},
 		{
-			name: 'managerCancelWithdrawRequest',
+			name: 'viewInvestorEquity',
+			docs: [
+				'Read-only instruction that returns the equity of an [`Investor`] as return data.',
+			],
 			accounts: [
 				{
 					name: 'vault',
-					isMut: true,
+					isMut: false,
 					isSigner: false,
 				},
 				{
-					name: 'manager',
+					name: 'investor',
 					isMut: false,
-					isSigner: true,
+					isSigner: false,
 				},
 				{
 					name: 'marketRegistry',
@@ -4548,24 +7307,28 @@ export const IDL: PhoenixVaults = {
 				},
 				{
 					name: 'vaultUsdcTokenAccount',
-					isMut: true,
+					isMut: false,
 					isSigner: false,
 				},
 			],
 			args: [],
 		},
 		{
-			name: 'protocolWithdraw',
+			name: 'previewDeposit',
+			docs: [
+				'Read-only instruction that returns the shares an [`Investor`] deposit would mint as return data.',
+				'Fails wherever `investor_deposit` would, e.g. if the vault is at capacity or no longer accepts deposits.',
+			],
 			accounts: [
 				{
 					name: 'vault',
-					isMut: true,
+					isMut: false,
 					isSigner: false,
 				},
 				{
-					name: 'protocol',
+					name: 'investor',
 					isMut: false,
-					isSigner: true,
+					isSigner: false,
 				},
 				{
 					name: 'marketRegistry',
@@ -4573,948 +7336,1966 @@ export const IDL: PhoenixVaults = {
 					isSigner: false,
 				},
 				{
-					name: 'protocolQuoteTokenAccount',
-					isMut: true,
+					name: 'vaultUsdcTokenAccount',
+					isMut: false,
 					isSigner: false,
 				},
+			],
+			args: [
 				{
-					name: 'phoenix',
-					isMut: false,
-					isSigner: false,
+					name: 'amount',
+					type: 'u64',
 				},
+			],
+		},
+		{
+			name: 'previewWithdraw',
+			docs: [
+				'Read-only instruction that returns the USDC an [`Investor`] withdrawal would pay as return data.',
+			],
+			accounts: [
 				{
-					name: 'logAuthority',
+					name: 'vault',
 					isMut: false,
 					isSigner: false,
 				},
 				{
-					name: 'market',
-					isMut: true,
+					name: 'investor',
+					isMut: false,
 					isSigner: false,
 				},
 				{
-					name: 'seat',
+					name: 'marketRegistry',
 					isMut: false,
 					isSigner: false,
 				},
 				{
-					name: 'baseMint',
+					name: 'vaultUsdcTokenAccount',
 					isMut: false,
 					isSigner: false,
 				},
+			],
+			args: [
 				{
-					name: 'quoteMint',
-					isMut: false,
-					isSigner: false,
+					name: 'withdrawAmount',
+					type: 'u64',
 				},
 				{
-					name: 'vaultBaseTokenAccount',
-					isMut: true,
-					isSigner: false,
+					name: 'withdrawUnit',
+					type: {
+						defined: 'WithdrawUnit',
+					},
 				},
+			],
+		},
+		{
+			name: 'cancelAllOrders',
+			accounts: [
 				{
-					name: 'vaultQuoteTokenAccount',
+					name: 'vault',
 					isMut: true,
 					isSigner: false,
+					docs: [
+						'If delegate has authority to sign for vault, then any Phoenix CPI is valid.',
+						'Phoenix CPI validates that opaque instruction data is a [`PhoenixInstruction`],',
+						'so this is safe since any Phoenix CPI is secure.',
+					],
 				},
 				{
-					name: 'marketBaseTokenAccount',
-					isMut: true,
-					isSigner: false,
+					name: 'delegate',
+					isMut: false,
+					isSigner: true,
+					docs: [
+						'Is manager by default, but can be delegated to another pubkey using `update_delegate`',
+					],
 				},
 				{
-					name: 'marketQuoteTokenAccount',
-					isMut: true,
+					name: 'phoenix',
+					isMut: false,
 					isSigner: false,
 				},
 				{
-					name: 'tokenProgram',
+					name: 'logAuthority',
 					isMut: false,
 					isSigner: false,
 				},
+				{
+					name: 'market',
+					isMut: true,
+					isSigner: false,
+				},
 			],
 			args: [],
 		},
 		{
-			name: 'protocolRequestWithdraw',
+			name: 'cancelMultipleOrdersById',
 			accounts: [
 				{
 					name: 'vault',
 					isMut: true,
 					isSigner: false,
+					docs: [
+						'If delegate has authority to sign for vault, then any Phoenix CPI is valid.',
+						'Phoenix CPI validates that opaque instruction data is a [`PhoenixInstruction`],',
+						'so this is safe since any Phoenix CPI is secure.',
+					],
 				},
 				{
-					name: 'protocol',
+					name: 'delegate',
 					isMut: false,
 					isSigner: true,
+					docs: [
+						'Is manager by default, but can be delegated to another pubkey using `update_delegate`',
+					],
 				},
 				{
-					name: 'marketRegistry',
+					name: 'phoenix',
 					isMut: false,
 					isSigner: false,
 				},
 				{
-					name: 'vaultUsdcTokenAccount',
+					name: 'logAuthority',
+					isMut: false,
+					isSigner: false,
+				},
+				{
+					name: 'market',
 					isMut: true,
 					isSigner: false,
 				},
 			],
 			args: [
 				{
-					name: 'withdrawAmount',
-					type: 'u64',
-				},
-				{
-					name: 'withdrawUnit',
+					name: 'params',
 					type: {
-						defined: 'WithdrawUnit',
+						defined: 'CancelMultipleOrdersParams',
 					},
 				},
 			],
 		},
+	],
+	accounts: [
+		{
+			name: 'investor',
+			type: {
+				kind: 'struct',
+				fields: [
+					{
+						name: 'vault',
+						docs: ['The vault deposited into'],
+						type: 'publicKey',
+					},
+					{
+						name: 'pubkey',
+						docs: [
+							"The vault depositor account's pubkey. It is a pda of vault and authority",
+						],
+						type: 'publicKey',
+					},
+					{
+						name: 'authority',
+						docs: [
+							'The authority is the address w permission to deposit/withdraw',
+						],
+						type: 'publicKey',
+					},
+					{
+						name: 'vaultShares',
+						docs: [
+							"share of vault owned by this depositor. vault_shares / vault.total_shares is depositor's ownership of vault_equity",
+						],
+						type: 'u128',
+					},
+					{
+						name: 'lastWithdrawRequest',
+						docs: ['last withdraw request'],
+						type: {
+							defined: 'WithdrawRequest',
+						},
+					},
+					{
+						name: 'lastValidTs',
+						docs: ['creation ts of vault depositor'],
+						type: 'i64',
+					},
+					{
+						name: 'netDeposits',
+						docs: ['lifetime net deposits of vault depositor for the vault'],
+						type: 'i64',
+					},
+					{
+						name: 'totalDeposits',
+						docs: ['lifetime total deposits'],
+						type: 'u64',
+					},
+					{
+						name: 'totalWithdraws',
+						docs: ['lifetime total withdraws'],
+						type: 'u64',
+					},
+					{
+						name: 'cumulativeProfitShareAmount',
+						docs: [
+							'the token amount of gains the vault depositor has paid performance fees on (in other words, cumulative profit)',
+						],
+						type: 'i64',
+					},
+					{
+						name: 'profitShareFeePaid',
+						docs: [
+							'the token amount profit share paid out to the manager and protocol',
+						],
+						type: 'u64',
+					},
+					{
+						name: 'vaultSharesBase',
+						docs: ['the exponent for vault_shares decimal places'],
+						type: 'u32',
+					},
+					{
+						name: 'padding1',
+						type: 'u32',
+					},
+					{
+						name: 'epochDepositAmount',
+						docs: [
+							'USDC deposited during an epoch that has not been claimed as shares',
+						],
+						type: 'u64',
+					},
+					{
+						name: 'epochDepositEpoch',
+						docs: ['The epoch `epoch_deposit_amount` was deposited in'],
+						type: 'u64',
+					},
+					{
+						name: 'epochWithdrawEpoch',
+						docs: ['The epoch the last withdraw request was queued in'],
+						type: 'u64',
+					},
+					{
+						name: 'firstDepositTs',
+						docs: [
+							'When the investor first deposited, which starts the vault lockup',
+						],
+						type: 'i64',
+					},
+					{
+						name: 'padding',
+						type: {
+							array: ['u64', 4],
+						},
+					},
+				],
+			},
+		},
+		{
+			name: 'marketRegistry',
+			docs: [
+				'DriftVaults validates vault user positions against the remaining accounts provided for those markets.',
+				'If the remaining accounts do not contain every market the user has a position in, then the instruction errors.',
+				'For Phoenix, we use our MarketRegistry as the official source of truth for the "list of markets",',
+				"and we can get the TraderState for the vault within each market to determine the vault's positions.",
+				'If the remaining accounts do not contain every market in the MarketRegistry that the vault has a position in,',
+				'then the instruction will error.',
+			],
+			type: {
+				kind: 'struct',
+				fields: [
+					{
+						name: 'authority',
+						docs: [
+							'Authority over this account. This is a program admin-level keypair.',
+						],
+						type: 'publicKey',
+					},
+					{
+						name: 'solUsdcMarket',
+						docs: ['Phoenix SOL/USDC market'],
+						type: 'publicKey',
+					},
+					{
+						name: 'usdcMint',
+						docs: [
+							'Phoenix markets are denominated in USDC or SOL, so we must pre-define this',
+						],
+						type: 'publicKey',
+					},
+					{
+						name: 'solMint',
+						docs: [
+							'Phoenix markets are denominated in USDC or SOL, so we must pre-define this',
+						],
+						type: 'publicKey',
+					},
+				],
+			},
+		},
 		{
-			name: 'protocolCancelWithdrawRequest',
-			accounts: [
-				{
-					name: 'vault',
-					isMut: true,
-					isSigner: false,
-				},
-				{
-					name: 'protocol',
-					isMut: false,
-					isSigner: true,
-				},
-				{
-					name: 'marketRegistry',
-					isMut: false,
-					isSigner: false,
-				},
-				{
-					name: 'vaultUsdcTokenAccount',
-					isMut: true,
-					isSigner: false,
-				},
+			name: 'navHistory',
+			docs: [
+				'Ring buffer of vault equity and shares samples to chart the share price without an indexer.',
+				'Samples are appended at most once per [`MIN_NAV_SAMPLE_INTERVAL`] by the permissionless `update_nav_history` crank',
+				'and whenever a deposit or withdrawal computes the vault equity. Within the interval the latest sample is updated in place.',
 			],
-			args: [],
+			type: {
+				kind: 'struct',
+				fields: [
+					{
+						name: 'vault',
+						docs: ['The vault sampled'],
+						type: 'publicKey',
+					},
+					{
+						name: 'head',
+						docs: ['Index the next sample is written to'],
+						type: 'u64',
+					},
+					{
+						name: 'len',
+						docs: ['Number of samples written, up to [`NAV_HISTORY_LENGTH`]'],
+						type: 'u64',
+					},
+					{
+						name: 'lastSampleTs',
+						docs: [
+							"Timestamp the most recent sample was appended at. Updates in place don't move it.",
+						],
+						type: 'i64',
+					},
+					{
+						name: 'padding',
+						type: {
+							array: ['u8', 8],
+						},
+					},
+					{
+						name: 'samples',
+						type: {
+							array: [
+								{
+									defined: 'NavSample',
+								},
+								128,
+							],
+						},
+					},
+				],
+			},
 		},
 		{
-			name: 'cancelAllOrders',
-			accounts: [
-				{
-					name: 'vault',
-					isMut: true,
-					isSigner: false,
-					docs: [
-						'If delegate has authority to sign for vault, then any Phoenix CPI is valid.',
-						'Phoenix CPI validates that opaque instruction data is a [`PhoenixInstruction`],',
-						'so this is safe since any Phoenix CPI is secure.',
-					],
-				},
-				{
-					name: 'delegate',
-					isMut: false,
-					isSigner: true,
-					docs: [
-						'Is manager by default, but can be delegated to another pubkey using `update_delegate`',
-					],
-				},
-				{
-					name: 'phoenix',
-					isMut: false,
-					isSigner: false,
-				},
-				{
-					name: 'logAuthority',
-					isMut: false,
-					isSigner: false,
-				},
-				{
-					name: 'market',
-					isMut: true,
-					isSigner: false,
-				},
+			name: 'sharePrice',
+			docs: [
+				'Latest vault share price, so other programs can price vault shares',
+				'without recomputing equity over every market the vault trades.',
+				'Refreshed by the permissionless `update_share_price` crank.',
 			],
-			args: [],
+			type: {
+				kind: 'struct',
+				fields: [
+					{
+						name: 'vault',
+						docs: ['The vault priced'],
+						type: 'publicKey',
+					},
+					{
+						name: 'totalShares',
+						docs: ['Vault total shares at the last update'],
+						type: 'u128',
+					},
+					{
+						name: 'equity',
+						docs: ['Vault equity in USDC at the last update'],
+						type: 'u64',
+					},
+					{
+						name: 'price',
+						docs: ['USDC per share, precision: PRICE_PRECISION'],
+						type: 'u64',
+					},
+					{
+						name: 'slot',
+						docs: ['Slot of the last update'],
+						type: 'u64',
+					},
+					{
+						name: 'ts',
+						docs: ['Unix timestamp of the last update'],
+						type: 'i64',
+					},
+					{
+						name: 'maxStalenessSlots',
+						docs: [
+							'Number of slots after the last update before the price is considered stale',
+						],
+						type: 'u64',
+					},
+					{
+						name: 'sharesBase',
+						docs: ['Vault shares base at the last update'],
+						type: 'u32',
+					},
+					{
+						name: 'padding1',
+						type: 'u32',
+					},
+					{
+						name: 'padding',
+						type: {
+							array: ['u64', 4],
+						},
+					},
+				],
+			},
 		},
 		{
-			name: 'cancelMultipleOrdersById',
-			accounts: [
-				{
-					name: 'vault',
-					isMut: true,
-					isSigner: false,
-					docs: [
-						'If delegate has authority to sign for vault, then any Phoenix CPI is valid.',
-						'Phoenix CPI validates that opaque instruction data is a [`PhoenixInstruction`],',
-						'so this is safe since any Phoenix CPI is secure.',
-					],
-				},
-				{
-					name: 'delegate',
-					isMut: false,
-					isSigner: true,
-					docs: [
-						'Is manager by default, but can be delegated to another pubkey using `update_delegate`',
-					],
-				},
-				{
-					name: 'phoenix',
-					isMut: false,
-					isSigner: false,
-				},
-				{
-					name: 'logAuthority',
-					isMut: false,
-					isSigner: false,
-				},
-				{
-					name: 'market',
-					isMut: true,
-					isSigner: false,
-				},
-			],
-			args: [
-				{
-					name: 'params',
-					type: {
-						defined: 'CancelMultipleOrdersParams',
+			name: 'vault',
+			type: {
+				kind: 'struct',
+				fields: [
+					{
+						name: 'name',
+						docs: [
+							'The name of the vault. Vault pubkey is derived from this name.',
+						],
+						type: {
+							array: ['u8', 32],
+						},
+					},
+					{
+						name: 'pubkey',
+						docs: [
+							"The vault's pubkey. It is a PDA also used as the authority token accounts",
+						],
+						type: 'publicKey',
+					},
+					{
+						name: 'manager',
+						docs: [
+							'The manager of the vault who has ability to update vault config,',
+							'and earns a profit share or management fee.',
+						],
+						type: 'publicKey',
+					},
+					{
+						name: 'usdcMint',
+						docs: ['The Phoenix USDC mint.'],
+						type: 'publicKey',
+					},
+					{
+						name: 'solMint',
+						docs: ['The Phoenix (wrapped) SOL mint.'],
+						type: 'publicKey',
+					},
+					{
+						name: 'usdcTokenAccount',
+						docs: [
+							'The USDC token account investor transfer with,',
+							'and the vault transfer to Phoenix markets with.',
+						],
+						type: 'publicKey',
 					},
-				},
-			],
-		},
-	],
-	accounts: [
-		{
-			name: 'investor',
-			type: {
-				kind: 'struct',
-				fields: [
 					{
-						name: 'vault',
-						docs: ['The vault deposited into'],
+						name: 'solTokenAccount',
+						docs: [
+							'The SOL token account investor transfer with,',
+							'and the vault transfer to Phoenix markets with.',
+						],
 						type: 'publicKey',
 					},
 					{
-						name: 'pubkey',
+						name: 'liquidator',
 						docs: [
-							"The vault depositor account's pubkey. It is a pda of vault and authority",
+							'The delegate (investor) handling liquidation for an investor to withdraw their funds.',
 						],
 						type: 'publicKey',
 					},
 					{
-						name: 'authority',
+						name: 'delegate',
 						docs: [
-							'The authority is the address w permission to deposit/withdraw',
+							'The delegate is the "portfolio manager", "trader", or "bot" that trades the vault assets.',
+							'It can swap 100% of vault tokens.',
+							'This is the manager by default.',
 						],
 						type: 'publicKey',
 					},
 					{
-						name: 'vaultShares',
+						name: 'investorShares',
+						docs: ['The sum of all shares held by the investors'],
+						type: 'u128',
+					},
+					{
+						name: 'totalShares',
 						docs: [
-							"share of vault owned by this depositor. vault_shares / vault.total_shares is depositor's ownership of vault_equity",
+							'The sum of all shares: investor deposits, manager deposits, manager profit/fee, and protocol profit/fee.',
+							'The manager deposits are total_shares - investor_shares - protocol_profit_and_fee_shares.',
 						],
 						type: 'u128',
 					},
 					{
-						name: 'lastWithdrawRequest',
-						docs: ['last withdraw request'],
-						type: {
-							defined: 'WithdrawRequest',
-						},
+						name: 'lastFeeUpdateTs',
+						docs: ['Last fee update unix timestamp'],
+						type: 'i64',
 					},
 					{
-						name: 'lastValidTs',
-						docs: ['creation ts of vault depositor'],
+						name: 'liquidationStartTs',
+						docs: ['When the liquidation starts'],
+						type: 'i64',
+					},
+					{
+						name: 'redeemPeriod',
+						docs: [
+							'The period (in seconds) that an investor must wait after requesting a withdrawal to transfer funds.',
+							'The maximum is 90 days.',
+							'This is only updatable to lesser values.',
+						],
+						type: 'i64',
+					},
+					{
+						name: 'totalWithdrawRequested',
+						docs: ['The sum of all outstanding withdraw requests'],
+						type: 'u64',
+					},
+					{
+						name: 'maxTokens',
+						docs: [
+							'Max token capacity, once hit/passed vault will reject new deposits.',
+							'This is only updatable to lesser values.',
+						],
+						type: 'u64',
+					},
+					{
+						name: 'managementFee',
+						docs: [
+							'The annual fee charged on deposits by the manager.',
+							'Traditional funds typically charge 2% per year on assets under management.',
+							'This is only updatable to lesser values.',
+						],
+						type: 'i64',
+					},
+					{
+						name: 'initTs',
+						docs: ['Timestamp vault initialized'],
 						type: 'i64',
 					},
 					{
 						name: 'netDeposits',
-						docs: ['lifetime net deposits of vault depositor for the vault'],
+						docs: ['The net deposits for the vault'],
+						type: 'i64',
+					},
+					{
+						name: 'managerNetDeposits',
+						docs: ['The net deposits for the manager'],
 						type: 'i64',
 					},
 					{
 						name: 'totalDeposits',
-						docs: ['lifetime total deposits'],
+						docs: ['Total deposits'],
 						type: 'u64',
 					},
 					{
 						name: 'totalWithdraws',
-						docs: ['lifetime total withdraws'],
+						docs: ['Total withdraws'],
 						type: 'u64',
 					},
 					{
-						name: 'cumulativeProfitShareAmount',
-						docs: [
-							'the token amount of gains the vault depositor has paid performance fees on (in other words, cumulative profit)',
-						],
+						name: 'managerTotalDeposits',
+						docs: ['Total deposits for the manager'],
+						type: 'u64',
+					},
+					{
+						name: 'managerTotalWithdraws',
+						docs: ['Total withdraws for the manager'],
+						type: 'u64',
+					},
+					{
+						name: 'managerTotalFee',
+						docs: ['Total management fee accrued by the manager'],
 						type: 'i64',
 					},
 					{
-						name: 'profitShareFeePaid',
+						name: 'managerTotalProfitShare',
+						docs: ['Total profit share accrued by the manager'],
+						type: 'u64',
+					},
+					{
+						name: 'minDepositAmount',
 						docs: [
-							'the token amount profit share paid out to the manager and protocol',
+							'The minimum deposit amount.',
+							'This is only updatable to lesser values.',
 						],
 						type: 'u64',
 					},
 					{
-						name: 'vaultSharesBase',
-						docs: ['the exponent for vault_shares decimal places'],
+						name: 'lastManagerWithdrawRequest',
+						type: {
+							defined: 'WithdrawRequest',
+						},
+					},
+					{
+						name: 'sharesBase',
+						docs: [
+							'The base 10 exponent of the shares (given massive share inflation can occur at near zero vault equity)',
+						],
 						type: 'u32',
 					},
 					{
-						name: 'padding1',
+						name: 'profitShare',
+						docs: [
+							'Percentage the manager charges on all profits realized by depositors (multiplied by PERCENTAGE_PRECISION).',
+							'Traditional funds typically charge 20% of profits.',
+							'This is only updatable to lesser values.',
+						],
+						type: 'u32',
+					},
+					{
+						name: 'hurdleRate',
+						docs: [
+							'Vault manager only collect incentive fees during periods when returns are higher than this amount (multiplied by PERCENTAGE_PRECISION).',
+						],
+						type: 'u32',
+					},
+					{
+						name: 'protocolProfitShare',
+						docs: [
+							'Percentage the protocol charges on all profits realized by depositors: PERCENTAGE_PRECISION',
+						],
 						type: 'u32',
 					},
+					{
+						name: 'protocol',
+						docs: [
+							'The protocol, company, or entity that services the product using this vault.',
+							'The protocol is not allowed to deposit into the vault but can profit share and collect annual fees just like the manager.',
+						],
+						type: 'publicKey',
+					},
+					{
+						name: 'protocolProfitAndFeeShares',
+						docs: [
+							'The shares from profit share and annual fee unclaimed by the protocol.',
+						],
+						type: 'u128',
+					},
+					{
+						name: 'protocolFee',
+						docs: [
+							'The annual fee charged on deposits by the protocol (traditional hedge funds typically charge 2% per year on assets under management).',
+							"Unlike the management fee this can't be negative.",
+						],
+						type: 'u64',
+					},
+					{
+						name: 'protocolTotalWithdraws',
+						docs: ['Total withdraws for the protocol'],
+						type: 'u64',
+					},
+					{
+						name: 'protocolTotalFee',
+						docs: [
+							'Total fee charged by the protocol (annual management fee + profit share).',
+							"Unlike the management fee this can't be negative.",
+						],
+						type: 'u64',
+					},
+					{
+						name: 'protocolTotalProfitShare',
+						docs: ['Total profit share charged by the protocol'],
+						type: 'u64',
+					},
+					{
+						name: 'lastProtocolWithdrawRequest',
+						type: {
+							defined: 'WithdrawRequest',
+						},
+					},
+					{
+						name: 'positions',
+						type: {
+							array: [
+								{
+									defined: 'MarketPosition',
+								},
+								8,
+							],
+						},
+					},
+					{
+						name: 'permissioned',
+						docs: ['Whether anyone can be an investor'],
+						type: 'bool',
+					},
+					{
+						name: 'bump',
+						docs: ['The bump for the vault PDA'],
+						type: 'u8',
+					},
 					{
 						name: 'padding',
 						type: {
-							array: ['u64', 8],
+							array: ['u8', 6],
 						},
 					},
-				],
-			},
-		},
-		{
-			name: 'marketRegistry',
-			docs: [
-				'DriftVaults validates vault user positions against the remaining accounts provided for those markets.',
-				'If the remaining accounts do not contain every market the user has a position in, then the instruction errors.',
-				'For Phoenix, we use our MarketRegistry as the official source of truth for the "list of markets",',
-				"and we can get the TraderState for the vault within each market to determine the vault's positions.",
-				'If the remaining accounts do not contain every market in the MarketRegistry that the vault has a position in,',
-				'then the instruction will error.',
-			],
-			type: {
-				kind: 'struct',
-				fields: [
 					{
-						name: 'authority',
+						name: 'epochPendingWithdrawShares',
+						docs: [
+							'Shares requested for withdraw during the current epoch, burned by the next `settle_epoch`.',
+						],
+						type: 'u128',
+					},
+					{
+						name: 'epochUnclaimedDepositShares',
+						docs: [
+							'Shares minted by the last `settle_epoch` that investors have yet to claim.',
+						],
+						type: 'u128',
+					},
+					{
+						name: 'epochUnclaimedWithdrawShares',
+						docs: [
+							'Shares burned by the last `settle_epoch` that investors have yet to claim the value of.',
+						],
+						type: 'u128',
+					},
+					{
+						name: 'epochDuration',
 						docs: [
-							'Authority over this account. This is a program admin-level keypair.',
+							'The length (in seconds) of a subscription and redemption window.',
+							'If zero, deposits and withdraw requests are priced immediately at the current equity.',
+							'This can only be set when the vault is initialized.',
 						],
-						type: 'publicKey',
+						type: 'i64',
 					},
 					{
-						name: 'solUsdcMarket',
-						docs: ['Phoenix SOL/USDC market'],
-						type: 'publicKey',
+						name: 'epochStartTs',
+						docs: ['When the current epoch started'],
+						type: 'i64',
 					},
 					{
-						name: 'usdcMint',
+						name: 'currentEpoch',
 						docs: [
-							'Phoenix markets are denominated in USDC or SOL, so we must pre-define this',
+							'The number of epochs settled since the vault was initialized',
 						],
-						type: 'publicKey',
+						type: 'u64',
 					},
 					{
-						name: 'solMint',
+						name: 'epochPendingDeposits',
 						docs: [
-							'Phoenix markets are denominated in USDC or SOL, so we must pre-define this',
+							'USDC deposited during the current epoch, not yet priced into shares.',
 						],
-						type: 'publicKey',
+						type: 'u64',
 					},
-				],
-			},
-		},
-		{
-			name: 'vault',
-			type: {
-				kind: 'struct',
-				fields: [
 					{
-						name: 'name',
+						name: 'epochUnclaimedDeposits',
 						docs: [
-							'The name of the vault. Vault pubkey is derived from this name.',
+							'USDC deposits priced by the last `settle_epoch` that investors have yet to claim shares for.',
 						],
-						type: {
-							array: ['u8', 32],
-						},
+						type: 'u64',
 					},
 					{
-						name: 'pubkey',
+						name: 'epochUnclaimedWithdrawValue',
 						docs: [
-							"The vault's pubkey. It is a PDA also used as the authority token accounts",
+							'Value of the withdraw shares burned by the last `settle_epoch` that investors have yet to claim.',
 						],
-						type: 'publicKey',
+						type: 'u64',
 					},
 					{
-						name: 'manager',
+						name: 'epochRedemptionsOwed',
 						docs: [
-							'The manager of the vault who has ability to update vault config,',
-							'and earns a profit share or management fee.',
+							'USDC priced by a `settle_epoch` and owed to investors until they withdraw it.',
 						],
-						type: 'publicKey',
+						type: 'u64',
 					},
 					{
-						name: 'usdcMint',
-						docs: ['The Phoenix USDC mint.'],
-						type: 'publicKey',
+						name: 'entryFeeBps',
+						docs: [
+							'The fee (in basis points) withheld from investor deposits.',
+							'It stays in the vault to accrue to the remaining shareholders.',
+							'This is only updatable to lesser values.',
+						],
+						type: 'u16',
 					},
 					{
-						name: 'solMint',
-						docs: ['The Phoenix (wrapped) SOL mint.'],
-						type: 'publicKey',
+						name: 'exitFeeBps',
+						docs: [
+							'The fee (in basis points) withheld from investor withdrawals.',
+							'It stays in the vault to accrue to the remaining shareholders.',
+							'This is only updatable to lesser values.',
+						],
+						type: 'u16',
 					},
 					{
-						name: 'usdcTokenAccount',
+						name: 'maxDrawdown',
 						docs: [
-							'The USDC token account investor transfer with,',
-							'and the vault transfer to Phoenix markets with.',
+							'The drawdown of the share price from its high-water mark (multiplied by PERCENTAGE_PRECISION)',
+							'that halts delegate trading. If zero, trading is never halted.',
 						],
-						type: 'publicKey',
+						type: 'u32',
 					},
 					{
-						name: 'solTokenAccount',
+						name: 'highWaterMarkPrice',
 						docs: [
-							'The SOL token account investor transfer with,',
-							'and the vault transfer to Phoenix markets with.',
+							'The highest share price (USDC per share multiplied by PRICE_PRECISION) sampled by `update_nav_history`.',
 						],
-						type: 'publicKey',
+						type: 'u64',
 					},
 					{
-						name: 'liquidator',
+						name: 'tradingHalted',
 						docs: [
-							'The delegate (investor) handling liquidation for an investor to withdraw their funds.',
+							'Set when the share price drawdown exceeds `max_drawdown`.',
+							"The delegate can't place orders or deposit to markets, and investors can withdraw without waiting the redeem period,",
+							'until the manager and protocol call `resume_trading`.',
 						],
-						type: 'publicKey',
+						type: 'bool',
 					},
 					{
-						name: 'delegate',
+						name: 'state',
 						docs: [
-							'The delegate is the "portfolio manager", "trader", or "bot" that trades the vault assets.',
-							'It can swap 100% of vault tokens.',
-							'This is the manager by default.',
+							'The lifecycle state. Use [`Vault::state`] since fundraising ends at its deadline without an instruction.',
 						],
-						type: 'publicKey',
+						type: {
+							defined: 'VaultState',
+						},
 					},
 					{
-						name: 'investorShares',
-						docs: ['The sum of all shares held by the investors'],
-						type: 'u128',
+						name: 'padding2',
+						type: {
+							array: ['u8', 2],
+						},
 					},
 					{
-						name: 'totalShares',
+						name: 'managerMinStake',
 						docs: [
-							'The sum of all shares: investor deposits, manager deposits, manager profit/fee, and protocol profit/fee.',
-							'The manager deposits are total_shares - investor_shares - protocol_profit_and_fee_shares.',
+							'The minimum percentage of `total_shares` (multiplied by PERCENTAGE_PRECISION) the manager must hold',
+							'while investor shares are outstanding. This is only updatable to greater values.',
 						],
-						type: 'u128',
+						type: 'u32',
 					},
 					{
-						name: 'lastFeeUpdateTs',
-						docs: ['Last fee update unix timestamp'],
-						type: 'i64',
+						name: 'fundraisingSoftCap',
+						docs: [
+							'Total deposits required by the fundraising deadline for the vault to become active.',
+							'If zero, the vault is active from initialization.',
+						],
+						type: 'u64',
 					},
 					{
-						name: 'liquidationStartTs',
-						docs: ['When the liquidation starts'],
+						name: 'fundraisingDeadline',
+						docs: ['When fundraising ends'],
 						type: 'i64',
 					},
 					{
-						name: 'redeemPeriod',
+						name: 'finalEquity',
 						docs: [
-							'The period (in seconds) that an investor must wait after requesting a withdrawal to transfer funds.',
-							'The maximum is 90 days.',
-							'This is only updatable to lesser values.',
+							'The vault equity snapshotted by `finalize_wind_down`, which investors claim pro-rata once the vault is closed.',
 						],
-						type: 'i64',
+						type: 'u64',
 					},
 					{
-						name: 'totalWithdrawRequested',
-						docs: ['The sum of all outstanding withdraw requests'],
-						type: 'u64',
+						name: 'finalTotalShares',
+						docs: [
+							'The total shares snapshotted alongside `final_equity`. Zero until the wind-down is finalized.',
+						],
+						type: 'u128',
 					},
 					{
-						name: 'maxTokens',
+						name: 'liquidityBuffer',
 						docs: [
-							'Max token capacity, once hit/passed vault will reject new deposits.',
-							'This is only updatable to lesser values.',
+							'The percentage of equity (multiplied by PERCENTAGE_PRECISION) kept in the vault USDC token account',
+							"on top of outstanding withdraw requests, which `market_deposit` can't draw from.",
+							'This is only updatable to greater values.',
 						],
-						type: 'u64',
+						type: 'u32',
 					},
 					{
-						name: 'managementFee',
+						name: 'lockupPenaltyBps',
 						docs: [
-							'The annual fee charged on deposits by the manager.',
-							'Traditional funds typically charge 2% per year on assets under management.',
-							'This is only updatable to lesser values.',
+							"The penalty (in basis points) withheld from withdrawals requested during an investor's lockup.",
+							'It stays in the vault to accrue to the remaining shareholders.',
+							'If zero, withdraw requests during the lockup are rejected.',
 						],
-						type: 'i64',
+						type: 'u16',
 					},
 					{
-						name: 'initTs',
-						docs: ['Timestamp vault initialized'],
-						type: 'i64',
+						name: 'padding3',
+						type: {
+							array: ['u8', 2],
+						},
 					},
 					{
-						name: 'netDeposits',
-						docs: ['The net deposits for the vault'],
+						name: 'lockupDuration',
+						docs: [
+							"Seconds from an investor's first deposit during which withdraw requests are penalized or rejected.",
+							'If zero, there is no lockup. This is only updatable to lesser values.',
+						],
 						type: 'i64',
 					},
 					{
-						name: 'managerNetDeposits',
-						docs: ['The net deposits for the manager'],
+						name: 'liquidationWindow',
+						docs: [
+							'Seconds an appointed liquidator has to liquidate before the vault can exit liquidation',
+							'or another liquidator can be appointed. This is only updatable to lesser values.',
+						],
 						type: 'i64',
 					},
 					{
-						name: 'totalDeposits',
-						docs: ['Total deposits'],
-						type: 'u64',
+						name: 'padding4',
+						type: {
+							array: ['u64', 1],
+						},
+					},
+				],
+			},
+		},
+	],
+	types: [
+		{
+			name: 'MarketLookupTableParams',
+			type: {
+				kind: 'struct',
+				fields: [
+					{
+						name: 'solUsdcMarket',
+						type: 'publicKey',
 					},
 					{
-						name: 'totalWithdraws',
-						docs: ['Total withdraws'],
-						type: 'u64',
+						name: 'usdcMint',
+						type: 'publicKey',
 					},
 					{
-						name: 'managerTotalDeposits',
-						docs: ['Total deposits for the manager'],
+						name: 'solMint',
+						type: 'publicKey',
+					},
+				],
+			},
+		},
+		{
+			name: 'CancelMultipleOrdersParams',
+			type: {
+				kind: 'struct',
+				fields: [
+					{
+						name: 'orders',
+						type: {
+							vec: {
+								defined: 'CancelOrderParams',
+							},
+						},
+					},
+				],
+			},
+		},
+		{
+			name: 'CancelOrderParams',
+			type: {
+				kind: 'struct',
+				fields: [
+					{
+						name: 'side',
+						type: {
+							defined: 'Side',
+						},
+					},
+					{
+						name: 'priceInTicks',
 						type: 'u64',
 					},
 					{
-						name: 'managerTotalWithdraws',
-						docs: ['Total withdraws for the manager'],
+						name: 'orderSequenceNumber',
 						type: 'u64',
 					},
+				],
+			},
+		},
+		{
+			name: 'VaultParams',
+			type: {
+				kind: 'struct',
+				fields: [
 					{
-						name: 'managerTotalFee',
-						docs: ['Total management fee accrued by the manager'],
+						name: 'name',
+						type: {
+							array: ['u8', 32],
+						},
+					},
+					{
+						name: 'redeemPeriod',
 						type: 'i64',
 					},
 					{
-						name: 'managerTotalProfitShare',
-						docs: ['Total profit share accrued by the manager'],
+						name: 'maxTokens',
 						type: 'u64',
 					},
+					{
+						name: 'managementFee',
+						type: 'i64',
+					},
 					{
 						name: 'minDepositAmount',
+						type: 'u64',
+					},
+					{
+						name: 'profitShare',
+						type: 'u32',
+					},
+					{
+						name: 'hurdleRate',
+						type: 'u32',
+					},
+					{
+						name: 'permissioned',
+						type: 'bool',
+					},
+					{
+						name: 'protocol',
+						type: 'publicKey',
+					},
+					{
+						name: 'protocolFee',
+						type: 'u64',
+					},
+					{
+						name: 'protocolProfitShare',
+						type: 'u32',
+					},
+					{
+						name: 'epochDuration',
 						docs: [
-							'The minimum deposit amount.',
-							'This is only updatable to lesser values.',
+							'If non-zero, deposits and withdraw requests are queued and priced once per epoch by `settle_epoch`.',
 						],
-						type: 'u64',
+						type: 'i64',
 					},
 					{
-						name: 'lastManagerWithdrawRequest',
-						type: {
-							defined: 'WithdrawRequest',
-						},
+						name: 'entryFeeBps',
+						docs: [
+							'Fee (in basis points) withheld from investor deposits for the remaining shareholders.',
+						],
+						type: 'u16',
 					},
 					{
-						name: 'sharesBase',
+						name: 'exitFeeBps',
 						docs: [
-							'The base 10 exponent of the shares (given massive share inflation can occur at near zero vault equity)',
+							'Fee (in basis points) withheld from investor withdrawals for the remaining shareholders.',
 						],
-						type: 'u32',
+						type: 'u16',
 					},
 					{
-						name: 'profitShare',
+						name: 'maxDrawdown',
 						docs: [
-							'Percentage the manager charges on all profits realized by depositors (multiplied by PERCENTAGE_PRECISION).',
-							'Traditional funds typically charge 20% of profits.',
-							'This is only updatable to lesser values.',
+							'Share price drawdown from its high-water mark (multiplied by PERCENTAGE_PRECISION) that halts delegate trading.',
+							'If zero, trading is never halted.',
 						],
 						type: 'u32',
 					},
 					{
-						name: 'hurdleRate',
+						name: 'managerMinStake',
 						docs: [
-							'Vault manager only collect incentive fees during periods when returns are higher than this amount (multiplied by PERCENTAGE_PRECISION).',
+							'The minimum percentage of total shares (multiplied by PERCENTAGE_PRECISION) the manager must hold',
+							'while investors remain in the vault. If zero, the manager can withdraw everything.',
 						],
 						type: 'u32',
 					},
 					{
-						name: 'protocolProfitShare',
+						name: 'liquidityBuffer',
 						docs: [
-							'Percentage the protocol charges on all profits realized by depositors: PERCENTAGE_PRECISION',
+							'The percentage of equity (multiplied by PERCENTAGE_PRECISION) reserved in the vault USDC token account,',
+							"on top of outstanding withdraw requests, that the delegate can't deposit to markets.",
 						],
 						type: 'u32',
 					},
 					{
-						name: 'protocol',
+						name: 'lockupDuration',
 						docs: [
-							'The protocol, company, or entity that services the product using this vault.',
-							'The protocol is not allowed to deposit into the vault but can profit share and collect annual fees just like the manager.',
+							"Seconds from an investor's first deposit during which withdraw requests are penalized,",
+							'or rejected if `lockup_penalty_bps` is zero. If zero, there is no lockup.',
 						],
-						type: 'publicKey',
+						type: 'i64',
 					},
 					{
-						name: 'protocolProfitAndFeeShares',
+						name: 'lockupPenaltyBps',
+						type: 'u16',
+					},
+					{
+						name: 'liquidationWindow',
 						docs: [
-							'The shares from profit share and annual fee unclaimed by the protocol.',
+							'Seconds an appointed liquidator has to liquidate. If zero, the default of one hour.',
 						],
-						type: 'u128',
+						type: 'i64',
 					},
 					{
-						name: 'protocolFee',
+						name: 'fundraisingSoftCap',
 						docs: [
-							'The annual fee charged on deposits by the protocol (traditional hedge funds typically charge 2% per year on assets under management).',
-							"Unlike the management fee this can't be negative.",
+							'If non-zero, the vault raises funds until `fundraising_deadline` without trading,',
+							'and closes to refund investors if total deposits are below this soft cap.',
 						],
 						type: 'u64',
 					},
 					{
-						name: 'protocolTotalWithdraws',
-						docs: ['Total withdraws for the protocol'],
-						type: 'u64',
+						name: 'fundraisingDeadline',
+						type: 'i64',
 					},
+				],
+			},
+		},
+		{
+			name: 'PlaceOrderParams',
+			type: {
+				kind: 'struct',
+				fields: [
 					{
-						name: 'protocolTotalFee',
-						docs: [
-							'Total fee charged by the protocol (annual management fee + profit share).',
-							"Unlike the management fee this can't be negative.",
-						],
-						type: 'u64',
+						name: 'order',
+						type: 'bytes',
 					},
+				],
+			},
+		},
+		{
+			name: 'UpdateVaultParams',
+			type: {
+				kind: 'struct',
+				fields: [
 					{
-						name: 'protocolTotalProfitShare',
-						docs: ['Total profit share charged by the protocol'],
-						type: 'u64',
+						name: 'redeemPeriod',
+						type: {
+							option: 'i64',
+						},
 					},
 					{
-						name: 'lastProtocolWithdrawRequest',
+						name: 'maxTokens',
 						type: {
-							defined: 'WithdrawRequest',
+							option: 'u64',
 						},
 					},
 					{
-						name: 'positions',
+						name: 'managementFee',
 						type: {
-							array: [
-								{
-									defined: 'MarketPosition',
-								},
-								8,
-							],
+							option: 'i64',
+						},
+					},
+					{
+						name: 'minDepositAmount',
+						type: {
+							option: 'u64',
+						},
+					},
+					{
+						name: 'profitShare',
+						type: {
+							option: 'u32',
+						},
+					},
+					{
+						name: 'hurdleRate',
+						type: {
+							option: 'u32',
 						},
 					},
 					{
 						name: 'permissioned',
-						docs: ['Whether anyone can be an investor'],
-						type: 'bool',
+						type: {
+							option: 'bool',
+						},
 					},
 					{
-						name: 'bump',
-						docs: ['The bump for the vault PDA'],
-						type: 'u8',
+						name: 'delegate',
+						type: {
+							option: 'publicKey',
+						},
 					},
 					{
-						name: 'padding',
+						name: 'entryFeeBps',
 						type: {
-							array: ['u8', 6],
+							option: 'u16',
+						},
+					},
+					{
+						name: 'exitFeeBps',
+						type: {
+							option: 'u16',
+						},
+					},
+					{
+						name: 'maxDrawdown',
+						type: {
+							option: 'u32',
+						},
+					},
+					{
+						name: 'managerMinStake',
+						type: {
+							option: 'u32',
+						},
+					},
+					{
+						name: 'liquidityBuffer',
+						type: {
+							option: 'u32',
+						},
+					},
+					{
+						name: 'lockupDuration',
+						type: {
+							option: 'i64',
+						},
+					},
+					{
+						name: 'liquidationWindow',
+						type: {
+							option: 'i64',
 						},
 					},
 				],
 			},
 		},
-	],
-	types: [
 		{
-			name: 'MarketLookupTableParams',
+			name: 'MarketPosition',
 			type: {
 				kind: 'struct',
 				fields: [
 					{
-						name: 'solUsdcMarket',
+						name: 'market',
 						type: 'publicKey',
 					},
 					{
-						name: 'usdcMint',
-						type: 'publicKey',
+						name: 'quoteLotsLocked',
+						type: 'u64',
 					},
 					{
-						name: 'solMint',
-						type: 'publicKey',
+						name: 'quoteLotsFree',
+						type: 'u64',
+					},
+					{
+						name: 'baseLotsLocked',
+						type: 'u64',
+					},
+					{
+						name: 'baseLotsFree',
+						type: 'u64',
 					},
 				],
 			},
 		},
 		{
-			name: 'CancelMultipleOrdersParams',
+			name: 'NavSample',
 			type: {
 				kind: 'struct',
 				fields: [
 					{
-						name: 'orders',
+						name: 'totalShares',
+						docs: ['Vault total shares when sampled'],
+						type: 'u128',
+					},
+					{
+						name: 'ts',
+						docs: ['Unix timestamp of the sample'],
+						type: 'i64',
+					},
+					{
+						name: 'equity',
+						docs: ['Vault equity in USDC when sampled'],
+						type: 'u64',
+					},
+					{
+						name: 'sharesBase',
+						docs: ['Vault shares base when sampled'],
+						type: 'u32',
+					},
+					{
+						name: 'padding',
 						type: {
-							vec: {
-								defined: 'CancelOrderParams',
-							},
+							array: ['u8', 12],
 						},
 					},
 				],
 			},
 		},
 		{
-			name: 'CancelOrderParams',
+			name: 'DepositPreview',
+			docs: ['Return data of the `preview_deposit` instruction'],
 			type: {
 				kind: 'struct',
 				fields: [
 					{
-						name: 'side',
-						type: {
-							defined: 'Side',
-						},
+						name: 'shares',
+						docs: ['Vault shares minted for the deposit'],
+						type: 'u128',
 					},
 					{
-						name: 'priceInTicks',
+						name: 'entryFee',
+						docs: ['Entry fee withheld from the deposit'],
 						type: 'u64',
 					},
+				],
+			},
+		},
+		{
+			name: 'WithdrawPreview',
+			docs: ['Return data of the `preview_withdraw` instruction'],
+			type: {
+				kind: 'struct',
+				fields: [
 					{
-						name: 'orderSequenceNumber',
+						name: 'shares',
+						docs: ['Vault shares burned by the withdrawal'],
+						type: 'u128',
+					},
+					{
+						name: 'amount',
+						docs: [
+							'USDC paid to the investor after profit share and the exit fee',
+						],
+						type: 'u64',
+					},
+					{
+						name: 'exitFee',
+						docs: ['Exit fee withheld from the withdrawal'],
+						type: 'u64',
+					},
+					{
+						name: 'lockupPenalty',
+						docs: [
+							"Early exit penalty withheld from a withdrawal requested during the investor's lockup",
+						],
 						type: 'u64',
 					},
 				],
 			},
 		},
 		{
-			name: 'VaultParams',
+			name: 'MarketTransferParams',
 			type: {
 				kind: 'struct',
 				fields: [
 					{
-						name: 'name',
-						type: {
-							array: ['u8', 32],
-						},
+						name: 'quoteLots',
+						type: 'u64',
 					},
 					{
-						name: 'redeemPeriod',
-						type: 'i64',
+						name: 'baseLots',
+						type: 'u64',
+					},
+				],
+			},
+		},
+		{
+			name: 'WithdrawRequest',
+			type: {
+				kind: 'struct',
+				fields: [
+					{
+						name: 'shares',
+						docs: ['request shares of vault withdraw'],
+						type: 'u128',
 					},
 					{
-						name: 'maxTokens',
+						name: 'value',
+						docs: ['requested value in USDC of shares for withdraw'],
 						type: 'u64',
 					},
 					{
-						name: 'managementFee',
+						name: 'ts',
+						docs: ['request ts of vault withdraw'],
 						type: 'i64',
 					},
+				],
+			},
+		},
+		{
+			name: 'Side',
+			type: {
+				kind: 'enum',
+				variants: [
+					{
+						name: 'Bid',
+					},
+					{
+						name: 'Ask',
+					},
+				],
+			},
+		},
+		{
+			name: 'InvestorAction',
+			type: {
+				kind: 'enum',
+				variants: [
 					{
-						name: 'minDepositAmount',
-						type: 'u64',
+						name: 'Deposit',
 					},
 					{
-						name: 'profitShare',
-						type: 'u32',
+						name: 'WithdrawRequest',
 					},
 					{
-						name: 'hurdleRate',
-						type: 'u32',
+						name: 'CancelWithdrawRequest',
 					},
 					{
-						name: 'permissioned',
-						type: 'bool',
+						name: 'Withdraw',
 					},
 					{
-						name: 'protocol',
-						type: 'publicKey',
+						name: 'FeePayment',
 					},
 					{
-						name: 'protocolFee',
-						type: 'u64',
+						name: 'WithdrawInKind',
 					},
 					{
-						name: 'protocolProfitShare',
-						type: 'u32',
+						name: 'WindDownClaim',
 					},
 				],
 			},
 		},
 		{
-			name: 'PlaceOrderParams',
+			name: 'ManagerAction',
 			type: {
-				kind: 'struct',
-				fields: [
+				kind: 'enum',
+				variants: [
 					{
-						name: 'order',
-						type: 'bytes',
+						name: 'Deposit',
+					},
+					{
+						name: 'WithdrawRequest',
+					},
+					{
+						name: 'CancelWithdrawRequest',
+					},
+					{
+						name: 'Withdraw',
 					},
 				],
 			},
 		},
 		{
-			name: 'UpdateVaultParams',
+			name: 'ProtocolAction',
 			type: {
-				kind: 'struct',
-				fields: [
+				kind: 'enum',
+				variants: [
 					{
-						name: 'redeemPeriod',
-						type: {
-							option: 'i64',
-						},
+						name: 'WithdrawRequest',
 					},
 					{
-						name: 'maxTokens',
-						type: {
-							option: 'u64',
-						},
+						name: 'CancelWithdrawRequest',
 					},
 					{
-						name: 'managementFee',
-						type: {
-							option: 'i64',
-						},
+						name: 'Withdraw',
 					},
+				],
+			},
+		},
+		{
+			name: 'DelegateAction',
+			docs: [
+				'The delegate instruction and the order parameters it was called with',
+			],
+			type: {
+				kind: 'enum',
+				variants: [
 					{
-						name: 'minDepositAmount',
-						type: {
-							option: 'u64',
-						},
+						name: 'PlaceLimitOrder',
+						fields: [
+							{
+								name: 'order',
+								type: 'bytes',
+							},
+						],
 					},
 					{
-						name: 'profitShare',
-						type: {
-							option: 'u32',
-						},
+						name: 'CancelAllOrders',
 					},
 					{
-						name: 'hurdleRate',
-						type: {
-							option: 'u32',
-						},
+						name: 'CancelMultipleOrdersById',
+						fields: [
+							{
+								name: 'orders',
+								type: {
+									vec: {
+										defined: 'CancelOrderParams',
+									},
+								},
+							},
+						],
 					},
 					{
-						name: 'permissioned',
-						type: {
-							option: 'bool',
-						},
+						name: 'MarketDeposit',
+						fields: [
+							{
+								name: 'params',
+								type: {
+									defined: 'MarketTransferParams',
+								},
+							},
+						],
 					},
 					{
-						name: 'delegate',
-						type: {
-							option: 'publicKey',
-						},
+						name: 'MarketWithdraw',
+						fields: [
+							{
+								name: 'params',
+								type: {
+									defined: 'MarketTransferParams',
+								},
+							},
+						],
 					},
 				],
 			},
 		},
 		{
-			name: 'MarketPosition',
+			name: 'LiquidatorRole',
 			type: {
-				kind: 'struct',
-				fields: [
-					{
-						name: 'market',
-						type: 'publicKey',
-					},
-					{
-						name: 'quoteLotsLocked',
-						type: 'u64',
-					},
+				kind: 'enum',
+				variants: [
 					{
-						name: 'quoteLotsFree',
-						type: 'u64',
+						name: 'Investor',
 					},
 					{
-						name: 'baseLotsLocked',
-						type: 'u64',
+						name: 'Manager',
 					},
 					{
-						name: 'baseLotsFree',
-						type: 'u64',
+						name: 'Protocol',
 					},
 				],
 			},
 		},
 		{
-			name: 'MarketTransferParams',
+			name: 'VaultState',
+			docs: [
+				'Lifecycle of a [`Vault`](crate::state::Vault).',
+				"Every instruction checks the state with `Vault::check_state` and rejects actions the state doesn't allow.",
+			],
 			type: {
-				kind: 'struct',
-				fields: [
+				kind: 'enum',
+				variants: [
 					{
-						name: 'quoteLots',
-						type: 'u64',
+						name: 'Active',
 					},
 					{
-						name: 'baseLots',
-						type: 'u64',
+						name: 'Fundraising',
+					},
+					{
+						name: 'WindDown',
+					},
+					{
+						name: 'Closed',
 					},
 				],
 			},
 		},
 		{
-			name: 'WithdrawRequest',
+			name: 'WithdrawUnit',
 			type: {
-				kind: 'struct',
-				fields: [
+				kind: 'enum',
+				variants: [
 					{
-						name: 'shares',
-						docs: ['request shares of vault withdraw'],
-						type: 'u128',
+						name: 'Shares',
 					},
 					{
-						name: 'value',
-						docs: ['requested value in USDC of shares for withdraw'],
-						type: 'u64',
+						name: 'Token',
 					},
 					{
-						name: 'ts',
-						docs: ['request ts of vault withdraw'],
-						type: 'i64',
+						name: 'SharesPercent',
 					},
 				],
 			},
 		},
+	],
+	events: [
+		{
+			name: 'VaultRecord',
+			fields: [
+				{
+					name: 'ts',
+					type: 'i64',
+					index: false,
+				},
+				{
+					name: 'vault',
+					type: 'publicKey',
+					index: false,
+				},
+				{
+					name: 'vaultEquity',
+					type: 'u64',
+					index: false,
+				},
+				{
+					name: 'totalShares',
+					type: 'u128',
+					index: false,
+				},
+				{
+					name: 'investorShares',
+					type: 'u128',
+					index: false,
+				},
+				{
+					name: 'sharesBase',
+					type: 'u32',
+					index: false,
+				},
+			],
+		},
+		{
+			name: 'InvestorRecord',
+			fields: [
+				{
+					name: 'ts',
+					type: 'i64',
+					index: false,
+				},
+				{
+					name: 'vault',
+					type: 'publicKey',
+					index: false,
+				},
+				{
+					name: 'depositorAuthority',
+					type: 'publicKey',
+					index: false,
+				},
+				{
+					name: 'action',
+					type: {
+						defined: 'InvestorAction',
+					},
+					index: false,
+				},
+				{
+					name: 'amount',
+					type: 'u64',
+					index: false,
+				},
+				{
+					name: 'usdcMint',
+					type: 'publicKey',
+					index: false,
+				},
+				{
+					name: 'solMint',
+					type: 'publicKey',
+					index: false,
+				},
+				{
+					name: 'vaultSharesBefore',
+					type: 'u128',
+					index: false,
+				},
+				{
+					name: 'vaultSharesAfter',
+					type: 'u128',
+					index: false,
+				},
+				{
+					name: 'vaultEquityBefore',
+					type: 'u64',
+					index: false,
+				},
+				{
+					name: 'userVaultSharesBefore',
+					type: 'u128',
+					index: false,
+				},
+				{
+					name: 'totalVaultSharesBefore',
+					type: 'u128',
+					index: false,
+				},
+				{
+					name: 'protocolSharesBefore',
+					type: 'u128',
+					index: false,
+				},
+				{
+					name: 'userVaultSharesAfter',
+					type: 'u128',
+					index: false,
+				},
+				{
+					name: 'totalVaultSharesAfter',
+					type: 'u128',
+					index: false,
+				},
+				{
+					name: 'protocolSharesAfter',
+					type: 'u128',
+					index: false,
+				},
+				{
+					name: 'protocolProfitShare',
+					type: 'u64',
+					index: false,
+				},
+				{
+					name: 'protocolFee',
+					type: 'i64',
+					index: false,
+				},
+				{
+					name: 'protocolFeeShares',
+					type: 'i64',
+					index: false,
+				},
+				{
+					name: 'managerProfitShare',
+					type: 'u64',
+					index: false,
+				},
+				{
+					name: 'managementFee',
+					type: 'i64',
+					index: false,
+				},
+				{
+					name: 'managementFeeShares',
+					type: 'i64',
+					index: false,
+				},
+				{
+					name: 'entryFee',
+					type: 'u64',
+					index: false,
+				},
+				{
+					name: 'exitFee',
+					type: 'u64',
+					index: false,
+				},
+				{
+					name: 'lockupPenalty',
+					type: 'u64',
+					index: false,
+				},
+			],
+		},
 		{
-			name: 'Side',
-			type: {
-				kind: 'enum',
-				variants: [
-					{
-						name: 'Bid',
-					},
-					{
-						name: 'Ask',
+			name: 'ManagerRecord',
+			fields: [
+				{
+					name: 'ts',
+					type: 'i64',
+					index: false,
+				},
+				{
+					name: 'vault',
+					type: 'publicKey',
+					index: false,
+				},
+				{
+					name: 'manager',
+					type: 'publicKey',
+					index: false,
+				},
+				{
+					name: 'action',
+					type: {
+						defined: 'ManagerAction',
 					},
-				],
-			},
+					index: false,
+				},
+				{
+					name: 'amount',
+					type: 'u64',
+					index: false,
+				},
+				{
+					name: 'nShares',
+					type: 'u128',
+					index: false,
+				},
+				{
+					name: 'vaultEquityBefore',
+					type: 'u64',
+					index: false,
+				},
+				{
+					name: 'managerSharesBefore',
+					type: 'u128',
+					index: false,
+				},
+				{
+					name: 'totalVaultSharesBefore',
+					type: 'u128',
+					index: false,
+				},
+				{
+					name: 'userVaultSharesBefore',
+					type: 'u128',
+					index: false,
+				},
+				{
+					name: 'protocolSharesBefore',
+					type: 'u128',
+					index: false,
+				},
+				{
+					name: 'managerSharesAfter',
+					type: 'u128',
+					index: false,
+				},
+				{
+					name: 'totalVaultSharesAfter',
+					type: 'u128',
+					index: false,
+				},
+				{
+					name: 'userVaultSharesAfter',
+					type: 'u128',
+					index: false,
+				},
+				{
+					name: 'protocolSharesAfter',
+					type: 'u128',
+					index: false,
+				},
+				{
+					name: 'protocolFee',
+					type: 'i64',
+					index: false,
+				},
+				{
+					name: 'protocolFeeShares',
+					type: 'i64',
+					index: false,
+				},
+				{
+					name: 'managementFee',
+					type: 'i64',
+					index: false,
+				},
+				{
+					name: 'managementFeeShares',
+					type: 'i64',
+					index: false,
+				},
+			],
 		},
 		{
-			name: 'InvestorAction',
-			type: {
-				kind: 'enum',
-				variants: [
-					{
-						name: 'Deposit',
-					},
-					{
-						name: 'WithdrawRequest',
-					},
-					{
-						name: 'CancelWithdrawRequest',
-					},
-					{
-						name: 'Withdraw',
-					},
-					{
-						name: 'FeePayment',
+			name: 'ProtocolRecord',
+			fields: [
+				{
+					name: 'ts',
+					type: 'i64',
+					index: false,
+				},
+				{
+					name: 'vault',
+					type: 'publicKey',
+					index: false,
+				},
+				{
+					name: 'protocol',
+					type: 'publicKey',
+					index: false,
+				},
+				{
+					name: 'action',
+					type: {
+						defined: 'ProtocolAction',
 					},
-				],
-			},
+					index: false,
+				},
+				{
+					name: 'amount',
+					type: 'u64',
+					index: false,
+				},
+				{
+					name: 'nShares',
+					type: 'u128',
+					index: false,
+				},
+				{
+					name: 'vaultEquityBefore',
+					type: 'u64',
+					index: false,
+				},
+				{
+					name: 'protocolSharesBefore',
+					type: 'u128',
+					index: false,
+				},
+				{
+					name: 'totalVaultSharesBefore',
+					type: 'u128',
+					index: false,
+				},
+				{
+					name: 'userVaultSharesBefore',
+					type: 'u128',
+					index: false,
+				},
+				{
+					name: 'managerSharesBefore',
+					type: 'u128',
+					index: false,
+				},
+				{
+					name: 'protocolSharesAfter',
+					type: 'u128',
+					index: false,
+				},
+				{
+					name: 'totalVaultSharesAfter',
+					type: 'u128',
+					index: false,
+				},
+				{
+					name: 'userVaultSharesAfter',
+					type: 'u128',
+					index: false,
+				},
+				{
+					name: 'managerSharesAfter',
+					type: 'u128',
+					index: false,
+				},
+				{
+					name: 'protocolFee',
+					type: 'i64',
+					index: false,
+				},
+				{
+					name: 'protocolFeeShares',
+					type: 'i64',
+					index: false,
+				},
+				{
+					name: 'managementFee',
+					type: 'i64',
+					index: false,
+				},
+				{
+					name: 'managementFeeShares',
+					type: 'i64',
+					index: false,
+				},
+			],
 		},
 		{
-			name: 'WithdrawUnit',
-			type: {
-				kind: 'enum',
-				variants: [
-					{
-						name: 'Shares',
-					},
-					{
-						name: 'Token',
-					},
-					{
-						name: 'SharesPercent',
-					},
-				],
-			},
+			name: 'AppointLiquidatorRecord',
+			fields: [
+				{
+					name: 'ts',
+					type: 'i64',
+					index: false,
+				},
+				{
+					name: 'vault',
+					type: 'publicKey',
+					index: false,
+				},
+				{
+					name: 'liquidator',
+					type: 'publicKey',
+					index: false,
+				},
+				{
+					name: 'liquidationWindow',
+					type: 'i64',
+					index: false,
+				},
+			],
 		},
-	],
-	events: [
 		{
-			name: 'VaultRecord',
+			name: 'LiquidationRecord',
 			fields: [
 				{
 					name: 'ts',
@@ -5522,19 +9303,66 @@ export const IDL: PhoenixVaults = {
 					index: false,
 				},
 				{
-					name: 'spotMarketIndex',
-					type: 'u16',
+					name: 'vault',
+					type: 'publicKey',
 					index: false,
 				},
 				{
-					name: 'vaultEquityBefore',
+					name: 'liquidatorRole',
+					type: {
+						defined: 'LiquidatorRole',
+					},
+					index: false,
+				},
+				{
+					name: 'liquidator',
+					type: 'publicKey',
+					index: false,
+				},
+				{
+					name: 'market',
+					type: 'publicKey',
+					index: false,
+				},
+				{
+					name: 'liquidationWindow',
+					type: 'i64',
+					index: false,
+				},
+				{
+					name: 'baseLotsSold',
+					type: 'u64',
+					index: false,
+				},
+				{
+					name: 'avgPrice',
+					type: 'u64',
+					index: false,
+				},
+				{
+					name: 'quoteReceived',
+					type: 'u64',
+					index: false,
+				},
+				{
+					name: 'fees',
+					type: 'u64',
+					index: false,
+				},
+				{
+					name: 'withdrawRequestValueBefore',
+					type: 'u64',
+					index: false,
+				},
+				{
+					name: 'withdrawRequestValueAfter',
 					type: 'u64',
 					index: false,
 				},
 			],
 		},
 		{
-			name: 'InvestorRecord',
+			name: 'DelegateActionRecord',
 			fields: [
 				{
 					name: 'ts',
@@ -5547,64 +9375,148 @@ export const IDL: PhoenixVaults = {
 					index: false,
 				},
 				{
-					name: 'depositorAuthority',
+					name: 'delegate',
+					type: 'publicKey',
+					index: false,
+				},
+				{
+					name: 'market',
 					type: 'publicKey',
 					index: false,
 				},
 				{
 					name: 'action',
 					type: {
-						defined: 'InvestorAction',
+						defined: 'DelegateAction',
 					},
 					index: false,
 				},
 				{
-					name: 'amount',
-					type: 'u64',
+					name: 'positionBefore',
+					type: {
+						defined: 'MarketPosition',
+					},
 					index: false,
 				},
 				{
-					name: 'usdcMint',
-					type: 'publicKey',
+					name: 'positionAfter',
+					type: {
+						defined: 'MarketPosition',
+					},
 					index: false,
 				},
+			],
+		},
+		{
+			name: 'EpochRecord',
+			fields: [
 				{
-					name: 'solMint',
+					name: 'ts',
+					type: 'i64',
+					index: false,
+				},
+				{
+					name: 'vault',
 					type: 'publicKey',
 					index: false,
 				},
 				{
-					name: 'vaultSharesBefore',
+					name: 'epoch',
+					type: 'u64',
+					index: false,
+				},
+				{
+					name: 'vaultEquity',
+					type: 'u64',
+					index: false,
+				},
+				{
+					name: 'deposits',
+					type: 'u64',
+					index: false,
+				},
+				{
+					name: 'depositShares',
 					type: 'u128',
 					index: false,
 				},
 				{
-					name: 'vaultSharesAfter',
+					name: 'withdrawShares',
 					type: 'u128',
 					index: false,
 				},
 				{
-					name: 'vaultEquityBefore',
+					name: 'withdrawValue',
 					type: 'u64',
 					index: false,
 				},
 				{
-					name: 'userVaultSharesBefore',
+					name: 'totalVaultSharesBefore',
+					type: 'u128',
+					index: false,
+				},
+				{
+					name: 'totalVaultSharesAfter',
 					type: 'u128',
 					index: false,
 				},
+				{
+					name: 'protocolFee',
+					type: 'i64',
+					index: false,
+				},
+				{
+					name: 'protocolFeeShares',
+					type: 'i64',
+					index: false,
+				},
+				{
+					name: 'managementFee',
+					type: 'i64',
+					index: false,
+				},
+				{
+					name: 'managementFeeShares',
+					type: 'i64',
+					index: false,
+				},
+			],
+		},
+		{
+			name: 'FeeRecord',
+			fields: [
+				{
+					name: 'ts',
+					type: 'i64',
+					index: false,
+				},
+				{
+					name: 'vault',
+					type: 'publicKey',
+					index: false,
+				},
+				{
+					name: 'vaultEquity',
+					type: 'u64',
+					index: false,
+				},
 				{
 					name: 'totalVaultSharesBefore',
 					type: 'u128',
 					index: false,
 				},
 				{
-					name: 'protocolSharesBefore',
+					name: 'userVaultSharesBefore',
 					type: 'u128',
 					index: false,
 				},
 				{
-					name: 'userVaultSharesAfter',
+					name: 'managerSharesBefore',
+					type: 'u128',
+					index: false,
+				},
+				{
+					name: 'protocolSharesBefore',
 					type: 'u128',
 					index: false,
 				},
@@ -5614,28 +9526,28 @@ export const IDL: PhoenixVaults = {
 					index: false,
 				},
 				{
-					name: 'protocolSharesAfter',
+					name: 'userVaultSharesAfter',
 					type: 'u128',
 					index: false,
 				},
 				{
-					name: 'protocolProfitShare',
-					type: 'u64',
+					name: 'managerSharesAfter',
+					type: 'u128',
 					index: false,
 				},
 				{
-					name: 'protocolFee',
-					type: 'i64',
+					name: 'protocolSharesAfter',
+					type: 'u128',
 					index: false,
 				},
 				{
-					name: 'protocolFeeShares',
+					name: 'protocolFee',
 					type: 'i64',
 					index: false,
 				},
 				{
-					name: 'managerProfitShare',
-					type: 'u64',
+					name: 'protocolFeeShares',
+					type: 'i64',
 					index: false,
 				},
 				{
@@ -5857,5 +9769,125 @@ export const IDL: PhoenixVaults = {
 			name: 'MarketPositionNotFound',
 			msg: 'MarketPositionNotFound',
 		},
+		{
+			code: 6041,
+			name: 'InKindMarketAccountsMissing',
+			msg: 'InKindMarketAccountsMissing',
+		},
+		{
+			code: 6042,
+			name: 'InKindTokenAccountMismatch',
+			msg: 'InKindTokenAccountMismatch',
+		},
+		{
+			code: 6043,
+			name: 'InKindLotsLocked',
+			msg: 'InKindLotsLocked',
+		},
+		{
+			code: 6044,
+			name: 'EpochModeDisabled',
+			msg: 'EpochModeDisabled',
+		},
+		{
+			code: 6045,
+			name: 'EpochNotEnded',
+			msg: 'EpochNotEnded',
+		},
+		{
+			code: 6046,
+			name: 'EpochUnclaimed',
+			msg: 'EpochUnclaimed',
+		},
+		{
+			code: 6047,
+			name: 'EpochNotSettled',
+			msg: 'EpochNotSettled',
+		},
+		{
+			code: 6048,
+			name: 'InvalidInvestorAccount',
+			msg: 'InvalidInvestorAccount',
+		},
+		{
+			code: 6049,
+			name: 'EpochReservedUsdc',
+			msg: 'EpochReservedUsdc',
+		},
+		{
+			code: 6050,
+			name: 'NavSampleTooSoon',
+			msg: 'NavSampleTooSoon',
+		},
+		{
+			code: 6051,
+			name: 'InvalidNavHistory',
+			msg: 'InvalidNavHistory',
+		},
+		{
+			code: 6052,
+			name: 'InvalidSharePrice',
+			msg: 'InvalidSharePrice',
+		},
+		{
+			code: 6053,
+			name: 'StaleSharePrice',
+			msg: 'StaleSharePrice',
+		},
+		{
+			code: 6054,
+			name: 'SlippageExceeded',
+			msg: 'SlippageExceeded',
+		},
+		{
+			code: 6055,
+			name: 'DeadlineExceeded',
+			msg: 'DeadlineExceeded',
+		},
+		{
+			code: 6056,
+			name: 'TradingHalted',
+			msg: 'TradingHalted',
+		},
+		{
+			code: 6057,
+			name: 'TradingNotHalted',
+			msg: 'TradingNotHalted',
+		},
+		{
+			code: 6058,
+			name: 'InvalidVaultState',
+			msg: 'InvalidVaultState',
+		},
+		{
+			code: 6059,
+			name: 'WindDownPositionsOpen',
+			msg: 'WindDownPositionsOpen',
+		},
+		{
+			code: 6060,
+			name: 'WindDownNotFinalized',
+			msg: 'WindDownNotFinalized',
+		},
+		{
+			code: 6061,
+			name: 'ManagerStakeTooLow',
+			msg: 'ManagerStakeTooLow',
+		},
+		{
+			code: 6062,
+			name: 'LiquidityBufferBreached',
+			msg: 'LiquidityBufferBreached',
+		},
+		{
+			code: 6063,
+			name: 'InvestorLockedUp',
+			msg: 'InvestorLockedUp',
+		},
+		{
+			code: 6064,
+			name: 'InKindEpochMode',
+			msg: 'InKindEpochMode',
+		},
 	],
 };
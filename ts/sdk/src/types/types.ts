@@ -11,6 +11,13 @@ export class WithdrawUnit {
 	static readonly SHARES_PERCENT = { sharesPercent: {} };
 }
 
+export class VaultState {
+	static readonly ACTIVE = { active: {} };
+	static readonly FUNDRAISING = { fundraising: {} };
+	static readonly WIND_DOWN = { windDown: {} };
+	static readonly CLOSED = { closed: {} };
+}
+
 export type WithdrawRequest = {
 	shares: BN;
 	value: BN;
@@ -29,6 +36,17 @@ export type VaultParams = {
 	protocol: PublicKey;
 	protocolFee: BN;
 	protocolProfitShare: number;
+	epochDuration: BN;
+	entryFeeBps: number;
+	exitFeeBps: number;
+	maxDrawdown: number;
+	managerMinStake: number;
+	liquidityBuffer: number;
+	lockupDuration: BN;
+	lockupPenaltyBps: number;
+	liquidationWindow: BN;
+	fundraisingSoftCap: BN;
+	fundraisingDeadline: BN;
 };
 
 export type Vault = {
@@ -39,10 +57,12 @@ export type Vault = {
 	solMint: PublicKey;
 	usdcTokenAccount: PublicKey;
 	solTokenAccount: PublicKey;
+	liquidator: PublicKey;
 	delegate: PublicKey;
 	investorShares: BN;
 	totalShares: BN;
 	lastFeeUpdateTs: BN;
+	liquidationStartTs: BN;
 	redeemPeriod: BN;
 	totalWithdrawRequested: BN;
 	maxTokens: BN;
@@ -76,6 +96,35 @@ export type Vault = {
 	permissioned: boolean;
 	bump: number;
 	padding: number[];
+
+	epochPendingWithdrawShares: BN;
+	epochUnclaimedDepositShares: BN;
+	epochUnclaimedWithdrawShares: BN;
+	epochDuration: BN;
+	epochStartTs: BN;
+	currentEpoch: BN;
+	epochPendingDeposits: BN;
+	epochUnclaimedDeposits: BN;
+	epochUnclaimedWithdrawValue: BN;
+	epochRedemptionsOwed: BN;
+	entryFeeBps: number;
+	exitFeeBps: number;
+	maxDrawdown: number;
+	highWaterMarkPrice: BN;
+	tradingHalted: boolean;
+	state: VaultState;
+	padding2: number[];
+	managerMinStake: number;
+	fundraisingSoftCap: BN;
+	fundraisingDeadline: BN;
+	finalEquity: BN;
+	finalTotalShares: BN;
+	liquidityBuffer: number;
+	lockupPenaltyBps: number;
+	padding3: number[];
+	lockupDuration: BN;
+	liquidationWindow: BN;
+	padding4: BN[];
 };
 
 export type MarketPosition = {
@@ -99,6 +148,18 @@ export type MarketTransferParams = {
 	baseLots: BN;
 };
 
+export type DepositPreview = {
+	shares: BN;
+	entryFee: BN;
+};
+
+export type WithdrawPreview = {
+	shares: BN;
+	amount: BN;
+	exitFee: BN;
+	lockupPenalty: BN;
+};
+
 export type Investor = {
 	vault: PublicKey;
 	pubkey: PublicKey;
@@ -113,18 +174,29 @@ export type Investor = {
 	profitShareFeePaid: BN;
 	vaultSharesBase: number;
 	padding1: number;
+	epochDepositAmount: BN;
+	epochDepositEpoch: BN;
+	epochWithdrawEpoch: BN;
+	firstDepositTs: BN;
 	padding: BN[];
 };
 
 export type UpdateVaultParams = {
 	redeemPeriod: BN | null;
 	maxTokens: BN | null;
-	minDepositAmount: BN | null;
 	managementFee: BN | null;
+	minDepositAmount: BN | null;
 	profitShare: number | null;
 	hurdleRate: number | null;
 	permissioned: boolean | null;
 	delegate: PublicKey | null;
+	entryFeeBps: number | null;
+	exitFeeBps: number | null;
+	maxDrawdown: number | null;
+	managerMinStake: number | null;
+	liquidityBuffer: number | null;
+	lockupDuration: BN | null;
+	liquidationWindow: BN | null;
 };
 
 export class OrderSide {
@@ -1,3 +1,7 @@
+import { BN } from '@coral-xyz/anchor';
+import { PublicKey } from '@solana/web3.js';
+import { PRICE_PRECISION, ZERO } from '../constants';
+
 export const MAX_NAME_LENGTH = 32;
 
 export function encodeName(name: string): number[] {
@@ -16,3 +20,131 @@ export function decodeName(bytes: number[]): string {
 	const buffer = Buffer.from(bytes);
 	return buffer.toString('utf8').trim();
 }
+
+/**
+ * A Phoenix market as the vault valuation reads it: header fields, the best bid
+ * and the vault's lots on it.
+ */
+export interface MarketValuation {
+	baseMint: PublicKey;
+	quoteMint: PublicKey;
+	baseDecimals: number;
+	quoteDecimals: number;
+	/** Base atoms per base lot */
+	baseLotSize: BN;
+	/** Quote atoms per quote lot */
+	quoteLotSize: BN;
+	tickSizeInQuoteAtomsPerBaseUnit: BN;
+	rawBaseUnitsPerBaseUnit: number;
+	/** Zero if the market has no bids */
+	topBidInTicks: BN;
+	/** Free plus locked lots of the vault, undefined if the vault has no seat */
+	vaultLots?: { baseLots: BN; quoteLots: BN };
+}
+
+export function ticksToPricePrecision(
+	market: MarketValuation,
+	priceInTicks: BN
+): BN {
+	const rawBaseUnitsPerBaseUnit = new BN(
+		Math.max(market.rawBaseUnitsPerBaseUnit, 1)
+	);
+	const quoteAtomsPerQuoteUnit = new BN(10).pow(new BN(market.quoteDecimals));
+	return priceInTicks
+		.mul(market.tickSizeInQuoteAtomsPerBaseUnit)
+		.mul(PRICE_PRECISION)
+		.div(quoteAtomsPerQuoteUnit.mul(rawBaseUnitsPerBaseUnit));
+}
+
+export function solToUsdcDenom(basePrice: BN, solPrice: BN): BN {
+	return basePrice.mul(solPrice).div(PRICE_PRECISION);
+}
+
+export function baseLotsToRawBaseUnitsPrecision(
+	market: MarketValuation,
+	baseLots: BN
+): BN {
+	const baseAtomsPerRawBaseUnit = new BN(10).pow(new BN(market.baseDecimals));
+	return baseLots
+		.mul(market.baseLotSize)
+		.mul(PRICE_PRECISION)
+		.div(baseAtomsPerRawBaseUnit);
+}
+
+export function quoteLotsToQuoteUnitsPrecision(
+	market: MarketValuation,
+	quoteLots: BN
+): BN {
+	const quoteAtomsPerQuoteUnit = new BN(10).pow(new BN(market.quoteDecimals));
+	return quoteLots
+		.mul(market.quoteLotSize)
+		.mul(PRICE_PRECISION)
+		.div(quoteAtomsPerQuoteUnit);
+}
+
+/**
+ * Vault equity in USDC atoms, rounded as the program's `calculate_equity`
+ * rounds it, so the SDK and the program agree to the atom.
+ *
+ * `markets` are the markets of the vault's positions, `solUsdcMarket` prices
+ * SOL for the SOL-quoted ones.
+ */
+export function calculateEquity(
+	vault: { epochPendingDeposits: BN; epochRedemptionsOwed: BN },
+	vaultUsdcAmount: BN,
+	solMint: PublicKey,
+	usdcMint: PublicKey,
+	solUsdcMarket: MarketValuation,
+	markets: MarketValuation[]
+): BN {
+	if (
+		!solUsdcMarket.quoteMint.equals(usdcMint) ||
+		!solUsdcMarket.baseMint.equals(solMint)
+	) {
+		throw Error('SOL/USDC market has the wrong mints');
+	}
+	const solPrice = ticksToPricePrecision(
+		solUsdcMarket,
+		solUsdcMarket.topBidInTicks
+	);
+
+	// USDC queued or owed by epoch settlement isn't owned by current shareholders
+	const epochReservedUsdc = vault.epochPendingDeposits.add(
+		vault.epochRedemptionsOwed
+	);
+	let equity = BN.max(vaultUsdcAmount.sub(epochReservedUsdc), ZERO);
+
+	for (const market of markets) {
+		if (market.vaultLots === undefined) {
+			continue;
+		}
+		const price = ticksToPricePrecision(market, market.topBidInTicks);
+		let usdcPricePrecision: BN;
+		if (market.quoteMint.equals(usdcMint)) {
+			usdcPricePrecision = price;
+		} else if (market.quoteMint.equals(solMint)) {
+			usdcPricePrecision = solToUsdcDenom(price, solPrice);
+		} else {
+			throw Error(`Unrecognized quote mint ${market.quoteMint.toString()}`);
+		}
+
+		const baseUnitsPrecision = baseLotsToRawBaseUnitsPrecision(
+			market,
+			market.vaultLots.baseLots
+		);
+		const baseQuoteUnitsPrecision = baseUnitsPrecision
+			.mul(usdcPricePrecision)
+			.div(PRICE_PRECISION);
+
+		// quote lots of SOL-quoted markets are SOL, valued at the SOL price too
+		let quoteUnitsPrecision = quoteLotsToQuoteUnitsPrecision(
+			market,
+			market.vaultLots.quoteLots
+		);
+		if (market.quoteMint.equals(solMint)) {
+			quoteUnitsPrecision = solToUsdcDenom(quoteUnitsPrecision, solPrice);
+		}
+		equity = equity.add(baseQuoteUnitsPrecision).add(quoteUnitsPrecision);
+	}
+	return equity;
+}